@@ -12,15 +12,19 @@ use crate::{
 };
 use anyhow::{bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_api_types::{
-    AptosErrorCode, AsConverter, BcsBlock, GasEstimation, LedgerInfo, ResourceGroup,
-    TransactionOnChainData,
+    call_trace::{CallTrace, CallTracePayloadKind},
+    AptosErrorCode, AsConverter, BcsBlock, EventCursor, GasEstimation, HexEncodedBytes, LedgerInfo,
+    ResourceGroup, ResourceOverride, StateOverride, TableItemOverride, TransactionOnChainData,
 };
 use aptos_config::config::{NodeConfig, RoleType};
 use aptos_crypto::HashValue;
 use aptos_gas_schedule::{AptosGasParameters, FromOnChainGasSchedule};
 use aptos_logger::error;
-use aptos_mempool::{MempoolClientRequest, MempoolClientSender, SubmissionStatus};
-use aptos_state_view::TStateView;
+use aptos_mempool::{
+    CommittedTransactions, MempoolClientRequest, MempoolClientSender, MempoolSummary,
+    PendingTransactionStatus, SubmissionStatus,
+};
+use aptos_state_view::{overlay_state_view::OverlayStateView, StateView, TStateView};
 use aptos_storage_interface::{
     state_view::{DbStateView, DbStateViewAtVersion, LatestDbStateCheckpointView},
     DbReader, Order, MAX_REQUEST_LIMIT,
@@ -40,15 +44,22 @@ use aptos_types::{
         state_key::{StateKey, StateKeyInner},
         state_key_prefix::StateKeyPrefix,
         state_value::StateValue,
+        table::TableHandle,
+    },
+    transaction::{
+        ExecutionStatus, SignedTransaction, Transaction, TransactionOutput, TransactionStatus,
+        TransactionWithProof, Version,
     },
-    transaction::{SignedTransaction, TransactionWithProof, Version},
 };
 use aptos_utils::aptos_try;
-use aptos_vm::data_cache::AsMoveResolver;
+use aptos_vm::{data_cache::AsMoveResolver, storage_adapter::AsExecutorView, AptosVM, VMExecutor};
 use futures::{channel::oneshot, SinkExt};
+use move_binary_format::CompiledModule;
 use move_core_types::{
-    language_storage::{ModuleId, StructTag},
-    resolver::ModuleResolver,
+    call_trace::CallTraces,
+    identifier::IdentStr,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    resolver::{ModuleResolver, MoveResolver},
 };
 use std::{
     collections::{BTreeMap, HashMap},
@@ -56,6 +67,7 @@ use std::{
     sync::{Arc, RwLock, RwLockWriteGuard},
     time::Instant,
 };
+use tokio::sync::{broadcast, watch, OwnedSemaphorePermit, Semaphore};
 
 // Context holds application scope context
 #[derive(Clone)]
@@ -67,6 +79,10 @@ pub struct Context {
     gas_schedule_cache: Arc<RwLock<GasScheduleCache>>,
     gas_estimation_cache: Arc<RwLock<GasEstimationCache>>,
     gas_limit_cache: Arc<RwLock<GasLimitCache>>,
+    ledger_update_sender: Arc<watch::Sender<Version>>,
+    wait_by_hash_semaphore: Arc<Semaphore>,
+    transaction_stream_semaphore: Arc<Semaphore>,
+    call_trace_semaphore: Arc<Semaphore>,
 }
 
 impl std::fmt::Debug for Context {
@@ -82,6 +98,15 @@ impl Context {
         mp_sender: MempoolClientSender,
         node_config: NodeConfig,
     ) -> Self {
+        let wait_by_hash_semaphore = Arc::new(Semaphore::new(
+            node_config.api.wait_by_hash_max_active_connections,
+        ));
+        let transaction_stream_semaphore = Arc::new(Semaphore::new(
+            node_config.api.transaction_stream_max_active_connections,
+        ));
+        let call_trace_semaphore = Arc::new(Semaphore::new(
+            node_config.api.call_trace_max_active_replays,
+        ));
         Self {
             chain_id,
             db,
@@ -101,9 +126,51 @@ impl Context {
                 last_updated_epoch: None,
                 block_gas_limit: None,
             })),
+            ledger_update_sender: Arc::new(watch::channel(0).0),
+            wait_by_hash_semaphore,
+            transaction_stream_semaphore,
+            call_trace_semaphore,
         }
     }
 
+    /// Notifies the subscribers of ledger updates that transactions up to `version` were
+    /// committed.
+    pub fn notify_ledger_update(&self, version: Version) {
+        self.ledger_update_sender.send_replace(version);
+    }
+
+    /// Returns a receiver that is notified of the latest committed version, if the node forwards
+    /// its commit notifications to the API. Callers must not rely on it alone, and should also
+    /// re-check storage periodically.
+    pub fn subscribe_to_ledger_updates(&self) -> watch::Receiver<Version> {
+        self.ledger_update_sender.subscribe()
+    }
+
+    /// Reserves a slot for a request waiting on a transaction by hash. Returns `None` if the
+    /// maximum number of waiting requests is reached.
+    pub fn try_acquire_wait_by_hash_permit(&self) -> Option<OwnedSemaphorePermit> {
+        self.wait_by_hash_semaphore.clone().try_acquire_owned().ok()
+    }
+
+    /// Reserves a slot for a transaction stream, held for as long as the stream is open. Returns
+    /// `None` if the maximum number of open streams is reached.
+    pub fn try_acquire_transaction_stream_permit(&self) -> Option<OwnedSemaphorePermit> {
+        self.transaction_stream_semaphore
+            .clone()
+            .try_acquire_owned()
+            .ok()
+    }
+
+    /// Reserves a slot for a call trace request, held while its block is re-executed. Returns
+    /// `None` if the maximum number of concurrent replays is reached.
+    pub fn try_acquire_call_trace_permit(&self) -> Option<OwnedSemaphorePermit> {
+        self.call_trace_semaphore.clone().try_acquire_owned().ok()
+    }
+
+    pub fn wait_by_hash_max_timeout_secs(&self) -> u64 {
+        self.node_config.api.wait_by_hash_max_timeout_secs
+    }
+
     pub fn max_transactions_page_size(&self) -> u16 {
         self.node_config.api.max_transactions_page_size
     }
@@ -154,6 +221,298 @@ impl Context {
         self.db.state_view_at_version(Some(version))
     }
 
+    /// Layers the state overrides of a request on top of `state_view`.
+    ///
+    /// Module overrides are applied first, so that the values of resources and table items can
+    /// use types from overridden modules. The other overrides are applied in order.
+    /// Overriding a module changes the id of the returned view, so VMs warmed up on the
+    /// unmodified code are not reused for it.
+    pub fn apply_state_overrides<S: StateView>(
+        &self,
+        state_view: S,
+        state_overrides: Vec<StateOverride>,
+    ) -> Result<OverlayStateView<S>> {
+        let mut state_view = OverlayStateView::new(state_view);
+        let (modules, others): (Vec<_>, Vec<_>) = state_overrides
+            .into_iter()
+            .partition(|state_override| matches!(state_override, StateOverride::Module(_)));
+        for state_override in modules.into_iter().chain(others) {
+            let (state_key, bytes) = match state_override {
+                StateOverride::Module(module) => {
+                    let bytecode: Vec<u8> = module.bytecode.into();
+                    let module_id = CompiledModule::deserialize(&bytecode)
+                        .context("Failed to deserialize module override")?
+                        .self_id();
+                    (
+                        StateKey::access_path(AccessPath::code_access_path(module_id)),
+                        bytecode,
+                    )
+                },
+                StateOverride::Resource(resource) => {
+                    self.resource_override(&state_view, resource)?
+                },
+                StateOverride::TableItem(table_item) => {
+                    self.table_item_override(&state_view, table_item)?
+                },
+            };
+            state_view.overwrite(state_key, Some(StateValue::new_legacy(bytes.into())));
+        }
+        Ok(state_view)
+    }
+
+    fn resource_override(
+        &self,
+        state_view: &impl StateView,
+        resource: ResourceOverride,
+    ) -> Result<(StateKey, Vec<u8>)> {
+        let address: AccountAddress = resource.address.into();
+        let struct_tag: StructTag = resource
+            .resource_type
+            .try_into()
+            .context("Failed to parse resource_type")?;
+        let resolver = state_view.as_move_resolver();
+        let bytes = self.encode_override_value(
+            &resolver,
+            Some(TypeTag::Struct(Box::new(struct_tag.clone()))),
+            resource.data,
+            resource.bcs_data,
+        )?;
+
+        // Members of a resource group are stored within the group.
+        let group_tag =
+            aptos_framework::get_metadata(&resolver.get_module_metadata(&struct_tag.module_id()))
+                .and_then(|md| {
+                    md.struct_attributes
+                        .get(struct_tag.name.as_str())?
+                        .iter()
+                        .find_map(|attr| attr.get_resource_group_member())
+                });
+        match group_tag {
+            Some(group_tag) => {
+                let state_key = StateKey::access_path(AccessPath::resource_group_access_path(
+                    address, group_tag,
+                ));
+                let mut group = match state_view.get_state_value_bytes(&state_key)? {
+                    Some(group) => bcs::from_bytes::<ResourceGroup>(&group)?,
+                    None => ResourceGroup::new(),
+                };
+                group.insert(struct_tag, bytes);
+                Ok((state_key, bcs::to_bytes(&group)?))
+            },
+            None => Ok((
+                StateKey::access_path(AccessPath::resource_access_path(address, struct_tag)?),
+                bytes,
+            )),
+        }
+    }
+
+    fn table_item_override(
+        &self,
+        state_view: &impl StateView,
+        table_item: TableItemOverride,
+    ) -> Result<(StateKey, Vec<u8>)> {
+        let key_type = table_item
+            .key_type
+            .map(TypeTag::try_from)
+            .transpose()
+            .context("Failed to parse key_type")?;
+        let value_type = table_item
+            .value_type
+            .map(TypeTag::try_from)
+            .transpose()
+            .context("Failed to parse value_type")?;
+        let resolver = state_view.as_move_resolver();
+        let key =
+            self.encode_override_value(&resolver, key_type, table_item.key, table_item.bcs_key)?;
+        let value = self.encode_override_value(
+            &resolver,
+            value_type,
+            table_item.value,
+            table_item.bcs_value,
+        )?;
+        Ok((
+            StateKey::table_item(TableHandle(table_item.handle.into()), key),
+            value,
+        ))
+    }
+
+    /// Returns the BCS encoding of an override value, given either as JSON of type `ty` or as
+    /// BCS already.
+    fn encode_override_value<R: MoveResolver>(
+        &self,
+        resolver: &R,
+        ty: Option<TypeTag>,
+        json: Option<serde_json::Value>,
+        bcs: Option<HexEncodedBytes>,
+    ) -> Result<Vec<u8>> {
+        match (json, bcs) {
+            (Some(json), None) => {
+                let ty = ty.ok_or_else(|| format_err!("Missing type of JSON value {}", json))?;
+                resolver
+                    .as_converter(self.db.clone())
+                    .try_into_vm_value(&ty, json.clone())?
+                    .undecorate()
+                    .simple_serialize()
+                    .ok_or_else(|| format_err!("Failed to serialize value {}", json))
+            },
+            (None, Some(bcs)) => Ok(bcs.into()),
+            _ => bail!("Exactly one of a JSON and a BCS value must be given"),
+        }
+    }
+
+    /// Returns the state the transaction at `version` was executed against. The transactions
+    /// preceding it in its block are re-executed on top of the parent block's state, the same
+    /// way the block was originally executed.
+    pub fn block_prefix_state_view<E: StdApiError>(
+        &self,
+        version: Version,
+        latest_ledger_info: &LedgerInfo,
+    ) -> Result<OverlayStateView<DbStateView>, E> {
+        let (block_start_version, _, _) = self
+            .db
+            .get_block_info_by_version(version)
+            .map_err(|_| block_not_found_by_version(version, latest_ledger_info))?;
+        if let Some(base_version) = block_start_version.checked_sub(1) {
+            if base_version < latest_ledger_info.oldest_ledger_version.0 {
+                return Err(version_pruned(base_version, latest_ledger_info));
+            }
+        }
+
+        self.replay_block_prefix(block_start_version, version)
+            .map_err(|err| {
+                E::internal_with_code(err, AptosErrorCode::InternalError, latest_ledger_info)
+            })
+    }
+
+    fn replay_block_prefix(
+        &self,
+        block_start_version: Version,
+        version: Version,
+    ) -> Result<OverlayStateView<DbStateView>> {
+        // The genesis block has no parent, it starts from the empty state.
+        let mut state_view = OverlayStateView::new(
+            self.db
+                .state_view_at_version(block_start_version.checked_sub(1))?,
+        );
+        if version > block_start_version {
+            let block_prefix = self
+                .db
+                .get_transactions(
+                    block_start_version,
+                    version - block_start_version,
+                    version,
+                    false,
+                )
+                .context("Failed to read block prefix from DB")?
+                .transactions;
+            let outputs = AptosVM::execute_block(block_prefix, &state_view, None)
+                .map_err(|status| format_err!("Failed to replay block prefix: {:?}", status))?;
+            for output in outputs {
+                state_view.apply_write_set(output.write_set());
+            }
+        }
+        Ok(state_view)
+    }
+
+    /// Returns the call traces of `txns`, which must be consecutive committed transactions.
+    /// Transactions that did not execute any Move function have no call trace.
+    pub fn get_call_traces(
+        &self,
+        txns: &[TransactionOnChainData],
+    ) -> Result<Vec<Option<CallTrace>>> {
+        let first_version = match txns.first() {
+            Some(txn) => txn.version,
+            None => return Ok(vec![]),
+        };
+        let (block_start_version, _, _) = self.db.get_block_info_by_version(first_version)?;
+        let mut state_view = self.replay_block_prefix(block_start_version, first_version)?;
+        txns.iter()
+            .map(|txn| self.trace_transaction(&mut state_view, txn))
+            .collect()
+    }
+
+    /// Replays a committed transaction on top of `state_view`, which must hold the state right
+    /// before it, and returns its call trace. The transaction's writes are applied to
+    /// `state_view`, so that the next transaction can be traced on top of it.
+    ///
+    /// The replayed output must match the committed `TransactionInfo`, otherwise the trace would
+    /// not describe what actually happened on chain and an error is returned instead.
+    pub fn trace_transaction(
+        &self,
+        state_view: &mut OverlayStateView<DbStateView>,
+        txn: &TransactionOnChainData,
+    ) -> Result<Option<CallTrace>> {
+        let payload_kind = match &txn.transaction {
+            Transaction::UserTransaction(signed_txn) => {
+                CallTracePayloadKind::from(signed_txn.payload())
+            },
+            Transaction::BlockMetadata(_) => CallTracePayloadKind::BlockMetadata,
+            // Genesis applies its write set directly and state checkpoints execute nothing
+            _ => {
+                state_view.apply_write_set(&txn.changes);
+                return Ok(None);
+            },
+        };
+
+        let replayed = {
+            let executor_view = state_view.as_executor_view();
+            match &txn.transaction {
+                Transaction::UserTransaction(signed_txn) => {
+                    AptosVM::execute_user_transaction_with_call_trace(signed_txn, &executor_view)
+                },
+                Transaction::BlockMetadata(block_metadata) => {
+                    AptosVM::execute_block_metadata_with_call_trace(
+                        block_metadata.clone(),
+                        &executor_view,
+                    )
+                },
+                _ => unreachable!("Transactions without Move execution are handled above"),
+            }
+        };
+        let (_, output, call_traces) = replayed
+            .with_context(|| format!("Failed to replay transaction at version {}", txn.version))?;
+        output
+            .ensure_match_transaction_info(txn.version, &txn.info, None, None)
+            .context("Replayed transaction diverged from the committed transaction")?;
+
+        self.render_call_trace(state_view, &output, payload_kind, call_traces)
+    }
+
+    /// Applies the writes of `output` to `state_view` and converts the call traces recorded
+    /// while executing it into a `CallTrace`. Returns `None` if the transaction stopped before
+    /// its payload called any Move function.
+    ///
+    /// Values are decoded after the writes are applied, so that types from modules the
+    /// transaction published can be resolved.
+    pub fn render_call_trace(
+        &self,
+        state_view: &mut OverlayStateView<DbStateView>,
+        output: &TransactionOutput,
+        payload_kind: CallTracePayloadKind,
+        mut call_traces: CallTraces,
+    ) -> Result<Option<CallTrace>> {
+        state_view.apply_write_set(output.write_set());
+        let call_trace = match call_traces.root() {
+            Some(call_trace) => call_trace,
+            None => return Ok(None),
+        };
+
+        // The events of a failed execution are discarded, so the traced calls wrote none
+        let events = match output.status() {
+            TransactionStatus::Keep(ExecutionStatus::Success) => output.events(),
+            _ => &[],
+        };
+        let call_trace = state_view
+            .as_move_resolver()
+            .as_converter(self.db.clone())
+            .try_into_call_trace(call_trace, events)
+            .context("Failed to convert call trace")?;
+        Ok(Some(CallTrace {
+            payload_kind: Some(payload_kind),
+            ..call_trace
+        }))
+    }
+
     pub fn chain_id(&self) -> ChainId {
         self.chain_id
     }
@@ -620,6 +979,17 @@ impl Context {
         }
 
         let state_view = self.latest_state_view_poem(ledger_info)?;
+        self.render_transactions_with_state_view(ledger_info, &state_view, data)
+    }
+
+    /// Renders transactions that are not committed, e.g. simulated ones, resolving their types
+    /// through `state_view`, which may hold modules they published.
+    pub fn render_transactions_with_state_view<E: InternalError>(
+        &self,
+        ledger_info: &LedgerInfo,
+        state_view: &impl StateView,
+        data: Vec<TransactionOnChainData>,
+    ) -> Result<Vec<aptos_api_types::Transaction>, E> {
         let resolver = state_view.as_move_resolver();
         let converter = resolver.as_converter(self.db.clone());
         let txns: Vec<aptos_api_types::Transaction> = data
@@ -770,6 +1140,50 @@ impl Context {
         callback.await.map_err(anyhow::Error::from)
     }
 
+    pub async fn get_pending_transaction_statuses_by_account(
+        &self,
+        address: AccountAddress,
+    ) -> Result<Vec<PendingTransactionStatus>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetAccountTransactions(
+                address, req_sender,
+            ))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
+    pub async fn get_mempool_summary(&self) -> Result<MempoolSummary> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::GetSummary(req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
+    /// Subscribes to the user transactions committed from now on, as notified to mempool.
+    pub async fn subscribe_to_mempool_commits(
+        &self,
+    ) -> Result<broadcast::Receiver<Arc<CommittedTransactions>>> {
+        let (req_sender, callback) = oneshot::channel();
+
+        self.mp_sender
+            .clone()
+            .send(MempoolClientRequest::SubscribeToCommits(req_sender))
+            .await
+            .map_err(anyhow::Error::from)?;
+
+        callback.await.map_err(anyhow::Error::from)
+    }
+
     pub fn get_transaction_by_version(
         &self,
         version: u64,
@@ -830,6 +1244,77 @@ impl Context {
         }
     }
 
+    /// Returns the user transactions calling `function` in `module` starting at version `start`,
+    /// and the version to start the next page at if there are more.
+    pub fn get_transactions_by_function(
+        &self,
+        module: &ModuleId,
+        function: &IdentStr,
+        start: u64,
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<(Vec<TransactionOnChainData>, Option<u64>)> {
+        let mut versions = self.db.get_transaction_versions_by_function(
+            module,
+            function,
+            start,
+            limit as u64 + 1,
+            ledger_version,
+        )?;
+        let cursor = if versions.len() > limit as usize {
+            versions.pop()
+        } else {
+            None
+        };
+        let txns = versions
+            .into_iter()
+            .map(|version| self.get_transaction_by_version(version, ledger_version))
+            .collect::<Result<_>>()?;
+        Ok((txns, cursor))
+    }
+
+    /// Returns the events of type `type_tag` from `start` on and emitted before `end_version`,
+    /// and the position to start the next page at if there are more.
+    pub fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start: EventCursor,
+        end_version: u64,
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<(Vec<EventWithVersion>, Option<EventCursor>)> {
+        let mut events = self.db.get_events_by_type(
+            type_tag,
+            start.version,
+            start.event_index,
+            end_version,
+            limit as u64 + 1,
+            ledger_version,
+        )?;
+        let cursor = if events.len() > limit as usize {
+            events.pop().map(|(event_index, event)| EventCursor {
+                version: event.transaction_version,
+                event_index,
+            })
+        } else {
+            None
+        };
+        Ok((events.into_iter().map(|(_, event)| event).collect(), cursor))
+    }
+
+    /// Returns the writes to `state_key` starting at version `start`, as `(version, value)`
+    /// pairs where the value is `None` if the write deleted it.
+    pub fn get_state_value_history(
+        &self,
+        state_key: &StateKey,
+        start: u64,
+        limit: u16,
+        ledger_version: u64,
+    ) -> Result<Vec<(Version, Option<StateValue>)>> {
+        self.db
+            .get_state_value_history(state_key, start, limit as u64, ledger_version)
+    }
+
     fn next_bucket(&self, gas_unit_price: u64) -> u64 {
         match self
             .node_config
@@ -9,18 +9,19 @@ use crate::{
     failpoint::fail_point_poem,
     page::Page,
     response::{
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        InternalError,
+        api_disabled, BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus,
+        BasicResultWith404, InternalError,
     },
     ApiTags,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    verify_field_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
-    MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
+    verify_field_identifier, Address, AptosErrorCode, AsConverter, EventCursor, IdentifierWrapper,
+    LedgerInfo, MoveStructTag, VerifyInputWithRecursion, VersionedEvent, U64,
 };
-use aptos_types::event::EventKey;
+use aptos_types::{contract_event::EventWithVersion, event::EventKey};
 use aptos_vm::data_cache::AsMoveResolver;
+use move_core_types::language_storage::{StructTag, TypeTag};
 use poem_openapi::{
     param::{Path, Query},
     OpenApi,
@@ -141,6 +142,95 @@ impl EventsApi {
         let key = account.find_event_key(event_handle.0, field_name.0.into())?;
         self.list(account.latest_ledger_info, accept_type, page, key)
     }
+
+    /// Get events by event type
+    ///
+    /// Returns the events of the given Move type emitted across all accounts, in ascending
+    /// version order, including module events which have no event handle. To get the next page,
+    /// pass the cursor returned in the X-Aptos-Cursor header to `start`. If there is no cursor,
+    /// there are no more events.
+    ///
+    /// The events are only available on nodes maintaining the event by type index, otherwise
+    /// the server responds with a 403. Events at pruned versions are not returned.
+    #[oai(
+        path = "/events/by_type/:event_type",
+        method = "get",
+        operation_id = "get_events_by_type",
+        tag = "ApiTags::Events"
+    )]
+    async fn get_events_by_type(
+        &self,
+        accept_type: AcceptType,
+        /// Move type of the events e.g. `0x1::coin::DepositEvent`
+        event_type: Path<MoveStructTag>,
+        /// Cursor specifying where to start for pagination
+        ///
+        /// This is a ledger version, or a cursor returned in the X-Aptos-Cursor header.
+        /// If not provided, defaults to the first event
+        start: Query<Option<EventCursor>>,
+        /// Ledger version to end the list of events before, exclusive
+        ///
+        /// If not provided, defaults to after the latest version
+        end_version: Query<Option<U64>>,
+        /// Max number of events to retrieve.
+        ///
+        /// If unspecified, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
+        event_type
+            .0
+            .verify(0)
+            .context("'event_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_events_by_type")?;
+        self.context
+            .check_api_output_enabled("Get events by type", &accept_type)?;
+        if !self.context.db.event_by_type_index_enabled() {
+            return Err(api_disabled("Events by type"));
+        }
+        let event_type: StructTag = event_type
+            .0
+            .try_into()
+            .context("Failed to parse given event type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let start = start.0.unwrap_or(EventCursor {
+            version: 0,
+            event_index: 0,
+        });
+        let page = Page::new(
+            Some(start.version),
+            limit.0,
+            self.context.max_events_page_size(),
+        );
+
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_version = latest_ledger_info.version();
+        let type_tag = TypeTag::Struct(Box::new(event_type));
+        let (events, cursor) = self
+            .context
+            .get_events_by_type(
+                &type_tag,
+                start,
+                end_version.0.map_or(u64::MAX, |v| v.0),
+                page.limit(&latest_ledger_info)?,
+                ledger_version,
+            )
+            .context(format!("Failed to find events by type {}", type_tag))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?;
+
+        self.render(latest_ledger_info, accept_type, events)
+            .map(|v| v.with_event_cursor(cursor))
+    }
 }
 
 impl EventsApi {
@@ -170,6 +260,19 @@ impl EventsApi {
                 )
             })?;
 
+        self.render(latest_ledger_info, accept_type, events)
+    }
+
+    /// Render events read from storage
+    ///
+    /// JSON: Convert each event's data to a MoveValue
+    /// BCS: Leave the events as they are in storage
+    fn render(
+        &self,
+        latest_ledger_info: LedgerInfo,
+        accept_type: AcceptType,
+        events: Vec<EventWithVersion>,
+    ) -> BasicResultWith404<Vec<VersionedEvent>> {
         match accept_type {
             AcceptType::Json => {
                 let events = self
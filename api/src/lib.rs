@@ -16,14 +16,17 @@ mod events;
 mod failpoint;
 mod index;
 mod log;
+mod mempool;
 pub mod metrics;
 mod page;
+mod proofs;
 mod response;
 mod runtime;
 mod set_failpoints;
 mod state;
 #[cfg(test)]
 pub mod tests;
+mod transaction_stream;
 mod transactions;
 mod view_function;
 
@@ -44,6 +47,12 @@ pub enum ApiTags {
     /// General information
     General,
 
+    /// Access to the content of mempool
+    Mempool,
+
+    /// Access to data with proofs
+    Proofs,
+
     /// Access to tables
     Tables,
 
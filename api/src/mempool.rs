@@ -0,0 +1,200 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    accept_type::AcceptType,
+    failpoint::fail_point_poem,
+    response::{
+        api_disabled, api_forbidden, BasicError, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResult, BasicResultWith404,
+    },
+    ApiTags, Context,
+};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    Address, AptosErrorCode, AsConverter, LedgerInfo, MempoolBroadcastStatus, MempoolBucket,
+    MempoolRemovalCount, MempoolSummary, MempoolTransaction, MempoolTransactionSource,
+    MempoolTransactionStatus,
+};
+use aptos_mempool::{PendingTransactionStatus, SubmittedBy, TimelineState};
+use aptos_vm::data_cache::AsMoveResolver;
+use poem_openapi::{param::Path, OpenApi};
+use std::{sync::Arc, time::SystemTime};
+
+/// API for inspecting the transactions waiting in the mempool of the node
+pub struct MempoolApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl MempoolApi {
+    /// Get account pending transactions
+    ///
+    /// Retrieves the transactions of an account waiting in the mempool of this node, in
+    /// sequence number order, with their state in mempool: whether they can be included in the
+    /// next block or are parked behind a sequence number gap, their position in the queue,
+    /// their broadcast bucket and status, and the time they spent in mempool.
+    ///
+    /// The state is only the one of this node, other nodes may hold the transactions in a
+    /// different state. Only JSON is supported. The mempool APIs are disabled by default on
+    /// public fullnodes, in which case the server responds with a 403.
+    #[oai(
+        path = "/mempool/accounts/:address/transactions",
+        method = "get",
+        operation_id = "get_account_mempool_transactions",
+        tag = "ApiTags::Mempool"
+    )]
+    async fn get_account_mempool_transactions(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+    ) -> BasicResultWith404<Vec<MempoolTransaction>> {
+        fail_point_poem("endpoint_get_account_mempool_transactions")?;
+        if !self.context.node_config.api.mempool_api_enabled {
+            return Err(api_disabled("Get account mempool transactions"));
+        }
+        if AcceptType::Bcs == accept_type {
+            return Err(api_forbidden(
+                "Get account mempool transactions",
+                "Only JSON is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get account mempool transactions", &accept_type)?;
+
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let statuses = self
+            .context
+            .get_pending_transaction_statuses_by_account(address.0.into())
+            .await
+            .context("Failed to get pending transactions from mempool")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let transactions = self.render_transactions(statuses, &ledger_info)?;
+        BasicResponse::try_from_json((transactions, &ledger_info, BasicResponseStatus::Ok))
+    }
+
+    /// Get mempool summary
+    ///
+    /// Retrieves a summary of the mempool of this node: its size and capacity, the number of
+    /// ready and parked transactions, the number of transactions ready for broadcast in each
+    /// broadcast bucket, and the number of transactions removed since the node started, by
+    /// reason. Only JSON is supported. The mempool APIs are disabled by default on public
+    /// fullnodes, in which case the server responds with a 403.
+    #[oai(
+        path = "/mempool/summary",
+        method = "get",
+        operation_id = "get_mempool_summary",
+        tag = "ApiTags::Mempool"
+    )]
+    async fn get_mempool_summary(&self, accept_type: AcceptType) -> BasicResult<MempoolSummary> {
+        fail_point_poem("endpoint_get_mempool_summary")?;
+        if !self.context.node_config.api.mempool_api_enabled {
+            return Err(api_disabled("Get mempool summary"));
+        }
+        if AcceptType::Bcs == accept_type {
+            return Err(api_forbidden(
+                "Get mempool summary",
+                "Only JSON is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get mempool summary", &accept_type)?;
+
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let summary = self
+            .context
+            .get_mempool_summary()
+            .await
+            .context("Failed to get mempool summary")
+            .map_err(|err| {
+                BasicError::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+            })?;
+
+        let summary = MempoolSummary {
+            num_transactions: (summary.num_transactions as u64).into(),
+            size_bytes: (summary.size_bytes as u64).into(),
+            capacity: (summary.capacity as u64).into(),
+            capacity_bytes: (summary.capacity_bytes as u64).into(),
+            num_ready_transactions: (summary.num_ready_transactions as u64).into(),
+            parking_lot_size: (summary.parking_lot_size as u64).into(),
+            buckets: summary
+                .bucket_sizes
+                .into_iter()
+                .map(|(bucket, size)| MempoolBucket {
+                    bucket,
+                    num_transactions: (size as u64).into(),
+                })
+                .collect(),
+            removed_transactions: summary
+                .removal_counts
+                .into_iter()
+                .map(|(reason, count)| MempoolRemovalCount {
+                    reason,
+                    count: count.into(),
+                })
+                .collect(),
+        };
+        BasicResponse::try_from_json((summary, &ledger_info, BasicResponseStatus::Ok))
+    }
+}
+
+impl MempoolApi {
+    fn render_transactions(
+        &self,
+        statuses: Vec<PendingTransactionStatus>,
+        ledger_info: &LedgerInfo,
+    ) -> Result<Vec<MempoolTransaction>, BasicErrorWith404> {
+        let state_view = self.context.latest_state_view_poem(ledger_info)?;
+        let resolver = state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.context.db.clone());
+        let now = SystemTime::now();
+
+        statuses
+            .into_iter()
+            .map(|status| {
+                let transaction = converter
+                    .try_into_pending_transaction_poem(status.txn)
+                    .context("Failed to convert pending transaction")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            ledger_info,
+                        )
+                    })?;
+                let time_in_mempool = now
+                    .duration_since(status.insertion_time)
+                    .unwrap_or_default();
+                Ok(MempoolTransaction {
+                    transaction,
+                    status: if status.ready {
+                        MempoolTransactionStatus::Ready
+                    } else {
+                        MempoolTransactionStatus::Parked
+                    },
+                    queue_position: status.queue_position.map(|pos| (pos as u64).into()),
+                    ranking_score: status.ranking_score.into(),
+                    bucket: status.bucket,
+                    time_in_mempool_ms: (time_in_mempool.as_millis() as u64).into(),
+                    broadcast_status: match status.timeline_state {
+                        TimelineState::Ready(_) => MempoolBroadcastStatus::Ready,
+                        TimelineState::NotReady => MempoolBroadcastStatus::NotReady,
+                        TimelineState::NonQualified => MempoolBroadcastStatus::NotQualified,
+                    },
+                    submitted_by: match status.submitted_by {
+                        SubmittedBy::Client => MempoolTransactionSource::Client,
+                        SubmittedBy::Downstream => MempoolTransactionSource::Downstream,
+                        SubmittedBy::PeerValidator => MempoolTransactionSource::PeerValidator,
+                    },
+                })
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    accept_type::AcceptType,
+    failpoint::fail_point_poem,
+    response::{
+        api_forbidden, transaction_not_found_by_version, version_pruned, BasicErrorWith404,
+        BasicResponse, BasicResponseStatus, BasicResultWith404,
+    },
+    ApiTags, Context,
+};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    Address, AptosErrorCode, LedgerInfo, MoveStructTag, MoveValue, RawStateValueRequest,
+    StateValueWithProofBcs, TransactionWithProofBcs, U64,
+};
+use aptos_types::{
+    access_path::AccessPath, ledger_info::LedgerInfoWithSignatures, state_proof::StateProof,
+    state_store::state_key::StateKey,
+};
+use move_core_types::language_storage::StructTag;
+use poem_openapi::{
+    param::{Path, Query},
+    payload::Json,
+    OpenApi,
+};
+use std::{convert::TryInto, sync::Arc};
+
+/// API for retrieving data along with the proofs to verify it
+///
+/// Every response carries a [`StateProof`] from the `known_version` of the client to the latest
+/// ledger info, so that a client holding a trusted waypoint can verify the ledger info before
+/// verifying the data against it.
+pub struct ProofsApi {
+    pub context: Arc<Context>,
+}
+
+#[OpenApi]
+impl ProofsApi {
+    /// Get ledger info with proof
+    ///
+    /// Retrieves the latest ledger info with signatures, and the epoch change proof from the
+    /// epoch of `known_version` to the epoch of the latest ledger info.
+    ///
+    /// Only BCS is supported.
+    #[oai(
+        path = "/proofs/ledger_info",
+        method = "get",
+        operation_id = "get_ledger_info_with_proof",
+        tag = "ApiTags::Proofs"
+    )]
+    async fn get_ledger_info_with_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Ledger version already verified by the client
+        known_version: Query<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_ledger_info_with_proof")?;
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get ledger info with proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get ledger info with proof", &accept_type)?;
+
+        let (ledger_info, state_proof) = self.state_proof(known_version.0 .0)?;
+        BasicResponse::try_from_bcs((state_proof, &ledger_info, BasicResponseStatus::Ok))
+    }
+
+    /// Get account resource with proof
+    ///
+    /// Retrieves a resource from a given account, along with the proofs to verify it against
+    /// the latest ledger info. The resource is read from the latest state snapshot, whose
+    /// version is returned with it and can be slightly behind the latest ledger version. If
+    /// the resource doesn't exist, the proof of its absence is returned.
+    ///
+    /// Only BCS is supported.
+    #[oai(
+        path = "/proofs/accounts/:address/resource/:resource_type",
+        method = "get",
+        operation_id = "get_account_resource_with_proof",
+        tag = "ApiTags::Proofs"
+    )]
+    async fn get_account_resource_with_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Name of struct to retrieve e.g. `0x1::account::Account`
+        resource_type: Path<MoveStructTag>,
+        /// Ledger version already verified by the client
+        known_version: Query<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_account_resource_with_proof")?;
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get account resource with proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get account resource with proof", &accept_type)?;
+
+        let resource_type: StructTag = resource_type
+            .0
+            .try_into()
+            .context("Failed to parse given resource type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let access_path = AccessPath::resource_access_path(address.0.into(), resource_type)
+            .context("Failed to build the access path of the resource")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        self.state_value_with_proof(StateKey::access_path(access_path), known_version.0 .0)
+    }
+
+    /// Get state value with proof
+    ///
+    /// Retrieves the state value identified by the key provided in the request body, along with
+    /// the proofs to verify it against the latest ledger info. The value is read from the
+    /// latest state snapshot, whose version is returned with it and can be slightly behind the
+    /// latest ledger version. If the value doesn't exist, the proof of its absence is returned.
+    ///
+    /// Only BCS is supported.
+    #[oai(
+        path = "/proofs/state_value",
+        method = "post",
+        operation_id = "get_state_value_with_proof",
+        tag = "ApiTags::Proofs"
+    )]
+    async fn get_state_value_with_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Request that carries the state key.
+        request: Json<RawStateValueRequest>,
+        /// Ledger version already verified by the client
+        known_version: Query<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_state_value_with_proof")?;
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get state value with proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get state value with proof", &accept_type)?;
+
+        let state_key = bcs::from_bytes(&request.0.key.0)
+            .context(format!(
+                "Failed deserializing state key. key: {}",
+                request.0.key
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        self.state_value_with_proof(state_key, known_version.0 .0)
+    }
+
+    /// Get transaction with proof
+    ///
+    /// Retrieves a transaction and its events by version, along with the proofs to verify them
+    /// against the latest ledger info.
+    ///
+    /// Only BCS is supported.
+    #[oai(
+        path = "/proofs/transactions/by_version/:txn_version",
+        method = "get",
+        operation_id = "get_transaction_with_proof",
+        tag = "ApiTags::Proofs"
+    )]
+    async fn get_transaction_with_proof(
+        &self,
+        accept_type: AcceptType,
+        /// Version of transaction to retrieve
+        txn_version: Path<U64>,
+        /// Ledger version already verified by the client
+        known_version: Query<U64>,
+    ) -> BasicResultWith404<MoveValue> {
+        fail_point_poem("endpoint_get_transaction_with_proof")?;
+        if AcceptType::Json == accept_type {
+            return Err(api_forbidden(
+                "Get transaction with proof",
+                "Only BCS is supported as an AcceptType.",
+            ));
+        }
+        self.context
+            .check_api_output_enabled("Get transaction with proof", &accept_type)?;
+
+        let version = txn_version.0 .0;
+        let (ledger_info, state_proof) = self.state_proof(known_version.0 .0)?;
+        let proof_version = state_proof.latest_ledger_info().version();
+        if version > proof_version {
+            return Err(transaction_not_found_by_version(version, &ledger_info));
+        }
+        if version < ledger_info.oldest_version() {
+            return Err(version_pruned(version, &ledger_info));
+        }
+
+        let transaction_with_proof = self
+            .context
+            .db
+            .get_transaction_by_version(version, proof_version, true)
+            .context(format!("Failed to get transaction by version {}", version))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        BasicResponse::try_from_bcs((
+            TransactionWithProofBcs {
+                transaction_with_proof,
+                state_proof,
+            },
+            &ledger_info,
+            BasicResponseStatus::Ok,
+        ))
+    }
+}
+
+impl ProofsApi {
+    /// Builds the proof from `known_version` to the latest ledger info with signatures
+    fn state_proof(
+        &self,
+        known_version: u64,
+    ) -> Result<(LedgerInfo, StateProof), BasicErrorWith404> {
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let ledger_info_with_sigs: LedgerInfoWithSignatures = self
+            .context
+            .get_latest_ledger_info_with_signatures()
+            .context("Failed to get latest ledger info with signatures")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let latest_version = ledger_info_with_sigs.ledger_info().version();
+        if known_version > latest_version {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "Known version({}) is newer than the latest ledger version({})",
+                    known_version, latest_version
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+
+        let state_proof = self
+            .context
+            .db
+            .get_state_proof_with_ledger_info(known_version, ledger_info_with_sigs)
+            .context(format!(
+                "Failed to get state proof from known version {}",
+                known_version
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        Ok((ledger_info, state_proof))
+    }
+
+    /// Reads the state value from the latest state snapshot covered by the latest ledger info,
+    /// and proves it against that ledger info
+    fn state_value_with_proof(
+        &self,
+        state_key: StateKey,
+        known_version: u64,
+    ) -> BasicResultWith404<MoveValue> {
+        let (ledger_info, state_proof) = self.state_proof(known_version)?;
+        let proof_version = state_proof.latest_ledger_info().version();
+
+        let to_internal = |err: anyhow::Error| {
+            BasicErrorWith404::internal_with_code(err, AptosErrorCode::InternalError, &ledger_info)
+        };
+        let (version, _root_hash) = self
+            .context
+            .db
+            .get_state_snapshot_before(proof_version + 1)
+            .context("Failed to get the latest state snapshot")
+            .map_err(to_internal)?
+            .context(format!(
+                "No state snapshot at or before version {}",
+                proof_version
+            ))
+            .map_err(to_internal)?;
+        let (state_value, state_value_proof) = self
+            .context
+            .db
+            .get_state_value_with_proof_by_version(&state_key, version)
+            .context(format!(
+                "Failed to get state value with proof at version {}",
+                version
+            ))
+            .map_err(to_internal)?;
+        let transaction_info_with_proof = self
+            .context
+            .db
+            .get_transaction_by_version(version, proof_version, false)
+            .context(format!(
+                "Failed to get transaction info with proof at version {}",
+                version
+            ))
+            .map_err(to_internal)?
+            .proof;
+
+        BasicResponse::try_from_bcs((
+            StateValueWithProofBcs {
+                version,
+                state_value,
+                state_value_proof,
+                transaction_info_with_proof,
+                state_proof,
+            },
+            &ledger_info,
+            BasicResponseStatus::Ok,
+        ))
+    }
+}
@@ -471,6 +471,28 @@ macro_rules! generate_success_response {
                 }
                 self
             }
+
+            pub fn with_event_cursor(mut self, new_cursor: Option<aptos_api_types::EventCursor>) -> Self {
+                match self {
+                    $(
+                    [<$enum_name>]::$name(_, _, _, _, _, _, _, _, ref mut cursor) => {
+                        *cursor = new_cursor.map(|c| c.to_string());
+                    }
+                    )*
+                }
+                self
+            }
+
+            pub fn with_version_cursor(mut self, new_cursor: Option<u64>) -> Self {
+                match self {
+                    $(
+                    [<$enum_name>]::$name(_, _, _, _, _, _, _, _, ref mut cursor) => {
+                        *cursor = new_cursor.map(|c| c.to_string());
+                    }
+                    )*
+                }
+                self
+            }
         }
         }
     };
@@ -604,6 +626,14 @@ pub fn transaction_not_found_by_hash<E: NotFoundError>(
     )
 }
 
+pub fn transaction_expired<E: GoneError>(hash: HashValue, ledger_info: &LedgerInfo) -> E {
+    E::gone_with_code(
+        format!("Transaction hash({}) expired before it was committed", hash),
+        AptosErrorCode::TransactionExpired,
+        ledger_info,
+    )
+}
+
 pub fn version_pruned<E: GoneError>(ledger_version: u64, ledger_info: &LedgerInfo) -> E {
     E::gone_with_code(
         format!("Ledger version({}) has been pruned", ledger_version),
@@ -5,16 +5,18 @@
 use crate::{
     accounts::AccountsApi, basic::BasicApi, blocks::BlocksApi, check_size::PostSizeLimit,
     context::Context, error_converter::convert_error, events::EventsApi, index::IndexApi,
-    log::middleware_log, set_failpoints, state::StateApi, transactions::TransactionsApi,
-    view_function::ViewFunctionApi,
+    log::middleware_log, mempool::MempoolApi, proofs::ProofsApi, set_failpoints, state::StateApi,
+    transactions::TransactionsApi, view_function::ViewFunctionApi,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::X_APTOS_CLIENT;
 use aptos_config::config::{ApiConfig, NodeConfig};
+use aptos_event_notifications::LedgerUpdateNotificationListener;
 use aptos_logger::info;
 use aptos_mempool::MempoolClientSender;
 use aptos_storage_interface::DbReader;
 use aptos_types::chain_id::ChainId;
+use futures::StreamExt;
 use poem::{
     http::{header, Method},
     listener::{Listener, RustlsCertificate, RustlsConfig, TcpListener},
@@ -28,17 +30,30 @@ use tokio::runtime::{Handle, Runtime};
 const VERSION: &str = include_str!("../doc/.version");
 
 /// Create a runtime and attach the Poem webserver to it.
+///
+/// If given, the ledger update listener notifies the streaming endpoints of new commits.
+/// Without it, they fall back to polling storage.
 pub fn bootstrap(
     config: &NodeConfig,
     chain_id: ChainId,
     db: Arc<dyn DbReader>,
     mp_sender: MempoolClientSender,
+    ledger_update_listener: Option<LedgerUpdateNotificationListener>,
 ) -> anyhow::Result<Runtime> {
     let max_runtime_workers = get_max_runtime_workers(&config.api);
     let runtime = aptos_runtimes::spawn_named_runtime("api".into(), Some(max_runtime_workers));
 
     let context = Context::new(chain_id, db, mp_sender, config.clone());
 
+    if let Some(mut ledger_update_listener) = ledger_update_listener {
+        let context = context.clone();
+        runtime.spawn(async move {
+            while let Some(notification) = ledger_update_listener.next().await {
+                context.notify_ledger_update(notification.version);
+            }
+        });
+    }
+
     attach_poem_to_runtime(runtime.handle(), context, config, false)
         .context("Failed to attach poem to runtime")?;
 
@@ -61,6 +76,8 @@ pub fn get_api_service(
         BlocksApi,
         EventsApi,
         IndexApi,
+        MempoolApi,
+        ProofsApi,
         StateApi,
         TransactionsApi,
         ViewFunctionApi,
@@ -84,6 +101,12 @@ pub fn get_api_service(
         IndexApi {
             context: context.clone(),
         },
+        MempoolApi {
+            context: context.clone(),
+        },
+        ProofsApi {
+            context: context.clone(),
+        },
         StateApi {
             context: context.clone(),
         },
@@ -4,28 +4,31 @@
 use crate::{
     accept_type::AcceptType,
     failpoint::fail_point_poem,
+    page::Page,
     response::{
-        api_forbidden, build_not_found, module_not_found, resource_not_found, table_item_not_found,
-        BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
-        InternalError,
+        api_disabled, api_forbidden, build_not_found, module_not_found, resource_not_found,
+        table_item_not_found, BadRequestError, BasicErrorWith404, BasicResponse,
+        BasicResponseStatus, BasicResultWith404, GoneError, InternalError,
     },
     ApiTags, Context,
 };
 use anyhow::Context as AnyhowContext;
 use aptos_api_types::{
-    verify_module_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper,
+    verify_module_identifier, Address, AptosErrorCode, AsConverter, IdentifierWrapper, LedgerInfo,
     MoveModuleBytecode, MoveResource, MoveStructTag, MoveValue, RawStateValueRequest,
-    RawTableItemRequest, TableItemRequest, VerifyInput, VerifyInputWithRecursion, U64,
+    RawTableItemRequest, ResourceWrite, StateValueWriteBcs, TableItemRequest, TableItemWrite,
+    VerifyInput, VerifyInputWithRecursion, U64,
 };
 use aptos_state_view::TStateView;
 use aptos_types::{
     access_path::AccessPath,
-    state_store::{state_key::StateKey, table::TableHandle},
+    state_store::{state_key::StateKey, state_value::StateValue, table::TableHandle},
+    transaction::Version,
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use move_core_types::{
     language_storage::{ModuleId, StructTag},
-    resolver::MoveResolver,
+    resolver::{ModuleResolver, MoveResolver},
 };
 use poem_openapi::{
     param::{Path, Query},
@@ -84,6 +87,57 @@ impl StateApi {
         )
     }
 
+    /// Get account resource history
+    ///
+    /// Retrieves the writes to a resource of a given account, in ascending version order. Each
+    /// write carries the resource as it was after the write, or no data if the write deleted
+    /// the resource. To get the next page, set `start` to the version after the last write
+    /// returned. Resources stored in a resource group have no history of their own, and
+    /// requesting one responds with a 400.
+    ///
+    /// The history is only available on nodes maintaining the state value history index,
+    /// otherwise the server responds with a 403. Writes at pruned versions are not returned,
+    /// and starting before the first version indexed by the node responds with a 410.
+    #[oai(
+        path = "/accounts/:address/resource/:resource_type/history",
+        method = "get",
+        operation_id = "get_account_resource_history",
+        tag = "ApiTags::Accounts"
+    )]
+    async fn get_account_resource_history(
+        &self,
+        accept_type: AcceptType,
+        /// Address of account with or without a `0x` prefix
+        address: Path<Address>,
+        /// Name of struct to retrieve the history of e.g. `0x1::account::Account`
+        resource_type: Path<MoveStructTag>,
+        /// Ledger version to start the list of writes at
+        ///
+        /// If not provided, defaults to the first version indexed by the node
+        start: Query<Option<U64>>,
+        /// Max number of writes to retrieve
+        ///
+        /// If not provided, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<ResourceWrite>> {
+        resource_type
+            .0
+            .verify(0)
+            .context("'resource_type' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_account_resource_history")?;
+        self.context
+            .check_api_output_enabled("Get account resource history", &accept_type)?;
+        let page = Page::new(
+            start.0.map(|v| v.0),
+            limit.0,
+            self.context.max_transactions_page_size(),
+        );
+        self.resource_history(&accept_type, address.0, resource_type.0, page)
+    }
+
     /// Get account module
     ///
     /// Retrieves an individual module from a given account and at a specific ledger version. If the
@@ -168,6 +222,56 @@ impl StateApi {
         )
     }
 
+    /// Get table item history
+    ///
+    /// Retrieves the writes to the table item identified by {table_handle} in the path and the
+    /// "key" (TableItemRequest) provided in the request body, in ascending version order. Each
+    /// write carries the value as it was after the write, or no value if the write deleted
+    /// the item. To get the next page, set `start` to the version after the last write returned.
+    ///
+    /// The history is only available on nodes maintaining the state value history index,
+    /// otherwise the server responds with a 403. Writes at pruned versions are not returned,
+    /// and starting before the first version indexed by the node responds with a 410.
+    #[oai(
+        path = "/tables/:table_handle/item/history",
+        method = "post",
+        operation_id = "get_table_item_history",
+        tag = "ApiTags::Tables"
+    )]
+    async fn get_table_item_history(
+        &self,
+        accept_type: AcceptType,
+        /// Table handle hex encoded 32-byte string
+        table_handle: Path<Address>,
+        /// Table request detailing the key type, key, and value type
+        table_item_request: Json<TableItemRequest>,
+        /// Ledger version to start the list of writes at
+        ///
+        /// If not provided, defaults to the first version indexed by the node
+        start: Query<Option<U64>>,
+        /// Max number of writes to retrieve
+        ///
+        /// If not provided, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<TableItemWrite>> {
+        table_item_request
+            .0
+            .verify()
+            .context("'table_item_request' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_table_item_history")?;
+        self.context
+            .check_api_output_enabled("Get table item history", &accept_type)?;
+        let page = Page::new(
+            start.0.map(|v| v.0),
+            limit.0,
+            self.context.max_transactions_page_size(),
+        );
+        self.table_item_history(&accept_type, table_handle.0, table_item_request.0, page)
+    }
+
     /// Get raw table item
     ///
     /// Get a table item at a specific ledger version from the table identified by {table_handle}
@@ -317,6 +421,89 @@ impl StateApi {
         }
     }
 
+    /// Read the writes to a resource, starting at the page's start version
+    ///
+    /// JSON: Convert each value to MoveResource
+    /// BCS: Leave each value encoded as the resource
+    fn resource_history(
+        &self,
+        accept_type: &AcceptType,
+        address: Address,
+        resource_type: MoveStructTag,
+        page: Page,
+    ) -> BasicResultWith404<Vec<ResourceWrite>> {
+        let resource_type: StructTag = resource_type
+            .try_into()
+            .context("Failed to parse given resource type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let access_path = AccessPath::resource_access_path(address.into(), resource_type.clone())
+            .context("Failed to build the access path of the resource")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let state_key = StateKey::access_path(access_path);
+
+        let (ledger_info, _ledger_version, state_view) = self.context.state_view(None)?;
+        let resolver = state_view.as_move_resolver();
+        // Members of a resource group are stored within the group, and the writes to the group
+        // don't tell which members they changed.
+        let group_tag = aptos_framework::get_metadata(
+            &resolver.get_module_metadata(&resource_type.module_id()),
+        )
+        .and_then(|md| {
+            md.struct_attributes
+                .get(resource_type.name.as_str())?
+                .iter()
+                .find_map(|attr| attr.get_resource_group_member())
+        });
+        if let Some(group_tag) = group_tag {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "{} is stored in resource group {}, which has no history per member",
+                    resource_type, group_tag
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        let history = self.state_value_history(&state_key, &page, &ledger_info)?;
+
+        match accept_type {
+            AcceptType::Json => {
+                let converter = resolver.as_converter(self.context.db.clone());
+                let writes = history
+                    .into_iter()
+                    .map(|(version, value)| {
+                        let data = value
+                            .map(|value| converter.try_into_resource(&resource_type, value.bytes()))
+                            .transpose()?;
+                        Ok(ResourceWrite {
+                            version: version.into(),
+                            data,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .context("Failed to deserialize resource data retrieved from DB")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+
+                BasicResponse::try_from_json((writes, &ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Bcs => BasicResponse::try_from_bcs((
+                Self::state_value_writes_bcs(history),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+        }
+    }
+
     /// Retrieve the module
     ///
     /// JSON: Parse ABI and bytecode
@@ -464,6 +651,154 @@ impl StateApi {
         }
     }
 
+    /// Read the writes to a table item, starting at the page's start version
+    pub fn table_item_history(
+        &self,
+        accept_type: &AcceptType,
+        table_handle: Address,
+        table_item_request: TableItemRequest,
+        page: Page,
+    ) -> BasicResultWith404<Vec<TableItemWrite>> {
+        // Parse the key and value types for the table
+        let key_type = table_item_request
+            .key_type
+            .try_into()
+            .context("Failed to parse key_type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        let key = table_item_request.key;
+        let value_type = table_item_request
+            .value_type
+            .try_into()
+            .context("Failed to parse value_type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+
+        let (ledger_info, _ledger_version, state_view) = self.context.state_view(None)?;
+        let resolver = state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.context.db.clone());
+
+        // Convert key to lookup version for DB
+        let vm_key = converter.try_into_vm_value(&key_type, key).map_err(|err| {
+            BasicErrorWith404::bad_request_with_code(
+                err,
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            )
+        })?;
+        let raw_key = vm_key.undecorate().simple_serialize().ok_or_else(|| {
+            BasicErrorWith404::bad_request_with_code(
+                "Failed to serialize table key",
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            )
+        })?;
+
+        let state_key = StateKey::table_item(TableHandle(table_handle.into()), raw_key);
+        let history = self.state_value_history(&state_key, &page, &ledger_info)?;
+
+        match accept_type {
+            AcceptType::Json => {
+                let writes = history
+                    .into_iter()
+                    .map(|(version, value)| {
+                        let value = value
+                            .map(|value| converter.try_into_move_value(&value_type, value.bytes()))
+                            .transpose()?;
+                        Ok(TableItemWrite {
+                            version: version.into(),
+                            value,
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .context("Failed to deserialize table item retrieved from DB")
+                    .map_err(|err| {
+                        BasicErrorWith404::internal_with_code(
+                            err,
+                            AptosErrorCode::InternalError,
+                            &ledger_info,
+                        )
+                    })?;
+
+                BasicResponse::try_from_json((writes, &ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Bcs => BasicResponse::try_from_bcs((
+                Self::state_value_writes_bcs(history),
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+        }
+    }
+
+    /// Read a page of the writes to a state key from the state value history index
+    fn state_value_history(
+        &self,
+        state_key: &StateKey,
+        page: &Page,
+        ledger_info: &LedgerInfo,
+    ) -> Result<Vec<(Version, Option<StateValue>)>, BasicErrorWith404> {
+        if !self.context.db.state_value_history_index_enabled() {
+            return Err(api_disabled("State value history"));
+        }
+        let indexed_since = self
+            .context
+            .db
+            .get_state_value_history_indexed_since()
+            .context("Failed to read the first indexed version from storage")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )
+            })?
+            .ok_or_else(|| api_disabled("State value history"))?;
+        // Writes at pruned versions are skipped, but writes before the index was built are
+        // unknown.
+        let start = std::cmp::max(
+            page.start_option().unwrap_or(indexed_since),
+            ledger_info.oldest_version(),
+        );
+        if start < indexed_since {
+            return Err(BasicErrorWith404::gone_with_code(
+                format!(
+                    "State value history is only indexed since version {}",
+                    indexed_since
+                ),
+                AptosErrorCode::VersionPruned,
+                ledger_info,
+            ));
+        }
+        let limit = page.limit(ledger_info)?;
+        self.context
+            .get_state_value_history(state_key, start, limit, ledger_info.version())
+            .context(format!(
+                "Failed to read the history of {:?} from storage",
+                state_key
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )
+            })
+    }
+
+    fn state_value_writes_bcs(
+        history: Vec<(Version, Option<StateValue>)>,
+    ) -> Vec<StateValueWriteBcs> {
+        history
+            .into_iter()
+            .map(|(version, value)| StateValueWriteBcs {
+                version,
+                value: value.map(|value| value.bytes().to_vec()),
+            })
+            .collect()
+    }
+
     /// Retrieve table item for a specific ledger version
     pub fn raw_table_item(
         &self,
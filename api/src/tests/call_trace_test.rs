@@ -1,12 +1,65 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::{assert_json, current_function_name, pretty, TestContext};
-use super::new_test_context;
+use aptos_config::config::NodeConfig;
+use aptos_types::transaction::{
+    EntryFunction, MultisigTransactionPayload, Script, TransactionArgument,
+};
+use move_core_types::{
+    ident_str,
+    language_storage::{ModuleId, CORE_CODE_ADDRESS},
+    value::{serialize_values, MoveValue},
+};
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+/// A test context with the call trace APIs enabled
+fn new_call_trace_test_context(test_name: String) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config.api.call_trace_enabled = true;
+    new_test_context_with_config(test_name, node_config)
+}
+
+/// Checks that the exclusive gas of every frame is its inclusive gas minus that of its sub
+/// traces, then removes the gas fields so the golden output doesn't change with the gas
+/// schedule. Returns the inclusive gas of `trace`.
+fn check_and_strip_gas(trace: &mut Value) -> u64 {
+    let sub_traces_gas: u64 = trace["sub_traces"]
+        .as_array_mut()
+        .unwrap()
+        .iter_mut()
+        .map(check_and_strip_gas)
+        .sum();
+    let trace = trace.as_object_mut().unwrap();
+    let inclusive: u64 = trace
+        .remove("inclusive_internal_gas")
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let exclusive: u64 = trace
+        .remove("exclusive_internal_gas")
+        .unwrap()
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!(inclusive > 0, "{}", trace["func_name"]);
+    assert_eq!(
+        inclusive,
+        exclusive + sub_traces_gas,
+        "{}",
+        trace["func_name"]
+    );
+    inclusive
+}
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_call_trace_by_hash() {
-    let mut context = new_test_context(current_function_name!());
+    let mut context = new_call_trace_test_context(current_function_name!());
     let account = context.gen_account();
     let txn = context.create_user_account(&account).await;
     context.commit_block(&vec![txn.clone()]).await;
@@ -22,11 +75,491 @@ async fn test_get_call_trace_by_hash() {
         .await;
     assert_json(resp, txns[0].clone());
 
-    let call_trace_resp = context.
-        get(&format!(
+    let mut call_trace_resp = context
+        .get(&format!(
             "/call_trace/by_hash/{}",
             txns[0]["hash"].as_str().unwrap()
         ))
         .await;
+    check_and_strip_gas(&mut call_trace_resp);
     context.check_golden_output(call_trace_resp);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_replays_block_prefix() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let account1 = context.gen_account();
+    let txn1 = context.create_user_account_by(&mut root_account, &account1);
+    let account2 = context.gen_account();
+    let txn2 = context.create_user_account_by(&mut root_account, &account2);
+    context.commit_block(&vec![txn1, txn2.clone()]).await;
+
+    // The second transaction only passes its prologue if the first one is replayed before it.
+    let call_trace_resp = context
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            txn2.committed_hash().to_hex_literal()
+        ))
+        .await;
+    assert_eq!(
+        call_trace_resp["func_name"].as_str().unwrap(),
+        "create_account",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        call_trace_resp["inputs"][0].as_str().unwrap(),
+        account2.address().to_hex_literal(),
+        "{}",
+        pretty(&call_trace_resp)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_of_multisig_transaction() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let owner_account = &mut context.create_account().await;
+    let multisig_account = context
+        .create_multisig_account(owner_account, vec![], 1, 1000)
+        .await;
+    let multisig_payload = bcs::to_bytes(&MultisigTransactionPayload::EntryFunction(
+        EntryFunction::new(
+            ModuleId::new(CORE_CODE_ADDRESS, ident_str!("aptos_account").to_owned()),
+            ident_str!("transfer").to_owned(),
+            vec![],
+            serialize_values(&vec![
+                MoveValue::Address(owner_account.address()),
+                MoveValue::U64(1000),
+            ]),
+        ),
+    ))
+    .unwrap();
+    context
+        .create_multisig_transaction(owner_account, multisig_account, multisig_payload)
+        .await;
+    context
+        .execute_multisig_transaction(owner_account, multisig_account, 202)
+        .await;
+
+    let txns = context
+        .get(&format!(
+            "/accounts/{}/transactions",
+            owner_account.address().to_hex_literal()
+        ))
+        .await;
+    let execute_txn = txns.as_array().unwrap().last().unwrap();
+    let call_trace_resp = context
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            execute_txn["hash"].as_str().unwrap()
+        ))
+        .await;
+    // The trace starts at the inner payload, which is executed on behalf of the multisig account.
+    assert_eq!(
+        call_trace_resp["payload_kind"],
+        "multisig",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        call_trace_resp["func_name"],
+        "transfer",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    // Signers are rendered as their address.
+    assert_eq!(
+        call_trace_resp["inputs"][0].as_str().unwrap(),
+        multisig_account.to_hex_literal(),
+        "{}",
+        pretty(&call_trace_resp)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_of_script() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let receiver = context.gen_account();
+    let path = PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
+        .join("../aptos-move/move-examples/scripts/minter");
+    let package =
+        aptos_framework::BuiltPackage::build(path, aptos_framework::BuildOptions::default())
+            .unwrap();
+    let code = package.extract_script_code().into_iter().next().unwrap();
+    let args = vec![
+        TransactionArgument::Address(receiver.address()),
+        TransactionArgument::U64(1000),
+    ];
+    let txn = root_account.sign_with_transaction_builder(
+        context
+            .transaction_factory()
+            .script(Script::new(code, vec![], args)),
+    );
+    context.commit_block(&vec![txn.clone()]).await;
+
+    let mut call_trace_resp = context
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            txn.committed_hash().to_hex_literal()
+        ))
+        .await;
+    check_and_strip_gas(&mut call_trace_resp);
+    assert_eq!(
+        call_trace_resp["payload_kind"],
+        "script",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        call_trace_resp["func_name"],
+        "main",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        call_trace_resp["inputs"][1].as_str().unwrap(),
+        receiver.address().to_hex_literal(),
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    let callees: Vec<_> = call_trace_resp["sub_traces"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|sub_trace| sub_trace["func_name"].as_str().unwrap())
+        .collect();
+    assert!(
+        callees.contains(&"transfer"),
+        "{}",
+        pretty(&call_trace_resp)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_of_module_publish() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let mut publisher = context.create_account().await;
+    let named_addresses = vec![("resource_groups_primary".to_string(), publisher.address())];
+    let path = PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
+        .join("../aptos-move/move-examples/resource_groups/primary");
+    let payload = TestContext::build_package(path, named_addresses);
+    let txn = context.publish_package(&mut publisher, payload).await;
+
+    let mut call_trace_resp = context
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            txn.committed_hash().to_hex_literal()
+        ))
+        .await;
+    check_and_strip_gas(&mut call_trace_resp);
+    assert_eq!(
+        call_trace_resp["func_name"],
+        "publish_package_txn",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    // The initializer of the published module runs after the publishing call returns, and is
+    // recorded as a call made by the transaction.
+    let init_module = call_trace_resp["sub_traces"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|sub_trace| sub_trace["func_name"] == "init_module")
+        .unwrap_or_else(|| panic!("{}", pretty(&call_trace_resp)));
+    assert_eq!(
+        init_module["module_id"].as_str().unwrap(),
+        format!("{}::primary", publisher.address().to_hex()),
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        init_module["resources"][0]["access"],
+        "move_to",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+}
+
+/// Returns the events of `trace` and its sub traces, in the order they were emitted.
+fn collect_events(trace: &Value, events: &mut Vec<Value>) {
+    events.extend(trace["events"].as_array().unwrap().iter().cloned());
+    for sub_trace in trace["sub_traces"].as_array().unwrap() {
+        collect_events(sub_trace, events);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_events() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let mut user = context.create_account().await;
+    let user_addr = user.address();
+    let named_addresses = vec![("event".to_string(), user_addr)];
+    let path =
+        PathBuf::from(std::env!("CARGO_MANIFEST_DIR")).join("../aptos-move/move-examples/event");
+    let payload = TestContext::build_package(path, named_addresses);
+    context.publish_package(&mut user, payload).await;
+    context
+        .api_execute_entry_function(
+            &mut user,
+            &format!("0x{}::event::emit", user_addr.to_hex()),
+            json!([]),
+            json!(["7"]),
+        )
+        .await;
+
+    let txns = context
+        .get(&format!("/accounts/{}/transactions", user_addr))
+        .await;
+    let txn = &txns.as_array().unwrap()[1];
+    let call_trace_resp = context
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            txn["hash"].as_str().unwrap()
+        ))
+        .await;
+    let mut events = vec![];
+    collect_events(&call_trace_resp, &mut events);
+    // The fee statement is emitted by the epilogue, outside of the traced calls.
+    let txn_events = txn["events"].as_array().unwrap();
+    assert_eq!(
+        events.as_slice(),
+        &txn_events[..txn_events.len() - 1],
+        "{}",
+        pretty(&call_trace_resp)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_of_aborted_transaction() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let account = context.gen_account();
+    let txn1 = context.create_user_account_by(&mut root_account, &account);
+    // Creating the same account a second time aborts in `account::create_account`.
+    let txn2 = context.create_user_account_by(&mut root_account, &account);
+    context.commit_block(&vec![txn1, txn2.clone()]).await;
+
+    let call_trace_resp = context
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            txn2.committed_hash().to_hex_literal()
+        ))
+        .await;
+    assert!(
+        call_trace_resp.get("abort").is_none(),
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    let create_account = &call_trace_resp["sub_traces"][0];
+    assert_eq!(
+        create_account["func_name"],
+        "create_account",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        create_account["abort"]["status_code"],
+        "ABORTED",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        create_account["abort"]["location"],
+        "0x1::account",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        create_account["resources"][0]["access"],
+        "exists",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_of_block_metadata_transaction() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+
+    let block_metadata_txn = context.get("/transactions/by_version/1").await;
+    assert_eq!(block_metadata_txn["type"], "block_metadata_transaction");
+    let call_trace_resp = context
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            block_metadata_txn["hash"].as_str().unwrap()
+        ))
+        .await;
+    assert_eq!(
+        call_trace_resp["payload_kind"],
+        "block_metadata",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    assert_eq!(
+        call_trace_resp["func_name"],
+        "block_prologue",
+        "{}",
+        pretty(&call_trace_resp)
+    );
+    // Only the root frame carries the payload kind.
+    assert!(call_trace_resp["sub_traces"][0]
+        .get("payload_kind")
+        .is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_of_genesis_transaction() {
+    let context = new_call_trace_test_context(current_function_name!());
+    let genesis_txn = context.get("/transactions/by_version/0").await;
+    let resp = context
+        .expect_status_code(400)
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            genesis_txn["hash"].as_str().unwrap()
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "invalid_input", "{}", pretty(&resp));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_by_version() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn.clone()]).await;
+
+    let by_hash = context
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            txn.committed_hash().to_hex_literal()
+        ))
+        .await;
+    let by_version = context.get("/call_trace/by_version/2").await;
+    assert_json(by_version, by_hash);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_by_version_not_found() {
+    let context = new_call_trace_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(404)
+        .get("/call_trace/by_version/10000")
+        .await;
+    assert_eq!(
+        resp["error_code"],
+        "transaction_not_found",
+        "{}",
+        pretty(&resp)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_call_trace_api_disabled() {
+    let context = new_test_context(current_function_name!());
+    let genesis_txn = context.get("/transactions/by_version/0").await;
+    context
+        .expect_status_code(403)
+        .get(&format!(
+            "/call_trace/by_hash/{}",
+            genesis_txn["hash"].as_str().unwrap()
+        ))
+        .await;
+    context
+        .expect_status_code(403)
+        .get("/call_trace/by_version/0")
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_trace_over_limit() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.call_trace_enabled = true;
+    node_config.api.call_trace_max_active_replays = 0;
+    let context = new_test_context_with_config(current_function_name!(), node_config);
+    let resp = context
+        .expect_status_code(503)
+        .get("/call_trace/by_version/0")
+        .await;
+    assert_eq!(resp["error_code"], "too_many_connections");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_call_traces_of_version_range() {
+    let mut context = new_call_trace_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let account1 = context.gen_account();
+    let txn1 = context.create_user_account_by(&mut root_account, &account1);
+    let account2 = context.gen_account();
+    let txn2 = context.create_user_account_by(&mut root_account, &account2);
+    context.commit_block(&vec![txn1, txn2]).await;
+    let account3 = context.gen_account();
+    let txn3 = context.create_user_account_by(&mut root_account, &account3);
+    context.commit_block(&vec![txn3]).await;
+
+    // Start in the middle of the first block, so that its prefix has to be replayed, and run
+    // into the second block, so that traced transactions are applied across block boundaries.
+    let ledger_version = context.get_latest_ledger_info().version();
+    let txns = context
+        .context
+        .get_transactions(3, 100, ledger_version)
+        .unwrap();
+    let call_traces = context.context.get_call_traces(&txns).unwrap();
+    assert_eq!(call_traces.len(), txns.len());
+    for (txn, call_trace) in txns.iter().zip(call_traces) {
+        let path = format!("/call_trace/by_version/{}", txn.version);
+        match call_trace {
+            Some(call_trace) => assert_json(
+                serde_json::to_value(call_trace).unwrap(),
+                context.get(&path).await,
+            ),
+            None => {
+                context.expect_status_code(400).get(&path).await;
+            },
+        }
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_transaction_with_call_trace() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.create_account().await;
+    let receiver = context.gen_account();
+    let request = context
+        .simulation_request(
+            &account,
+            json!({
+                "type": "entry_function_payload",
+                "function": "0x1::aptos_account::transfer",
+                "type_arguments": [],
+                "arguments": [receiver.address().to_hex_literal(), "1"],
+            }),
+        )
+        .await;
+
+    let resp = context
+        .post("/transactions/simulate?trace=true", request.clone())
+        .await;
+    let call_trace = &resp[0]["call_trace"];
+    assert_eq!(
+        call_trace["payload_kind"],
+        "entry_function",
+        "{}",
+        pretty(&resp)
+    );
+    assert_eq!(call_trace["func_name"], "transfer", "{}", pretty(&resp));
+    assert_eq!(
+        call_trace["inputs"][1].as_str().unwrap(),
+        receiver.address().to_hex_literal(),
+        "{}",
+        pretty(&resp)
+    );
+
+    // The call trace is only returned when asked for.
+    let resp = context.post("/transactions/simulate", request).await;
+    assert!(resp[0].get("call_trace").is_none(), "{}", pretty(&resp));
+}
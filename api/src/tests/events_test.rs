@@ -2,10 +2,11 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::NodeConfig;
 use percent_encoding::{utf8_percent_encode, NON_ALPHANUMERIC};
-use serde_json::json;
+use serde_json::{json, Value};
 use std::path::PathBuf;
 
 static ACCOUNT_ADDRESS: &str = "0xa550c18";
@@ -180,6 +181,84 @@ async fn test_module_events() {
         .map_or(false, |t| t.as_str().unwrap() == "0x0"))));
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type() {
+    let mut node_config = NodeConfig::default();
+    node_config
+        .storage
+        .rocksdb_configs
+        .enable_event_by_type_index = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+
+    let mut user = context.create_account().await;
+    let user_addr = user.address();
+    let named_addresses = vec![("event".to_string(), user_addr)];
+    let txn = futures::executor::block_on(async move {
+        let path = PathBuf::from(std::env!("CARGO_MANIFEST_DIR"))
+            .join("../aptos-move/move-examples/event");
+        TestContext::build_package(path, named_addresses)
+    });
+    context.publish_package(&mut user, txn).await;
+
+    context
+        .api_execute_entry_function(
+            &mut user,
+            &format!("0x{}::event::emit", user_addr.to_hex()),
+            json!([]),
+            json!(["7"]),
+        )
+        .await;
+
+    let path = format!("/events/by_type/0x{}::event::MyEvent", user_addr.to_hex());
+    let resp = context.get(&path).await;
+    let events = resp.as_array().unwrap();
+    assert_eq!(events.len(), 7);
+    for (seq, event) in events.iter().enumerate() {
+        assert_eq!(event["data"]["seq"], seq.to_string());
+    }
+
+    // Pages can end in the middle of a version, the cursor points to the next event.
+    let mut paged_events = Vec::new();
+    let mut start = "0".to_owned();
+    loop {
+        let req = warp::test::request()
+            .method("GET")
+            .path(&format!("/v1{}?limit=2&start={}", path, start));
+        let resp = context.reply(req).await;
+        assert_eq!(resp.status(), 200);
+        let page: Vec<Value> = serde_json::from_slice(resp.body()).unwrap();
+        assert!(page.len() <= 2);
+        paged_events.extend(page);
+        match resp.headers().get("X-Aptos-Cursor") {
+            Some(cursor) => start = cursor.to_str().unwrap().to_owned(),
+            None => break,
+        }
+    }
+    assert_eq!(&paged_events, events);
+
+    let version: u64 = events[0]["version"].as_str().unwrap().parse().unwrap();
+    let resp = context.get(&format!("{}?start={}_3", path, version)).await;
+    assert_eq!(resp.as_array().unwrap().len(), 4);
+    let resp = context
+        .get(&format!("{}?start={}", path, version + 1))
+        .await;
+    assert!(resp.as_array().unwrap().is_empty());
+    let resp = context
+        .get(&format!("{}?end_version={}", path, version))
+        .await;
+    assert!(resp.as_array().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_events_by_type_index_disabled() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(403)
+        .get("/events/by_type/0x1::block::NewBlockEvent")
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
+
 // until we have generics in the genesis
 #[ignore]
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -0,0 +1,77 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use aptos_api_test_context::{assert_json, current_function_name};
+use aptos_config::config::NodeConfig;
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_mempool_transactions() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+    let pending_txn = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+
+    let resp = context
+        .get(&format!(
+            "/mempool/accounts/{}/transactions",
+            txn.sender().to_hex_literal()
+        ))
+        .await;
+    let txns = resp.as_array().unwrap();
+    assert_eq!(txns.len(), 1);
+    assert_json(txns[0]["transaction"].clone(), pending_txn);
+    assert_eq!(txns[0]["status"], "ready");
+    assert_eq!(txns[0]["queue_position"], "0");
+    assert_eq!(txns[0]["submitted_by"], "client");
+
+    let resp = context
+        .get(&format!(
+            "/mempool/accounts/{}/transactions",
+            account.address().to_hex_literal()
+        ))
+        .await;
+    assert_eq!(resp.as_array().unwrap().len(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_mempool_summary() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+
+    let resp = context.get("/mempool/summary").await;
+    assert_eq!(resp["num_transactions"], "1");
+    assert_eq!(resp["num_ready_transactions"], "1");
+    assert_eq!(resp["parking_lot_size"], "0");
+    assert!(!resp["buckets"].as_array().unwrap().is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_mempool_api_disabled() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.mempool_api_enabled = false;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+
+    context
+        .expect_status_code(403)
+        .get(&format!(
+            "/mempool/accounts/{}/transactions",
+            account.address().to_hex_literal()
+        ))
+        .await;
+    context
+        .expect_status_code(403)
+        .get("/mempool/summary")
+        .await;
+}
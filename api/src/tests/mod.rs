@@ -4,21 +4,25 @@
 
 mod accounts_test;
 mod blocks_test;
+mod call_trace_test;
 mod converter_test;
 mod events_test;
 mod index_test;
 mod invalid_post_request_test;
+mod mempool_test;
 mod modules;
 mod multisig_transactions_test;
 mod objects;
+mod proofs_test;
 mod resource_groups;
 mod secp256k1_ecdsa;
+mod state_override_test;
 mod state_test;
 mod string_resource_test;
+mod transaction_stream_test;
 mod transaction_vector_test;
 mod transactions_test;
 mod view_function;
-mod call_trace_test;
 
 use aptos_api_test_context::{new_test_context as super_new_test_context, TestContext};
 use aptos_config::config::NodeConfig;
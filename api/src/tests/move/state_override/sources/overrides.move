@@ -0,0 +1,40 @@
+module state_override::overrides {
+    use aptos_std::table::{Self, Table};
+
+    struct Values has key {
+        table: Table<u64, u64>,
+    }
+
+    #[resource_group(scope = global)]
+    struct Group { }
+
+    #[resource_group_member(group = state_override::overrides::Group)]
+    struct Member has key {
+        value: u64,
+    }
+
+    fun init_module(owner: &signer) {
+        let table = table::new();
+        table::add(&mut table, 1, 1);
+        move_to(owner, Values { table });
+    }
+
+    #[view]
+    public fun table_value(owner: address, key: u64): u64 acquires Values {
+        *table::borrow(&borrow_global<Values>(owner).table, key)
+    }
+
+    #[view]
+    public fun member_value(account: address): u64 acquires Member {
+        borrow_global<Member>(account).value
+    }
+
+    #[view]
+    public fun answer(): u64 {
+        1
+    }
+
+    public entry fun assert_answer(expected: u64) {
+        assert!(answer() == expected, 0);
+    }
+}
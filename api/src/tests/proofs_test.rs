@@ -0,0 +1,265 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use aptos_api_test_context::{current_function_name, ApiSpecificConfig, TestContext};
+use aptos_api_types::{mime_types::BCS, StateValueWithProofBcs, TransactionWithProofBcs};
+use aptos_crypto::hash::CryptoHash;
+use aptos_rest_client::{verifying::VerifyingClient, Client};
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    access_path::AccessPath,
+    account_config::AccountResource,
+    state_proof::StateProof,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    trusted_state::TrustedState,
+    waypoint::Waypoint,
+};
+use move_core_types::move_resource::MoveStructType;
+use reqwest::Url;
+use serde::de::DeserializeOwned;
+use std::net::SocketAddr;
+use warp::{
+    http::{
+        header::{ACCEPT_ENCODING, CONTENT_LENGTH, HOST, TRANSFER_ENCODING},
+        HeaderMap, Method, Response,
+    },
+    hyper::body::Bytes,
+    path::FullPath,
+    Filter,
+};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_proofs() {
+    let mut context = new_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let account = context.gen_account();
+    let txn = context.create_user_account_by(&mut root_account, &account);
+    context.commit_block(&vec![txn.clone()]).await;
+
+    let trusted_state = TrustedState::from_epoch_waypoint(genesis_waypoint(&context));
+
+    let state_proof: StateProof = get_bcs(&context, "/proofs/ledger_info?known_version=0").await;
+    let trusted_state = trusted_state
+        .verify_and_ratchet(&state_proof)
+        .unwrap()
+        .new_state()
+        .unwrap();
+    let ledger_info = state_proof.latest_ledger_info();
+    assert_eq!(trusted_state.version(), ledger_info.version());
+
+    let resp: StateValueWithProofBcs = get_bcs(
+        &context,
+        &format!(
+            "/proofs/accounts/{}/resource/0x1::account::Account?known_version={}",
+            root_account.address(),
+            trusted_state.version()
+        ),
+    )
+    .await;
+    assert!(trusted_state
+        .verify_and_ratchet(&resp.state_proof)
+        .unwrap()
+        .new_state()
+        .is_none());
+    assert!(resp.version <= ledger_info.version());
+    resp.transaction_info_with_proof
+        .verify(ledger_info, resp.version)
+        .unwrap();
+    let state_key = StateKey::access_path(
+        AccessPath::resource_access_path(root_account.address(), AccountResource::struct_tag())
+            .unwrap(),
+    );
+    resp.state_value_proof
+        .verify(
+            resp.transaction_info_with_proof
+                .transaction_info()
+                .ensure_state_checkpoint_hash()
+                .unwrap(),
+            state_key.hash(),
+            resp.state_value.as_ref(),
+        )
+        .unwrap();
+    let account_resource: AccountResource =
+        bcs::from_bytes(resp.state_value.unwrap().bytes()).unwrap();
+    assert_eq!(
+        account_resource.authentication_key(),
+        root_account.authentication_key().to_vec()
+    );
+
+    // The block is made of the block metadata, the user transaction and the state checkpoint.
+    let version = ledger_info.version() - 1;
+    let resp: TransactionWithProofBcs = get_bcs(
+        &context,
+        &format!(
+            "/proofs/transactions/by_version/{}?known_version={}",
+            version,
+            trusted_state.version()
+        ),
+    )
+    .await;
+    resp.transaction_with_proof
+        .verify(ledger_info, version)
+        .unwrap();
+    assert_eq!(
+        resp.transaction_with_proof
+            .transaction
+            .try_as_signed_user_txn()
+            .unwrap(),
+        &txn
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_proofs_with_invalid_known_version() {
+    let context = new_test_context(current_function_name!());
+    let version = context.get_latest_ledger_info().version();
+
+    let req = warp::test::request()
+        .method("GET")
+        .path(&context.prepend_path(&format!(
+            "/proofs/ledger_info?known_version={}",
+            version + 1
+        )))
+        .header("Accept", BCS);
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 400);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_proofs_json_forbidden() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(403)
+        .get("/proofs/ledger_info?known_version=0")
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_verifying_client() {
+    let mut context = new_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let account = context.gen_account();
+    let txn = context.create_user_account_by(&mut root_account, &account);
+    context.commit_block(&vec![txn.clone()]).await;
+    let waypoint = genesis_waypoint(&context);
+    let poem_address = match context.api_specific_config {
+        ApiSpecificConfig::V1(address) => address,
+    };
+
+    let client = VerifyingClient::new(client_for(poem_address), waypoint);
+    let ledger_info = client.get_ledger_info().await.unwrap().into_inner();
+    assert_eq!(
+        client.trusted_state().await.version(),
+        ledger_info.ledger_info().version()
+    );
+    let account_resource: AccountResource = client
+        .get_account_resource_bcs(root_account.address(), AccountResource::struct_tag())
+        .await
+        .unwrap()
+        .into_inner()
+        .unwrap();
+    assert_eq!(
+        account_resource.authentication_key(),
+        root_account.authentication_key().to_vec()
+    );
+    // The block is made of the block metadata, the user transaction and the state checkpoint.
+    let transaction_with_proof = client
+        .get_transaction_by_version(ledger_info.ledger_info().version() - 1)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        transaction_with_proof
+            .transaction
+            .try_as_signed_user_txn()
+            .unwrap(),
+        &txn
+    );
+
+    // The ledger info goes through the proxy untouched, but the tampered resource is rejected.
+    let client = VerifyingClient::new(
+        client_for(start_tampering_proxy(poem_address).await),
+        waypoint,
+    );
+    client.get_ledger_info().await.unwrap();
+    assert!(client
+        .get_account_resource_bcs::<AccountResource>(
+            root_account.address(),
+            AccountResource::struct_tag()
+        )
+        .await
+        .is_err());
+}
+
+fn genesis_waypoint(context: &TestContext) -> Waypoint {
+    let genesis_li = context.db.get_epoch_ending_ledger_info(0).unwrap();
+    Waypoint::new_epoch_boundary(genesis_li.ledger_info()).unwrap()
+}
+
+fn client_for(address: SocketAddr) -> Client {
+    Client::new(Url::parse(&format!("http://{}", address)).unwrap())
+}
+
+/// Serves the API at `poem_address` through a proxy that replaces the state values returned
+/// with proofs, leaving their proofs as they are.
+async fn start_tampering_proxy(poem_address: SocketAddr) -> SocketAddr {
+    let proxy = warp::method()
+        .and(warp::path::full())
+        .and(warp::query::raw().or(warp::any().map(String::new)).unify())
+        .and(warp::header::headers_cloned())
+        .and(warp::body::bytes())
+        .and_then(
+            move |method: Method,
+                  path: FullPath,
+                  query: String,
+                  mut headers: HeaderMap,
+                  body: Bytes| async move {
+                headers.remove(HOST);
+                headers.remove(ACCEPT_ENCODING);
+                let resp = reqwest::Client::new()
+                    .request(
+                        method,
+                        format!("http://{}{}?{}", poem_address, path.as_str(), query),
+                    )
+                    .headers(headers)
+                    .body(body)
+                    .send()
+                    .await
+                    .unwrap();
+
+                let status = resp.status();
+                let mut headers = resp.headers().clone();
+                headers.remove(CONTENT_LENGTH);
+                headers.remove(TRANSFER_ENCODING);
+                let mut body = resp.bytes().await.unwrap().to_vec();
+                let tamper = path.as_str().starts_with("/v1/proofs/accounts/")
+                    || path.as_str() == "/v1/proofs/state_value";
+                if status.is_success() && tamper {
+                    let mut state_value_with_proof: StateValueWithProofBcs =
+                        bcs::from_bytes(&body).unwrap();
+                    state_value_with_proof.state_value =
+                        Some(StateValue::from(b"tampered".to_vec()));
+                    body = bcs::to_bytes(&state_value_with_proof).unwrap();
+                }
+
+                let mut response = Response::builder().status(status);
+                *response.headers_mut().unwrap() = headers;
+                Ok::<_, warp::Rejection>(response.body(body).unwrap())
+            },
+        );
+    let (address, server) = warp::serve(proxy).bind_ephemeral(([127, 0, 0, 1], 0));
+    tokio::spawn(server);
+    address
+}
+
+async fn get_bcs<T: DeserializeOwned>(context: &TestContext, path: &str) -> T {
+    let req = warp::test::request()
+        .method("GET")
+        .path(&context.prepend_path(path))
+        .header("Accept", BCS);
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    bcs::from_bytes(resp.body()).unwrap()
+}
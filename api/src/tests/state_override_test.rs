@@ -0,0 +1,263 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::new_test_context;
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_sdk::types::LocalAccount;
+use move_binary_format::{file_format::Bytecode, CompiledModule};
+use move_core_types::ident_str;
+use serde_json::{json, Value};
+use std::path::PathBuf;
+
+const COIN_STORE: &str = "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>";
+const LARGE_BALANCE: &str = "1000000000000000";
+
+/// Returns an override of the APT balance of `account`.
+async fn balance_override(context: &TestContext, account: &LocalAccount, balance: &str) -> Value {
+    let mut coin_store = context
+        .get(&format!(
+            "/accounts/{}/resource/{}",
+            account.address(),
+            COIN_STORE
+        ))
+        .await;
+    coin_store["data"]["coin"]["value"] = json!(balance);
+    json!({
+        "type": "resource",
+        "address": account.address(),
+        "resource_type": COIN_STORE,
+        "data": coin_store["data"],
+    })
+}
+
+/// Publishes the `state_override` test package under a new account.
+async fn publish_overrides_package(context: &mut TestContext) -> LocalAccount {
+    let mut owner = context.create_account().await;
+    let named_addresses = vec![("state_override".to_string(), owner.address())];
+    let txn = futures::executor::block_on(async move {
+        let path =
+            PathBuf::from(std::env!("CARGO_MANIFEST_DIR")).join("src/tests/move/state_override");
+        TestContext::build_package(path, named_addresses)
+    });
+    context.publish_package(&mut owner, txn).await;
+    owner
+}
+
+async fn view(context: &TestContext, function: &str, arguments: Value, overrides: Value) -> Value {
+    context
+        .post(
+            "/view",
+            json!({
+                "function": function,
+                "arguments": arguments,
+                "type_arguments": [],
+                "state_overrides": overrides,
+            }),
+        )
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_with_resource_override() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.create_account().await;
+    let state_override = balance_override(&context, &account, LARGE_BALANCE).await;
+
+    let request = json!({
+        "function": "0x1::coin::balance",
+        "arguments": vec![account.address().to_string()],
+        "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+    });
+    let mut request_with_overrides = request.clone();
+    request_with_overrides["state_overrides"] = json!([state_override]);
+
+    let resp = context.post("/view", request_with_overrides).await;
+    assert_eq!(resp, json!([LARGE_BALANCE]));
+
+    // Overrides are not persisted.
+    let resp = context.post("/view", request).await;
+    assert_eq!(resp, json!(["200000000"]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_with_invalid_override() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.create_account().await;
+
+    context
+        .expect_status_code(400)
+        .post(
+            "/view",
+            json!({
+                "function": "0x1::coin::balance",
+                "arguments": vec![account.address().to_string()],
+                "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+                "state_overrides": [{
+                    "type": "resource",
+                    "address": account.address(),
+                    "resource_type": COIN_STORE,
+                }],
+            }),
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_with_resource_override() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.create_account().await;
+    let receiver = context.gen_account();
+    let payload = json!({
+        "type": "entry_function_payload",
+        "function": "0x1::aptos_account::transfer",
+        "type_arguments": [],
+        "arguments": [receiver.address().to_hex_literal(), "1000000000000"],
+    });
+
+    let resp = context
+        .simulate_transaction(&account, payload.clone(), 200)
+        .await;
+    assert!(!resp[0]["success"].as_bool().unwrap());
+
+    let mut request = context.simulation_request(&account, payload).await;
+    request["state_overrides"] = json!([balance_override(&context, &account, LARGE_BALANCE).await]);
+    let resp = context.post("/transactions/simulate", request).await;
+    assert!(resp[0]["success"].as_bool().unwrap(), "{}", resp);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_module_override() {
+    let mut context = new_test_context(current_function_name!());
+    let owner = publish_overrides_package(&mut context).await;
+    let answer = format!("{}::overrides::answer", owner.address());
+
+    // Load the module into the VM before overriding it.
+    let resp = view(&context, &answer, json!([]), json!([])).await;
+    assert_eq!(resp, json!(["1"]));
+
+    let module = context
+        .get(&format!("/accounts/{}/module/overrides", owner.address()))
+        .await;
+    let bytecode = hex::decode(
+        module["bytecode"]
+            .as_str()
+            .unwrap()
+            .trim_start_matches("0x"),
+    )
+    .unwrap();
+    let mut module = CompiledModule::deserialize(&bytecode).unwrap();
+    let answer_idx = module
+        .function_defs
+        .iter()
+        .position(|def| {
+            module.identifier_at(module.function_handle_at(def.function).name)
+                == ident_str!("answer")
+        })
+        .unwrap();
+    module.function_defs[answer_idx].code.as_mut().unwrap().code =
+        vec![Bytecode::LdU64(2), Bytecode::Ret];
+    let mut bytecode = vec![];
+    module.serialize(&mut bytecode).unwrap();
+    let module_override = json!([{
+        "type": "module",
+        "bytecode": format!("0x{}", hex::encode(bytecode)),
+    }]);
+
+    let resp = view(&context, &answer, json!([]), module_override.clone()).await;
+    assert_eq!(resp, json!(["2"]));
+
+    let account = context.create_account().await;
+    let payload = json!({
+        "type": "entry_function_payload",
+        "function": format!("{}::overrides::assert_answer", owner.address()),
+        "type_arguments": [],
+        "arguments": ["2"],
+    });
+    let resp = context
+        .simulate_transaction(&account, payload.clone(), 200)
+        .await;
+    assert!(!resp[0]["success"].as_bool().unwrap());
+    let mut request = context.simulation_request(&account, payload).await;
+    request["state_overrides"] = module_override;
+    let resp = context.post("/transactions/simulate", request).await;
+    assert!(resp[0]["success"].as_bool().unwrap(), "{}", resp);
+
+    // The overridden module is not cached for later requests.
+    let resp = view(&context, &answer, json!([]), json!([])).await;
+    assert_eq!(resp, json!(["1"]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_table_item_override() {
+    let mut context = new_test_context(current_function_name!());
+    let owner = publish_overrides_package(&mut context).await;
+    let table_value = format!("{}::overrides::table_value", owner.address());
+    let values = context
+        .get(&format!(
+            "/accounts/{}/resource/{}::overrides::Values",
+            owner.address(),
+            owner.address()
+        ))
+        .await;
+    let handle = &values["data"]["table"]["handle"];
+    let arguments = json!([owner.address().to_hex_literal(), "1"]);
+
+    let resp = view(&context, &table_value, arguments.clone(), json!([])).await;
+    assert_eq!(resp, json!(["1"]));
+
+    let resp = view(
+        &context,
+        &table_value,
+        arguments.clone(),
+        json!([{
+            "type": "table_item",
+            "handle": handle,
+            "key_type": "u64",
+            "value_type": "u64",
+            "key": "1",
+            "value": "100",
+        }]),
+    )
+    .await;
+    assert_eq!(resp, json!(["100"]));
+
+    // Items can be given as BCS, and can be created.
+    let resp = view(
+        &context,
+        &table_value,
+        json!([owner.address().to_hex_literal(), "2"]),
+        json!([{
+            "type": "table_item",
+            "handle": handle,
+            "bcs_key": format!("0x{}", hex::encode(bcs::to_bytes(&2u64).unwrap())),
+            "bcs_value": format!("0x{}", hex::encode(bcs::to_bytes(&7u64).unwrap())),
+        }]),
+    )
+    .await;
+    assert_eq!(resp, json!(["7"]));
+
+    let resp = view(&context, &table_value, arguments, json!([])).await;
+    assert_eq!(resp, json!(["1"]));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_resource_group_override() {
+    let mut context = new_test_context(current_function_name!());
+    let owner = publish_overrides_package(&mut context).await;
+    let account = context.create_account().await;
+    let member = format!("{}::overrides::Member", owner.address());
+
+    let resp = view(
+        &context,
+        &format!("{}::overrides::member_value", owner.address()),
+        json!([account.address().to_hex_literal()]),
+        json!([{
+            "type": "resource",
+            "address": account.address(),
+            "resource_type": member,
+            "data": { "value": "5" },
+        }]),
+    )
+    .await;
+    assert_eq!(resp, json!(["5"]));
+}
@@ -1,8 +1,9 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_config::config::NodeConfig;
 use aptos_sdk::{transaction_builder::aptos_stdlib::aptos_token_stdlib, types::LocalAccount};
 use aptos_storage_interface::DbReader;
 use move_core_types::account_address::AccountAddress;
@@ -95,6 +96,160 @@ async fn test_get_account_resource_with_version_too_large() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_history() {
+    let mut context = new_state_value_history_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context.commit_block(&vec![txn]).await;
+    let version = context.get_latest_ledger_info().version();
+
+    let resp = context
+        .get(&get_account_resource_history(
+            &account.address().to_hex_literal(),
+            "0x1::account::Account",
+        ))
+        .await;
+    let writes = resp.as_array().unwrap();
+    assert_eq!(writes.len(), 1);
+    let write_version: u64 = writes[0]["version"].as_str().unwrap().parse().unwrap();
+    assert!(write_version <= version);
+    assert_eq!(
+        writes[0]["data"]["sequence_number"],
+        Value::String("0".to_owned())
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_history_pagination() {
+    let mut context = new_state_value_history_test_context(current_function_name!());
+    let mut account = context.create_account().await;
+    let receiver = context.gen_account();
+    for _ in 0..2 {
+        let txn = context.account_transfer(&mut account, &receiver, 1);
+        context.commit_block(&vec![txn]).await;
+    }
+    let path =
+        get_account_resource_history(&account.address().to_hex_literal(), "0x1::account::Account");
+
+    let history = context.get(&path).await;
+    let history = history.as_array().unwrap();
+    assert!(history.len() >= 3);
+    assert_eq!(
+        history.last().unwrap()["data"]["sequence_number"],
+        Value::String("2".to_owned())
+    );
+
+    let mut pages = Vec::new();
+    let mut start = 0;
+    loop {
+        let page = context
+            .get(&format!("{}?start={}&limit=2", path, start))
+            .await;
+        let page = page.as_array().unwrap();
+        assert!(page.len() <= 2);
+        match page.last() {
+            Some(last) => {
+                let last_version: u64 = last["version"].as_str().unwrap().parse().unwrap();
+                start = last_version + 1;
+                pages.extend(page.iter().cloned());
+            },
+            None => break,
+        }
+    }
+    assert_eq!(&pages, history);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_history_resource_group_member() {
+    let context = new_state_value_history_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(400)
+        .get(&get_account_resource_history(
+            "0x1",
+            "0x1::object::ObjectCore",
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "invalid_input");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_account_resource_history_index_disabled() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(403)
+        .get(&get_account_resource_history(
+            "0xA550C18",
+            "0x1::account::Account",
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_table_item_history() {
+    let mut context = new_state_value_history_test_context(current_function_name!());
+    let mut creator = context.create_account().await;
+    let collection_name = "collection name".to_owned().into_bytes();
+    let collection_txn =
+        creator.sign_with_transaction_builder(context.transaction_factory().payload(
+            aptos_token_stdlib::token_create_collection_script(
+                collection_name.clone(),
+                "description".to_owned().into_bytes(),
+                "uri".to_owned().into_bytes(),
+                20_000_000,
+                vec![false, false, false],
+            ),
+        ));
+    context.commit_block(&vec![collection_txn]).await;
+    let token_txn = creator.sign_with_transaction_builder(context.transaction_factory().payload(
+        aptos_token_stdlib::token_create_token_script(
+            collection_name,
+            "token name".to_owned().into_bytes(),
+            "token description".to_owned().into_bytes(),
+            1,
+            1,
+            "uri".to_owned().into_bytes(),
+            creator.address(),
+            1,
+            0,
+            vec![false, false, false, false, false],
+            vec![],
+            vec![],
+            vec![],
+        ),
+    ));
+    context.commit_block(&vec![token_txn]).await;
+
+    let collections = context
+        .get(&get_account_resource(
+            &creator.address().to_hex_literal(),
+            "0x3::token::Collections",
+        ))
+        .await;
+    let handle: AccountAddress = collections["data"]["collection_data"]["handle"]
+        .as_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    let resp = context
+        .post(
+            &format!("{}/history", get_table_item(handle)),
+            json!({
+                "key_type": "0x1::string::String",
+                "value_type": "0x3::token::CollectionData",
+                "key": "collection name",
+            }),
+        )
+        .await;
+
+    // The collection is created, then its supply counts the new token.
+    let writes = resp.as_array().unwrap();
+    assert_eq!(writes.len(), 2);
+    assert_eq!(writes[0]["value"]["supply"], Value::String("0".to_owned()));
+    assert_eq!(writes[1]["value"]["supply"], Value::String("1".to_owned()));
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_get_account_module() {
     let mut context = new_test_context(current_function_name!());
@@ -298,6 +453,15 @@ async fn test_get_table_item() {
     assert_table_item(ctx, &nested_table, "u8", "u8", 2, 3).await;
 }
 
+fn new_state_value_history_test_context(test_name: String) -> TestContext {
+    let mut node_config = NodeConfig::default();
+    node_config
+        .storage
+        .rocksdb_configs
+        .enable_state_value_history_index = true;
+    new_test_context_with_config(test_name, node_config)
+}
+
 fn get_account_resource(address: &str, struct_tag: &str) -> String {
     format!("/accounts/{}/resource/{}", address, struct_tag)
 }
@@ -309,6 +473,10 @@ fn get_account_resource_with_version(address: &str, struct_tag: &str, version: u
     )
 }
 
+fn get_account_resource_history(address: &str, struct_tag: &str) -> String {
+    format!("/accounts/{}/resource/{}/history", address, struct_tag)
+}
+
 fn get_account_module(address: &str, name: &str) -> String {
     format!("/accounts/{}/module/{}", address, name)
 }
@@ -0,0 +1,153 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::{new_test_context, new_test_context_with_config};
+use crate::transaction_stream::{stream_transactions, TransactionFilter};
+use aptos_api_test_context::{current_function_name, TestContext};
+use aptos_api_types::Transaction;
+use aptos_cached_packages::aptos_stdlib;
+use aptos_config::config::NodeConfig;
+use aptos_types::{account_address::AccountAddress, transaction::SignedTransaction};
+use futures::{stream::BoxStream, StreamExt};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_from_start() {
+    let context = new_test_context(current_function_name!());
+    let stream_context = Arc::new(context.context.clone());
+    let permit = stream_context
+        .try_acquire_transaction_stream_permit()
+        .unwrap();
+
+    let txns: Vec<_> = stream_transactions(stream_context, 0, TransactionFilter::default(), permit)
+        .take(2)
+        .collect()
+        .await;
+    assert!(matches!(txns[0], Transaction::GenesisTransaction(_)));
+    assert_eq!(txns[1].version(), Some(1));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_by_sender() {
+    let mut context = new_test_context(current_function_name!());
+    let mut account = context.create_account().await;
+    let receiver = context.gen_account();
+    let stream_context = Arc::new(context.context.clone());
+    let permit = stream_context
+        .try_acquire_transaction_stream_permit()
+        .unwrap();
+
+    // Only transactions committed after the stream started are followed.
+    let start_version = context.get_latest_ledger_info().version() + 1;
+    let filter = TransactionFilter {
+        sender: Some(account.address()),
+        ..TransactionFilter::default()
+    };
+    let mut stream = stream_transactions(stream_context, start_version, filter, permit);
+
+    let txn = context.account_transfer(&mut account, &receiver, 1);
+    context.commit_block(&vec![txn]).await;
+
+    let txn = tokio::time::timeout(Duration::from_secs(10), stream.next())
+        .await
+        .unwrap()
+        .unwrap();
+    match txn {
+        Transaction::UserTransaction(txn) => {
+            assert_eq!(txn.request.sender, account.address().into());
+        },
+        txn => panic!("Expected a user transaction, got {:?}", txn),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_by_function() {
+    let mut context = new_test_context(current_function_name!());
+    let filter = TransactionFilter {
+        function: Some((
+            ModuleId::new(
+                AccountAddress::ONE,
+                Identifier::new("aptos_account").unwrap(),
+            ),
+            Identifier::new("set_allow_direct_coin_transfers").unwrap(),
+        )),
+        ..TransactionFilter::default()
+    };
+    let (mut stream, config_update) =
+        stream_after_transfer_and_config_update(&mut context, filter).await;
+
+    // The transfer calls a different function and is skipped.
+    assert_next_is(&mut stream, &config_update).await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_transactions_by_event_type() {
+    let mut context = new_test_context(current_function_name!());
+    let filter = TransactionFilter {
+        event_type: Some(TypeTag::Struct(Box::new(
+            StructTag::from_str("0x1::aptos_account::DirectCoinTransferConfigUpdatedEvent")
+                .unwrap(),
+        ))),
+        ..TransactionFilter::default()
+    };
+    let (mut stream, config_update) =
+        stream_after_transfer_and_config_update(&mut context, filter).await;
+
+    // Only the config update emits the event, the transfer is skipped.
+    assert_next_is(&mut stream, &config_update).await;
+}
+
+#[tokio::test]
+async fn test_stream_transactions_over_limit() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.transaction_stream_max_active_connections = 0;
+    let context = new_test_context_with_config(current_function_name!(), node_config);
+
+    context
+        .expect_status_code(503)
+        .get("/transactions/stream")
+        .await;
+}
+
+/// Opens a stream at the next version and commits a transfer followed by a call to
+/// `aptos_account::set_allow_direct_coin_transfers` from the same account.
+async fn stream_after_transfer_and_config_update(
+    context: &mut TestContext,
+    filter: TransactionFilter,
+) -> (BoxStream<'static, Transaction>, SignedTransaction) {
+    let mut account = context.create_account().await;
+    let receiver = context.gen_account();
+    let stream_context = Arc::new(context.context.clone());
+    let permit = stream_context
+        .try_acquire_transaction_stream_permit()
+        .unwrap();
+    let start_version = context.get_latest_ledger_info().version() + 1;
+    let stream = stream_transactions(stream_context, start_version, filter, permit);
+
+    let transfer = context.account_transfer(&mut account, &receiver, 1);
+    let config_update =
+        account.sign_with_transaction_builder(context.transaction_factory().payload(
+            aptos_stdlib::aptos_account_set_allow_direct_coin_transfers(true),
+        ));
+    context
+        .commit_block(&[transfer, config_update.clone()])
+        .await;
+    (stream, config_update)
+}
+
+async fn assert_next_is(stream: &mut BoxStream<'static, Transaction>, txn: &SignedTransaction) {
+    let next = tokio::time::timeout(Duration::from_secs(10), stream.next())
+        .await
+        .unwrap()
+        .unwrap();
+    match next {
+        Transaction::UserTransaction(next) => {
+            assert_eq!(next.info.hash, txn.clone().committed_hash().into());
+        },
+        next => panic!("Expected a user transaction, got {:?}", next),
+    }
+}
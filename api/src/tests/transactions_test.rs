@@ -13,10 +13,10 @@ use aptos_crypto::{
 };
 use aptos_sdk::types::LocalAccount;
 use aptos_types::{
-    account_address::AccountAddress,
+    account_address::{create_multisig_account_address, AccountAddress},
     transaction::{
         authenticator::{AuthenticationKey, TransactionAuthenticator},
-        EntryFunction, Script, SignedTransaction,
+        EntryFunction, MultisigTransactionPayload, Script, SignedTransaction, TransactionPayload,
     },
     utility_coin::APTOS_COIN_TYPE,
 };
@@ -488,6 +488,184 @@ async fn test_get_pending_transaction_by_hash() {
     context.check_golden_output(not_found);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_wait_transaction_by_hash() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let body = bcs::to_bytes(&txn).unwrap();
+    let pending_txn = context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", body)
+        .await;
+    let txn_hash = pending_txn["hash"].as_str().unwrap();
+
+    // The transaction is still pending when the timeout passes.
+    let resp = context
+        .get(&format!(
+            "/transactions/wait_by_hash/{}?timeout_secs=0",
+            txn_hash
+        ))
+        .await;
+    assert_eq!(resp["type"], "pending_transaction");
+
+    context.commit_mempool_txns(1).await;
+    let resp = context
+        .get(&format!("/transactions/wait_by_hash/{}", txn_hash))
+        .await;
+    assert_eq!(resp["type"], "user_transaction");
+    assert_eq!(resp["hash"], txn_hash);
+
+    let not_found = context
+        .expect_status_code(404)
+        .get("/transactions/wait_by_hash/0xdadfeddcca7cb6396c735e9094c76c6e4e9cb3e3ef814730693aed59bd87b31d")
+        .await;
+    assert_eq!(not_found["error_code"], "transaction_not_found");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_wait_transaction_by_hash_returns_on_commit() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    let txn_hash = txn.clone().committed_hash().to_hex_literal();
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn).unwrap())
+        .await;
+
+    let waiter = context.clone();
+    let (resp, _) = futures::join!(
+        waiter.get(&format!("/transactions/wait_by_hash/{}", txn_hash)),
+        async {
+            // Let the request start waiting before the transaction is committed.
+            sleep(Duration::from_secs(1)).await;
+            context.commit_mempool_txns(1).await;
+        }
+    );
+    assert_eq!(resp["type"], "user_transaction");
+    assert_eq!(resp["hash"], txn_hash);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_wait_transaction_by_hash_expired() {
+    let mut context = new_test_context(current_function_name!());
+    let mut root_account = context.root_account().await;
+    let receiver = context.gen_account();
+    let ledger_timestamp_secs = context.get_latest_ledger_info().timestamp() / 1_000_000;
+    let txn = root_account.sign_with_transaction_builder(
+        context
+            .transaction_factory()
+            .account_transfer(receiver.address(), 1)
+            .expiration_timestamp_secs(ledger_timestamp_secs + 1),
+    );
+    let txn_hash = txn.clone().committed_hash().to_hex_literal();
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn).unwrap())
+        .await;
+
+    let waiter = context.expect_status_code(410);
+    let (resp, _) = futures::join!(
+        waiter.get(&format!("/transactions/wait_by_hash/{}", txn_hash)),
+        async {
+            sleep(Duration::from_secs(1)).await;
+            // Each block moves the ledger time half a second forward, past the expiration.
+            for _ in 0..3 {
+                context.commit_block(&[]).await;
+            }
+        }
+    );
+    assert_eq!(resp["error_code"], "transaction_expired");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_wait_transaction_by_hash_over_limit() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.wait_by_hash_max_active_connections = 0;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.gen_account();
+    let txn = context.create_user_account(&account).await;
+    context
+        .expect_status_code(202)
+        .post_bcs_txn("/transactions", bcs::to_bytes(&txn).unwrap())
+        .await;
+
+    let resp = context
+        .expect_status_code(503)
+        .get(&format!(
+            "/transactions/wait_by_hash/{}",
+            txn.committed_hash().to_hex_literal()
+        ))
+        .await;
+    assert_eq!(resp["error_code"], "too_many_connections");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_by_function() {
+    let mut node_config = NodeConfig::default();
+    node_config
+        .storage
+        .rocksdb_configs
+        .enable_transaction_by_function_index = true;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let mut root_account = context.root_account().await;
+    let txns: Vec<_> = (0..3)
+        .map(|_| {
+            let account = context.gen_account();
+            context.create_user_account_by(&mut root_account, &account)
+        })
+        .collect();
+    context.commit_block(&txns).await;
+
+    let path = "/v1/transactions/by_function/0x1::aptos_account::create_account";
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("{}?limit=2", path));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    let cursor = resp
+        .headers()
+        .get("X-Aptos-Cursor")
+        .expect("Cursor header was missing")
+        .to_str()
+        .unwrap()
+        .to_owned();
+    let page: Vec<serde_json::Value> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(page.len(), 2);
+    assert_eq!(
+        page[0]["hash"],
+        txns[0].clone().committed_hash().to_hex_literal()
+    );
+    assert_eq!(
+        page[1]["hash"],
+        txns[1].clone().committed_hash().to_hex_literal()
+    );
+
+    let req = warp::test::request()
+        .method("GET")
+        .path(&format!("{}?limit=2&start={}", path, cursor));
+    let resp = context.reply(req).await;
+    assert_eq!(resp.status(), 200);
+    assert!(!resp.headers().contains_key("X-Aptos-Cursor"));
+    let page: Vec<serde_json::Value> = serde_json::from_slice(resp.body()).unwrap();
+    assert_eq!(page.len(), 1);
+    assert_eq!(
+        page[0]["hash"],
+        txns[2].clone().committed_hash().to_hex_literal()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_get_transactions_by_function_index_disabled() {
+    let context = new_test_context(current_function_name!());
+    let resp = context
+        .expect_status_code(403)
+        .get("/transactions/by_function/0x1::aptos_account::create_account")
+        .await;
+    assert_eq!(resp["error_code"], "api_disabled");
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_signing_message_with_entry_function_payload() {
     let mut context = new_test_context(current_function_name!());
@@ -1293,6 +1471,251 @@ async fn test_gas_estimation_static_override() {
     context.check_golden_output(resp);
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_bundle_on_shared_state() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.create_account().await;
+    let receiver = context.gen_account();
+    let transfer = |to: &LocalAccount| {
+        json!({
+            "type": "entry_function_payload",
+            "function": "0x1::aptos_account::transfer",
+            "type_arguments": [],
+            "arguments": [to.address().to_hex_literal(), "1000"],
+        })
+    };
+
+    // The receiver only exists after the first transaction, so the second one only succeeds
+    // when both are simulated together.
+    let resp = context
+        .simulate_transaction(&receiver, transfer(&account), 200)
+        .await;
+    assert!(!resp[0]["success"].as_bool().unwrap());
+
+    let first = context
+        .simulation_request(&account, transfer(&receiver))
+        .await;
+    let second = context
+        .simulation_request(&receiver, transfer(&account))
+        .await;
+    let resp = context
+        .post(
+            "/transactions/simulate_bundle",
+            json!({ "transactions": [first, second] }),
+        )
+        .await;
+    let resp = resp.as_array().unwrap();
+    assert_eq!(resp.len(), 2);
+    assert!(resp[0]["success"].as_bool().unwrap(), "{:?}", resp);
+    assert!(resp[1]["success"].as_bool().unwrap(), "{:?}", resp);
+
+    // Nothing is persisted.
+    context
+        .expect_status_code(404)
+        .get(&format!("/accounts/{}", receiver.address()))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_empty_bundle() {
+    let context = new_test_context(current_function_name!());
+    context
+        .expect_status_code(400)
+        .post(
+            "/transactions/simulate_bundle",
+            json!({ "transactions": [] }),
+        )
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_bundle_multisig_lifecycle() {
+    let mut context = new_test_context(current_function_name!());
+    let mut owner_1 = context.create_account().await;
+    let mut owner_2 = context.create_account().await;
+    let multisig_address =
+        create_multisig_account_address(owner_1.address(), owner_1.sequence_number());
+    let entry_function = |function: &str, arguments: serde_json::Value| {
+        json!({
+            "type": "entry_function_payload",
+            "function": function,
+            "type_arguments": [],
+            "arguments": arguments,
+        })
+    };
+    let multisig_payload = bcs::to_bytes(&MultisigTransactionPayload::EntryFunction(
+        EntryFunction::new(
+            ModuleId::new(
+                AccountAddress::ONE,
+                Identifier::new("aptos_account").unwrap(),
+            ),
+            Identifier::new("transfer").unwrap(),
+            vec![],
+            vec![
+                bcs::to_bytes(&owner_2.address()).unwrap(),
+                bcs::to_bytes(&1000u64).unwrap(),
+            ],
+        ),
+    ))
+    .unwrap();
+
+    // The multisig account is created, funded, and its transaction proposed, approved and
+    // executed within the bundle.
+    let mut transactions = vec![];
+    let payloads = [
+        (
+            true,
+            entry_function(
+                "0x1::multisig_account::create_with_owners",
+                json!([[owner_2.address().to_hex_literal()], "2", [], []]),
+            ),
+        ),
+        (
+            true,
+            entry_function(
+                "0x1::aptos_account::transfer",
+                json!([multisig_address.to_hex_literal(), "1000"]),
+            ),
+        ),
+        (
+            true,
+            entry_function(
+                "0x1::multisig_account::create_transaction",
+                json!([
+                    multisig_address.to_hex_literal(),
+                    format!("0x{}", hex::encode(&multisig_payload)),
+                ]),
+            ),
+        ),
+        (
+            false,
+            entry_function(
+                "0x1::multisig_account::approve_transaction",
+                json!([multisig_address.to_hex_literal(), "1"]),
+            ),
+        ),
+        (
+            false,
+            json!({
+                "type": "multisig_payload",
+                "multisig_address": multisig_address.to_hex_literal(),
+            }),
+        ),
+    ];
+    for (from_owner_1, payload) in payloads {
+        let sender = if from_owner_1 {
+            &mut owner_1
+        } else {
+            &mut owner_2
+        };
+        transactions.push(context.simulation_request(sender, payload).await);
+        sender.increment_sequence_number();
+    }
+    let resp = context
+        .post(
+            "/transactions/simulate_bundle",
+            json!({ "transactions": transactions }),
+        )
+        .await;
+    let resp = resp.as_array().unwrap();
+    assert_eq!(resp.len(), 5);
+    for txn in resp {
+        assert!(txn["success"].as_bool().unwrap(), "{:?}", resp);
+    }
+    let withdraw_event = resp[4]["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|event| event["type"] == "0x1::coin::WithdrawEvent")
+        .unwrap();
+    assert_eq!(
+        AccountAddress::from_hex_literal(
+            withdraw_event["guid"]["account_address"].as_str().unwrap()
+        )
+        .unwrap(),
+        multisig_address
+    );
+    assert_eq!(withdraw_event["data"]["amount"], "1000");
+
+    // Nothing is persisted.
+    context
+        .expect_status_code(404)
+        .get(&format!("/accounts/{}", multisig_address))
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_simulate_bundle_publish_and_call_module() {
+    let mut context = new_test_context(current_function_name!());
+    let mut account = context.create_account().await;
+    let named_addresses = vec![("state_override".to_string(), account.address())];
+    let publish = futures::executor::block_on(async move {
+        let path =
+            PathBuf::from(std::env!("CARGO_MANIFEST_DIR")).join("src/tests/move/state_override");
+        TestContext::build_package(path, named_addresses)
+    });
+    let args = match publish {
+        TransactionPayload::EntryFunction(entry_function) => entry_function.into_inner().3,
+        _ => unreachable!(),
+    };
+    let metadata: Vec<u8> = bcs::from_bytes(&args[0]).unwrap();
+    let code: Vec<Vec<u8>> = bcs::from_bytes(&args[1]).unwrap();
+
+    let publish = context
+        .simulation_request(
+            &account,
+            json!({
+                "type": "entry_function_payload",
+                "function": "0x1::code::publish_package_txn",
+                "type_arguments": [],
+                "arguments": [
+                    format!("0x{}", hex::encode(metadata)),
+                    code.iter()
+                        .map(|module| format!("0x{}", hex::encode(module)))
+                        .collect::<Vec<_>>(),
+                ],
+            }),
+        )
+        .await;
+    account.increment_sequence_number();
+    // The signing message of a call to a module that is not published yet can't be encoded, so
+    // the call reuses the (invalid) signature of another payload.
+    let mut call = context
+        .simulation_request(
+            &account,
+            json!({
+                "type": "entry_function_payload",
+                "function": "0x1::aptos_account::transfer",
+                "type_arguments": [],
+                "arguments": [account.address().to_hex_literal(), "0"],
+            }),
+        )
+        .await;
+    call["payload"] = json!({
+        "type": "entry_function_payload",
+        "function": format!("{}::overrides::assert_answer", account.address()),
+        "type_arguments": [],
+        "arguments": ["1"],
+    });
+
+    let resp = context
+        .post(
+            "/transactions/simulate_bundle",
+            json!({ "transactions": [publish, call] }),
+        )
+        .await;
+    let resp = resp.as_array().unwrap();
+    assert_eq!(resp.len(), 2);
+    assert!(resp[0]["success"].as_bool().unwrap(), "{:?}", resp);
+    assert!(resp[1]["success"].as_bool().unwrap(), "{:?}", resp);
+
+    // Nothing is persisted.
+    context
+        .expect_status_code(404)
+        .get(&format!("/accounts/{}/module/overrides", account.address()))
+        .await;
+}
+
 fn gen_string(len: u64) -> String {
     let mut rng = thread_rng();
     std::iter::repeat(())
@@ -1,9 +1,11 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use super::new_test_context;
+use super::{new_test_context, new_test_context_with_config};
 use aptos_api_test_context::current_function_name;
-use serde_json::json;
+use aptos_config::config::NodeConfig;
+use aptos_sdk::types::account_address::AccountAddress;
+use serde_json::{json, Value};
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_simple_view() {
@@ -111,3 +113,141 @@ async fn test_view_tuple() {
         .await;
     context.check_golden_output_no_prune(resp);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch() {
+    let mut context = new_test_context(current_function_name!());
+    let creator = &mut context.gen_account();
+    let owner = &mut context.gen_account();
+    let txn1 = context.mint_user_account(creator).await;
+    let txn2 = context.account_transfer(creator, owner, 100_000);
+
+    context.commit_block(&vec![txn1, txn2]).await;
+
+    let resp = context
+        .post(
+            "/view/batch",
+            json!([
+                {
+                    "function":"0x1::coin::balance",
+                    "arguments": vec![owner.address().to_string()],
+                    "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+                },
+                {
+                    "function":"0x1::aptos_account::assert_account_exists",
+                    "arguments": vec![owner.address().to_string()],
+                    "type_arguments": [],
+                },
+            ]),
+        )
+        .await;
+
+    // A failed call does not fail the batch.
+    let results = resp.as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0]["values"], json!(["100000"]));
+    assert!(results[0].get("error").is_none());
+    assert!(results[1].get("values").is_none());
+    assert!(results[1]["error"].is_string());
+}
+
+fn balance_call(address: AccountAddress) -> Value {
+    json!({
+        "function": "0x1::coin::balance",
+        "arguments": vec![address.to_string()],
+        "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+    })
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch_partial_failure() {
+    let mut context = new_test_context(current_function_name!());
+    let account = context.create_account().await;
+    let min_gas = context
+        .context
+        .node_config
+        .api
+        .min_gas_view_function_batch_call;
+
+    let resp = context
+        .post(
+            "/view/batch",
+            json!([
+                balance_call(account.address()),
+                {
+                    "function": "0x1::coin::does_not_exist",
+                    "arguments": [],
+                    "type_arguments": [],
+                },
+                {
+                    "function": "0x1::coin::balance",
+                    "arguments": vec![account.address().to_string()],
+                    "type_arguments": vec!["0x1::aptos_coin::AptosCoin"],
+                    "state_overrides": [{
+                        "type": "resource",
+                        "address": account.address(),
+                        "resource_type": "0x1::coin::CoinStore<0x1::aptos_coin::AptosCoin>",
+                    }],
+                },
+                balance_call(account.address()),
+            ]),
+        )
+        .await;
+
+    let results = resp.as_array().unwrap();
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[0]["values"], results[3]["values"]);
+    assert!(results[0]["values"].is_array());
+    // Calls that fail before they are executed are still charged the minimum gas.
+    for result in &results[1..3] {
+        assert!(result.get("values").is_none());
+        assert!(result["error"].is_string());
+        assert_eq!(result["gas_used"], min_gas.to_string());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch_out_of_gas() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.view_function_batch_gas_multiplier = 1;
+    node_config.api.min_gas_view_function_batch_call = node_config.api.max_gas_view_function / 2;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.create_account().await;
+
+    let resp = context
+        .post(
+            "/view/batch",
+            json!([
+                balance_call(account.address()),
+                balance_call(account.address()),
+                balance_call(account.address()),
+            ]),
+        )
+        .await;
+
+    let results = resp.as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert!(results[0]["values"].is_array());
+    assert!(results[1]["values"].is_array());
+    assert_eq!(results[2]["error"], "The batch ran out of gas");
+    assert_eq!(results[2]["gas_used"], "0");
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_view_batch_too_large() {
+    let mut node_config = NodeConfig::default();
+    node_config.api.max_view_function_batch_size = 1;
+    let mut context = new_test_context_with_config(current_function_name!(), node_config);
+    let account = context.create_account().await;
+
+    context
+        .expect_status_code(400)
+        .post(
+            "/view/batch",
+            json!([
+                balance_call(account.address()),
+                balance_call(account.address()),
+            ]),
+        )
+        .await;
+}
@@ -0,0 +1,191 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{context::Context, response::BasicError};
+use anyhow::{bail, Context as AnyhowContext};
+use aptos_api_types::{Transaction, TransactionOnChainData};
+use aptos_logger::warn;
+use aptos_types::{
+    account_address::AccountAddress,
+    transaction::{
+        EntryFunction, MultisigTransactionPayload, Transaction as CoreTransaction,
+        TransactionPayload, Version,
+    },
+};
+use futures::stream::{self, BoxStream, StreamExt};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, TypeTag},
+};
+use std::{collections::VecDeque, sync::Arc, time::Duration};
+use tokio::sync::{watch, OwnedSemaphorePermit};
+
+/// How long to wait for a ledger update notification before checking storage again. Commit
+/// notifications are not forwarded to the API in every setup, e.g. in tests.
+pub const LEDGER_UPDATE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often a keep-alive comment is sent on an idle stream.
+pub const STREAM_KEEP_ALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Filters of a transaction stream. A transaction is streamed if it matches all the filters
+/// that are set.
+#[derive(Clone, Debug, Default)]
+pub struct TransactionFilter {
+    /// Only user transactions sent by this account
+    pub sender: Option<AccountAddress>,
+    /// Only user transactions calling this entry function, directly or through a multisig
+    /// account
+    pub function: Option<(ModuleId, Identifier)>,
+    /// Only transactions emitting an event of this type
+    pub event_type: Option<TypeTag>,
+}
+
+impl TransactionFilter {
+    pub fn matches(&self, txn: &TransactionOnChainData) -> bool {
+        if let Some(sender) = &self.sender {
+            match &txn.transaction {
+                CoreTransaction::UserTransaction(user_txn) if user_txn.sender() == *sender => {},
+                _ => return false,
+            }
+        }
+        if let Some((module, function)) = &self.function {
+            let entry_function = match &txn.transaction {
+                CoreTransaction::UserTransaction(user_txn) => entry_function(user_txn.payload()),
+                _ => None,
+            };
+            match entry_function {
+                Some(entry_function)
+                    if entry_function.module() == module
+                        && entry_function.function() == function.as_ident_str() => {},
+                _ => return false,
+            }
+        }
+        if let Some(event_type) = &self.event_type {
+            if !txn
+                .events
+                .iter()
+                .any(|event| event.type_tag() == event_type)
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Returns the entry function a payload calls, if any.
+fn entry_function(payload: &TransactionPayload) -> Option<&EntryFunction> {
+    match payload {
+        TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+        TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+            Some(MultisigTransactionPayload::EntryFunction(entry_function)) => Some(entry_function),
+            None => None,
+        },
+        _ => None,
+    }
+}
+
+/// Returns a stream of the committed transactions matching `filter`, starting at
+/// `start_version` and following the chain as new transactions are committed.
+///
+/// Storage is read a page at a time on the blocking pool. The stream ends if reading fails,
+/// e.g. because the next version was pruned while the client fell behind. `permit` is held
+/// until the stream is dropped.
+pub fn stream_transactions(
+    context: Arc<Context>,
+    start_version: Version,
+    filter: TransactionFilter,
+    permit: OwnedSemaphorePermit,
+) -> BoxStream<'static, Transaction> {
+    let state = TransactionStream {
+        ledger_updates: context.subscribe_to_ledger_updates(),
+        context,
+        next_version: start_version,
+        filter: Arc::new(filter),
+        buffer: VecDeque::new(),
+        _permit: permit,
+    };
+    stream::unfold(state, |mut state| async move {
+        match state.next().await {
+            Ok(txn) => Some((txn, state)),
+            Err(err) => {
+                warn!("Transaction stream ended: {:#}", err);
+                None
+            },
+        }
+    })
+    .boxed()
+}
+
+struct TransactionStream {
+    context: Arc<Context>,
+    ledger_updates: watch::Receiver<Version>,
+    next_version: Version,
+    filter: Arc<TransactionFilter>,
+    buffer: VecDeque<Transaction>,
+    _permit: OwnedSemaphorePermit,
+}
+
+impl TransactionStream {
+    async fn next(&mut self) -> anyhow::Result<Transaction> {
+        loop {
+            if let Some(txn) = self.buffer.pop_front() {
+                return Ok(txn);
+            }
+            let context = self.context.clone();
+            let next_version = self.next_version;
+            let filter = self.filter.clone();
+            let page =
+                tokio::task::spawn_blocking(move || read_page(&context, next_version, &filter))
+                    .await
+                    .context("Failed to read transactions")??;
+            match page {
+                Some((next_version, txns)) => {
+                    self.next_version = next_version;
+                    self.buffer.extend(txns);
+                },
+                None => {
+                    // Wait for the next commit, or for the poll interval to pass.
+                    let _ = tokio::time::timeout(
+                        LEDGER_UPDATE_POLL_INTERVAL,
+                        self.ledger_updates.changed(),
+                    )
+                    .await;
+                },
+            }
+        }
+    }
+}
+
+/// Reads the page of transactions starting at `next_version`, and returns the version after it
+/// and the transactions of the page matching `filter`. Returns `None` if there are no new
+/// transactions.
+fn read_page(
+    context: &Context,
+    next_version: Version,
+    filter: &TransactionFilter,
+) -> anyhow::Result<Option<(Version, Vec<Transaction>)>> {
+    let ledger_info = context.get_latest_ledger_info_wrapped()?;
+    let ledger_version = ledger_info.version();
+    if next_version > ledger_version {
+        return Ok(None);
+    }
+    if next_version < ledger_info.oldest_ledger_version.0 {
+        bail!("Ledger version({}) has been pruned", next_version);
+    }
+
+    let limit = std::cmp::min(
+        ledger_version - next_version + 1,
+        context.max_transactions_page_size() as u64,
+    ) as u16;
+    let data = context
+        .get_transactions(next_version, limit, ledger_version)
+        .context("Failed to read raw transactions from storage")?;
+    let next_version = next_version + data.len() as u64;
+
+    let data = data.into_iter().filter(|txn| filter.matches(txn)).collect();
+    let txns = context
+        .render_transactions_non_sequential::<BasicError>(&ledger_info, data)
+        .map_err(anyhow::Error::from)?;
+    Ok(Some((next_version, txns)))
+}
@@ -11,17 +11,32 @@ use crate::{
     generate_error_response, generate_success_response,
     page::Page,
     response::{
-        api_disabled, api_forbidden, transaction_not_found_by_hash,
+        api_disabled, api_forbidden, transaction_expired, transaction_not_found_by_hash,
         transaction_not_found_by_version, version_pruned, BadRequestError, BasicError,
         BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResult, BasicResultWith404,
-        InsufficientStorageError, InternalError,
+        InsufficientStorageError, InternalError, ServiceUnavailableError,
     },
+    transaction_stream::{stream_transactions, TransactionFilter, STREAM_KEEP_ALIVE_INTERVAL},
     ApiTags,
 };
 use anyhow::{anyhow, Context as AnyhowContext};
-use aptos_api_types::{verify_function_identifier, verify_module_identifier, Address, AptosError, AptosErrorCode, AsConverter, EncodeSubmissionRequest, GasEstimation, GasEstimationBcs, HashValue, HexEncodedBytes, LedgerInfo, MoveType, PendingTransaction, SubmitTransactionRequest, Transaction, TransactionData, TransactionOnChainData, TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction, VerifyInput, VerifyInputWithRecursion, MAX_RECURSIVE_TYPES_ALLOWED, U64, ViewRequest};
+use aptos_api_types::{
+    call_trace::CallTrace, verify_function_identifier, verify_module_identifier, Address,
+    AptosError, AptosErrorCode, AsConverter, EncodeSubmissionRequest, EntryFunctionId,
+    GasEstimation, GasEstimationBcs, HashValue, HexEncodedBytes, LedgerInfo, MoveStructTag,
+    MoveType, PendingTransaction, SimulateBundleRequest, SimulateTransactionRequest,
+    SubmitTransactionRequest, Transaction, TransactionData, TransactionOnChainData,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult, UserTransaction,
+    VerifyInput, VerifyInputWithRecursion, MAX_RECURSIVE_TYPES_ALLOWED, U64,
+};
 use aptos_crypto::{hash::CryptoHash, signing_message};
+use aptos_mempool::CommittedTransactions;
+use aptos_state_view::{
+    account_with_state_view::AccountWithStateView, overlay_state_view::OverlayStateView, StateView,
+};
+use aptos_storage_interface::state_view::DbStateView;
 use aptos_types::{
+    account_address::AccountAddress,
     account_config::CoinStoreResource,
     account_view::AccountView,
     mempool_status::MempoolStatusCode,
@@ -32,15 +47,21 @@ use aptos_types::{
     vm_status::StatusCode,
 };
 use aptos_vm::{data_cache::AsMoveResolver, storage_adapter::AsExecutorView, AptosVM};
+use futures::{future, stream::BoxStream};
+use move_core_types::{
+    identifier::Identifier,
+    language_storage::{ModuleId, StructTag, TypeTag},
+};
 use poem_openapi::{
     param::{Path, Query},
-    payload::Json,
+    payload::{EventStream, Json},
     ApiRequest, OpenApi,
 };
-use std::sync::Arc;
-use aptos_api_types::call_trace::{CallTrace};
-use aptos_types::transaction::Version;
-use move_core_types::call_trace::CallTraces;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{broadcast, broadcast::error::RecvError};
 
 generate_success_response!(SubmitTransactionResponse, (202, Accepted));
 
@@ -86,6 +107,46 @@ pub enum SubmitTransactionPost {
     Bcs(Bcs),
 }
 
+// Simulations take the same content types, but a JSON request may also override the state the
+// transaction is simulated against.
+#[derive(ApiRequest, Debug)]
+pub enum SimulateTransactionPost {
+    #[oai(content_type = "application/json")]
+    Json(Json<SimulateTransactionRequest>),
+
+    #[oai(content_type = "application/x.aptos.signed_transaction+bcs")]
+    Bcs(Bcs),
+}
+
+impl VerifyInput for SimulateTransactionPost {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            SimulateTransactionPost::Json(inner) => inner.0.verify(),
+            SimulateTransactionPost::Bcs(_) => Ok(()),
+        }
+    }
+}
+
+// Bundle simulations take either a SimulateBundleRequest as JSON or a list of signed
+// transactions as BCS, like batch submissions.
+#[derive(ApiRequest, Debug)]
+pub enum SimulateBundlePost {
+    #[oai(content_type = "application/json")]
+    Json(Json<SimulateBundleRequest>),
+
+    #[oai(content_type = "application/x.aptos.signed_transaction+bcs")]
+    Bcs(Bcs),
+}
+
+impl VerifyInput for SimulateBundlePost {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            SimulateBundlePost::Json(inner) => inner.0.verify(),
+            SimulateBundlePost::Bcs(_) => Ok(()),
+        }
+    }
+}
+
 impl VerifyInput for SubmitTransactionPost {
     fn verify(&self) -> anyhow::Result<()> {
         match self {
@@ -167,6 +228,95 @@ impl TransactionsApi {
         self.list(&accept_type, page)
     }
 
+    /// Stream transactions
+    ///
+    /// Stream committed transactions as server-sent events, in the same format as /transactions,
+    /// starting at ledger version `start` and following the chain as new transactions are
+    /// committed. The data of each event is one transaction.
+    ///
+    /// Transactions can be filtered by `sender`, entry `function` (directly or through a
+    /// multisig account) and emitted `event_type`. A transaction is sent if it matches all the
+    /// given filters. To resume after a disconnect, reconnect with `start` set to the version
+    /// after the last transaction received.
+    ///
+    /// If `start` has been pruned, the server responds with a 410. If the client falls behind
+    /// the pruning window, the stream ends. The number of open streams is limited by the node,
+    /// and requests over the limit are rejected with a 503.
+    #[oai(
+        path = "/transactions/stream",
+        method = "get",
+        operation_id = "stream_transactions",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn stream_transactions(
+        &self,
+        /// Ledger version to start streaming from
+        ///
+        /// If not provided, only transactions committed after the request are streamed
+        start: Query<Option<U64>>,
+        /// Only stream user transactions sent by this account
+        sender: Query<Option<Address>>,
+        /// Only stream user transactions calling this entry function, e.g. `0x1::coin::transfer`
+        function: Query<Option<EntryFunctionId>>,
+        /// Only stream transactions emitting an event of this type
+        event_type: Query<Option<MoveStructTag>>,
+    ) -> Result<EventStream<BoxStream<'static, Transaction>>, BasicErrorWith404> {
+        fail_point_poem("endpoint_stream_transactions")?;
+        self.context
+            .check_api_output_enabled("Stream transactions", &AcceptType::Json)?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+
+        let start_version = match start.0 {
+            Some(start) => {
+                if start.0 < ledger_info.oldest_ledger_version.0 {
+                    return Err(version_pruned(start.0, &ledger_info));
+                }
+                start.0
+            },
+            None => ledger_info.version() + 1,
+        };
+        let event_type = event_type
+            .0
+            .map(|event_type| {
+                StructTag::try_from(event_type).map(|tag| TypeTag::Struct(Box::new(tag)))
+            })
+            .transpose()
+            .context("Invalid event type")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+        let filter = TransactionFilter {
+            sender: sender.0.map(Into::into),
+            function: function
+                .0
+                .map(|function| (function.module.into(), function.name.into())),
+            event_type,
+        };
+
+        let permit = self
+            .context
+            .try_acquire_transaction_stream_permit()
+            .ok_or_else(|| {
+                BasicErrorWith404::service_unavailable_with_code(
+                    "Too many open transaction streams",
+                    AptosErrorCode::TooManyConnections,
+                    &ledger_info,
+                )
+            })?;
+
+        Ok(EventStream::new(stream_transactions(
+            self.context.clone(),
+            start_version,
+            filter,
+            permit,
+        ))
+        .keep_alive(STREAM_KEEP_ALIVE_INTERVAL))
+    }
+
     /// Get transaction by hash
     ///
     /// Look up a transaction by its hash. This is the same hash that is returned
@@ -201,11 +351,65 @@ impl TransactionsApi {
             .await
     }
 
+    /// Wait for transaction by hash
+    ///
+    /// Look up a transaction by its hash like `/transactions/by_hash`, but if the
+    /// transaction is still pending in mempool, hold the request until it is committed,
+    /// it expires, or `timeout_secs` pass, whichever comes first.
+    ///
+    /// A committed transaction is returned as soon as it is committed. A transaction that
+    /// expired before being committed returns a 410 with the `transaction_expired` error
+    /// code, and an unknown transaction returns a 404. If the timeout passes first, the
+    /// pending transaction is returned.
+    ///
+    /// The number of requests waiting at the same time is limited by the node. Requests
+    /// over the limit return a 503 with the `too_many_connections` error code.
+    #[oai(
+        path = "/transactions/wait_by_hash/:txn_hash",
+        method = "get",
+        operation_id = "wait_transaction_by_hash",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn wait_transaction_by_hash(
+        &self,
+        accept_type: AcceptType,
+        /// Hash of transaction to wait for
+        txn_hash: Path<HashValue>,
+        /// Maximum number of seconds to wait for
+        ///
+        /// If not provided, or larger than the node's maximum, the node's maximum is used.
+        timeout_secs: Query<Option<u64>>,
+    ) -> BasicResultWith404<Transaction> {
+        fail_point_poem("endpoint_wait_transaction_by_hash")?;
+        self.context
+            .check_api_output_enabled("Wait transaction by hash", &accept_type)?;
+        self.wait_transaction_by_hash_inner(&accept_type, txn_hash.0, timeout_secs.0)
+            .await
+    }
+
+    /// Get call trace by hash
+    ///
+    /// Replays a committed user transaction against the state it was originally executed on
+    /// and returns the tree of Move calls made by its payload. Entry function, script, multisig
+    /// and module bundle payloads are supported, as well as the `block_prologue` call of block
+    /// metadata transactions. The root frame is tagged with the kind of payload that was traced.
+    ///
+    /// Each frame carries its decoded arguments and return values, the events it emitted, the
+    /// resources it accessed and the gas it consumed. If the transaction failed, the call that
+    /// failed carries the abort code and location.
+    ///
+    /// The replay goes through the full user transaction flow, so the returned trace reflects
+    /// the signer, gas charging and everything the transaction observed on chain.
+    ///
+    /// As the transactions preceding it in its block are executed again, the call trace APIs
+    /// are disabled unless enabled in the API config of the node, in which case the server
+    /// responds with a 403. Requests beyond the maximum number of concurrent replays are
+    /// rejected with a 503.
     #[oai(
-    path = "/call_trace/by_hash/:txn_hash",
-    method = "get",
-    operation_id = "get_call_trace_by_hash",
-    tag = "ApiTags::Transactions"
+        path = "/call_trace/by_hash/:txn_hash",
+        method = "get",
+        operation_id = "get_call_trace_by_hash",
+        tag = "ApiTags::Transactions"
     )]
     async fn get_transaction_call_trace_by_hash(
         &self,
@@ -214,6 +418,9 @@ impl TransactionsApi {
         txn_hash: Path<HashValue>,
     ) -> BasicResultWith404<CallTrace> {
         fail_point_poem("endpoint_transaction_call_trace_by_hash")?;
+        if !self.context.node_config.api.call_trace_enabled {
+            return Err(api_disabled("Get transaction call trace by hash"));
+        }
         self.context
             .check_api_output_enabled("Get transaction call trace by hash", &accept_type)?;
         let ledger_info = self.context.get_latest_ledger_info()?;
@@ -232,106 +439,65 @@ impl TransactionsApi {
             .context(format!("Failed to find transaction with hash: {}", hash))
             .map_err(|_| transaction_not_found_by_hash(hash, &ledger_info))?;
 
-        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
-        let resolver = state_view.as_move_resolver();
-        let transaction = match txn_data {
-            TransactionData::OnChain(txn) => {
-                let timestamp =
-                    self.context.get_block_timestamp(&ledger_info, txn.version)?;
-                resolver
-                    .as_converter(self.context.db.clone())
-                    .try_into_onchain_transaction(timestamp, txn)
-                    .context("Failed to convert on chain transaction to Transaction")
-                    .map_err(|err| {
-                        BasicErrorWith404::internal_with_code(
-                            err,
-                            AptosErrorCode::InternalError,
-                            &ledger_info,
-                        )
-                    })?
-            },
-            TransactionData::Pending(txn) => resolver
-                .as_converter(self.context.db.clone())
-                .try_into_pending_transaction(*txn)
-                .context("Failed to convert on pending transaction to Transaction")
-                .map_err(|err| {
-                    BasicErrorWith404::internal_with_code(
-                        err,
-                        AptosErrorCode::InternalError,
-                        &ledger_info,
-                    )
-                })?,
-        };
+        match txn_data {
+            TransactionData::OnChain(txn) => self.get_call_trace_inner(txn, &ledger_info),
+            TransactionData::Pending(_) => Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "Transaction {} is still pending, call traces require a committed transaction",
+                    hash
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            )),
+        }
+    }
 
-        let call_trace = match transaction {
-            Transaction::PendingTransaction(_) => {
-                Ok(CallTraces::new())
-            }
-            Transaction::UserTransaction(user_transaction) => {
-                // use the previous ledger version where it doesn't take the effect
-                let previous_ledger_version  = user_transaction.info.version.0 - 1;
-                let (requested_ledger_info, _) = self
-                    .context
-                    .get_latest_ledger_info_and_verify_lookup_version(
-                        Some(Version::from(previous_ledger_version)),
-                    )?;
-                let state_view = self
-                    .context
-                    .state_view_at_version(Version::from(previous_ledger_version))
-                    .map_err(|err| {
-                        BasicErrorWith404::bad_request_with_code(
-                            err,
-                            AptosErrorCode::InternalError,
-                            &requested_ledger_info,
-                        )
-                    })?;
-                let payload = user_transaction.request.payload;
-                match payload {
-                    aptos_api_types::TransactionPayload::EntryFunctionPayload(entry_func_payload) => {
-                        let entry_func = resolver
-                            .as_converter(self.context.db.clone())
-                            .convert_view_function(ViewRequest {
-                                function: entry_func_payload.function,
-                                type_arguments: entry_func_payload.type_arguments,
-                                arguments: entry_func_payload.arguments
-                            })
-                            .map_err(|err| {
-                                BasicErrorWith404::bad_request_with_code(
-                                    err,
-                                    AptosErrorCode::InvalidInput,
-                                    &requested_ledger_info,
-                                )
-                            })?;
-                        AptosVM::get_call_trace(
-                            &state_view.as_executor_view(),
-                            entry_func.module().clone(),
-                            entry_func.function().to_owned(),
-                            entry_func.ty_args().to_owned(),
-                            entry_func.args().to_owned(),
-                            self.context.node_config.api.max_gas_view_function,
-                        )
-                    }
-                    aptos_api_types::TransactionPayload::ScriptPayload(_) => {Ok(CallTraces::new())}
-                    aptos_api_types::TransactionPayload::ModuleBundlePayload(_) => {Ok(CallTraces::new())}
-                    aptos_api_types::TransactionPayload::MultisigPayload(_) => {Ok(CallTraces::new())}
-                }
-            }
-            Transaction::GenesisTransaction(_) => {Ok(CallTraces::new())}
-            Transaction::BlockMetadataTransaction(_) => {Ok(CallTraces::new())}
-            Transaction::StateCheckpointTransaction(_) => {Ok(CallTraces::new())}
-        };
+    /// Get call trace by version
+    ///
+    /// Same as /call_trace/by_hash, but looks the transaction up by its version. If the version
+    /// has been pruned, a 410 will be returned.
+    #[oai(
+        path = "/call_trace/by_version/:txn_version",
+        method = "get",
+        operation_id = "get_call_trace_by_version",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_transaction_call_trace_by_version(
+        &self,
+        accept_type: AcceptType,
+        /// Version of transaction to retrieve
+        txn_version: Path<U64>,
+    ) -> BasicResultWith404<CallTrace> {
+        fail_point_poem("endpoint_transaction_call_trace_by_version")?;
+        if !self.context.node_config.api.call_trace_enabled {
+            return Err(api_disabled("Get transaction call trace by version"));
+        }
+        self.context
+            .check_api_output_enabled("Get transaction call trace by version", &accept_type)?;
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let version = txn_version.0 .0;
+        let txn_data = self
+            .get_by_version(version, &ledger_info)
+            .context(format!("Failed to get transaction by version {}", version))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
 
-        match call_trace {
-            Ok(mut _call_traces) => {
-                BasicResponse::try_from_json((CallTrace::from(_call_traces.root().unwrap()), &ledger_info, BasicResponseStatus::Ok))
-            }
-            Err(_) => {
-                Err(BasicErrorWith404::bad_request_with_code(
-                        "Cannot get ing status",
-                        AptosErrorCode::InvalidInput,
-                        &ledger_info,
-                ))
-            }
+        match txn_data {
+            GetByVersionResponse::Found(TransactionData::OnChain(txn)) => {
+                self.get_call_trace_inner(txn, &ledger_info)
+            },
+            GetByVersionResponse::Found(TransactionData::Pending(_)) => {
+                unreachable!("Transactions looked up by version are always committed")
+            },
+            GetByVersionResponse::VersionTooNew => {
+                Err(transaction_not_found_by_version(version, &ledger_info))
+            },
+            GetByVersionResponse::VersionTooOld => Err(version_pruned(version, &ledger_info)),
         }
     }
 
@@ -397,6 +563,57 @@ impl TransactionsApi {
         self.list_by_account(&accept_type, page, address.0)
     }
 
+    /// Get transactions by function
+    ///
+    /// Retrieves on-chain committed user transactions calling an entry function, directly or
+    /// through a multisig account, in ascending version order. To get the next page, pass the
+    /// cursor returned in the X-Aptos-Cursor header to `start`. If there is no cursor, there
+    /// are no more transactions.
+    ///
+    /// The transactions are only available on nodes maintaining the transaction by function
+    /// index, otherwise the server responds with a 403. Transactions at pruned versions are not
+    /// returned.
+    #[oai(
+        path = "/transactions/by_function/:function_id",
+        method = "get",
+        operation_id = "get_transactions_by_function",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn get_transactions_by_function(
+        &self,
+        accept_type: AcceptType,
+        /// Entry function called by the transactions, e.g. `0x1::coin::transfer`
+        function_id: Path<EntryFunctionId>,
+        /// Cursor specifying where to start for pagination
+        ///
+        /// This is a ledger version. If not provided, defaults to the first transaction
+        start: Query<Option<U64>>,
+        /// Max number of transactions to retrieve.
+        ///
+        /// If not provided, defaults to default page size
+        limit: Query<Option<u16>>,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        function_id
+            .0
+            .verify()
+            .context("'function_id' invalid")
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code_no_info(err, AptosErrorCode::InvalidInput)
+            })?;
+        fail_point_poem("endpoint_get_transactions_by_function")?;
+        self.context
+            .check_api_output_enabled("Get transactions by function", &accept_type)?;
+        if !self.context.db.transaction_by_function_index_enabled() {
+            return Err(api_disabled("Transactions by function"));
+        }
+        let page = Page::new(
+            start.0.map(|v| v.0),
+            limit.0,
+            self.context.max_transactions_page_size(),
+        );
+        self.list_by_function(&accept_type, page, function_id.0)
+    }
+
     /// Submit transaction
     ///
     /// This endpoint accepts transaction submissions in two formats.
@@ -442,7 +659,8 @@ impl TransactionsApi {
         self.context
             .check_api_output_enabled("Submit transaction", &accept_type)?;
         let ledger_info = self.context.get_latest_ledger_info()?;
-        let signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
+        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
+        let signed_transaction = self.get_signed_transaction(&ledger_info, data, &state_view)?;
         self.create(&accept_type, &ledger_info, signed_transaction)
             .await
     }
@@ -524,6 +742,14 @@ impl TransactionsApi {
     ///
     /// To use this endpoint with BCS, you must submit a SignedTransaction
     /// encoded as BCS. See SignedTransaction in types/src/transaction/mod.rs.
+    ///
+    /// If `trace` is set, the call trace of the transaction's payload is returned in the
+    /// `call_trace` field of the simulated transaction, in the same format as /call_trace/by_hash.
+    /// This is only supported for JSON responses.
+    ///
+    /// JSON requests may also contain `state_overrides`, which replace resources, table items or
+    /// modules before the transaction is simulated, e.g. to simulate with a larger balance or
+    /// against a module that has not been published yet.
     #[oai(
         path = "/transactions/simulate",
         method = "post",
@@ -542,7 +768,9 @@ impl TransactionsApi {
         /// If set to true, the transaction will use a higher price than the original
         /// estimate.
         estimate_prioritized_gas_unit_price: Query<Option<bool>>,
-        data: SubmitTransactionPost,
+        /// If set to true, the call trace of the transaction will be returned
+        trace: Query<Option<bool>>,
+        data: SimulateTransactionPost,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
         data.verify()
             .context("Simulated transaction invalid")
@@ -559,7 +787,29 @@ impl TransactionsApi {
         self.context
             .check_api_output_enabled("Simulate transaction", &accept_type)?;
         let ledger_info = self.context.get_latest_ledger_info()?;
-        let mut signed_transaction = self.get_signed_transaction(&ledger_info, data)?;
+        let (data, state_overrides) = match data {
+            SimulateTransactionPost::Json(request) => (
+                SubmitTransactionPost::Json(Json(request.0.transaction)),
+                request.0.state_overrides.unwrap_or_default(),
+            ),
+            SimulateTransactionPost::Bcs(data) => (SubmitTransactionPost::Bcs(data), vec![]),
+        };
+        let state_view = self
+            .context
+            .apply_state_overrides(
+                self.context.latest_state_view_poem(&ledger_info)?,
+                state_overrides,
+            )
+            .context("Failed to apply state overrides")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+        let mut signed_transaction =
+            self.get_signed_transaction(&ledger_info, data, &state_view)?;
 
         let estimated_gas_unit_price = match (
             estimate_gas_unit_price.0.unwrap_or_default(),
@@ -586,22 +836,24 @@ impl TransactionsApi {
                 / u64::from(gas_params.vm.txn.gas_unit_scaling_factor);
             let max_number_of_gas_units = u64::from(gas_params.vm.txn.maximum_number_of_gas_units);
 
-            // Retrieve account balance to determine max gas available
-            let account_state = self
-                .context
-                .get_account_state(
-                    signed_transaction.sender(),
-                    ledger_info.version(),
+            // Retrieve account balance to determine max gas available, including overrides
+            let sender = signed_transaction.sender();
+            let account = AccountWithStateView::new(&sender, &state_view);
+            let account_resource = account.get_account_resource().map_err(|err| {
+                SubmitTransactionError::internal_with_code(
+                    format!("Failed to get account resource {}", err),
+                    AptosErrorCode::InternalError,
                     &ledger_info,
-                )?
-                .ok_or_else(|| {
-                    SubmitTransactionError::bad_request_with_code(
-                        "Account not found",
-                        AptosErrorCode::InvalidInput,
-                        &ledger_info,
-                    )
-                })?;
-            let coin_store: CoinStoreResource = account_state
+                )
+            })?;
+            if account_resource.is_none() {
+                return Err(SubmitTransactionError::bad_request_with_code(
+                    "Account not found",
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                ));
+            }
+            let coin_store: CoinStoreResource = account
                 .get_coin_store_resource()
                 .and_then(|inner| {
                     inner.ok_or_else(|| {
@@ -651,8 +903,173 @@ impl TransactionsApi {
             );
         }
 
-        self.simulate(&accept_type, ledger_info, signed_transaction)
-            .await
+        self.simulate(
+            &accept_type,
+            ledger_info,
+            signed_transaction,
+            trace.0.unwrap_or_default(),
+            state_view,
+        )
+        .await
+    }
+
+    /// Simulate a bundle of transactions
+    ///
+    /// Simulates an ordered list of transactions on a shared scratch state: each transaction
+    /// sees the writes of the transactions before it, e.g. an account created by the first
+    /// transaction can send the second one. The outputs of all transactions are returned in
+    /// order, including those of transactions that failed. Nothing is persisted.
+    ///
+    /// Like with /transactions/simulate, the transactions must not have valid signatures.
+    /// JSON requests take a SimulateBundleRequest, which may contain `state_overrides` applied
+    /// before the first transaction. BCS requests take a list of SignedTransactions, like
+    /// /transactions/batch, and return a list of simulated transactions in the same format as
+    /// /transactions/simulate.
+    #[oai(
+        path = "/transactions/simulate_bundle",
+        method = "post",
+        operation_id = "simulate_bundle",
+        tag = "ApiTags::Transactions"
+    )]
+    async fn simulate_bundle(
+        &self,
+        accept_type: AcceptType,
+        /// If set to true, the call trace of each transaction will be returned
+        trace: Query<Option<bool>>,
+        data: SimulateBundlePost,
+    ) -> SimulateTransactionResult<Vec<UserTransaction>> {
+        data.verify()
+            .context("Simulated bundle invalid")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code_no_info(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                )
+            })?;
+        fail_point_poem("endpoint_simulate_bundle")?;
+        if !self.context.node_config.api.transaction_simulation_enabled {
+            return Err(api_disabled("Simulate bundle"));
+        }
+        self.context
+            .check_api_output_enabled("Simulate bundle", &accept_type)?;
+        let trace = trace.0.unwrap_or_default();
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        if trace && accept_type == AcceptType::Bcs {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                "Call traces of simulated transactions are only supported with JSON",
+                AptosErrorCode::BcsNotSupported,
+                &ledger_info,
+            ));
+        }
+
+        let (json_transactions, bcs_transactions, state_overrides) = match data {
+            SimulateBundlePost::Json(request) => {
+                let request = request.0;
+                let state_overrides = request.state_overrides.unwrap_or_default();
+                (request.transactions, vec![], state_overrides)
+            },
+            SimulateBundlePost::Bcs(data) => {
+                let signed_transactions: Vec<SignedTransaction> =
+                    bcs::from_bytes_with_limit(&data.0, MAX_RECURSIVE_TYPES_ALLOWED as usize)
+                        .context("Failed to deserialize input into a list of SignedTransaction")
+                        .map_err(|err| {
+                            SubmitTransactionError::bad_request_with_code(
+                                err,
+                                AptosErrorCode::InvalidInput,
+                                &ledger_info,
+                            )
+                        })?;
+                for signed_transaction in &signed_transactions {
+                    TransactionsApi::validate_signed_transaction_payload(
+                        &ledger_info,
+                        signed_transaction,
+                    )?;
+                }
+                (vec![], signed_transactions, vec![])
+            },
+        };
+        let num_transactions = json_transactions.len() + bcs_transactions.len();
+        if num_transactions == 0 {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                "Simulated bundle must contain at least one transaction",
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+        if self.context.max_submit_transaction_batch_size() < num_transactions {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                format!(
+                    "Simulated too many transactions: {}, while limit is {}",
+                    num_transactions,
+                    self.context.max_submit_transaction_batch_size(),
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+
+        let mut state_view = self
+            .context
+            .apply_state_overrides(
+                self.context.latest_state_view_poem(&ledger_info)?,
+                state_overrides,
+            )
+            .context("Failed to apply state overrides")
+            .map_err(|err| {
+                SubmitTransactionError::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+
+        // Transactions are rendered right after they are simulated, against the state they were
+        // simulated on plus their own writes, so that later transactions in the bundle do not
+        // change how earlier ones are rendered.
+        let mut simulated_txns = Vec::with_capacity(num_transactions);
+        let mut user_transactions = Vec::with_capacity(num_transactions);
+        let mut json_transactions = json_transactions.into_iter();
+        let mut bcs_transactions = bcs_transactions.into_iter();
+        loop {
+            // JSON transactions are converted one at a time, so that their arguments can use
+            // types from modules published earlier in the bundle.
+            let signed_transaction = if let Some(request) = json_transactions.next() {
+                self.get_signed_transaction(
+                    &ledger_info,
+                    SubmitTransactionPost::Json(Json(request)),
+                    &state_view,
+                )?
+            } else if let Some(signed_transaction) = bcs_transactions.next() {
+                signed_transaction
+            } else {
+                break;
+            };
+            let simulated_txn = self.simulate_on_state_view(
+                &ledger_info,
+                signed_transaction,
+                trace,
+                &mut state_view,
+            )?;
+            match accept_type {
+                AcceptType::Json => user_transactions.extend(self.render_simulated_transactions(
+                    &ledger_info,
+                    &state_view,
+                    vec![simulated_txn],
+                )?),
+                AcceptType::Bcs => simulated_txns.push(simulated_txn.0),
+            }
+        }
+
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                user_transactions,
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((simulated_txns, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
     }
 
     /// Encode submission
@@ -815,6 +1232,122 @@ impl TransactionsApi {
             .await
     }
 
+    async fn wait_transaction_by_hash_inner(
+        &self,
+        accept_type: &AcceptType,
+        hash: HashValue,
+        timeout_secs: Option<u64>,
+    ) -> BasicResultWith404<Transaction> {
+        let max_timeout_secs = self.context.wait_by_hash_max_timeout_secs();
+        let timeout_secs = timeout_secs.map_or(max_timeout_secs, |secs| secs.min(max_timeout_secs));
+        let deadline = Instant::now() + Duration::from_secs(timeout_secs);
+        let ledger_info = self.context.get_latest_ledger_info()?;
+        let _permit = self
+            .context
+            .try_acquire_wait_by_hash_permit()
+            .ok_or_else(|| {
+                BasicErrorWith404::service_unavailable_with_code(
+                    "Too many requests waiting for transactions",
+                    AptosErrorCode::TooManyConnections,
+                    &ledger_info,
+                )
+            })?;
+        // Subscribe before the first lookup, so a commit in between isn't missed.
+        let mut commits = self
+            .context
+            .subscribe_to_mempool_commits()
+            .await
+            .context("Failed to subscribe to mempool commits")
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+        let mut expiration_timestamp_secs = None;
+
+        loop {
+            let ledger_info = self.context.get_latest_ledger_info()?;
+            let txn_data = self
+                .get_by_hash(hash.into(), &ledger_info)
+                .await
+                .context(format!("Failed to get transaction by hash {}", hash))
+                .map_err(|err| {
+                    BasicErrorWith404::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        &ledger_info,
+                    )
+                })?;
+
+            match txn_data {
+                Some(TransactionData::Pending(txn)) => {
+                    let expiration = txn.expiration_timestamp_secs();
+                    if expiration <= ledger_info.timestamp() / 1_000_000 {
+                        return Err(transaction_expired(hash, &ledger_info));
+                    }
+                    expiration_timestamp_secs = Some(expiration);
+
+                    if Instant::now() >= deadline {
+                        return self
+                            .get_transaction_inner(
+                                accept_type,
+                                TransactionData::Pending(txn),
+                                &ledger_info,
+                            )
+                            .await;
+                    }
+                    let _ = tokio::time::timeout_at(
+                        deadline.into(),
+                        wait_for_commit(
+                            &mut commits,
+                            txn.sender(),
+                            txn.sequence_number(),
+                            expiration,
+                        ),
+                    )
+                    .await;
+                },
+                Some(txn_data) => {
+                    return self
+                        .get_transaction_inner(accept_type, txn_data, &ledger_info)
+                        .await
+                },
+                None => {
+                    // The transaction may have been committed and removed from mempool between
+                    // the storage and mempool lookups, so look in storage once more.
+                    let ledger_info = self.context.get_latest_ledger_info()?;
+                    let from_db = self
+                        .context
+                        .get_transaction_by_hash(hash.into(), ledger_info.version())
+                        .context(format!("Failed to get transaction by hash {}", hash))
+                        .map_err(|err| {
+                            BasicErrorWith404::internal_with_code(
+                                err,
+                                AptosErrorCode::InternalError,
+                                &ledger_info,
+                            )
+                        })?;
+                    return match from_db {
+                        Some(txn) => {
+                            self.get_transaction_inner(accept_type, txn.into(), &ledger_info)
+                                .await
+                        },
+                        None => match expiration_timestamp_secs {
+                            Some(expiration)
+                                if expiration <= ledger_info.timestamp() / 1_000_000 =>
+                            {
+                                Err(transaction_expired(hash, &ledger_info))
+                            },
+                            _ => Err(transaction_not_found_by_hash(hash, &ledger_info)),
+                        },
+                    };
+                },
+            }
+        }
+    }
+
     async fn get_transaction_by_version_inner(
         &self,
         accept_type: &AcceptType,
@@ -844,6 +1377,49 @@ impl TransactionsApi {
         }
     }
 
+    /// Replays a committed transaction against the exact state it was executed on and
+    /// returns the call trace of its payload.
+    fn get_call_trace_inner(
+        &self,
+        txn: TransactionOnChainData,
+        ledger_info: &LedgerInfo,
+    ) -> BasicResultWith404<CallTrace> {
+        let _permit = self
+            .context
+            .try_acquire_call_trace_permit()
+            .ok_or_else(|| {
+                BasicErrorWith404::service_unavailable_with_code(
+                    "Too many call traces being replayed",
+                    AptosErrorCode::TooManyConnections,
+                    ledger_info,
+                )
+            })?;
+        let mut state_view = self
+            .context
+            .block_prefix_state_view(txn.version, ledger_info)?;
+        let call_trace = self
+            .context
+            .trace_transaction(&mut state_view, &txn)
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )
+            })?
+            .ok_or_else(|| {
+                BasicErrorWith404::bad_request_with_code(
+                    format!(
+                        "Transaction at version {} did not execute any Move function",
+                        txn.version
+                    ),
+                    AptosErrorCode::InvalidInput,
+                    ledger_info,
+                )
+            })?;
+        BasicResponse::try_from_json((call_trace, ledger_info, BasicResponseStatus::Ok))
+    }
+
     /// Converts a transaction into the outgoing type
     async fn get_transaction_inner(
         &self,
@@ -968,11 +1544,56 @@ impl TransactionsApi {
         }
     }
 
+    /// List the user transactions calling an entry function
+    fn list_by_function(
+        &self,
+        accept_type: &AcceptType,
+        page: Page,
+        function_id: EntryFunctionId,
+    ) -> BasicResultWith404<Vec<Transaction>> {
+        let latest_ledger_info = self.context.get_latest_ledger_info()?;
+        let module: ModuleId = function_id.module.into();
+        let function: Identifier = function_id.name.into();
+        let (data, cursor) = self
+            .context
+            .get_transactions_by_function(
+                &module,
+                &function,
+                page.start_option().unwrap_or(0),
+                page.limit(&latest_ledger_info)?,
+                latest_ledger_info.version(),
+            )
+            .context(format!(
+                "Failed to find transactions calling {}::{}",
+                module, function
+            ))
+            .map_err(|err| {
+                BasicErrorWith404::internal_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &latest_ledger_info,
+                )
+            })?;
+        match accept_type {
+            AcceptType::Json => BasicResponse::try_from_json((
+                self.context
+                    .render_transactions_non_sequential(&latest_ledger_info, data)?,
+                &latest_ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+            AcceptType::Bcs => {
+                BasicResponse::try_from_bcs((data, &latest_ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+        .map(|v| v.with_version_cursor(cursor))
+    }
+
     /// Parses a single signed transaction
     fn get_signed_transaction(
         &self,
         ledger_info: &LedgerInfo,
         data: SubmitTransactionPost,
+        state_view: &impl StateView,
     ) -> Result<SignedTransaction, SubmitTransactionError> {
         match data {
             SubmitTransactionPost::Bcs(data) => {
@@ -987,58 +1608,14 @@ impl TransactionsApi {
                             )
                         })?;
                 // Verify the signed transaction
-                match signed_transaction.payload() {
-                    TransactionPayload::EntryFunction(entry_function) => {
-                        TransactionsApi::validate_entry_function_payload_format(
-                            ledger_info,
-                            entry_function,
-                        )?;
-                    },
-                    TransactionPayload::Script(script) => {
-                        if script.code().is_empty() {
-                            return Err(SubmitTransactionError::bad_request_with_code(
-                                "Script payload bytecode must not be empty",
-                                AptosErrorCode::InvalidInput,
-                                ledger_info,
-                            ));
-                        }
-
-                        for arg in script.ty_args() {
-                            let arg = MoveType::from(arg);
-                            arg.verify(0)
-                                .context("Transaction script function type arg invalid")
-                                .map_err(|err| {
-                                    SubmitTransactionError::bad_request_with_code(
-                                        err,
-                                        AptosErrorCode::InvalidInput,
-                                        ledger_info,
-                                    )
-                                })?;
-                        }
-                    },
-                    TransactionPayload::Multisig(multisig) => {
-                        if let Some(payload) = &multisig.transaction_payload {
-                            match payload {
-                                MultisigTransactionPayload::EntryFunction(entry_function) => {
-                                    TransactionsApi::validate_entry_function_payload_format(
-                                        ledger_info,
-                                        entry_function,
-                                    )?;
-                                },
-                            }
-                        }
-                    },
-
-                    // Deprecated. Will be removed in the future.
-                    TransactionPayload::ModuleBundle(_) => {},
-                }
-                // TODO: Verify script args?
+                TransactionsApi::validate_signed_transaction_payload(
+                    ledger_info,
+                    &signed_transaction,
+                )?;
 
                 Ok(signed_transaction)
             },
-            SubmitTransactionPost::Json(data) => self
-                .context
-                .latest_state_view_poem(ledger_info)?
+            SubmitTransactionPost::Json(data) => state_view
                 .as_move_resolver()
                 .as_converter(self.context.db.clone())
                 .try_into_signed_transaction_poem(data.0, self.context.chain_id())
@@ -1053,6 +1630,60 @@ impl TransactionsApi {
         }
     }
 
+    /// Validates the format of the payload of a transaction submitted as BCS.
+    fn validate_signed_transaction_payload(
+        ledger_info: &LedgerInfo,
+        signed_transaction: &SignedTransaction,
+    ) -> Result<(), SubmitTransactionError> {
+        match signed_transaction.payload() {
+            TransactionPayload::EntryFunction(entry_function) => {
+                TransactionsApi::validate_entry_function_payload_format(
+                    ledger_info,
+                    entry_function,
+                )?;
+            },
+            TransactionPayload::Script(script) => {
+                if script.code().is_empty() {
+                    return Err(SubmitTransactionError::bad_request_with_code(
+                        "Script payload bytecode must not be empty",
+                        AptosErrorCode::InvalidInput,
+                        ledger_info,
+                    ));
+                }
+
+                for arg in script.ty_args() {
+                    let arg = MoveType::from(arg);
+                    arg.verify(0)
+                        .context("Transaction script function type arg invalid")
+                        .map_err(|err| {
+                            SubmitTransactionError::bad_request_with_code(
+                                err,
+                                AptosErrorCode::InvalidInput,
+                                ledger_info,
+                            )
+                        })?;
+                }
+            },
+            TransactionPayload::Multisig(multisig) => {
+                if let Some(payload) = &multisig.transaction_payload {
+                    match payload {
+                        MultisigTransactionPayload::EntryFunction(entry_function) => {
+                            TransactionsApi::validate_entry_function_payload_format(
+                                ledger_info,
+                                entry_function,
+                            )?;
+                        },
+                    }
+                }
+            },
+
+            // Deprecated. Will be removed in the future.
+            TransactionPayload::ModuleBundle(_) => {},
+        }
+        // TODO: Verify script args?
+        Ok(())
+    }
+
     // Validates that the module, function, and args in EntryFunction payload are correctly
     // formatted.
     fn validate_entry_function_payload_format(
@@ -1305,20 +1936,82 @@ impl TransactionsApi {
         accept_type: &AcceptType,
         ledger_info: LedgerInfo,
         txn: SignedTransaction,
+        trace: bool,
+        mut state_view: OverlayStateView<DbStateView>,
     ) -> SimulateTransactionResult<Vec<UserTransaction>> {
+        if trace && accept_type == &AcceptType::Bcs {
+            return Err(SubmitTransactionError::bad_request_with_code(
+                "Call traces of simulated transactions are only supported with JSON",
+                AptosErrorCode::BcsNotSupported,
+                &ledger_info,
+            ));
+        }
+
+        let simulated_txn =
+            self.simulate_on_state_view(&ledger_info, txn, trace, &mut state_view)?;
+        match accept_type {
+            AcceptType::Json => {
+                let user_transactions = self.render_simulated_transactions(
+                    &ledger_info,
+                    &state_view,
+                    vec![simulated_txn],
+                )?;
+                BasicResponse::try_from_json((
+                    user_transactions,
+                    &ledger_info,
+                    BasicResponseStatus::Ok,
+                ))
+            },
+            AcceptType::Bcs => BasicResponse::try_from_bcs((
+                simulated_txn.0,
+                &ledger_info,
+                BasicResponseStatus::Ok,
+            )),
+        }
+    }
+
+    /// Simulates a transaction on top of `state_view` and applies its writes to it, so that
+    /// transactions simulated after it see them.
+    fn simulate_on_state_view(
+        &self,
+        ledger_info: &LedgerInfo,
+        txn: SignedTransaction,
+        trace: bool,
+        state_view: &mut OverlayStateView<DbStateView>,
+    ) -> Result<(TransactionOnChainData, Option<CallTrace>), SubmitTransactionError> {
         // Transactions shouldn't have a valid signature or this could be used to attack
         if txn.signature_is_valid() {
             return Err(SubmitTransactionError::bad_request_with_code(
                 "Simulated transactions must have a non-valid signature",
                 AptosErrorCode::InvalidInput,
-                &ledger_info,
+                ledger_info,
             ));
         }
 
         // Simulate transaction
-        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
-        let (_, output) =
-            AptosVM::simulate_signed_transaction(&txn, &state_view.as_executor_view());
+        let (output, call_trace) = if trace {
+            let (_, output, call_traces) = AptosVM::simulate_signed_transaction_with_call_trace(
+                &txn,
+                &state_view.as_executor_view(),
+            );
+            // Also applies the writes of the transaction.
+            let call_trace = self
+                .context
+                .render_call_trace(state_view, &output, txn.payload().into(), call_traces)
+                .map_err(|err| {
+                    SubmitTransactionError::internal_with_code(
+                        err,
+                        AptosErrorCode::InternalError,
+                        ledger_info,
+                    )
+                })?;
+            (output, call_trace)
+        } else {
+            let (_, output) =
+                AptosVM::simulate_signed_transaction(&txn, &state_view.as_executor_view());
+            state_view.apply_write_set(output.write_set());
+            (output, None)
+        };
         let version = ledger_info.version();
 
         // Ensure that all known statuses return their values in the output (even if they aren't supposed to)
@@ -1348,38 +2041,41 @@ impl TransactionsApi {
             accumulator_root_hash: zero_hash,
             changes: output.write_set().clone(),
         };
+        Ok((simulated_txn, call_trace))
+    }
 
-        match accept_type {
-            AcceptType::Json => {
-                let transactions = self
-                    .context
-                    .render_transactions_non_sequential(&ledger_info, vec![simulated_txn])?;
-
-                // Users can only make requests to simulate UserTransactions, so unpack
-                // the Vec<Transaction> into Vec<UserTransaction>.
-                let mut user_transactions = Vec::new();
-                for transaction in transactions.into_iter() {
-                    match transaction {
-                        Transaction::UserTransaction(user_txn) => user_transactions.push(*user_txn),
-                        _ => {
-                            return Err(SubmitTransactionError::internal_with_code(
-                                "Simulation transaction resulted in a non-UserTransaction",
-                                AptosErrorCode::InternalError,
-                                &ledger_info,
-                            ))
-                        },
-                    }
-                }
-                BasicResponse::try_from_json((
-                    user_transactions,
-                    &ledger_info,
-                    BasicResponseStatus::Ok,
-                ))
-            },
-            AcceptType::Bcs => {
-                BasicResponse::try_from_bcs((simulated_txn, &ledger_info, BasicResponseStatus::Ok))
-            },
-        }
+    /// Renders simulated transactions as JSON, resolving types through `state_view`, which must
+    /// be the state they were simulated on with their writes applied.
+    fn render_simulated_transactions(
+        &self,
+        ledger_info: &LedgerInfo,
+        state_view: &OverlayStateView<DbStateView>,
+        simulated_txns: Vec<(TransactionOnChainData, Option<CallTrace>)>,
+    ) -> Result<Vec<UserTransaction>, SubmitTransactionError> {
+        let (simulated_txns, call_traces): (Vec<_>, Vec<_>) = simulated_txns.into_iter().unzip();
+        let transactions = self.context.render_transactions_with_state_view(
+            ledger_info,
+            state_view,
+            simulated_txns,
+        )?;
+
+        // Users can only make requests to simulate UserTransactions, so unpack
+        // the Vec<Transaction> into Vec<UserTransaction>.
+        transactions
+            .into_iter()
+            .zip(call_traces)
+            .map(|(transaction, call_trace)| match transaction {
+                Transaction::UserTransaction(user_txn) => Ok(UserTransaction {
+                    call_trace,
+                    ..*user_txn
+                }),
+                _ => Err(SubmitTransactionError::internal_with_code(
+                    "Simulation transaction resulted in a non-UserTransaction",
+                    AptosErrorCode::InternalError,
+                    ledger_info,
+                )),
+            })
+            .collect()
     }
 
     /// Encode message as BCS
@@ -1462,6 +2158,35 @@ fn override_gas_parameters(
     SignedTransaction::new_with_authenticator(raw_txn, signed_txn.authenticator())
 }
 
+/// Waits until mempool is notified of a block committing `sender`'s transaction with
+/// `sequence_number`, or of a block at or past `expiration_timestamp_secs`, after which the
+/// pending transaction can't be committed anymore.
+async fn wait_for_commit(
+    commits: &mut broadcast::Receiver<Arc<CommittedTransactions>>,
+    sender: AccountAddress,
+    sequence_number: u64,
+    expiration_timestamp_secs: u64,
+) {
+    loop {
+        match commits.recv().await {
+            Ok(committed) => {
+                if committed.block_timestamp_usecs / 1_000_000 >= expiration_timestamp_secs
+                    || committed
+                        .transactions
+                        .iter()
+                        .any(|txn| txn.sender == sender && txn.sequence_number == sequence_number)
+                {
+                    return;
+                }
+            },
+            // Some commits were missed, so the transaction has to be looked up again.
+            Err(RecvError::Lagged(_)) => return,
+            // Mempool is shutting down, nothing else gets committed.
+            Err(RecvError::Closed) => future::pending().await,
+        }
+    }
+}
+
 enum GetByVersionResponse {
     VersionTooNew,
     VersionTooOld,
@@ -3,18 +3,44 @@
 
 use crate::{
     accept_type::AcceptType,
+    bcs_payload::Bcs,
     failpoint::fail_point_poem,
     response::{
         BadRequestError, BasicErrorWith404, BasicResponse, BasicResponseStatus, BasicResultWith404,
     },
     ApiTags, Context,
 };
-use aptos_api_types::{AptosErrorCode, AsConverter, MoveValue, ViewRequest, U64};
+use anyhow::Context as AnyhowContext;
+use aptos_api_types::{
+    AptosErrorCode, AsConverter, MoveValue, VerifyInput, ViewFunctionResult, ViewFunctionResultBcs,
+    ViewRequest, MAX_RECURSIVE_TYPES_ALLOWED, U64,
+};
+use aptos_state_view::{overlay_state_view::OverlayStateView, StateView};
+use aptos_storage_interface::state_view::DbStateView;
+use aptos_types::transaction::EntryFunction;
 use aptos_vm::{data_cache::AsMoveResolver, AptosVM};
 use move_core_types::language_storage::TypeTag;
-use poem_openapi::{param::Query, payload::Json, OpenApi};
+use poem_openapi::{param::Query, payload::Json, ApiRequest, OpenApi};
 use std::sync::Arc;
 
+// We need a custom type here because we use different types for each of the
+// content types possible for the POST data.
+#[derive(ApiRequest, Debug)]
+pub enum ViewFunctionBatchPost {
+    #[oai(content_type = "application/json")]
+    Json(Json<Vec<ViewRequest>>),
+
+    // A list of EntryFunctions encoded as BCS
+    #[oai(content_type = "application/x.aptos.view_function+bcs")]
+    Bcs(Bcs),
+}
+
+/// One call of a view function batch
+enum ViewFunctionCall {
+    Json(ViewRequest),
+    Bcs(EntryFunction),
+}
+
 /// API for executing Move view function.
 pub struct ViewFunctionApi {
     pub context: Arc<Context>,
@@ -28,6 +54,9 @@ impl ViewFunctionApi {
     ///
     /// The Aptos nodes prune account state history, via a configurable time window.
     /// If the requested ledger version has been pruned, the server responds with a 410.
+    ///
+    /// The request may contain `state_overrides`, which replace resources, table items or modules
+    /// before the function is executed.
     #[oai(
         path = "/view",
         method = "post",
@@ -54,26 +83,51 @@ impl ViewFunctionApi {
                 ledger_version.map(|inner| inner.0),
             )?;
 
-        let state_view = self.context.latest_state_view_poem(&ledger_info)?;
-        let resolver = state_view.as_move_resolver();
+        let mut request = request.0;
+        let state_overrides = request.state_overrides.take().unwrap_or_default();
+        for state_override in &state_overrides {
+            state_override
+                .verify()
+                .context("Invalid state override")
+                .map_err(|err| {
+                    BasicErrorWith404::bad_request_with_code(
+                        err,
+                        AptosErrorCode::InvalidInput,
+                        &ledger_info,
+                    )
+                })?;
+        }
 
-        let entry_func = resolver
-            .as_converter(self.context.db.clone())
-            .convert_view_function(request.0)
+        let state_view = self
+            .context
+            .state_view_at_version(requested_version)
             .map_err(|err| {
                 BasicErrorWith404::bad_request_with_code(
                     err,
-                    AptosErrorCode::InvalidInput,
+                    AptosErrorCode::InternalError,
                     &ledger_info,
                 )
             })?;
         let state_view = self
             .context
-            .state_view_at_version(requested_version)
+            .apply_state_overrides(state_view, state_overrides)
+            .context("Failed to apply state overrides")
             .map_err(|err| {
                 BasicErrorWith404::bad_request_with_code(
                     err,
-                    AptosErrorCode::InternalError,
+                    AptosErrorCode::InvalidInput,
+                    &ledger_info,
+                )
+            })?;
+        let resolver = state_view.as_move_resolver();
+
+        let entry_func = resolver
+            .as_converter(self.context.db.clone())
+            .convert_view_function(request)
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InvalidInput,
                     &ledger_info,
                 )
             })?;
@@ -94,14 +148,8 @@ impl ViewFunctionApi {
                 BasicResponse::try_from_bcs((return_vals, &ledger_info, BasicResponseStatus::Ok))
             },
             AcceptType::Json => {
-                let return_types = resolver
-                    .as_converter(self.context.db.clone())
-                    .function_return_types(&entry_func)
-                    .and_then(|tys| {
-                        tys.into_iter()
-                            .map(TypeTag::try_from)
-                            .collect::<anyhow::Result<Vec<_>>>()
-                    })
+                let move_vals = self
+                    .render_return_values(&state_view, &entry_func, return_vals)
                     .map_err(|err| {
                         BasicErrorWith404::bad_request_with_code(
                             err,
@@ -110,25 +158,255 @@ impl ViewFunctionApi {
                         )
                     })?;
 
-                let move_vals = return_vals
+                BasicResponse::try_from_json((move_vals, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+
+    /// Execute a batch of view functions
+    ///
+    /// Execute the Move functions in order against the state at a single ledger version and
+    /// return the result of each call, or why it failed. A failed call does not fail the batch.
+    ///
+    /// Each call is limited to `max_gas_view_function` gas, and the whole batch to
+    /// `max_gas_view_function` times `view_function_batch_gas_multiplier`. Every call counts at
+    /// least `min_gas_view_function_batch_call` against the limit of the batch, including calls
+    /// that fail before they are executed. Once the batch ran out of gas, the remaining calls
+    /// fail. Batches of more than `max_view_function_batch_size` calls are rejected.
+    ///
+    /// JSON requests are a list of view requests, each of which may contain its own
+    /// `state_overrides`. BCS requests use the `application/x.aptos.view_function+bcs` content
+    /// type and are a list of EntryFunctions encoded as BCS. BCS responses contain the BCS
+    /// encoded return values of each call.
+    #[oai(
+        path = "/view/batch",
+        method = "post",
+        operation_id = "view_batch",
+        tag = "ApiTags::View"
+    )]
+    async fn view_function_batch(
+        &self,
+        accept_type: AcceptType,
+        /// View function requests
+        request: ViewFunctionBatchPost,
+        /// Ledger version to get state of account
+        ///
+        /// If not provided, it will be the latest version
+        ledger_version: Query<Option<U64>>,
+    ) -> BasicResultWith404<Vec<ViewFunctionResult>> {
+        fail_point_poem("endpoint_view_function_batch")?;
+        self.context
+            .check_api_output_enabled("View function batch", &accept_type)?;
+
+        let (ledger_info, requested_version) = self
+            .context
+            .get_latest_ledger_info_and_verify_lookup_version(
+                ledger_version.map(|inner| inner.0),
+            )?;
+
+        let calls = match request {
+            ViewFunctionBatchPost::Json(requests) => requests
+                .0
+                .into_iter()
+                .map(ViewFunctionCall::Json)
+                .collect::<Vec<_>>(),
+            ViewFunctionBatchPost::Bcs(data) => {
+                let entry_funcs: Vec<EntryFunction> =
+                    bcs::from_bytes_with_limit(&data.0, MAX_RECURSIVE_TYPES_ALLOWED as usize)
+                        .context("Failed to deserialize input into a list of EntryFunction")
+                        .map_err(|err| {
+                            BasicErrorWith404::bad_request_with_code(
+                                err,
+                                AptosErrorCode::InvalidInput,
+                                &ledger_info,
+                            )
+                        })?;
+                entry_funcs.into_iter().map(ViewFunctionCall::Bcs).collect()
+            },
+        };
+
+        let max_batch_size = self.context.node_config.api.max_view_function_batch_size;
+        if calls.len() > max_batch_size {
+            return Err(BasicErrorWith404::bad_request_with_code(
+                format!(
+                    "Too many calls in the view function batch: {}, while limit is {}",
+                    calls.len(),
+                    max_batch_size,
+                ),
+                AptosErrorCode::InvalidInput,
+                &ledger_info,
+            ));
+        }
+
+        // All calls share one snapshot, so that they see a consistent state.
+        let state_view = self
+            .context
+            .state_view_at_version(requested_version)
+            .map_err(|err| {
+                BasicErrorWith404::bad_request_with_code(
+                    err,
+                    AptosErrorCode::InternalError,
+                    &ledger_info,
+                )
+            })?;
+
+        match accept_type {
+            AcceptType::Bcs => {
+                let results = self
+                    .execute_batch(&state_view, calls, |_, _, return_vals| Ok(return_vals))
                     .into_iter()
-                    .zip(return_types.into_iter())
-                    .map(|(v, ty)| {
-                        resolver
-                            .as_converter(self.context.db.clone())
-                            .try_into_move_value(&ty, &v)
+                    .map(|(values, gas_used)| match values {
+                        Ok(values) => ViewFunctionResultBcs {
+                            values: Some(values),
+                            error: None,
+                            gas_used,
+                        },
+                        Err(err) => ViewFunctionResultBcs {
+                            values: None,
+                            error: Some(format!("{:#}", err)),
+                            gas_used,
+                        },
                     })
-                    .collect::<anyhow::Result<Vec<_>>>()
-                    .map_err(|err| {
-                        BasicErrorWith404::bad_request_with_code(
-                            err,
-                            AptosErrorCode::InternalError,
-                            &ledger_info,
-                        )
-                    })?;
+                    .collect::<Vec<_>>();
+                BasicResponse::try_from_bcs((results, &ledger_info, BasicResponseStatus::Ok))
+            },
+            AcceptType::Json => {
+                let results = self
+                    .execute_batch(&state_view, calls, |state_view, entry_func, return_vals| {
+                        self.render_return_values(state_view, entry_func, return_vals)
+                    })
+                    .into_iter()
+                    .map(|(values, gas_used)| match values {
+                        Ok(values) => ViewFunctionResult {
+                            values: Some(values),
+                            error: None,
+                            gas_used: gas_used.into(),
+                        },
+                        Err(err) => ViewFunctionResult {
+                            values: None,
+                            error: Some(format!("{:#}", err)),
+                            gas_used: gas_used.into(),
+                        },
+                    })
+                    .collect::<Vec<_>>();
+                BasicResponse::try_from_json((results, &ledger_info, BasicResponseStatus::Ok))
+            },
+        }
+    }
+}
 
-                BasicResponse::try_from_json((move_vals, &ledger_info, BasicResponseStatus::Ok))
+impl ViewFunctionApi {
+    /// Executes the calls of a batch in order against `state_view`, returning the rendered
+    /// return values or the error of each call, and the gas counted against the batch for it.
+    fn execute_batch<T>(
+        &self,
+        state_view: &DbStateView,
+        calls: Vec<ViewFunctionCall>,
+        render: impl Fn(
+            &OverlayStateView<&DbStateView>,
+            &EntryFunction,
+            Vec<Vec<u8>>,
+        ) -> anyhow::Result<T>,
+    ) -> Vec<(anyhow::Result<T>, u64)> {
+        let max_gas_per_call = self.context.node_config.api.max_gas_view_function;
+        let min_gas_per_call = self
+            .context
+            .node_config
+            .api
+            .min_gas_view_function_batch_call;
+        let mut remaining_gas = max_gas_per_call.saturating_mul(
+            self.context
+                .node_config
+                .api
+                .view_function_batch_gas_multiplier,
+        );
+        let mut results = Vec::with_capacity(calls.len());
+        for call in calls {
+            if remaining_gas == 0 {
+                results.push((Err(anyhow::anyhow!("The batch ran out of gas")), 0));
+                continue;
+            }
+
+            let (state_view, entry_func) = match self.prepare_batch_call(state_view, call) {
+                Ok(prepared) => prepared,
+                Err(err) => {
+                    let gas_charged = std::cmp::min(min_gas_per_call, remaining_gas);
+                    remaining_gas -= gas_charged;
+                    results.push((Err(err), gas_charged));
+                    continue;
+                },
+            };
+            let output = AptosVM::execute_view_function_with_gas_used(
+                &state_view,
+                entry_func.module().clone(),
+                entry_func.function().to_owned(),
+                entry_func.ty_args().to_owned(),
+                entry_func.args().to_owned(),
+                std::cmp::min(max_gas_per_call, remaining_gas),
+            );
+            let gas_charged = std::cmp::min(
+                std::cmp::max(output.gas_used, min_gas_per_call),
+                remaining_gas,
+            );
+            remaining_gas -= gas_charged;
+            let values = output
+                .values
+                .and_then(|return_vals| render(&state_view, &entry_func, return_vals));
+            results.push((values, gas_charged));
+        }
+        results
+    }
+
+    /// Applies the state overrides of a call on top of `state_view`, and converts the call into
+    /// an entry function.
+    fn prepare_batch_call<'a>(
+        &self,
+        state_view: &'a DbStateView,
+        call: ViewFunctionCall,
+    ) -> anyhow::Result<(OverlayStateView<&'a DbStateView>, EntryFunction)> {
+        match call {
+            ViewFunctionCall::Json(mut request) => {
+                let state_overrides = request.state_overrides.take().unwrap_or_default();
+                for state_override in &state_overrides {
+                    state_override.verify().context("Invalid state override")?;
+                }
+                let state_view = self
+                    .context
+                    .apply_state_overrides(state_view, state_overrides)
+                    .context("Failed to apply state overrides")?;
+                let entry_func = state_view
+                    .as_move_resolver()
+                    .as_converter(self.context.db.clone())
+                    .convert_view_function(request)?;
+                Ok((state_view, entry_func))
+            },
+            ViewFunctionCall::Bcs(entry_func) => {
+                Ok((OverlayStateView::new(state_view), entry_func))
             },
         }
     }
+
+    /// Converts the return values of `entry_func` to JSON, resolving types through `state_view`.
+    fn render_return_values(
+        &self,
+        state_view: &impl StateView,
+        entry_func: &EntryFunction,
+        return_vals: Vec<Vec<u8>>,
+    ) -> anyhow::Result<Vec<MoveValue>> {
+        let resolver = state_view.as_move_resolver();
+        let converter = resolver.as_converter(self.context.db.clone());
+        let return_types = converter
+            .function_return_types(entry_func)
+            .and_then(|tys| {
+                tys.into_iter()
+                    .map(TypeTag::try_from)
+                    .collect::<anyhow::Result<Vec<_>>>()
+            })?;
+
+        return_vals
+            .into_iter()
+            .zip(return_types.into_iter())
+            .map(|(v, ty)| converter.try_into_move_value(&ty, &v))
+            .collect()
+    }
 }
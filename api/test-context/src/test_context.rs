@@ -10,8 +10,8 @@ use aptos_api_types::{
 use aptos_cached_packages::aptos_stdlib;
 use aptos_config::{
     config::{
-        NodeConfig, RocksdbConfigs, BUFFERED_STATE_TARGET_ITEMS,
-        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD, NO_OP_STORAGE_PRUNER_CONFIG,
+        NodeConfig, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        NO_OP_STORAGE_PRUNER_CONFIG,
     },
     keys::ConfigKey,
 };
@@ -122,7 +122,7 @@ pub fn new_test_context(
                 &tmp_dir,
                 false,                       /* readonly */
                 NO_OP_STORAGE_PRUNER_CONFIG, /* pruner */
-                RocksdbConfigs::default(),
+                node_config.storage.rocksdb_configs,
                 false, /* indexer */
                 BUFFERED_STATE_TARGET_ITEMS,
                 DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
@@ -833,6 +833,15 @@ impl TestContext {
         payload: Value,
         status_code: u16,
     ) -> Value {
+        let request = self.simulation_request(sender, payload).await;
+        self.expect_status_code(status_code)
+            .post("/transactions/simulate", request)
+            .await
+    }
+
+    /// Builds a JSON request to simulate `payload` sent by `sender`, signed with an invalid
+    /// signature as the simulation API requires.
+    pub async fn simulation_request(&mut self, sender: &LocalAccount, payload: Value) -> Value {
         let mut request = json!({
             "sender": sender.address(),
             "sequence_number": sender.sequence_number().to_string(),
@@ -863,10 +872,7 @@ impl TestContext {
             "public_key": HexEncodedBytes::from(sender.public_key().to_bytes().to_vec()),
             "signature": HexEncodedBytes::from(sig.to_bytes().to_vec()),
         });
-
-        self.expect_status_code(status_code)
-            .post("/transactions/simulate", request)
-            .await
+        request
     }
 
     pub fn prepend_path(&self, path: &str) -> String {
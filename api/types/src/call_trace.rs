@@ -1,33 +1,120 @@
-use poem_openapi_derive::Object;
+use crate::{Address, Event, MoveModuleId, MoveStructTag, U64};
+use aptos_types::transaction::TransactionPayload;
+use move_core_types::call_trace::ResourceAccessKind;
+use poem_openapi_derive::{Enum, Object};
 use serde::{Deserialize, Serialize};
-use move_core_types::call_trace::InternalCallTrace;
 
 /// A call trace
 ///
 /// This is a representation of the debug call trace
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Object)]
 pub struct CallTrace {
+    /// Kind of the transaction payload the trace was recorded for. Only set on the root frame.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_kind: Option<CallTracePayloadKind>,
     pub pc: u16,
     pub module_id: String,
     pub func_name: String,
-    pub inputs: Vec<String>,
-    pub outputs: Vec<String>,
+    /// Arguments of the call, in the same JSON format as resources. References are shown as
+    /// the value they point to, and values that cannot be decoded are null.
+    pub inputs: Vec<serde_json::Value>,
+    /// Return values of the call, in the same format as `inputs`
+    pub outputs: Vec<serde_json::Value>,
     pub type_args: Vec<String>,
+    /// Events emitted by the function itself
+    pub events: Vec<Event>,
+    /// Resources the function itself accessed in global storage, in order of first access
+    pub resources: Vec<CallTraceResource>,
+    /// Internal gas units consumed by the call, including its sub traces
+    pub inclusive_internal_gas: U64,
+    /// Internal gas units consumed by the call, excluding its sub traces
+    pub exclusive_internal_gas: U64,
+    /// Set if the transaction failed while this call was the innermost one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abort: Option<CallTraceAbort>,
     pub sub_traces: Vec<CallTrace>,
 }
 
-impl From<InternalCallTrace> for CallTrace {
-    fn from(value: InternalCallTrace) -> Self {
-        CallTrace {
-            pc: value.pc,
-            module_id: value.module_id,
-            func_name: value.func_name,
-            inputs: value.inputs,
-            outputs: value.outputs,
-            type_args: value.type_args,
-            sub_traces: value.sub_traces.into_iter().enumerate().map(|(_, trace)| {
-                CallTrace::from(trace)
-            }).collect(),
+/// Kind of the transaction payload a call trace was recorded for
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum CallTracePayloadKind {
+    /// An entry function payload
+    EntryFunction,
+    /// A script payload
+    Script,
+    /// A multisig payload, traced from its inner entry function
+    Multisig,
+    /// A module bundle payload, traced as a frame charged the publishing gas, with the
+    /// initializers of its modules as sub traces
+    ModuleBundle,
+    /// The `block_prologue` call of a block metadata transaction
+    BlockMetadata,
+}
+
+impl From<&TransactionPayload> for CallTracePayloadKind {
+    fn from(payload: &TransactionPayload) -> Self {
+        match payload {
+            TransactionPayload::EntryFunction(_) => CallTracePayloadKind::EntryFunction,
+            TransactionPayload::Script(_) => CallTracePayloadKind::Script,
+            TransactionPayload::Multisig(_) => CallTracePayloadKind::Multisig,
+            TransactionPayload::ModuleBundle(_) => CallTracePayloadKind::ModuleBundle,
+        }
+    }
+}
+
+/// A resource accessed in global storage by a traced call
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Object)]
+pub struct CallTraceResource {
+    pub address: Address,
+    #[serde(rename = "type")]
+    #[oai(rename = "type")]
+    pub typ: MoveStructTag,
+    pub access: CallTraceResourceAccess,
+}
+
+/// How a traced call accessed a resource
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum CallTraceResourceAccess {
+    /// `exists<T>`
+    Exists,
+    /// `borrow_global<T>`
+    Read,
+    /// `borrow_global_mut<T>`
+    Write,
+    /// `move_from<T>`
+    MoveFrom,
+    /// `move_to<T>`
+    MoveTo,
+}
+
+impl From<ResourceAccessKind> for CallTraceResourceAccess {
+    fn from(kind: ResourceAccessKind) -> Self {
+        match kind {
+            ResourceAccessKind::Exists => CallTraceResourceAccess::Exists,
+            ResourceAccessKind::Read => CallTraceResourceAccess::Read,
+            ResourceAccessKind::Write => CallTraceResourceAccess::Write,
+            ResourceAccessKind::MoveFrom => CallTraceResourceAccess::MoveFrom,
+            ResourceAccessKind::MoveTo => CallTraceResourceAccess::MoveTo,
         }
     }
 }
+
+/// The error that stopped execution inside a traced call
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Object)]
+pub struct CallTraceAbort {
+    /// The VM status code of the error, e.g. `ABORTED` or `OUT_OF_GAS`
+    pub status_code: String,
+    /// The abort code, if the call aborted explicitly
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub abort_code: Option<U64>,
+    /// The module the error was raised in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<MoveModuleId>,
+    /// The offset of the failing instruction in the function's bytecode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_offset: Option<u16>,
+}
@@ -3,6 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    call_trace::{CallTrace, CallTraceAbort, CallTraceResource},
     transaction::{
         DecodedTableData, DeleteModule, DeleteResource, DeleteTableItem, DeletedTableData,
         ModuleBundlePayload, MultisigPayload, MultisigTransactionPayload,
@@ -11,10 +12,11 @@ use crate::{
     },
     view::ViewRequest,
     Bytecode, DirectWriteSet, EntryFunctionId, EntryFunctionPayload, Event, HexEncodedBytes,
-    MoveFunction, MoveModuleBytecode, MoveResource, MoveScriptBytecode, MoveType, MoveValue,
-    PendingTransaction, ResourceGroup, ScriptPayload, ScriptWriteSet, SubmitTransactionRequest,
-    Transaction, TransactionInfo, TransactionOnChainData, TransactionPayload,
-    UserTransactionRequest, VersionedEvent, WriteSet, WriteSetChange, WriteSetPayload,
+    MoveFunction, MoveModuleBytecode, MoveModuleId, MoveResource, MoveScriptBytecode, MoveType,
+    MoveValue, PendingTransaction, ResourceGroup, ScriptPayload, ScriptWriteSet,
+    SubmitTransactionRequest, Transaction, TransactionInfo, TransactionOnChainData,
+    TransactionPayload, UserTransactionRequest, VersionedEvent, WriteSet, WriteSetChange,
+    WriteSetPayload, U64,
 };
 use anyhow::{bail, ensure, format_err, Context as AnyhowContext, Result};
 use aptos_crypto::{hash::CryptoHash, HashValue};
@@ -37,11 +39,13 @@ use aptos_types::{
 use move_binary_format::file_format::FunctionHandleIndex;
 use move_core_types::{
     account_address::AccountAddress,
+    call_trace::{InternalCallTrace, InternalCallTraceValue},
     ident_str,
     identifier::{IdentStr, Identifier},
-    language_storage::{ModuleId, StructTag, TypeTag},
+    language_storage::{ModuleId, StructTag, TypeTag, CORE_CODE_ADDRESS},
     resolver::MoveResolver,
     value::{MoveStructLayout, MoveTypeLayout},
+    vm_status::StatusCode,
 };
 use move_resource_viewer::MoveValueAnnotator;
 use serde_json::Value;
@@ -52,6 +56,7 @@ use std::{
     sync::Arc,
 };
 
+const EVENT_MODULE: &IdentStr = ident_str!("event");
 const OBJECT_MODULE: &IdentStr = ident_str!("object");
 const OBJECT_STRUCT: &IdentStr = ident_str!("Object");
 
@@ -481,6 +486,115 @@ impl<'a, R: MoveResolver + ?Sized> MoveConverter<'a, R> {
         Ok(ret)
     }
 
+    /// Converts a call trace recorded by the VM. Calls to native functions are folded into
+    /// their callers: the events they write show up as events of the caller, and so does the
+    /// error if a native aborts.
+    ///
+    /// `events` are the events of the output of the traced execution, in the order they were
+    /// emitted. They are attributed to the calls to the event store natives in execution order,
+    /// so they must be empty if the execution failed and its events were discarded.
+    pub fn try_into_call_trace(
+        &self,
+        trace: InternalCallTrace,
+        events: &[ContractEvent],
+    ) -> Result<CallTrace> {
+        let mut events = events.iter();
+        let call_trace = self.try_into_call_trace_with_events(trace, &mut events)?;
+        Ok(call_trace)
+    }
+
+    fn try_into_call_trace_with_events(
+        &self,
+        trace: InternalCallTrace,
+        output_events: &mut std::slice::Iter<ContractEvent>,
+    ) -> Result<CallTrace> {
+        let mut events = vec![];
+        let mut abort = trace.abort;
+        let mut sub_traces = vec![];
+        let mut sub_traces_gas = 0;
+        for sub_trace in trace.sub_traces {
+            if sub_trace.is_native {
+                if Self::is_event_store_write(&sub_trace) && sub_trace.abort.is_none() {
+                    if let Some(event) = output_events.next() {
+                        events.push(event.clone());
+                    }
+                }
+                abort = abort.or(sub_trace.abort);
+            } else {
+                sub_traces_gas += sub_trace.gas_used;
+                sub_traces.push(self.try_into_call_trace_with_events(sub_trace, output_events)?);
+            }
+        }
+
+        Ok(CallTrace {
+            payload_kind: None,
+            pc: trace.pc,
+            module_id: trace.module_id,
+            func_name: trace.func_name,
+            inputs: trace
+                .inputs
+                .into_iter()
+                .map(|value| self.try_into_call_trace_value(value))
+                .collect(),
+            outputs: trace
+                .outputs
+                .into_iter()
+                .map(|value| self.try_into_call_trace_value(value))
+                .collect(),
+            type_args: trace.type_args,
+            events: self.try_into_events(&events)?,
+            resources: trace
+                .resource_accesses
+                .into_iter()
+                .map(|access| CallTraceResource {
+                    address: access.address.into(),
+                    typ: access.struct_tag.into(),
+                    access: access.kind.into(),
+                })
+                .collect(),
+            inclusive_internal_gas: trace.gas_used.into(),
+            exclusive_internal_gas: trace.gas_used.saturating_sub(sub_traces_gas).into(),
+            abort: abort.map(|abort| CallTraceAbort {
+                status_code: format!("{:?}", abort.status_code),
+                abort_code: match abort.status_code {
+                    StatusCode::ABORTED => abort.sub_status.map(U64::from),
+                    _ => None,
+                },
+                location: abort.location.map(MoveModuleId::from),
+                code_offset: abort.code_offset,
+            }),
+            sub_traces,
+        })
+    }
+
+    /// Renders a value of a call trace in the same format as resource fields. Signers, which
+    /// cannot be stored in resources, are rendered as their address.
+    fn try_into_call_trace_value(&self, value: Option<InternalCallTraceValue>) -> Value {
+        value
+            .and_then(|value| {
+                let move_value = match value.value {
+                    move_core_types::value::MoveValue::Signer(address) => {
+                        MoveValue::Address(address.into())
+                    },
+                    value_data => {
+                        let bytes = value_data.simple_serialize()?;
+                        self.try_into_move_value(&value.type_tag, &bytes).ok()?
+                    },
+                };
+                move_value.json().ok()
+            })
+            .unwrap_or(Value::Null)
+    }
+
+    /// Whether the traced call is to one of the natives writing an event to the event store.
+    fn is_event_store_write(trace: &InternalCallTrace) -> bool {
+        trace.module_id == ModuleId::new(CORE_CODE_ADDRESS, EVENT_MODULE.to_owned()).to_string()
+            && matches!(
+                trace.func_name.as_str(),
+                "write_to_event_store" | "write_module_event_to_store"
+            )
+    }
+
     pub fn try_into_versioned_events(
         &self,
         events: &[EventWithVersion],
@@ -887,6 +1001,7 @@ impl<'a, R: MoveResolver + ?Sized> MoveConverter<'a, R> {
             function,
             type_arguments,
             arguments,
+            ..
         } = view_request;
 
         let module = function.module.clone();
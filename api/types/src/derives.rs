@@ -20,8 +20,8 @@
 
 use crate::{
     move_types::{MoveAbility, MoveStructValue},
-    Address, EntryFunctionId, HashValue, HexEncodedBytes, IdentifierWrapper, MoveModuleId,
-    MoveStructTag, MoveType, StateKeyWrapper, U128, U256, U64,
+    Address, EntryFunctionId, EventCursor, HashValue, HexEncodedBytes, IdentifierWrapper,
+    MoveModuleId, MoveStructTag, MoveType, StateKeyWrapper, U128, U256, U64,
 };
 use aptos_openapi::{impl_poem_parameter, impl_poem_type};
 use indoc::indoc;
@@ -63,6 +63,19 @@ impl_poem_type!(
     )
 );
 
+impl_poem_type!(
+    EventCursor,
+    "string",
+    (
+        example = Some(serde_json::Value::String("32425224034_2".to_string())),
+        description = Some(indoc! {"
+          Position of an event, as `{version}_{event index}`. This is used for cursor based pagination.
+
+          A version alone refers to the first event of the version.
+        "})
+    )
+);
+
 impl_poem_type!(HashValue, "string", ());
 
 impl_poem_type!(
@@ -293,6 +306,8 @@ impl_poem_type!(
 
 impl_poem_parameter!(
     Address,
+    EntryFunctionId,
+    EventCursor,
     HashValue,
     IdentifierWrapper,
     HexEncodedBytes,
@@ -86,6 +86,8 @@ pub enum AptosErrorCode {
     VersionPruned = 200,
     /// Block is fully or partially pruned
     BlockPruned = 201,
+    /// Transaction expired before it was committed
+    TransactionExpired = 202,
 
     /// The API's inputs were invalid
     InvalidInput = 300,
@@ -101,6 +103,8 @@ pub enum AptosErrorCode {
     HealthCheckFailed = 500,
     /// The mempool is full, no new transactions can be submitted.
     MempoolIsFull = 501,
+    /// The node serves too many requests of this kind at the same time, retry later.
+    TooManyConnections = 502,
 
     /// Internal server error
     InternalError = 600,
@@ -8,6 +8,7 @@ mod account;
 mod address;
 mod block;
 mod bytecode;
+pub mod call_trace;
 mod convert;
 mod derives;
 mod error;
@@ -15,14 +16,16 @@ mod hash;
 mod headers;
 mod index;
 mod ledger_info;
+mod mempool;
 pub mod mime_types;
 mod move_types;
+mod proof;
 mod state;
+mod state_override;
 mod table;
 pub mod transaction;
 mod view;
 mod wrappers;
-pub mod call_trace;
 
 pub use account::AccountData;
 pub use address::Address;
@@ -34,6 +37,10 @@ pub use hash::HashValue;
 pub use headers::*;
 pub use index::{IndexResponse, IndexResponseBcs};
 pub use ledger_info::LedgerInfo;
+pub use mempool::{
+    MempoolBroadcastStatus, MempoolBucket, MempoolRemovalCount, MempoolSummary, MempoolTransaction,
+    MempoolTransactionSource, MempoolTransactionStatus,
+};
 pub use move_types::{
     verify_field_identifier, verify_function_identifier, verify_module_identifier, EntryFunctionId,
     HexEncodedBytes, MoveAbility, MoveFunction, MoveFunctionGenericTypeParam,
@@ -41,8 +48,10 @@ pub use move_types::{
     MoveScriptBytecode, MoveStruct, MoveStructField, MoveStructTag, MoveType, MoveValue,
     ResourceGroup, MAX_RECURSIVE_TYPES_ALLOWED, U128, U256, U64,
 };
+pub use proof::{StateValueWithProofBcs, TransactionWithProofBcs};
 use serde::{Deserialize, Deserializer};
-pub use state::RawStateValueRequest;
+pub use state::{RawStateValueRequest, ResourceWrite, StateValueWriteBcs, TableItemWrite};
+pub use state_override::{ModuleOverride, ResourceOverride, StateOverride, TableItemOverride};
 use std::str::FromStr;
 pub use table::{RawTableItemRequest, TableItemRequest};
 pub use transaction::{
@@ -51,15 +60,15 @@ pub use transaction::{
     FeePayerSignature, GasEstimation, GasEstimationBcs, GenesisPayload, GenesisTransaction,
     ModuleBundlePayload, MultiAgentSignature, MultiEd25519Signature, MultisigPayload,
     MultisigTransactionPayload, PendingTransaction, ScriptPayload, ScriptWriteSet,
-    Secp256k1EcdsaSignature, SubmitTransactionRequest, Transaction, TransactionData, TransactionId,
-    TransactionInfo, TransactionOnChainData, TransactionPayload, TransactionSignature,
-    TransactionSigningMessage, TransactionsBatchSingleSubmissionFailure,
-    TransactionsBatchSubmissionResult, UserCreateSigningMessageRequest, UserTransaction,
-    UserTransactionRequest, VersionedEvent, WriteModule, WriteResource, WriteSet, WriteSetChange,
-    WriteSetPayload, WriteTableItem,
+    Secp256k1EcdsaSignature, SimulateBundleRequest, SimulateTransactionRequest,
+    SubmitTransactionRequest, Transaction, TransactionData, TransactionId, TransactionInfo,
+    TransactionOnChainData, TransactionPayload, TransactionSignature, TransactionSigningMessage,
+    TransactionsBatchSingleSubmissionFailure, TransactionsBatchSubmissionResult,
+    UserCreateSigningMessageRequest, UserTransaction, UserTransactionRequest, VersionedEvent,
+    WriteModule, WriteResource, WriteSet, WriteSetChange, WriteSetPayload, WriteTableItem,
 };
-pub use view::ViewRequest;
-pub use wrappers::{EventGuid, IdentifierWrapper, StateKeyWrapper};
+pub use view::{ViewFunctionResult, ViewFunctionResultBcs, ViewRequest};
+pub use wrappers::{EventCursor, EventGuid, IdentifierWrapper, StateKeyWrapper};
 
 pub fn deserialize_from_string<'de, D, T>(deserializer: D) -> Result<T, D::Error>
 where
@@ -0,0 +1,97 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{PendingTransaction, U64};
+use poem_openapi_derive::{Enum, Object};
+use serde::{Deserialize, Serialize};
+
+/// A transaction waiting in mempool, with its state in mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolTransaction {
+    pub transaction: PendingTransaction,
+    pub status: MempoolTransactionStatus,
+    /// Position of the transaction in the queue consensus pulls transactions from, starting
+    /// at 0. Only set for ready transactions within the first 10000 positions of the queue.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub queue_position: Option<U64>,
+    /// Score the queue is ordered by, i.e. the gas unit price
+    pub ranking_score: U64,
+    /// Broadcast bucket of the ranking score, named after the lowest score of the bucket
+    pub bucket: String,
+    /// Time spent in mempool since the transaction was inserted, in milliseconds
+    pub time_in_mempool_ms: U64,
+    pub broadcast_status: MempoolBroadcastStatus,
+    pub submitted_by: MempoolTransactionSource,
+}
+
+/// Whether a transaction in mempool can be included in the next block
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum MempoolTransactionStatus {
+    /// The transaction is in the queue consensus pulls transactions from
+    Ready,
+    /// The transaction waits for the transactions filling the sequence number gap before it
+    Parked,
+}
+
+/// Whether a transaction in mempool is broadcast to the peers of the node
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum MempoolBroadcastStatus {
+    /// The transaction is in the broadcast timeline of its bucket
+    Ready,
+    /// The transaction will be broadcast once it's ready
+    NotReady,
+    /// The transaction is never broadcast by this node, e.g. it was received from a validator
+    NotQualified,
+}
+
+/// Where a transaction in mempool was received from
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize, Enum)]
+#[serde(rename_all = "snake_case")]
+#[oai(rename_all = "snake_case")]
+pub enum MempoolTransactionSource {
+    /// Submitted through the REST API of this node
+    Client,
+    /// Broadcast by a downstream peer
+    Downstream,
+    /// Broadcast by another validator
+    PeerValidator,
+}
+
+/// Node-wide summary of mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolSummary {
+    pub num_transactions: U64,
+    pub size_bytes: U64,
+    pub capacity: U64,
+    pub capacity_bytes: U64,
+    /// Number of transactions consensus can pull
+    pub num_ready_transactions: U64,
+    /// Number of transactions waiting for a sequence number gap to be filled
+    pub parking_lot_size: U64,
+    /// Broadcast buckets, in increasing ranking score order
+    pub buckets: Vec<MempoolBucket>,
+    /// Number of transactions removed from mempool since the node started, by reason
+    pub removed_transactions: Vec<MempoolRemovalCount>,
+}
+
+/// A broadcast bucket of mempool
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolBucket {
+    /// Lowest ranking score of the bucket, which names it
+    pub bucket: String,
+    /// Number of transactions ready for broadcast in the bucket
+    pub num_transactions: U64,
+}
+
+/// Number of transactions removed from mempool for a reason
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct MempoolRemovalCount {
+    /// One of `committed`, `rejected`, `gas_upgraded`, `mempool_full`, `system_ttl` and
+    /// `client_expiration`
+    pub reason: String,
+    pub count: U64,
+}
@@ -0,0 +1,36 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    proof::{SparseMerkleProof, TransactionInfoWithProof},
+    state_proof::StateProof,
+    state_store::state_value::StateValue,
+    transaction::TransactionWithProof,
+};
+use serde::{Deserialize, Serialize};
+
+/// A state value read from a state snapshot, with the proofs to verify it from a trusted
+/// waypoint
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateValueWithProofBcs {
+    /// Version of the state snapshot the value is read from
+    pub version: u64,
+    /// The value, `None` if the state key doesn't exist at the version
+    pub state_value: Option<StateValue>,
+    /// Proves the value, or its absence, against the state checkpoint hash of the transaction
+    /// info at `version`
+    pub state_value_proof: SparseMerkleProof,
+    /// Proves the transaction info at `version` against the latest ledger info in `state_proof`
+    pub transaction_info_with_proof: TransactionInfoWithProof,
+    /// The latest ledger info, and the epoch changes since the version known by the client
+    pub state_proof: StateProof,
+}
+
+/// A committed transaction, with the proofs to verify it from a trusted waypoint
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransactionWithProofBcs {
+    /// The transaction and its events, proven against the latest ledger info in `state_proof`
+    pub transaction_with_proof: TransactionWithProof,
+    /// The latest ledger info, and the epoch changes since the version known by the client
+    pub state_proof: StateProof,
+}
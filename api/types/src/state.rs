@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::HexEncodedBytes;
+use crate::{HexEncodedBytes, MoveResource, MoveValue, U64};
 use poem_openapi::Object;
 use serde::{Deserialize, Serialize};
 
@@ -10,3 +10,32 @@ use serde::{Deserialize, Serialize};
 pub struct RawStateValueRequest {
     pub key: HexEncodedBytes,
 }
+
+/// A write to an account resource
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ResourceWrite {
+    /// Version of the transaction that wrote the resource
+    pub version: U64,
+    /// The resource after the write, not set if the write deleted it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<MoveResource>,
+}
+
+/// A write to a table item
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TableItemWrite {
+    /// Version of the transaction that wrote the table item
+    pub version: U64,
+    /// The value of the table item after the write, not set if the write deleted it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<MoveValue>,
+}
+
+/// A write to a resource or table item, with the BCS encoded value
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateValueWriteBcs {
+    /// Version of the transaction that wrote the value
+    pub version: u64,
+    /// The value after the write, `None` if the write deleted it
+    pub value: Option<Vec<u8>>,
+}
@@ -0,0 +1,119 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    Address, HexEncodedBytes, MoveStructTag, MoveType, VerifyInput, VerifyInputWithRecursion,
+};
+use anyhow::bail;
+use poem_openapi::{Object, Union};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An override of the state a simulation or view function is executed against
+///
+/// Overrides are applied on top of the state at the requested ledger version, and are never
+/// persisted. Module overrides are applied first, so that values can use types they define.
+/// Values are given either as JSON Move values, in the same format the API returns them, or as
+/// raw BCS.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Union)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[oai(one_of, discriminator_name = "type", rename_all = "snake_case")]
+pub enum StateOverride {
+    Resource(ResourceOverride),
+    TableItem(TableItemOverride),
+    Module(ModuleOverride),
+}
+
+impl VerifyInput for StateOverride {
+    fn verify(&self) -> anyhow::Result<()> {
+        match self {
+            StateOverride::Resource(inner) => inner.verify(),
+            StateOverride::TableItem(inner) => inner.verify(),
+            StateOverride::Module(_) => Ok(()),
+        }
+    }
+}
+
+fn verify_value(
+    name: &str,
+    json: &Option<Value>,
+    bcs: &Option<HexEncodedBytes>,
+) -> anyhow::Result<()> {
+    match (json, bcs) {
+        (Some(_), None) | (None, Some(_)) => Ok(()),
+        _ => bail!("Exactly one of '{}' and 'bcs_{}' must be set", name, name),
+    }
+}
+
+/// Replaces a resource, or creates it if it does not exist
+///
+/// Resources that are members of a resource group are updated within their group.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ResourceOverride {
+    pub address: Address,
+    pub resource_type: MoveStructTag,
+    /// The resource as a JSON Move value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+    /// The resource encoded as BCS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bcs_data: Option<HexEncodedBytes>,
+}
+
+impl VerifyInput for ResourceOverride {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.resource_type.verify(0)?;
+        verify_value("data", &self.data, &self.bcs_data)
+    }
+}
+
+/// Replaces a table item, or creates it if it does not exist
+///
+/// `key_type` is required if the key is given as JSON, `value_type` if the value is.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct TableItemOverride {
+    pub handle: Address,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_type: Option<MoveType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_type: Option<MoveType>,
+    /// The key as a JSON Move value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key: Option<Value>,
+    /// The key encoded as BCS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bcs_key: Option<HexEncodedBytes>,
+    /// The value as a JSON Move value
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    /// The value encoded as BCS
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bcs_value: Option<HexEncodedBytes>,
+}
+
+impl VerifyInput for TableItemOverride {
+    fn verify(&self) -> anyhow::Result<()> {
+        verify_value("key", &self.key, &self.bcs_key)?;
+        verify_value("value", &self.value, &self.bcs_value)?;
+        match (&self.key, &self.key_type) {
+            (Some(_), None) => bail!("'key_type' is required for a JSON key"),
+            (_, Some(key_type)) => key_type.verify(0)?,
+            _ => (),
+        }
+        match (&self.value, &self.value_type) {
+            (Some(_), None) => bail!("'value_type' is required for a JSON value"),
+            (_, Some(value_type)) => value_type.verify(0)?,
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+/// Replaces a module, or publishes it if it does not exist
+///
+/// The module id is read from the bytecode. Its compatibility with the module it replaces is
+/// not checked.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ModuleOverride {
+    pub bytecode: HexEncodedBytes,
+}
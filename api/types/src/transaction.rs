@@ -3,9 +3,9 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    Address, AptosError, EntryFunctionId, EventGuid, HashValue, HexEncodedBytes,
-    MoveModuleBytecode, MoveModuleId, MoveResource, MoveScriptBytecode, MoveStructTag, MoveType,
-    MoveValue, VerifyInput, VerifyInputWithRecursion, U64,
+    call_trace::CallTrace, Address, AptosError, EntryFunctionId, EventGuid, HashValue,
+    HexEncodedBytes, MoveModuleBytecode, MoveModuleId, MoveResource, MoveScriptBytecode,
+    MoveStructTag, MoveType, MoveValue, StateOverride, VerifyInput, VerifyInputWithRecursion, U64,
 };
 use anyhow::{bail, Context as AnyhowContext};
 use aptos_crypto::{
@@ -272,6 +272,7 @@ impl
             request: (txn, payload).into(),
             events,
             timestamp: timestamp.into(),
+            call_trace: None,
         }))
     }
 }
@@ -372,6 +373,10 @@ pub struct UserTransaction {
     /// Events generated by the transaction
     pub events: Vec<Event>,
     pub timestamp: U64,
+    /// Call trace of the transaction's payload. Only set on simulated transactions when
+    /// requested with `trace=true`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub call_trace: Option<CallTrace>,
 }
 
 /// A state checkpoint transaction
@@ -401,6 +406,55 @@ impl VerifyInput for SubmitTransactionRequest {
     }
 }
 
+/// A request to simulate a transaction
+///
+/// This is a transaction and its (invalid) signature, like a SubmitTransactionRequest, with
+/// optional overrides of the state it is simulated against
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SimulateTransactionRequest {
+    #[serde(flatten)]
+    #[oai(flatten)]
+    pub transaction: SubmitTransactionRequest,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<Vec<StateOverride>>,
+}
+
+impl VerifyInput for SimulateTransactionRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        self.transaction.verify()?;
+        for state_override in self.state_overrides.iter().flatten() {
+            state_override.verify()?;
+        }
+        Ok(())
+    }
+}
+
+/// A request to simulate a bundle of transactions one after another
+///
+/// Each transaction is a SubmitTransactionRequest with an (invalid) signature. The state
+/// overrides apply to the state the first transaction is simulated against.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct SimulateBundleRequest {
+    pub transactions: Vec<SubmitTransactionRequest>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<Vec<StateOverride>>,
+}
+
+impl VerifyInput for SimulateBundleRequest {
+    fn verify(&self) -> anyhow::Result<()> {
+        if self.transactions.is_empty() {
+            bail!("A bundle must contain at least one transaction");
+        }
+        for transaction in &self.transactions {
+            transaction.verify()?;
+        }
+        for state_override in self.state_overrides.iter().flatten() {
+            state_override.verify()?;
+        }
+        Ok(())
+    }
+}
+
 /// Batch transaction submission result
 ///
 /// Tells which transactions failed
@@ -1,7 +1,7 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{EntryFunctionId, MoveType};
+use crate::{EntryFunctionId, MoveType, MoveValue, StateOverride, U64};
 use poem_openapi::Object;
 use serde::{Deserialize, Serialize};
 
@@ -13,4 +13,33 @@ pub struct ViewRequest {
     pub type_arguments: Vec<MoveType>,
     /// Arguments of the function
     pub arguments: Vec<serde_json::Value>,
+    /// Overrides of the state the function is executed against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_overrides: Option<Vec<StateOverride>>,
+}
+
+/// The result of one call of a view function batch
+///
+/// Exactly one of `values` and `error` is set.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, Object)]
+pub struct ViewFunctionResult {
+    /// The return values of the function, if the call succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub values: Option<Vec<MoveValue>>,
+    /// Why the call failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Gas counted against the gas limit of the batch for the call
+    ///
+    /// This is the gas used by the call, but at least `min_gas_view_function_batch_call`, also
+    /// for calls that failed before they were executed.
+    pub gas_used: U64,
+}
+
+/// The result of one call of a view function batch, with BCS encoded return values
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewFunctionResultBcs {
+    pub values: Option<Vec<Vec<u8>>>,
+    pub error: Option<String>,
+    pub gas_used: u64,
 }
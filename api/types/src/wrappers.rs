@@ -151,3 +151,34 @@ impl From<StateKeyWrapper> for StateKey {
         value.0
     }
 }
+
+/// The position of an event, as the version of its transaction and its index in the
+/// transaction. It is represented as `<version>_<event_index>`, or as just a version for
+/// the first event of the version.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct EventCursor {
+    pub version: u64,
+    pub event_index: u64,
+}
+
+impl fmt::Display for EventCursor {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}_{}", self.version, self.event_index)
+    }
+}
+
+impl FromStr for EventCursor {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self, anyhow::Error> {
+        let (version, event_index) = s.split_once('_').unwrap_or((s, "0"));
+        Ok(EventCursor {
+            version: version
+                .parse()
+                .context("Failed to parse the version of the event cursor")?,
+            event_index: event_index
+                .parse()
+                .context("Failed to parse the event index of the event cursor")?,
+        })
+    }
+}
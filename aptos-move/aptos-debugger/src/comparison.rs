@@ -0,0 +1,163 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use aptos_types::{
+    contract_event::ContractEvent,
+    state_store::state_key::StateKey,
+    transaction::{TransactionInfo, TransactionOutput, TransactionStatus, Version},
+    write_set::WriteOp,
+};
+use std::{collections::BTreeMap, fmt};
+
+/// A write to a state key that differs between two executions of a transaction.
+/// `before` is None if the key was only written with the overrides, `after` is None if it was
+/// only written without them.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteDiff {
+    pub state_key: StateKey,
+    pub before: Option<WriteOp>,
+    pub after: Option<WriteOp>,
+}
+
+/// The outputs of a historical transaction replayed as is and with [`crate::ReplayOverrides`].
+#[derive(Clone, Debug)]
+pub struct TransactionComparison {
+    pub version: Version,
+    /// Why the replay without the overrides does not match the committed transaction, if it
+    /// does not. The differences are then not only caused by the overrides.
+    pub committed_mismatch: Option<String>,
+    pub status_before: TransactionStatus,
+    pub status_after: TransactionStatus,
+    pub gas_used_before: u64,
+    pub gas_used_after: u64,
+    /// Writes that were added, removed or changed, ordered by state key.
+    pub write_set_diff: Vec<WriteDiff>,
+    /// Events only emitted without the overrides.
+    pub removed_events: Vec<ContractEvent>,
+    /// Events only emitted with the overrides.
+    pub added_events: Vec<ContractEvent>,
+}
+
+impl TransactionComparison {
+    pub fn new(
+        version: Version,
+        txn_info: &TransactionInfo,
+        before: &TransactionOutput,
+        after: &TransactionOutput,
+    ) -> Self {
+        let committed_mismatch = before
+            .ensure_match_transaction_info(version, txn_info, None, None)
+            .err()
+            .map(|err| err.to_string());
+
+        let mut writes_before: BTreeMap<_, _> = before.write_set().iter().collect();
+        let mut write_set_diff = vec![];
+        let writes_after: BTreeMap<_, _> = after.write_set().iter().collect();
+        for (state_key, op_after) in writes_after {
+            let op_before = writes_before.remove(state_key);
+            if op_before != Some(op_after) {
+                write_set_diff.push(WriteDiff {
+                    state_key: state_key.clone(),
+                    before: op_before.cloned(),
+                    after: Some(op_after.clone()),
+                });
+            }
+        }
+        for (state_key, op_before) in writes_before {
+            write_set_diff.push(WriteDiff {
+                state_key: state_key.clone(),
+                before: Some(op_before.clone()),
+                after: None,
+            });
+        }
+        write_set_diff.sort_by(|diff1, diff2| diff1.state_key.cmp(&diff2.state_key));
+
+        // Match the events pairwise, so that an event emitted more often with the overrides
+        // shows up as added.
+        let mut added_events = after.events().to_vec();
+        let mut removed_events = vec![];
+        for event in before.events() {
+            match added_events.iter().position(|added| added == event) {
+                Some(idx) => {
+                    added_events.remove(idx);
+                },
+                None => removed_events.push(event.clone()),
+            }
+        }
+
+        Self {
+            version,
+            committed_mismatch,
+            status_before: before.status().clone(),
+            status_after: after.status().clone(),
+            gas_used_before: before.gas_used(),
+            gas_used_after: after.gas_used(),
+            write_set_diff,
+            removed_events,
+            added_events,
+        }
+    }
+
+    /// Returns true if the overrides did not change the output.
+    pub fn is_unchanged(&self) -> bool {
+        self.status_before == self.status_after
+            && self.gas_used_before == self.gas_used_after
+            && self.write_set_diff.is_empty()
+            && self.removed_events.is_empty()
+            && self.added_events.is_empty()
+    }
+}
+
+impl fmt::Display for TransactionComparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(mismatch) = &self.committed_mismatch {
+            writeln!(
+                f,
+                "Version {}: replay does not match the chain: {}",
+                self.version, mismatch
+            )?;
+        }
+        if self.is_unchanged() {
+            return writeln!(f, "Version {}: unchanged", self.version);
+        }
+
+        writeln!(f, "Version {}:", self.version)?;
+        if self.status_before != self.status_after {
+            writeln!(
+                f,
+                "  status: {:?} -> {:?}",
+                self.status_before, self.status_after
+            )?;
+        }
+        if self.gas_used_before != self.gas_used_after {
+            writeln!(
+                f,
+                "  gas used: {} -> {} ({:+})",
+                self.gas_used_before,
+                self.gas_used_after,
+                self.gas_used_after as i128 - self.gas_used_before as i128
+            )?;
+        }
+        for diff in &self.write_set_diff {
+            match (&diff.before, &diff.after) {
+                (None, Some(after)) => writeln!(f, "  + write {:?}: {:?}", diff.state_key, after)?,
+                (Some(before), None) => {
+                    writeln!(f, "  - write {:?}: {:?}", diff.state_key, before)?
+                },
+                (Some(before), Some(after)) => writeln!(
+                    f,
+                    "  ~ write {:?}: {:?} -> {:?}",
+                    diff.state_key, before, after
+                )?,
+                (None, None) => unreachable!("a write is present in at least one output"),
+            }
+        }
+        for event in &self.removed_events {
+            writeln!(f, "  - event {}", event)?;
+        }
+        for event in &self.added_events {
+            writeln!(f, "  + event {}", event)?;
+        }
+        Ok(())
+    }
+}
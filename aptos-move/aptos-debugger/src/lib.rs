@@ -1,26 +1,34 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use anyhow::{format_err, Result};
+mod comparison;
+#[cfg(test)]
+mod tests;
+
+use anyhow::{bail, ensure, format_err, Result};
 use aptos_gas_meter::{StandardGasAlgebra, StandardGasMeter};
-use aptos_gas_profiling::{GasProfiler, TransactionGasLog};
+use aptos_gas_profiling::{GasProfileFile, GasProfileFormat, GasProfiler, TransactionGasLog};
 use aptos_gas_schedule::{MiscGasParameters, NativeGasParameters, LATEST_GAS_FEATURE_VERSION};
 use aptos_memory_usage_tracker::MemoryTrackedGasMeter;
 use aptos_resource_viewer::{AnnotatedAccountStateBlob, AptosValueAnnotator};
 use aptos_rest_client::Client;
-use aptos_state_view::TStateView;
+use aptos_state_view::{overlay_state_view::OverlayStateView, TStateView};
 use aptos_types::{
+    access_path::AccessPath,
     account_address::AccountAddress,
     chain_id::ChainId,
-    on_chain_config::{Features, OnChainConfig, TimedFeaturesBuilder},
+    on_chain_config::{Features, GasScheduleV2, OnChainConfig, TimedFeaturesBuilder},
+    state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{
         SignedTransaction, Transaction, TransactionInfo, TransactionOutput, TransactionPayload,
         Version,
     },
     vm_status::VMStatus,
+    waypoint::Waypoint,
 };
 use aptos_validator_interface::{
-    AptosValidatorInterface, DBDebuggerInterface, DebuggerStateView, RestDebuggerInterface,
+    AptosValidatorInterface, BackupDebuggerInterface, DBDebuggerInterface, DebuggerStateView,
+    RestDebuggerInterface,
 };
 use aptos_vm::{
     data_cache::AsMoveResolver,
@@ -29,8 +37,90 @@ use aptos_vm::{
 };
 use aptos_vm_logging::log_schema::AdapterLogSchema;
 use aptos_vm_types::{change_set::VMChangeSet, output::VMOutput, storage::ChangeSetConfigs};
-use move_binary_format::errors::VMResult;
-use std::{path::Path, sync::Arc};
+pub use comparison::{TransactionComparison, WriteDiff};
+use futures::{stream, StreamExt, TryStreamExt};
+use move_binary_format::{errors::VMResult, CompiledModule};
+use std::{
+    collections::HashSet,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+const CONCURRENT_PREWARM_FETCHES: usize = 16;
+
+/// Changes to the on-chain state to replay historical transactions with, to see how they would
+/// have behaved e.g. after a framework upgrade or a gas schedule change.
+#[derive(Clone, Debug, Default)]
+pub struct ReplayOverrides {
+    /// Modules replacing the published modules with the same ids, or added if there are none.
+    pub modules: Vec<CompiledModule>,
+    pub gas_schedule: Option<GasScheduleV2>,
+    /// Feature flags to enable on top of the on-chain ones, as defined in `std::features`.
+    pub enable_features: Vec<u64>,
+    /// Feature flags to disable on top of the on-chain ones, as defined in `std::features`.
+    pub disable_features: Vec<u64>,
+}
+
+impl ReplayOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.modules.is_empty()
+            && self.gas_schedule.is_none()
+            && self.enable_features.is_empty()
+            && self.disable_features.is_empty()
+    }
+
+    /// Layers the overrides on top of the given state view.
+    fn apply<S: TStateView<Key = StateKey>>(&self, state_view: S) -> Result<OverlayStateView<S>> {
+        let mut overwrites = vec![];
+        for module in &self.modules {
+            let mut bytes = vec![];
+            module.serialize(&mut bytes)?;
+            overwrites.push((
+                StateKey::access_path(AccessPath::code_access_path(module.self_id())),
+                bytes,
+            ));
+        }
+        if let Some(gas_schedule) = &self.gas_schedule {
+            overwrites.push((
+                StateKey::access_path(GasScheduleV2::access_path()?),
+                bcs::to_bytes(gas_schedule)?,
+            ));
+        }
+        if !self.enable_features.is_empty() || !self.disable_features.is_empty() {
+            let mut features = Features::fetch_config(&state_view.as_move_resolver())
+                .ok_or_else(|| format_err!("Failed to fetch the on-chain feature flags."))?;
+            for flag in &self.enable_features {
+                set_feature(&mut features, *flag, true);
+            }
+            for flag in &self.disable_features {
+                set_feature(&mut features, *flag, false);
+            }
+            overwrites.push((
+                StateKey::access_path(Features::access_path()?),
+                bcs::to_bytes(&features)?,
+            ));
+        }
+
+        let mut state_view = OverlayStateView::new(state_view);
+        for (state_key, bytes) in overwrites {
+            state_view.overwrite(state_key, Some(StateValue::new_legacy(bytes.into())));
+        }
+        Ok(state_view)
+    }
+}
+
+fn set_feature(features: &mut Features, flag: u64, enabled: bool) {
+    let byte_index = (flag / 8) as usize;
+    let bit_mask = 1 << (flag % 8);
+    if features.features.len() <= byte_index {
+        features.features.resize(byte_index + 1, 0);
+    }
+    if enabled {
+        features.features[byte_index] |= bit_mask;
+    } else {
+        features.features[byte_index] &= !bit_mask;
+    }
+}
 
 pub struct AptosDebugger {
     debugger: Arc<dyn AptosValidatorInterface + Send>,
@@ -51,6 +141,21 @@ impl AptosDebugger {
         )?)))
     }
 
+    pub async fn backup(
+        backup_dir: PathBuf,
+        metadata_cache_dir: Option<PathBuf>,
+        trusted_waypoints: Vec<Waypoint>,
+    ) -> Result<Self> {
+        let trusted_waypoints = trusted_waypoints
+            .into_iter()
+            .map(|waypoint| (waypoint.version(), waypoint))
+            .collect();
+        Ok(Self::new(Arc::new(
+            BackupDebuggerInterface::open(backup_dir, metadata_cache_dir, trusted_waypoints)
+                .await?,
+        )))
+    }
+
     pub fn execute_transactions_at_version(
         &self,
         version: Version,
@@ -61,6 +166,19 @@ impl AptosDebugger {
             .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
     }
 
+    /// Executes the transactions on top of the state at the given version, with the overrides
+    /// applied.
+    pub fn execute_transactions_at_version_with_overrides(
+        &self,
+        version: Version,
+        txns: Vec<Transaction>,
+        overrides: &ReplayOverrides,
+    ) -> Result<Vec<TransactionOutput>> {
+        let state_view = overrides.apply(DebuggerStateView::new(self.debugger.clone(), version))?;
+        AptosVM::execute_block(txns, &state_view, None)
+            .map_err(|err| format_err!("Unexpected VM Error: {:?}", err))
+    }
+
     pub fn execute_transaction_at_version_with_gas_profiler(
         &self,
         version: Version,
@@ -106,6 +224,45 @@ impl AptosDebugger {
         Ok((status, output, gas_profiler.finish()))
     }
 
+    /// Executes the transaction with the gas profiler and exports the resulting gas log in the
+    /// given format, labeling the generated graphs with the title.
+    pub fn execute_transaction_at_version_with_gas_profiler_in_format(
+        &self,
+        version: Version,
+        txn: SignedTransaction,
+        format: GasProfileFormat,
+        title: &str,
+    ) -> Result<(VMStatus, VMOutput, TransactionGasLog, Vec<GasProfileFile>)> {
+        let (status, output, gas_log) =
+            self.execute_transaction_at_version_with_gas_profiler(version, txn)?;
+        let files = gas_log.export(format, title)?;
+        Ok((status, output, gas_log, files))
+    }
+
+    /// Replays the committed user transaction at the given version with the gas profiler.
+    pub async fn execute_past_transaction_with_gas_profiler(
+        &self,
+        version: Version,
+    ) -> Result<(VMStatus, VMOutput, TransactionGasLog)> {
+        let (mut txns, _) = self.debugger.get_committed_transactions(version, 1).await?;
+        let txn = match txns.pop() {
+            Some(Transaction::UserTransaction(txn)) => txn,
+            Some(_) => bail!(
+                "transaction at version {} is not a user transaction",
+                version
+            ),
+            None => bail!("transaction at version {} not found", version),
+        };
+        match txn.payload() {
+            TransactionPayload::Script(_) | TransactionPayload::EntryFunction(_) => (),
+            TransactionPayload::ModuleBundle(_) | TransactionPayload::Multisig(_) => bail!(
+                "gas profiling of the payload of transaction {} is not supported",
+                version
+            ),
+        }
+        self.execute_transaction_at_version_with_gas_profiler(version, txn)
+    }
+
     pub async fn execute_past_transactions(
         &self,
         mut begin: Version,
@@ -136,6 +293,83 @@ impl AptosDebugger {
         Ok(ret)
     }
 
+    /// Fetches the committed transactions and the values of the state keys they write as of the
+    /// version before `begin`, without replaying them. Values that are only read are fetched by
+    /// the first replay.
+    pub async fn prewarm_past_transactions(&self, begin: Version, limit: u64) -> Result<()> {
+        ensure!(begin > 0, "Expecting a non-genesis version");
+        self.debugger
+            .get_committed_transactions(begin, limit)
+            .await?;
+        let state_keys = self
+            .debugger
+            .get_committed_write_sets(begin, limit)
+            .await?
+            .iter()
+            .flat_map(|write_set| write_set.iter().map(|(state_key, _)| state_key.clone()))
+            .collect::<HashSet<_>>();
+        println!("Fetching {} state values.", state_keys.len());
+
+        stream::iter(state_keys)
+            .map(|state_key| async move {
+                self.debugger
+                    .get_state_value_by_version(&state_key, begin - 1)
+                    .await
+            })
+            .buffer_unordered(CONCURRENT_PREWARM_FETCHES)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(())
+    }
+
+    /// Replays the committed transactions twice, as is and with the overrides, and compares the
+    /// outputs of each transaction.
+    ///
+    /// Both replays are split at the epoch boundaries of the original replay. Transactions after
+    /// a reconfiguration that only happens with the overrides show up with a `Retry` status.
+    pub async fn execute_past_transactions_with_overrides(
+        &self,
+        mut begin: Version,
+        mut limit: u64,
+        overrides: &ReplayOverrides,
+    ) -> Result<Vec<TransactionComparison>> {
+        let (mut txns, mut txn_infos) = self
+            .debugger
+            .get_committed_transactions(begin, limit)
+            .await?;
+
+        let mut ret = vec![];
+        while limit != 0 {
+            println!(
+                "Starting epoch execution at {:?}, {:?} transactions remaining",
+                begin, limit
+            );
+            let epoch_result = self
+                .execute_transactions_by_epoch(begin, txns.clone())
+                .await?;
+            let epoch_txns = txns.drain(0..epoch_result.len()).collect::<Vec<_>>();
+            let epoch_txn_infos = txn_infos.drain(0..epoch_result.len());
+            let overridden_result =
+                self.execute_transactions_at_version_with_overrides(begin, epoch_txns, overrides)?;
+            for (idx, ((before, after), txn_info)) in epoch_result
+                .iter()
+                .zip(&overridden_result)
+                .zip(epoch_txn_infos)
+                .enumerate()
+            {
+                ret.push(TransactionComparison::new(
+                    begin + idx as Version,
+                    &txn_info,
+                    before,
+                    after,
+                ));
+            }
+            begin += epoch_result.len() as u64;
+            limit -= epoch_result.len() as u64;
+        }
+        Ok(ret)
+    }
+
     fn print_mismatches(
         txn_outputs: &[TransactionOutput],
         expected_txn_infos: &[TransactionInfo],
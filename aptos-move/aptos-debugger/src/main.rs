@@ -2,19 +2,45 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use aptos_debugger::AptosDebugger;
+use aptos_debugger::{AptosDebugger, ReplayOverrides};
 use aptos_rest_client::Client;
+use aptos_types::waypoint::Waypoint;
+use aptos_validator_interface::{
+    AptosValidatorInterface, CachedDebuggerInterface, RestDebuggerInterface,
+};
 use aptos_vm::AptosVM;
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use move_binary_format::CompiledModule;
+use std::{path::PathBuf, sync::Arc};
 use url::Url;
 
 #[derive(Subcommand)]
 pub enum Target {
     /// Use full node's rest api as query endpoint.
-    Rest { endpoint: String },
+    Rest {
+        endpoint: String,
+        /// Directory of a persistent cache for the data fetched from the rest api. Created if it
+        /// does not exist yet.
+        #[clap(long)]
+        cache_dir: Option<PathBuf>,
+    },
+    /// Use a cache previously populated through `rest --cache-dir` as query endpoint, without
+    /// fetching anything.
+    Cache { path: PathBuf },
     /// Use a local db instance to serve as query endpoint.
     DB { path: PathBuf },
+    /// Use a backup on the local file system, as written by `aptos-db-tool backup`, to serve as
+    /// query endpoint.
+    Backup {
+        path: PathBuf,
+        /// Directory to cache the backup metadata in. Defaults to a temporary directory.
+        #[clap(long)]
+        metadata_cache_dir: Option<PathBuf>,
+        /// Waypoint to check the epoch ending ledger info at its version against, instead of
+        /// checking the signatures on it. Can be given multiple times.
+        #[clap(long)]
+        trust_waypoint: Vec<Waypoint>,
+    },
 }
 #[derive(Parser)]
 pub struct Argument {
@@ -29,6 +55,48 @@ pub struct Argument {
 
     #[clap(long, default_value_t = 1)]
     concurrency_level: usize,
+
+    /// Only populate the cache with the transactions and the state values they write, without
+    /// replaying them. Values that are only read are fetched by the first replay.
+    #[clap(long)]
+    prewarm: bool,
+
+    /// Compiled module (.mv) to replay the transactions with, replacing the published module with
+    /// the same id. Can be given multiple times. The outputs are then compared against a replay
+    /// without any overrides.
+    #[clap(long)]
+    override_module: Vec<PathBuf>,
+
+    /// Gas schedule (a `GasScheduleV2` in JSON) to replay the transactions with.
+    #[clap(long)]
+    override_gas_schedule: Option<PathBuf>,
+
+    /// Feature flag to enable when replaying the transactions. Can be given multiple times.
+    #[clap(long)]
+    enable_feature: Vec<u64>,
+
+    /// Feature flag to disable when replaying the transactions. Can be given multiple times.
+    #[clap(long)]
+    disable_feature: Vec<u64>,
+}
+
+impl Argument {
+    fn overrides(&self) -> Result<ReplayOverrides> {
+        let mut modules = vec![];
+        for path in &self.override_module {
+            modules.push(CompiledModule::deserialize(&std::fs::read(path)?)?);
+        }
+        let gas_schedule = match &self.override_gas_schedule {
+            Some(path) => Some(serde_json::from_slice(&std::fs::read(path)?)?),
+            None => None,
+        };
+        Ok(ReplayOverrides {
+            modules,
+            gas_schedule,
+            enable_features: self.enable_feature.clone(),
+            disable_features: self.disable_feature.clone(),
+        })
+    }
 }
 
 #[tokio::main]
@@ -36,20 +104,58 @@ async fn main() -> Result<()> {
     aptos_logger::Logger::new().init();
     let args = Argument::parse();
     AptosVM::set_concurrency_level_once(args.concurrency_level);
+    let overrides = args.overrides()?;
 
+    let mut cache = None;
     let debugger = match args.target {
-        Target::Rest { endpoint } => {
-            AptosDebugger::rest_client(Client::new(Url::parse(&endpoint)?))?
+        Target::Rest {
+            endpoint,
+            cache_dir: None,
+        } => AptosDebugger::rest_client(Client::new(Url::parse(&endpoint)?))?,
+        Target::Rest {
+            endpoint,
+            cache_dir: Some(cache_dir),
+        } => {
+            let rest: Arc<dyn AptosValidatorInterface + Send> = Arc::new(
+                RestDebuggerInterface::new(Client::new(Url::parse(&endpoint)?)),
+            );
+            let cached = Arc::new(CachedDebuggerInterface::open(cache_dir, Some(rest))?);
+            cache = Some(cached.clone());
+            AptosDebugger::new(cached)
+        },
+        Target::Cache { path } => {
+            let cached = Arc::new(CachedDebuggerInterface::open(path, None)?);
+            cache = Some(cached.clone());
+            AptosDebugger::new(cached)
         },
         Target::DB { path } => AptosDebugger::db(path)?,
+        Target::Backup {
+            path,
+            metadata_cache_dir,
+            trust_waypoint,
+        } => AptosDebugger::backup(path, metadata_cache_dir, trust_waypoint).await?,
     };
 
-    println!(
-        "{:#?}",
+    if args.prewarm {
         debugger
+            .prewarm_past_transactions(args.begin_version, args.limit)
+            .await?;
+    } else if overrides.is_empty() {
+        let outputs = debugger
             .execute_past_transactions(args.begin_version, args.limit)
-            .await?
-    );
+            .await?;
+        println!("{:#?}", outputs);
+    } else {
+        let comparisons = debugger
+            .execute_past_transactions_with_overrides(args.begin_version, args.limit, &overrides)
+            .await?;
+        for comparison in comparisons {
+            print!("{}", comparison);
+        }
+    }
+    if let Some(cache) = cache {
+        println!("{}", cache.stats());
+    }
 
     Ok(())
 }
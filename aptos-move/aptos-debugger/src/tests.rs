@@ -0,0 +1,178 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{AptosDebugger, ReplayOverrides};
+use anyhow::{bail, Result};
+use aptos_cached_packages::aptos_stdlib;
+use aptos_crypto::hash::{CryptoHash, EventAccumulatorHasher};
+use aptos_language_e2e_tests::{data_store::FakeDataStore, executor::FakeExecutor};
+use aptos_state_view::TStateView;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    account_config::CORE_CODE_ADDRESS,
+    account_state::AccountState,
+    proof::accumulator::InMemoryAccumulator,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{ExecutionStatus, Transaction, TransactionInfo, TransactionStatus, Version},
+    write_set::WriteSet,
+};
+use aptos_validator_interface::AptosValidatorInterface;
+use move_binary_format::{file_format::Bytecode, CompiledModule};
+use move_core_types::{ident_str, language_storage::ModuleId};
+use std::sync::Arc;
+
+/// Serves the same state at every version, and committed transactions starting at `BEGIN`.
+struct TestInterface {
+    state: FakeDataStore,
+    txns: Vec<Transaction>,
+    txn_infos: Vec<TransactionInfo>,
+}
+
+const BEGIN: Version = 100;
+
+#[async_trait::async_trait]
+impl AptosValidatorInterface for TestInterface {
+    async fn get_account_state_by_version(
+        &self,
+        _account: AccountAddress,
+        _version: Version,
+    ) -> Result<Option<AccountState>> {
+        bail!("Not supported in tests.")
+    }
+
+    async fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        _version: Version,
+    ) -> Result<Option<StateValue>> {
+        self.state.get_state_value(state_key)
+    }
+
+    async fn get_committed_transactions(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        let range = (start - BEGIN) as usize..(start - BEGIN + limit) as usize;
+        Ok((
+            self.txns[range.clone()].to_vec(),
+            self.txn_infos[range].to_vec(),
+        ))
+    }
+
+    async fn get_committed_write_sets(
+        &self,
+        _start: Version,
+        _limit: u64,
+    ) -> Result<Vec<WriteSet>> {
+        bail!("Not supported in tests.")
+    }
+
+    async fn get_latest_version(&self) -> Result<Version> {
+        Ok(BEGIN + self.txns.len() as Version - 1)
+    }
+
+    async fn get_version_by_account_sequence(
+        &self,
+        _account: AccountAddress,
+        _seq: u64,
+    ) -> Result<Option<Version>> {
+        bail!("Not supported in tests.")
+    }
+}
+
+/// Returns `0x1::aptos_account` with `transfer` replaced by a function that aborts with 42.
+fn aborting_aptos_account(state: &FakeDataStore) -> CompiledModule {
+    let module_id = ModuleId::new(CORE_CODE_ADDRESS, ident_str!("aptos_account").to_owned());
+    let bytes = state
+        .get_state_value_bytes(&StateKey::access_path(AccessPath::code_access_path(
+            module_id,
+        )))
+        .unwrap()
+        .unwrap();
+    let mut module = CompiledModule::deserialize(&bytes).unwrap();
+    let transfer_idx = module
+        .function_defs
+        .iter()
+        .position(|def| {
+            module.identifier_at(module.function_handle_at(def.function).name)
+                == ident_str!("transfer")
+        })
+        .unwrap();
+    let code_unit = module.function_defs[transfer_idx].code.as_mut().unwrap();
+    code_unit.code = vec![Bytecode::LdU64(42), Bytecode::Abort];
+    module
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_replay_with_module_override() {
+    let mut executor = FakeExecutor::from_head_genesis();
+    let sender = executor.create_raw_account_data(1_000_000_000, 0);
+    let receiver = executor.create_raw_account_data(0, 0);
+    executor.add_account_data(&sender);
+    executor.add_account_data(&receiver);
+
+    let txn = sender
+        .account()
+        .transaction()
+        .payload(aptos_stdlib::aptos_account_transfer(
+            *receiver.address(),
+            100,
+        ))
+        .sequence_number(0)
+        .sign();
+    let output = executor.execute_transaction(txn.clone());
+    assert_eq!(
+        output.status(),
+        &TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    let txn = Transaction::UserTransaction(txn);
+    let event_hashes = output
+        .events()
+        .iter()
+        .map(CryptoHash::hash)
+        .collect::<Vec<_>>();
+    let txn_info = TransactionInfo::new(
+        txn.hash(),
+        output.write_set().hash(),
+        InMemoryAccumulator::<EventAccumulatorHasher>::from_leaves(&event_hashes).root_hash(),
+        None,
+        output.gas_used(),
+        output.status().status().unwrap(),
+    );
+
+    let state = executor.data_store().clone();
+    let overrides = ReplayOverrides {
+        modules: vec![aborting_aptos_account(&state)],
+        ..Default::default()
+    };
+    let debugger = AptosDebugger::new(Arc::new(TestInterface {
+        state,
+        txns: vec![txn],
+        txn_infos: vec![txn_info],
+    }));
+
+    // The replay without the overrides warms up a VM with the original module loaded, which must
+    // not be reused for the replay with the overrides.
+    let comparisons = debugger
+        .execute_past_transactions_with_overrides(BEGIN, 1, &overrides)
+        .await
+        .unwrap();
+    assert_eq!(comparisons.len(), 1);
+    let comparison = &comparisons[0];
+    assert_eq!(comparison.committed_mismatch, None);
+    assert_eq!(
+        comparison.status_before,
+        TransactionStatus::Keep(ExecutionStatus::Success)
+    );
+    assert!(
+        matches!(
+            comparison.status_after,
+            TransactionStatus::Keep(ExecutionStatus::MoveAbort { code: 42, .. })
+        ),
+        "{:?}",
+        comparison.status_after
+    );
+    assert!(!comparison.removed_events.is_empty());
+}
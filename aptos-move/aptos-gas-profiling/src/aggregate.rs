@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
+    json::{JsonCallFrame, JsonExecutionAndIOCosts, JsonGasEvent},
     log::{ExecutionAndIOCosts, ExecutionGasEvent},
     render::{Render, TableKey},
 };
@@ -127,3 +128,49 @@ impl ExecutionAndIOCosts {
         }
     }
 }
+
+impl JsonExecutionAndIOCosts {
+    /// Counts the number of hits and aggregates the gas costs for each type of event.
+    ///
+    /// Unlike [`ExecutionAndIOCosts::aggregate_gas_events`], items are keyed by their full names,
+    /// and storage writes by their full keys.
+    pub fn aggregate_gas_events(&self) -> AggregatedExecutionGasEvents {
+        fn visit(
+            frame: &JsonCallFrame,
+            ops: &mut BTreeMap<String, (usize, InternalGas)>,
+            storage_reads: &mut BTreeMap<String, (usize, InternalGas)>,
+        ) {
+            use JsonGasEvent::*;
+
+            for event in &frame.events {
+                match event {
+                    Loc { .. } => (),
+                    Bytecode { op, cost } => insert_or_add(ops, op.clone(), (*cost).into()),
+                    Call(inner_frame) => visit(inner_frame, ops, storage_reads),
+                    CallNative { name, cost } => insert_or_add(ops, name.clone(), (*cost).into()),
+                    LoadResource { ty, cost, .. } => {
+                        insert_or_add(storage_reads, ty.clone(), (*cost).into())
+                    },
+                }
+            }
+        }
+
+        let mut ops = BTreeMap::new();
+        let mut storage_reads = BTreeMap::new();
+        let mut storage_writes = BTreeMap::new();
+
+        visit(&self.call_graph, &mut ops, &mut storage_reads);
+        for write in &self.write_set_transient {
+            insert_or_add(&mut storage_writes, write.key.clone(), write.cost.into());
+        }
+
+        AggregatedExecutionGasEvents {
+            gas_scaling_factor: self.gas_scaling_factor.into(),
+            total: self.total.into(),
+
+            ops: into_sorted_vec(ops),
+            storage_reads: into_sorted_vec(storage_reads),
+            storage_writes: into_sorted_vec(storage_writes),
+        }
+    }
+}
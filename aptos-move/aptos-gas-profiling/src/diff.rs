@@ -0,0 +1,328 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::json::{JsonCallFrame, JsonGasEvent, JsonGasLog, JsonStorageFees};
+use anyhow::bail;
+use serde::Serialize;
+use std::collections::{btree_map, BTreeMap};
+
+/// Number of hits and total cost of an item in one of the compared gas logs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct GasCount {
+    pub count: usize,
+    pub cost: u64,
+}
+
+/// The change of an item between two gas logs.
+/// `before` is None for new items and `after` is None for removed ones.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct GasDelta {
+    pub name: String,
+    pub before: Option<GasCount>,
+    pub after: Option<GasCount>,
+}
+
+impl GasDelta {
+    pub fn cost_before(&self) -> u64 {
+        self.before.map(|count| count.cost).unwrap_or(0)
+    }
+
+    pub fn cost_after(&self) -> u64 {
+        self.after.map(|count| count.cost).unwrap_or(0)
+    }
+
+    /// The difference in cost, positive if the cost increased.
+    pub fn delta(&self) -> i128 {
+        self.cost_after() as i128 - self.cost_before() as i128
+    }
+}
+
+/// The change of a total between two gas logs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub struct TotalDelta {
+    pub before: u64,
+    pub after: u64,
+}
+
+impl TotalDelta {
+    pub fn delta(&self) -> i128 {
+        self.after as i128 - self.before as i128
+    }
+}
+
+/// The difference between two transaction gas logs, e.g. of the same transaction before and
+/// after a package upgrade or a gas schedule change.
+///
+/// Only items that changed are listed, sorted by the absolute change in cost, from high to low.
+/// Execution & IO costs are in internal gas units, storage fees in Octa.
+#[derive(Clone, Debug, Serialize)]
+pub struct GasLogDiff {
+    pub gas_scaling_factor: u64,
+
+    pub execution_and_io: TotalDelta,
+    pub intrinsic: TotalDelta,
+    pub storage_fee: TotalDelta,
+
+    /// Call paths (e.g. `0x1::coin::transfer;0x1::coin::withdraw`) with their inclusive costs.
+    pub call_paths: Vec<GasDelta>,
+    /// Functions with the cost of their own instructions, native calls and resource loads,
+    /// summed over all their calls.
+    pub functions: Vec<GasDelta>,
+    /// Instructions & native calls.
+    pub ops: Vec<GasDelta>,
+    pub storage_reads: Vec<GasDelta>,
+    pub storage_writes: Vec<GasDelta>,
+    pub storage_fees: Vec<GasDelta>,
+}
+
+fn insert_or_add(map: &mut BTreeMap<String, GasCount>, key: String, cost: u64) {
+    match map.entry(key) {
+        btree_map::Entry::Occupied(entry) => {
+            let r = entry.into_mut();
+            r.count += 1;
+            r.cost += cost;
+        },
+        btree_map::Entry::Vacant(entry) => {
+            entry.insert(GasCount { count: 1, cost });
+        },
+    }
+}
+
+fn from_aggregated<N: Into<u64>>(items: Vec<(String, usize, N)>) -> BTreeMap<String, GasCount> {
+    items
+        .into_iter()
+        .map(|(name, count, cost)| {
+            (name, GasCount {
+                count,
+                cost: cost.into(),
+            })
+        })
+        .collect()
+}
+
+fn diff_items(
+    mut before: BTreeMap<String, GasCount>,
+    after: BTreeMap<String, GasCount>,
+) -> Vec<GasDelta> {
+    let mut deltas = vec![];
+    for (name, after) in after {
+        let before = before.remove(&name);
+        if before != Some(after) {
+            deltas.push(GasDelta {
+                name,
+                before,
+                after: Some(after),
+            });
+        }
+    }
+    deltas.extend(before.into_iter().map(|(name, before)| GasDelta {
+        name,
+        before: Some(before),
+        after: None,
+    }));
+
+    // Sort by the absolute change in cost in descending order, keeping the names in order
+    // for equal changes.
+    deltas.sort_by(|delta1, delta2| {
+        delta2
+            .delta()
+            .abs()
+            .cmp(&delta1.delta().abs())
+            .then_with(|| delta1.name.cmp(&delta2.name))
+    });
+    deltas
+}
+
+impl JsonCallFrame {
+    fn collect_costs(
+        &self,
+        path: &mut Vec<String>,
+        call_paths: &mut BTreeMap<String, GasCount>,
+        functions: &mut BTreeMap<String, GasCount>,
+    ) {
+        path.push(self.name.clone());
+        insert_or_add(call_paths, path.join(";"), self.inclusive_cost);
+        insert_or_add(functions, self.name.clone(), self.exclusive_cost);
+        for event in &self.events {
+            if let JsonGasEvent::Call(inner_frame) = event {
+                inner_frame.collect_costs(path, call_paths, functions);
+            }
+        }
+        path.pop();
+    }
+}
+
+impl JsonStorageFees {
+    fn items(&self) -> BTreeMap<String, GasCount> {
+        let mut items = BTreeMap::new();
+        insert_or_add(&mut items, "transaction".to_string(), self.txn_storage);
+        for write in &self.write_set_storage {
+            insert_or_add(&mut items, format!("write_set;{}", write.key), write.cost);
+        }
+        for event in &self.events {
+            insert_or_add(&mut items, format!("events;{}", event.ty), event.cost);
+        }
+        items
+    }
+}
+
+impl JsonGasLog {
+    fn call_costs(&self) -> (BTreeMap<String, GasCount>, BTreeMap<String, GasCount>) {
+        let mut call_paths = BTreeMap::new();
+        let mut functions = BTreeMap::new();
+        self.exec_io
+            .call_graph
+            .collect_costs(&mut vec![], &mut call_paths, &mut functions);
+        (call_paths, functions)
+    }
+
+    /// Compares this log against another one. Deltas are positive if `after` costs more.
+    pub fn diff(&self, after: &JsonGasLog) -> anyhow::Result<GasLogDiff> {
+        let before = self;
+        if before.exec_io.gas_scaling_factor != after.exec_io.gas_scaling_factor {
+            bail!(
+                "cannot compare gas logs with different gas scaling factors ({} and {})",
+                before.exec_io.gas_scaling_factor,
+                after.exec_io.gas_scaling_factor
+            );
+        }
+
+        let (call_paths_before, functions_before) = before.call_costs();
+        let (call_paths_after, functions_after) = after.call_costs();
+        let aggregated_before = before.exec_io.aggregate_gas_events();
+        let aggregated_after = after.exec_io.aggregate_gas_events();
+
+        Ok(GasLogDiff {
+            gas_scaling_factor: before.exec_io.gas_scaling_factor,
+
+            execution_and_io: TotalDelta {
+                before: before.exec_io.total,
+                after: after.exec_io.total,
+            },
+            intrinsic: TotalDelta {
+                before: before.exec_io.intrinsic_cost,
+                after: after.exec_io.intrinsic_cost,
+            },
+            storage_fee: TotalDelta {
+                before: before.storage.total,
+                after: after.storage.total,
+            },
+
+            call_paths: diff_items(call_paths_before, call_paths_after),
+            functions: diff_items(functions_before, functions_after),
+            ops: diff_items(
+                from_aggregated(aggregated_before.ops),
+                from_aggregated(aggregated_after.ops),
+            ),
+            storage_reads: diff_items(
+                from_aggregated(aggregated_before.storage_reads),
+                from_aggregated(aggregated_after.storage_reads),
+            ),
+            storage_writes: diff_items(
+                from_aggregated(aggregated_before.storage_writes),
+                from_aggregated(aggregated_after.storage_writes),
+            ),
+            storage_fees: diff_items(before.storage.items(), after.storage.items()),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::log::TransactionGasLog;
+
+    fn changed(name: &str, before: Option<(usize, u64)>, after: Option<(usize, u64)>) -> GasDelta {
+        let count = |(count, cost)| GasCount { count, cost };
+        GasDelta {
+            name: name.to_string(),
+            before: before.map(count),
+            after: after.map(count),
+        }
+    }
+
+    #[test]
+    fn test_diff_of_identical_logs() {
+        let log = TransactionGasLog::new_for_test(7).to_json();
+        let diff = log.diff(&log).unwrap();
+        assert_eq!(diff.execution_and_io.delta(), 0);
+        assert_eq!(diff.intrinsic.delta(), 0);
+        assert_eq!(diff.storage_fee.delta(), 0);
+        assert!(diff.call_paths.is_empty());
+        assert!(diff.functions.is_empty());
+        assert!(diff.ops.is_empty());
+        assert!(diff.storage_reads.is_empty());
+        assert!(diff.storage_writes.is_empty());
+        assert!(diff.storage_fees.is_empty());
+    }
+
+    #[test]
+    fn test_diff_of_changed_costs() {
+        let before = TransactionGasLog::new_for_test(7).to_json();
+        let after = TransactionGasLog::new_for_test(10).to_json();
+        let diff = before.diff(&after).unwrap();
+
+        assert_eq!(diff.execution_and_io.delta(), 3);
+        assert_eq!(diff.intrinsic.delta(), 0);
+        // The call path of `withdraw` is unchanged, so only its caller is listed.
+        let call_path = changed("0x1::coin::transfer", Some((1, 57)), Some((1, 60)));
+        assert_eq!(diff.call_paths, vec![call_path]);
+        let function = changed("0x1::coin::transfer", Some((1, 7)), Some((1, 10)));
+        assert_eq!(diff.functions, vec![function]);
+        let op = changed("ld_u64", Some((1, 7)), Some((1, 10)));
+        assert_eq!(diff.ops, vec![op]);
+        assert!(diff.storage_reads.is_empty());
+        assert!(diff.storage_fees.is_empty());
+
+        // Deltas are signed from the point of view of `after`.
+        let diff = after.diff(&before).unwrap();
+        assert_eq!(diff.execution_and_io.delta(), -3);
+        assert_eq!(diff.ops[0].delta(), -3);
+    }
+
+    #[test]
+    fn test_diff_of_new_and_removed_items() {
+        let before = TransactionGasLog::new_for_test(7).to_json();
+        let mut after = before.clone();
+        after
+            .exec_io
+            .call_graph
+            .events
+            .push(JsonGasEvent::CallNative {
+                name: "0x1::coin::native_deposit".to_string(),
+                cost: 5,
+            });
+        after.storage.write_set_storage.clear();
+        let diff = before.diff(&after).unwrap();
+
+        let op = changed("0x1::coin::native_deposit", None, Some((1, 5)));
+        assert_eq!(diff.ops, vec![op]);
+        let storage_fee = changed("write_set;table_item<0x1,0x010203>", Some((1, 10)), None);
+        assert_eq!(diff.storage_fees, vec![storage_fee]);
+    }
+
+    #[test]
+    fn test_diff_sorts_by_absolute_change() {
+        let before = TransactionGasLog::new_for_test(7).to_json();
+        let mut after = TransactionGasLog::new_for_test(4).to_json();
+        after
+            .exec_io
+            .call_graph
+            .events
+            .push(JsonGasEvent::CallNative {
+                name: "0x1::coin::native_deposit".to_string(),
+                cost: 2,
+            });
+        let diff = before.diff(&after).unwrap();
+        let ops: Vec<_> = diff.ops.iter().map(|delta| delta.delta()).collect();
+        assert_eq!(ops, vec![-3, 2]);
+    }
+
+    #[test]
+    fn test_diff_rejects_different_scaling_factors() {
+        let before = TransactionGasLog::new_for_test(7).to_json();
+        let mut after = before.clone();
+        after.exec_io.gas_scaling_factor += 1;
+        assert!(before.diff(&after).is_err());
+    }
+}
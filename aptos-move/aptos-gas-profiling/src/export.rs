@@ -0,0 +1,107 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::log::TransactionGasLog;
+use anyhow::bail;
+use std::{fmt, str::FromStr};
+
+/// The format a transaction gas log can be exported in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum GasProfileFormat {
+    /// SVG flamegraphs, one for execution & IO and one for the storage fees.
+    #[default]
+    Flamegraph,
+    /// The full gas log as versioned JSON. See [`crate::JsonGasLog`].
+    Json,
+    /// A speedscope file, which can be opened in https://www.speedscope.app.
+    Speedscope,
+}
+
+impl GasProfileFormat {
+    pub fn variants() -> &'static [&'static str] {
+        &["flamegraph", "json", "speedscope"]
+    }
+}
+
+impl fmt::Display for GasProfileFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Self::Flamegraph => "flamegraph",
+            Self::Json => "json",
+            Self::Speedscope => "speedscope",
+        })
+    }
+}
+
+impl FromStr for GasProfileFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "flamegraph" => Self::Flamegraph,
+            "json" => Self::Json,
+            "speedscope" => Self::Speedscope,
+            _ => bail!(
+                "unknown gas profile format {}, expected one of {:?}",
+                s,
+                Self::variants()
+            ),
+        })
+    }
+}
+
+/// A file generated from a transaction gas log.
+pub struct GasProfileFile {
+    /// Suffix to append to the file name, including the extension, e.g. `exec_io.svg`.
+    pub suffix: &'static str,
+    /// Human readable name of the content, e.g. `execution & IO flamegraph`.
+    pub name: &'static str,
+    /// Human readable description of the content, e.g. `Execution & IO Gas flamegraph`.
+    pub description: &'static str,
+    /// `None` if there was nothing to draw, in which case no file should be written.
+    pub content: Option<Vec<u8>>,
+}
+
+impl TransactionGasLog {
+    /// Exports the log in the given format. The title is used to label the generated graphs.
+    ///
+    /// Flamegraphs without any samples are returned without content.
+    pub fn export(
+        &self,
+        format: GasProfileFormat,
+        title: &str,
+    ) -> anyhow::Result<Vec<GasProfileFile>> {
+        Ok(match format {
+            GasProfileFormat::Flamegraph => vec![
+                GasProfileFile {
+                    suffix: "exec_io.svg",
+                    name: "execution & IO flamegraph",
+                    description: "Execution & IO Gas flamegraph",
+                    content: self
+                        .exec_io
+                        .to_flamegraph(format!("{} -- Execution & IO", title))?,
+                },
+                GasProfileFile {
+                    suffix: "storage.svg",
+                    name: "storage fee flamegraph",
+                    description: "Storage fee flamegraph",
+                    content: self
+                        .storage
+                        .to_flamegraph(format!("{} -- Storage Fee", title))?,
+                },
+            ],
+            GasProfileFormat::Json => vec![GasProfileFile {
+                suffix: "gas_log.json",
+                name: "gas log",
+                description: "Gas log",
+                content: Some(self.to_json().to_bytes()?),
+            }],
+            GasProfileFormat::Speedscope => vec![GasProfileFile {
+                suffix: "speedscope.json",
+                name: "speedscope gas profile",
+                description: "Speedscope gas profile",
+                content: Some(self.to_speedscope(title.to_string())?),
+            }],
+        })
+    }
+}
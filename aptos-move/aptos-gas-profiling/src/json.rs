@@ -0,0 +1,351 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::log::{
+    CallFrame, EventStorage, ExecutionAndIOCosts, ExecutionGasEvent, FrameName, StorageFees,
+    TransactionGasLog, WriteOpType, WriteStorage, WriteTransient,
+};
+use anyhow::bail;
+use aptos_types::state_store::state_key::{StateKey, StateKeyInner};
+use move_core_types::{
+    identifier::IdentStr,
+    language_storage::{ModuleId, TypeTag},
+};
+use serde::{Deserialize, Serialize};
+use std::ops::Deref;
+
+/// Version of the JSON representation of the transaction gas log.
+///
+/// This must be bumped whenever a field is removed or changes its meaning, so that tools
+/// consuming the exported logs can reject files they do not understand.
+pub const JSON_GAS_LOG_VERSION: u32 = 1;
+
+/// A stable, serializable representation of a [`TransactionGasLog`].
+///
+/// Unlike the flamegraphs, names are rendered in full (no truncated addresses) so that the
+/// logs of different runs can be compared item by item.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonGasLog {
+    pub version: u32,
+    pub entry_point: String,
+    pub exec_io: JsonExecutionAndIOCosts,
+    pub storage: JsonStorageFees,
+}
+
+/// Execution and IO costs, in internal gas units.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonExecutionAndIOCosts {
+    /// Number of internal gas units per external gas unit.
+    pub gas_scaling_factor: u64,
+    pub total: u64,
+    pub intrinsic_cost: u64,
+    pub call_graph: JsonCallFrame,
+    pub write_set_transient: Vec<JsonWriteOp>,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonCallFrame {
+    pub name: String,
+    /// Cost of the frame, including all the calls it made.
+    pub inclusive_cost: u64,
+    /// Cost of the instructions, native calls and resource loads of the frame itself.
+    pub exclusive_cost: u64,
+    pub events: Vec<JsonGasEvent>,
+}
+
+/// A gas event of a call frame. See [`ExecutionGasEvent`].
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum JsonGasEvent {
+    Loc { offset: u16 },
+    Bytecode { op: String, cost: u64 },
+    Call(JsonCallFrame),
+    CallNative { name: String, cost: u64 },
+    LoadResource { addr: String, ty: String, cost: u64 },
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonWriteOpType {
+    Creation,
+    Modification,
+    Deletion,
+}
+
+/// A write to a storage item. The unit of the cost depends on the containing struct.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonWriteOp {
+    pub key: String,
+    pub op_type: JsonWriteOpType,
+    pub cost: u64,
+}
+
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonEventStorage {
+    pub ty: String,
+    pub cost: u64,
+}
+
+/// Storage fees, in Octa.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct JsonStorageFees {
+    pub total: u64,
+    pub write_set_storage: Vec<JsonWriteOp>,
+    pub events: Vec<JsonEventStorage>,
+    pub event_discount: u64,
+    pub txn_storage: u64,
+}
+
+impl JsonGasLog {
+    /// Serializes the log as pretty-printed JSON.
+    pub fn to_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        Ok(serde_json::to_vec_pretty(self)?)
+    }
+
+    /// Parses a log previously produced by [`JsonGasLog::to_bytes`], rejecting logs of an
+    /// unsupported version.
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let value: serde_json::Value = serde_json::from_slice(bytes)?;
+        match value.get("version").and_then(|version| version.as_u64()) {
+            Some(version) if version == JSON_GAS_LOG_VERSION as u64 => (),
+            Some(version) => bail!(
+                "unsupported gas log version {}, expected {}",
+                version,
+                JSON_GAS_LOG_VERSION
+            ),
+            None => bail!("gas log is missing a version"),
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+fn render_function(module_id: &ModuleId, name: &IdentStr, ty_args: &[TypeTag]) -> String {
+    let mut rendered = format!("{}::{}", module_id.short_str_lossless(), name);
+    if !ty_args.is_empty() {
+        rendered.push_str(&format!(
+            "<{}>",
+            ty_args
+                .iter()
+                .map(|ty| ty.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    rendered
+}
+
+fn render_hex(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+    format!("0x{}", hex)
+}
+
+fn render_state_key(key: &StateKey) -> String {
+    use StateKeyInner::*;
+
+    match key.deref() {
+        AccessPath(ap) => format!("{}::{}", ap.address.to_hex_literal(), ap.get_path()),
+        TableItem { handle, key } => format!(
+            "table_item<{},{}>",
+            handle.0.to_hex_literal(),
+            render_hex(key)
+        ),
+        Raw(bytes) => format!("raw<{}>", render_hex(bytes)),
+    }
+}
+
+impl FrameName {
+    /// Renders the name of the frame in full, e.g. `0x1::coin::transfer<0x1::aptos_coin::AptosCoin>`.
+    pub fn to_full_string(&self) -> String {
+        match self {
+            Self::Script => "<script>".to_string(),
+            Self::Function {
+                module_id,
+                name,
+                ty_args,
+            } => render_function(module_id, name.as_ident_str(), ty_args),
+        }
+    }
+}
+
+impl From<&WriteOpType> for JsonWriteOpType {
+    fn from(op_type: &WriteOpType) -> Self {
+        match op_type {
+            WriteOpType::Creation => Self::Creation,
+            WriteOpType::Modification => Self::Modification,
+            WriteOpType::Deletion => Self::Deletion,
+        }
+    }
+}
+
+impl CallFrame {
+    fn to_json(&self) -> JsonCallFrame {
+        use ExecutionGasEvent::*;
+
+        let mut inclusive_cost = 0;
+        let mut exclusive_cost = 0;
+
+        let events = self
+            .events
+            .iter()
+            .map(|event| match event {
+                Loc(offset) => JsonGasEvent::Loc { offset: *offset },
+                Bytecode { op, cost } => {
+                    exclusive_cost += u64::from(*cost);
+                    JsonGasEvent::Bytecode {
+                        op: format!("{:?}", op).to_ascii_lowercase(),
+                        cost: (*cost).into(),
+                    }
+                },
+                Call(frame) => {
+                    let frame = frame.to_json();
+                    inclusive_cost += frame.inclusive_cost;
+                    JsonGasEvent::Call(frame)
+                },
+                CallNative {
+                    module_id,
+                    fn_name,
+                    ty_args,
+                    cost,
+                } => {
+                    exclusive_cost += u64::from(*cost);
+                    JsonGasEvent::CallNative {
+                        name: render_function(module_id, fn_name.as_ident_str(), ty_args),
+                        cost: (*cost).into(),
+                    }
+                },
+                LoadResource { addr, ty, cost } => {
+                    exclusive_cost += u64::from(*cost);
+                    JsonGasEvent::LoadResource {
+                        addr: addr.to_hex_literal(),
+                        ty: ty.to_string(),
+                        cost: (*cost).into(),
+                    }
+                },
+            })
+            .collect();
+
+        JsonCallFrame {
+            name: self.name.to_full_string(),
+            inclusive_cost: inclusive_cost + exclusive_cost,
+            exclusive_cost,
+            events,
+        }
+    }
+}
+
+impl WriteTransient {
+    fn to_json(&self) -> JsonWriteOp {
+        JsonWriteOp {
+            key: render_state_key(&self.key),
+            op_type: (&self.op_type).into(),
+            cost: self.cost.into(),
+        }
+    }
+}
+
+impl WriteStorage {
+    fn to_json(&self) -> JsonWriteOp {
+        JsonWriteOp {
+            key: render_state_key(&self.key),
+            op_type: (&self.op_type).into(),
+            cost: self.cost.into(),
+        }
+    }
+}
+
+impl EventStorage {
+    fn to_json(&self) -> JsonEventStorage {
+        JsonEventStorage {
+            ty: self.ty.to_string(),
+            cost: self.cost.into(),
+        }
+    }
+}
+
+impl ExecutionAndIOCosts {
+    fn to_json(&self) -> JsonExecutionAndIOCosts {
+        JsonExecutionAndIOCosts {
+            gas_scaling_factor: self.gas_scaling_factor.into(),
+            total: self.total.into(),
+            intrinsic_cost: self.intrinsic_cost.into(),
+            call_graph: self.call_graph.to_json(),
+            write_set_transient: self
+                .write_set_transient
+                .iter()
+                .map(WriteTransient::to_json)
+                .collect(),
+        }
+    }
+}
+
+impl StorageFees {
+    fn to_json(&self) -> JsonStorageFees {
+        JsonStorageFees {
+            total: self.total.into(),
+            write_set_storage: self
+                .write_set_storage
+                .iter()
+                .map(WriteStorage::to_json)
+                .collect(),
+            events: self.events.iter().map(EventStorage::to_json).collect(),
+            event_discount: self.event_discount.into(),
+            txn_storage: self.txn_storage.into(),
+        }
+    }
+}
+
+impl TransactionGasLog {
+    /// Converts the log into its stable JSON representation.
+    pub fn to_json(&self) -> JsonGasLog {
+        JsonGasLog {
+            version: JSON_GAS_LOG_VERSION,
+            entry_point: self.entry_point().to_full_string(),
+            exec_io: self.exec_io.to_json(),
+            storage: self.storage.to_json(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_round_trip() {
+        let log = TransactionGasLog::new_for_test(7).to_json();
+        assert_eq!(log.version, JSON_GAS_LOG_VERSION);
+        assert_eq!(log.entry_point, "0x1::coin::transfer");
+
+        let transfer = &log.exec_io.call_graph;
+        assert_eq!(transfer.exclusive_cost, 7);
+        assert_eq!(transfer.inclusive_cost, 7 + 30 + 20);
+        let withdraw = match &transfer.events[2] {
+            JsonGasEvent::Call(frame) => frame,
+            event => panic!("expected a call, got {:?}", event),
+        };
+        assert_eq!(withdraw.name, "0x1::coin::withdraw");
+        assert_eq!(withdraw.exclusive_cost, 30 + 20);
+        assert_eq!(withdraw.inclusive_cost, 30 + 20);
+
+        let parsed = JsonGasLog::from_bytes(&log.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed, log);
+    }
+
+    #[test]
+    fn test_json_rejects_other_versions() {
+        let log = TransactionGasLog::new_for_test(7).to_json();
+
+        let mut value = serde_json::to_value(&log).unwrap();
+        value["version"] = (JSON_GAS_LOG_VERSION + 1).into();
+        let err = JsonGasLog::from_bytes(&serde_json::to_vec(&value).unwrap()).unwrap_err();
+        assert!(
+            err.to_string().contains("unsupported gas log version"),
+            "{}",
+            err
+        );
+
+        value.as_object_mut().unwrap().remove("version");
+        let err = JsonGasLog::from_bytes(&serde_json::to_vec(&value).unwrap()).unwrap_err();
+        assert!(err.to_string().contains("missing a version"), "{}", err);
+    }
+}
@@ -2,13 +2,24 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod aggregate;
+mod diff;
 mod erased;
+mod export;
 mod flamegraph;
+mod json;
 mod log;
 mod misc;
 mod profiler;
 mod render;
+mod speedscope;
 mod textualize;
 
+pub use aggregate::AggregatedExecutionGasEvents;
+pub use diff::{GasCount, GasDelta, GasLogDiff, TotalDelta};
+pub use export::{GasProfileFile, GasProfileFormat};
+pub use json::{
+    JsonCallFrame, JsonEventStorage, JsonExecutionAndIOCosts, JsonGasEvent, JsonGasLog,
+    JsonStorageFees, JsonWriteOp, JsonWriteOpType, JSON_GAS_LOG_VERSION,
+};
 pub use log::{FrameName, TransactionGasLog};
 pub use profiler::GasProfiler;
@@ -182,3 +182,66 @@ impl TransactionGasLog {
         &self.exec_io.call_graph.name
     }
 }
+
+#[cfg(test)]
+impl TransactionGasLog {
+    /// Builds the log of a call to `0x1::coin::transfer`, which executes an instruction costing
+    /// `bytecode_cost` and calls `0x1::coin::withdraw`, which loads a resource and calls a
+    /// native. The transaction writes one item and emits one event.
+    pub(crate) fn new_for_test(bytecode_cost: u64) -> Self {
+        use aptos_types::state_store::table::TableHandle;
+
+        let coin = ModuleId::new(AccountAddress::ONE, Identifier::new("coin").unwrap());
+
+        let mut withdraw =
+            CallFrame::new_function(coin.clone(), Identifier::new("withdraw").unwrap(), vec![]);
+        withdraw.events.push(ExecutionGasEvent::LoadResource {
+            addr: AccountAddress::ONE,
+            ty: TypeTag::U64,
+            cost: InternalGas::new(30),
+        });
+        withdraw.events.push(ExecutionGasEvent::CallNative {
+            module_id: coin.clone(),
+            fn_name: Identifier::new("native_withdraw").unwrap(),
+            ty_args: vec![],
+            cost: InternalGas::new(20),
+        });
+
+        let mut transfer =
+            CallFrame::new_function(coin, Identifier::new("transfer").unwrap(), vec![]);
+        transfer.events.push(ExecutionGasEvent::Bytecode {
+            op: Opcodes::LD_U64,
+            cost: InternalGas::new(bytecode_cost),
+        });
+        transfer.events.push(ExecutionGasEvent::Loc(3));
+        transfer.events.push(ExecutionGasEvent::Call(withdraw));
+
+        Self {
+            exec_io: ExecutionAndIOCosts {
+                gas_scaling_factor: GasScalingFactor::new(1_000_000),
+                total: InternalGas::new(100 + bytecode_cost + 30 + 20 + 40),
+                intrinsic_cost: InternalGas::new(100),
+                call_graph: transfer,
+                write_set_transient: vec![WriteTransient {
+                    key: StateKey::table_item(TableHandle(AccountAddress::ONE), vec![1, 2, 3]),
+                    op_type: WriteOpType::Modification,
+                    cost: InternalGas::new(40),
+                }],
+            },
+            storage: StorageFees {
+                total: Fee::new(5 + 10 + 2),
+                write_set_storage: vec![WriteStorage {
+                    key: StateKey::table_item(TableHandle(AccountAddress::ONE), vec![1, 2, 3]),
+                    op_type: WriteOpType::Modification,
+                    cost: Fee::new(10),
+                }],
+                events: vec![EventStorage {
+                    ty: TypeTag::U64,
+                    cost: Fee::new(2),
+                }],
+                event_discount: Fee::new(0),
+                txn_storage: Fee::new(5),
+            },
+        }
+    }
+}
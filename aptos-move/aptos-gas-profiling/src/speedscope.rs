@@ -0,0 +1,318 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    log::{CallFrame, ExecutionAndIOCosts, ExecutionGasEvent, StorageFees, TransactionGasLog},
+    render::Render,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+
+const SPEEDSCOPE_SCHEMA: &str = "https://www.speedscope.app/file-format-schema.json";
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct File {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    name: String,
+    exporter: &'static str,
+    active_profile_index: usize,
+    shared: Shared,
+    profiles: Vec<Profile>,
+}
+
+#[derive(Serialize)]
+struct Shared {
+    frames: Vec<Frame>,
+}
+
+#[derive(Serialize)]
+struct Frame {
+    name: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Profile {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    name: String,
+    unit: &'static str,
+    start_value: u64,
+    end_value: u64,
+    events: Vec<Event>,
+}
+
+#[derive(Serialize)]
+struct Event {
+    #[serde(rename = "type")]
+    ty: &'static str,
+    frame: usize,
+    at: u64,
+}
+
+/// Builds the evented profiles of a speedscope file. Gas costs are laid out one after another,
+/// so the position of an event on the timeline is the amount of gas spent before it.
+struct Builder {
+    frames: Vec<Frame>,
+    frame_indices: HashMap<String, usize>,
+}
+
+struct ProfileBuilder<'a> {
+    builder: &'a mut Builder,
+    events: Vec<Event>,
+    stack: Vec<usize>,
+    at: u64,
+}
+
+impl Builder {
+    fn new() -> Self {
+        Self {
+            frames: vec![],
+            frame_indices: HashMap::new(),
+        }
+    }
+
+    fn frame(&mut self, name: String) -> usize {
+        let frames = &mut self.frames;
+        *self.frame_indices.entry(name).or_insert_with_key(|name| {
+            frames.push(Frame { name: name.clone() });
+            frames.len() - 1
+        })
+    }
+
+    fn profile(&mut self) -> ProfileBuilder<'_> {
+        ProfileBuilder {
+            builder: self,
+            events: vec![],
+            stack: vec![],
+            at: 0,
+        }
+    }
+}
+
+impl<'a> ProfileBuilder<'a> {
+    fn open(&mut self, name: String) {
+        let frame = self.builder.frame(name);
+        self.events.push(Event {
+            ty: "O",
+            frame,
+            at: self.at,
+        });
+        self.stack.push(frame);
+    }
+
+    fn close(&mut self) {
+        let frame = self.stack.pop().expect("should have an open frame");
+        self.events.push(Event {
+            ty: "C",
+            frame,
+            at: self.at,
+        });
+    }
+
+    fn advance(&mut self, cost: impl Into<u64>) {
+        self.at += cost.into();
+    }
+
+    /// Adds a frame without children. Zero-cost leaves are skipped, like in the flamegraphs.
+    fn leaf(&mut self, name: String, cost: impl Into<u64>) {
+        let cost = cost.into();
+        if cost > 0 {
+            self.open(name);
+            self.advance(cost);
+            self.close();
+        }
+    }
+
+    fn finish(self, name: String, unit: &'static str) -> Profile {
+        assert!(self.stack.is_empty(), "all frames should be closed");
+        Profile {
+            ty: "evented",
+            name,
+            unit,
+            start_value: 0,
+            end_value: self.at,
+            events: self.events,
+        }
+    }
+}
+
+impl CallFrame {
+    fn to_speedscope_events(&self, profile: &mut ProfileBuilder) {
+        use ExecutionGasEvent::*;
+
+        profile.open(format!("{}", self.name));
+        for event in &self.events {
+            match event {
+                Loc(_) => (),
+                Bytecode { cost, .. } => profile.advance(*cost),
+                Call(inner_frame) => inner_frame.to_speedscope_events(profile),
+                CallNative {
+                    module_id,
+                    fn_name,
+                    ty_args,
+                    cost,
+                } => profile.leaf(
+                    format!(
+                        "{}",
+                        Render(&(module_id, fn_name.as_ident_str(), ty_args.as_slice()))
+                    ),
+                    *cost,
+                ),
+                LoadResource { addr, ty, cost } => {
+                    profile.leaf(format!("load<{}::{}>", Render(addr), ty), *cost)
+                },
+            }
+        }
+        profile.close();
+    }
+}
+
+impl ExecutionAndIOCosts {
+    fn to_speedscope_profile(&self, builder: &mut Builder) -> Profile {
+        let mut profile = builder.profile();
+
+        profile.leaf("intrinsic".to_string(), self.intrinsic_cost);
+        self.call_graph.to_speedscope_events(&mut profile);
+        if !self.write_set_transient.is_empty() {
+            profile.open("write_set".to_string());
+            for item in &self.write_set_transient {
+                profile.leaf(
+                    format!("{}<{}>", Render(&item.op_type), Render(&item.key)),
+                    item.cost,
+                );
+            }
+            profile.close();
+        }
+
+        profile.finish("Execution & IO (internal gas units)".to_string(), "none")
+    }
+}
+
+impl StorageFees {
+    fn to_speedscope_profile(&self, builder: &mut Builder) -> Profile {
+        let mut profile = builder.profile();
+
+        profile.leaf("transaction".to_string(), self.txn_storage);
+        if !self.write_set_storage.is_empty() {
+            profile.open("write_set".to_string());
+            for item in &self.write_set_storage {
+                profile.leaf(
+                    format!("{}<{}>", Render(&item.op_type), Render(&item.key)),
+                    item.cost,
+                );
+            }
+            profile.close();
+        }
+        if !self.events.is_empty() {
+            // TODO: Handle discounts.
+            profile.open("events".to_string());
+            for event in &self.events {
+                profile.leaf(format!("{}", event.ty), event.cost);
+            }
+            profile.close();
+        }
+
+        profile.finish("Storage Fee (Octa)".to_string(), "none")
+    }
+}
+
+impl TransactionGasLog {
+    /// Generates a speedscope file (https://www.speedscope.app) containing one evented profile
+    /// for the execution & IO costs and one for the storage fees.
+    pub fn to_speedscope(&self, title: String) -> anyhow::Result<Vec<u8>> {
+        let mut builder = Builder::new();
+        let profiles = vec![
+            self.exec_io.to_speedscope_profile(&mut builder),
+            self.storage.to_speedscope_profile(&mut builder),
+        ];
+
+        let file = File {
+            schema: SPEEDSCOPE_SCHEMA,
+            name: title,
+            exporter: "aptos-gas-profiling",
+            active_profile_index: 0,
+            shared: Shared {
+                frames: builder.frames,
+            },
+            profiles,
+        };
+        Ok(serde_json::to_vec(&file)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::log::TransactionGasLog;
+    use serde_json::Value;
+
+    /// Returns the names of the frames opened by the events of a profile, checking that every
+    /// frame is closed in order and that time never goes backwards.
+    fn opened_frames(file: &Value, profile: &Value) -> Vec<String> {
+        let frames = file["shared"]["frames"].as_array().unwrap();
+        let mut stack = vec![];
+        let mut opened = vec![];
+        let mut at = 0;
+        for event in profile["events"].as_array().unwrap() {
+            let frame = event["frame"].as_u64().unwrap();
+            assert!(event["at"].as_u64().unwrap() >= at);
+            at = event["at"].as_u64().unwrap();
+            match event["type"].as_str().unwrap() {
+                "O" => {
+                    stack.push(frame);
+                    opened.push(frames[frame as usize]["name"].as_str().unwrap().to_string());
+                },
+                "C" => assert_eq!(stack.pop(), Some(frame)),
+                ty => panic!("unexpected event type {}", ty),
+            }
+        }
+        assert!(stack.is_empty());
+        assert_eq!(profile["endValue"].as_u64().unwrap(), at);
+        opened
+    }
+
+    #[test]
+    fn test_speedscope_output() {
+        let log = TransactionGasLog::new_for_test(7);
+        let bytes = log.to_speedscope("test".to_string()).unwrap();
+        let file: Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(
+            file["$schema"],
+            "https://www.speedscope.app/file-format-schema.json"
+        );
+        assert_eq!(file["name"], "test");
+        let profiles = file["profiles"].as_array().unwrap();
+        assert_eq!(profiles.len(), 2);
+
+        let exec_io = &profiles[0];
+        assert_eq!(exec_io["type"], "evented");
+        assert_eq!(
+            exec_io["endValue"].as_u64().unwrap(),
+            u64::from(log.exec_io.total)
+        );
+        assert_eq!(opened_frames(&file, exec_io), vec![
+            "intrinsic",
+            "0x1::coin::transfer",
+            "0x1::coin::withdraw",
+            "load<0x1::u64>",
+            "0x1::coin::native_withdraw",
+            "write_set",
+            "modify<table_item<0x1,0x0102..>>",
+        ]);
+
+        let storage = &profiles[1];
+        assert_eq!(
+            storage["endValue"].as_u64().unwrap(),
+            u64::from(log.storage.total)
+        );
+        assert_eq!(opened_frames(&file, storage), vec![
+            "transaction",
+            "write_set",
+            "modify<table_item<0x1,0x0102..>>",
+            "events",
+            "u64",
+        ]);
+    }
+}
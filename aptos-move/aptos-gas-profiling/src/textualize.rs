@@ -3,6 +3,7 @@
 
 use crate::{
     aggregate::AggregatedExecutionGasEvents,
+    diff::{GasDelta, GasLogDiff, TotalDelta},
     erased::{Node, TypeErasedExecutionAndIoCosts, TypeErasedGasLog, TypeErasedStorageFees},
 };
 use aptos_gas_algebra::{GasQuantity, InternalGas};
@@ -194,3 +195,110 @@ impl AggregatedExecutionGasEvents {
         render_table(output, &table, 4)
     }
 }
+
+impl GasLogDiff {
+    pub fn textualize(&self, output: &mut impl Write) -> fmt::Result {
+        let scaling_factor = self.gas_scaling_factor as f64;
+
+        let fmt_amount = |amount: f64| {
+            let amount = format!("{:.8}", amount);
+            crate::misc::strip_trailing_zeros_and_decimal_point(&amount).to_string()
+        };
+        let fmt_gas = |cost: u64| fmt_amount(cost as f64 / scaling_factor);
+        let fmt_apt = |cost: u64| fmt_amount(cost as f64 / 1_0000_0000f64);
+        let fmt_delta = |delta: i128, fmt_cost: &dyn Fn(u64) -> String| {
+            let sign = if delta > 0 {
+                "+"
+            } else if delta < 0 {
+                "-"
+            } else {
+                ""
+            };
+            format!("{}{}", sign, fmt_cost(delta.unsigned_abs() as u64))
+        };
+        let fmt_percentage = |before: u64, delta: i128| {
+            if before == 0 {
+                "".to_string()
+            } else {
+                format!("{:+.2}%", delta as f64 / before as f64 * 100.0)
+            }
+        };
+
+        let fmt_total = |name: &str, total: &TotalDelta, fmt_cost: &dyn Fn(u64) -> String| {
+            [
+                name.to_string(),
+                "".to_string(),
+                fmt_cost(total.before),
+                fmt_cost(total.after),
+                fmt_delta(total.delta(), fmt_cost),
+                fmt_percentage(total.before, total.delta()),
+            ]
+        };
+        let fmt_item = |item: &GasDelta, fmt_cost: &dyn Fn(u64) -> String| {
+            let (status, before, after) = match (&item.before, &item.after) {
+                (None, _) => ("new", "".to_string(), fmt_cost(item.cost_after())),
+                (_, None) => ("removed", fmt_cost(item.cost_before()), "".to_string()),
+                (Some(_), Some(_)) => (
+                    "",
+                    fmt_cost(item.cost_before()),
+                    fmt_cost(item.cost_after()),
+                ),
+            };
+            [
+                format!("        {}", item.name),
+                status.to_string(),
+                before,
+                after,
+                fmt_delta(item.delta(), fmt_cost),
+                fmt_percentage(item.cost_before(), item.delta()),
+            ]
+        };
+        let header = |name: &str| {
+            [
+                name.to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+                "".to_string(),
+            ]
+        };
+
+        let mut table = vec![];
+
+        table.push([
+            "".to_string(),
+            "".to_string(),
+            "before".to_string(),
+            "after".to_string(),
+            "delta".to_string(),
+            "".to_string(),
+        ]);
+
+        table.push(fmt_total(
+            "execution & IO (gas unit)",
+            &self.execution_and_io,
+            &fmt_gas,
+        ));
+        table.push(fmt_total("    intrinsic", &self.intrinsic, &fmt_gas));
+        for (name, items) in [
+            ("    call paths", &self.call_paths),
+            ("    functions", &self.functions),
+            ("    instructions & native calls", &self.ops),
+            ("    storage reads", &self.storage_reads),
+            ("    storage writes", &self.storage_writes),
+        ] {
+            table.push(header(name));
+            for item in items {
+                table.push(fmt_item(item, &fmt_gas));
+            }
+        }
+
+        table.push(fmt_total("storage fee (APT)", &self.storage_fee, &fmt_apt));
+        for item in &self.storage_fees {
+            table.push(fmt_item(item, &fmt_apt));
+        }
+
+        render_table(output, &table, 4)
+    }
+}
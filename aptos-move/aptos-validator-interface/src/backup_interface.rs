@@ -0,0 +1,541 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::AptosValidatorInterface;
+use anyhow::{anyhow, bail, ensure, Result};
+use aptos_backup_cli::{
+    backup_types::{
+        epoch_ending::restore::{EpochHistory, EpochHistoryRestoreController},
+        state_snapshot::{manifest::StateSnapshotBackup, restore::StateSnapshotRestoreController},
+        transaction::{
+            manifest::{TransactionBackup, TransactionChunk},
+            restore::LoadedChunk,
+        },
+    },
+    metadata::{
+        cache::{sync_and_load, MetadataCacheOpt},
+        view::MetadataView,
+        StateSnapshotBackupMeta,
+    },
+    storage::{local_fs::LocalFs, BackupStorage, FileHandle},
+    utils::{storage_ext::BackupStorageExt, GlobalRestoreOptions, RestoreRunMode},
+};
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        state_value::StateValue,
+    },
+    transaction::{Transaction, TransactionInfo, Version},
+    waypoint::Waypoint,
+    write_set::{TransactionWrite, WriteSet},
+};
+use lru::LruCache;
+use std::{
+    collections::{BTreeSet, HashMap},
+    ops::Range,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+const NUM_CACHED_TRANSACTION_CHUNKS: usize = 64;
+const NUM_CACHED_STATE_SNAPSHOT_CHUNKS: usize = 64;
+const CONCURRENT_METADATA_DOWNLOADS: usize = 8;
+
+/// A transaction chunk of the backup, with its writes indexed by state key.
+struct TransactionChunkData {
+    first_version: Version,
+    txns: Vec<Transaction>,
+    txn_infos: Vec<TransactionInfo>,
+    write_sets: Vec<WriteSet>,
+    /// The values written to each key, in increasing version order. `None` marks deletions.
+    writes: HashMap<StateKey, Vec<(Version, Option<StateValue>)>>,
+}
+
+/// The versions at which each state key was written, over a contiguous range of versions.
+#[derive(Default)]
+struct WriteIndex {
+    versions: Range<Version>,
+    writes: HashMap<StateKey, BTreeSet<Version>>,
+}
+
+/// An interface backed by the artifacts of a backup on the local file system, i.e. epoch ending
+/// and transaction backups and state snapshots as written by `aptos-db-tool backup`.
+///
+/// The state at a version is the nearest state snapshot at or before it, plus the writes of the
+/// transactions after the snapshot. Nothing is restored up front: chunks of the snapshot and the
+/// transaction backups are loaded on first use and cached. The versions at which keys are written
+/// are indexed once, so that a lookup loads at most one transaction chunk.
+///
+/// Since state snapshots are ordered by key hash, the first account state read from a snapshot
+/// scans all of it to index the keys by account. Looking up transactions by account sequence
+/// number is not supported.
+pub struct BackupDebuggerInterface {
+    storage: Arc<dyn BackupStorage>,
+    metadata_view: MetadataView,
+    epoch_history: Arc<EpochHistory>,
+    write_index: tokio::sync::Mutex<WriteIndex>,
+    account_keys: tokio::sync::Mutex<HashMap<Version, Arc<HashMap<AccountAddress, Vec<StateKey>>>>>,
+    transaction_manifests: Mutex<HashMap<FileHandle, Arc<TransactionBackup>>>,
+    transaction_chunks: Mutex<LruCache<Version, Arc<TransactionChunkData>>>,
+    state_snapshot_manifests: Mutex<HashMap<FileHandle, Arc<StateSnapshotBackup>>>,
+    state_snapshot_chunks: Mutex<LruCache<FileHandle, Arc<HashMap<StateKey, StateValue>>>>,
+}
+
+impl BackupDebuggerInterface {
+    /// Opens the backup in `backup_dir`. Metadata is cached in `metadata_cache_dir`, or in a
+    /// temporary directory if not given.
+    ///
+    /// The epoch ending backups are verified up front, and the ledger infos of the transaction
+    /// chunks are checked against them as the chunks are loaded.
+    pub async fn open(
+        backup_dir: PathBuf,
+        metadata_cache_dir: Option<PathBuf>,
+        trusted_waypoints: HashMap<Version, Waypoint>,
+    ) -> Result<Self> {
+        let storage: Arc<dyn BackupStorage> = Arc::new(LocalFs::new(backup_dir));
+        let metadata_view = sync_and_load(
+            &MetadataCacheOpt::new(metadata_cache_dir),
+            storage.clone(),
+            CONCURRENT_METADATA_DOWNLOADS,
+        )
+        .await?;
+
+        let latest_version = metadata_view
+            .get_storage_state()?
+            .latest_transaction_version
+            .unwrap_or(0);
+        let epoch_handles = metadata_view
+            .select_epoch_ending_backups(latest_version)?
+            .into_iter()
+            .map(|backup| backup.manifest)
+            .collect();
+        let epoch_history = Arc::new(
+            EpochHistoryRestoreController::new(
+                epoch_handles,
+                GlobalRestoreOptions {
+                    target_version: latest_version,
+                    trusted_waypoints: Arc::new(trusted_waypoints),
+                    run_mode: Arc::new(RestoreRunMode::Verify),
+                    concurrent_downloads: CONCURRENT_METADATA_DOWNLOADS,
+                    replay_concurrency_level: 0, // won't replay, doesn't matter
+                },
+                storage.clone(),
+            )
+            .run()
+            .await?,
+        );
+
+        Ok(Self {
+            storage,
+            metadata_view,
+            epoch_history,
+            write_index: tokio::sync::Mutex::new(WriteIndex::default()),
+            account_keys: tokio::sync::Mutex::new(HashMap::new()),
+            transaction_manifests: Mutex::new(HashMap::new()),
+            transaction_chunks: Mutex::new(LruCache::new(NUM_CACHED_TRANSACTION_CHUNKS)),
+            state_snapshot_manifests: Mutex::new(HashMap::new()),
+            state_snapshot_chunks: Mutex::new(LruCache::new(NUM_CACHED_STATE_SNAPSHOT_CHUNKS)),
+        })
+    }
+
+    /// Returns the chunks of the transaction backups overlapping with [`first_version`,
+    /// `last_version`], in increasing version order.
+    async fn transaction_chunks(
+        &self,
+        first_version: Version,
+        last_version: Version,
+    ) -> Result<Vec<TransactionChunk>> {
+        let mut chunks = vec![];
+        for backup in self
+            .metadata_view
+            .select_transaction_backups(first_version, last_version)?
+        {
+            let cached = self
+                .transaction_manifests
+                .lock()
+                .unwrap()
+                .get(&backup.manifest)
+                .cloned();
+            let manifest = match cached {
+                Some(manifest) => manifest,
+                None => {
+                    let manifest: TransactionBackup =
+                        self.storage.load_json_file(&backup.manifest).await?;
+                    manifest.verify()?;
+                    let manifest = Arc::new(manifest);
+                    self.transaction_manifests
+                        .lock()
+                        .unwrap()
+                        .insert(backup.manifest, manifest.clone());
+                    manifest
+                },
+            };
+            chunks.extend(
+                manifest
+                    .chunks
+                    .iter()
+                    .filter(|chunk| {
+                        chunk.last_version >= first_version && chunk.first_version <= last_version
+                    })
+                    .cloned(),
+            );
+        }
+        Ok(chunks)
+    }
+
+    async fn load_transaction_chunk(
+        &self,
+        chunk: TransactionChunk,
+    ) -> Result<Arc<TransactionChunkData>> {
+        if let Some(data) = self
+            .transaction_chunks
+            .lock()
+            .unwrap()
+            .get(&chunk.first_version)
+        {
+            return Ok(data.clone());
+        }
+
+        let first_version = chunk.first_version;
+        let (txns, txn_infos, _events, write_sets) =
+            LoadedChunk::load(chunk, &self.storage, Some(&self.epoch_history))
+                .await?
+                .unpack();
+        let mut writes: HashMap<_, Vec<_>> = HashMap::new();
+        for (version, write_set) in (first_version..).zip(write_sets.iter()) {
+            for (state_key, write_op) in write_set.iter() {
+                writes
+                    .entry(state_key.clone())
+                    .or_default()
+                    .push((version, write_op.as_state_value()));
+            }
+        }
+
+        let data = Arc::new(TransactionChunkData {
+            first_version,
+            txns,
+            txn_infos,
+            write_sets,
+            writes,
+        });
+        self.transaction_chunks
+            .lock()
+            .unwrap()
+            .put(first_version, data.clone());
+        Ok(data)
+    }
+
+    /// Returns the write index, extended to cover [`first_version`, `last_version`] if needed.
+    async fn write_index(
+        &self,
+        first_version: Version,
+        last_version: Version,
+    ) -> Result<tokio::sync::MutexGuard<'_, WriteIndex>> {
+        let mut index = self.write_index.lock().await;
+        // Keep the indexed versions contiguous, filling any gap to the requested range.
+        let (start, end) = if index.versions.is_empty() {
+            (first_version, last_version + 1)
+        } else {
+            (
+                first_version.min(index.versions.start),
+                (last_version + 1).max(index.versions.end),
+            )
+        };
+        let missing = if index.versions.is_empty() {
+            vec![start..end]
+        } else {
+            vec![start..index.versions.start, index.versions.end..end]
+        };
+
+        for range in missing.into_iter().filter(|range| !range.is_empty()) {
+            for chunk in self.transaction_chunks(range.start, range.end - 1).await? {
+                let data = self.load_transaction_chunk(chunk).await?;
+                for (state_key, writes) in &data.writes {
+                    let versions = writes
+                        .iter()
+                        .map(|(version, _)| *version)
+                        .filter(|version| range.contains(version));
+                    index
+                        .writes
+                        .entry(state_key.clone())
+                        .or_default()
+                        .extend(versions);
+                }
+            }
+        }
+        index.versions = start..end;
+        Ok(index)
+    }
+
+    /// Returns the value written to `state_key` at `version`, which must be a version it was
+    /// written at.
+    async fn get_written_state_value(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        let chunk = self
+            .transaction_chunks(version, version)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow!("No transaction backup at version {}", version))?;
+        let data = self.load_transaction_chunk(chunk).await?;
+        data.writes
+            .get(state_key)
+            .and_then(|writes| writes.iter().find(|(v, _)| *v == version))
+            .map(|(_, value)| value.clone())
+            .ok_or_else(|| anyhow!("{:?} is not written at version {}", state_key, version))
+    }
+
+    async fn state_snapshot_manifest(
+        &self,
+        snapshot: &StateSnapshotBackupMeta,
+    ) -> Result<Arc<StateSnapshotBackup>> {
+        let cached = self
+            .state_snapshot_manifests
+            .lock()
+            .unwrap()
+            .get(&snapshot.manifest)
+            .cloned();
+        let manifest = match cached {
+            Some(manifest) => manifest,
+            None => {
+                let manifest: StateSnapshotBackup =
+                    self.storage.load_json_file(&snapshot.manifest).await?;
+                let manifest = Arc::new(manifest);
+                self.state_snapshot_manifests
+                    .lock()
+                    .unwrap()
+                    .insert(snapshot.manifest.clone(), manifest.clone());
+                manifest
+            },
+        };
+        Ok(manifest)
+    }
+
+    async fn get_state_value_from_snapshot(
+        &self,
+        snapshot: &StateSnapshotBackupMeta,
+        state_key: &StateKey,
+    ) -> Result<Option<StateValue>> {
+        let manifest = self.state_snapshot_manifest(snapshot).await?;
+
+        // Chunks are sorted by key hash, so at most one of them can contain the key.
+        let key_hash = state_key.hash();
+        let chunk = match manifest
+            .chunks
+            .iter()
+            .find(|chunk| chunk.first_key <= key_hash && key_hash <= chunk.last_key)
+        {
+            Some(chunk) => chunk,
+            None => return Ok(None),
+        };
+
+        let cached = self
+            .state_snapshot_chunks
+            .lock()
+            .unwrap()
+            .get(&chunk.blobs)
+            .cloned();
+        let values = match cached {
+            Some(values) => values,
+            None => {
+                let values = Arc::new(
+                    StateSnapshotRestoreController::read_state_value(
+                        &self.storage,
+                        chunk.blobs.clone(),
+                    )
+                    .await?
+                    .into_iter()
+                    .collect::<HashMap<_, _>>(),
+                );
+                self.state_snapshot_chunks
+                    .lock()
+                    .unwrap()
+                    .put(chunk.blobs.clone(), values.clone());
+                values
+            },
+        };
+        Ok(values.get(state_key).cloned())
+    }
+
+    /// Returns the keys in the snapshot of the resources and modules of each account, scanning
+    /// the whole snapshot the first time.
+    async fn account_keys_in_snapshot(
+        &self,
+        snapshot: &StateSnapshotBackupMeta,
+    ) -> Result<Arc<HashMap<AccountAddress, Vec<StateKey>>>> {
+        let mut account_keys = self.account_keys.lock().await;
+        if let Some(keys) = account_keys.get(&snapshot.version) {
+            return Ok(keys.clone());
+        }
+
+        let manifest = self.state_snapshot_manifest(snapshot).await?;
+        let mut keys: HashMap<_, Vec<_>> = HashMap::new();
+        for chunk in &manifest.chunks {
+            let values = StateSnapshotRestoreController::read_state_value(
+                &self.storage,
+                chunk.blobs.clone(),
+            )
+            .await?;
+            for (state_key, _) in values {
+                if let StateKeyInner::AccessPath(access_path) = state_key.inner() {
+                    keys.entry(access_path.address).or_default().push(state_key);
+                }
+            }
+        }
+        let keys = Arc::new(keys);
+        account_keys.insert(snapshot.version, keys.clone());
+        Ok(keys)
+    }
+}
+
+#[async_trait::async_trait]
+impl AptosValidatorInterface for BackupDebuggerInterface {
+    async fn get_account_state_by_version(
+        &self,
+        account: AccountAddress,
+        version: Version,
+    ) -> Result<Option<AccountState>> {
+        let snapshot = self
+            .metadata_view
+            .select_state_snapshot(version)?
+            .ok_or_else(|| anyhow!("No state snapshot at or before version {}", version))?;
+
+        let mut state_keys = self
+            .account_keys_in_snapshot(&snapshot)
+            .await?
+            .get(&account)
+            .cloned()
+            .unwrap_or_default();
+        if snapshot.version < version {
+            let index = self.write_index(snapshot.version + 1, version).await?;
+            state_keys.extend(
+                index
+                    .writes
+                    .keys()
+                    .filter(|state_key| match state_key.inner() {
+                        StateKeyInner::AccessPath(access_path) => access_path.address == account,
+                        _ => false,
+                    })
+                    .cloned(),
+            );
+        }
+
+        let mut kvs = HashMap::new();
+        for state_key in state_keys {
+            if kvs.contains_key(&state_key) {
+                continue;
+            }
+            if let Some(value) = self.get_state_value_by_version(&state_key, version).await? {
+                kvs.insert(state_key, value);
+            }
+        }
+        AccountState::from_access_paths_and_values(account, &kvs)
+    }
+
+    async fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        let snapshot = self
+            .metadata_view
+            .select_state_snapshot(version)?
+            .ok_or_else(|| anyhow!("No state snapshot at or before version {}", version))?;
+
+        // Look for the latest write after the snapshot.
+        if snapshot.version < version {
+            let latest_write = self
+                .write_index(snapshot.version + 1, version)
+                .await?
+                .writes
+                .get(state_key)
+                .and_then(|versions| versions.range(snapshot.version + 1..=version).next_back())
+                .copied();
+            if let Some(write_version) = latest_write {
+                return self.get_written_state_value(state_key, write_version).await;
+            }
+        }
+
+        self.get_state_value_from_snapshot(&snapshot, state_key)
+            .await
+    }
+
+    async fn get_committed_transactions(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        let mut txns = vec![];
+        let mut txn_infos = vec![];
+        if limit == 0 {
+            return Ok((txns, txn_infos));
+        }
+
+        let end = start + limit - 1;
+        for chunk in self.transaction_chunks(start, end).await? {
+            let data = self.load_transaction_chunk(chunk).await?;
+            for (version, (txn, txn_info)) in
+                (data.first_version..).zip(data.txns.iter().zip(data.txn_infos.iter()))
+            {
+                if version >= start && version <= end {
+                    txns.push(txn.clone());
+                    txn_infos.push(txn_info.clone());
+                }
+            }
+        }
+        ensure!(
+            txns.len() as u64 == limit,
+            "Backup has {} of the {} transactions starting at version {}.",
+            txns.len(),
+            limit,
+            start
+        );
+        Ok((txns, txn_infos))
+    }
+
+    async fn get_committed_write_sets(&self, start: Version, limit: u64) -> Result<Vec<WriteSet>> {
+        let mut write_sets = vec![];
+        if limit == 0 {
+            return Ok(write_sets);
+        }
+
+        let end = start + limit - 1;
+        for chunk in self.transaction_chunks(start, end).await? {
+            let data = self.load_transaction_chunk(chunk).await?;
+            for (version, write_set) in (data.first_version..).zip(data.write_sets.iter()) {
+                if version >= start && version <= end {
+                    write_sets.push(write_set.clone());
+                }
+            }
+        }
+        ensure!(
+            write_sets.len() as u64 == limit,
+            "Backup has {} of the {} write sets starting at version {}.",
+            write_sets.len(),
+            limit,
+            start
+        );
+        Ok(write_sets)
+    }
+
+    async fn get_latest_version(&self) -> Result<Version> {
+        self.metadata_view
+            .get_storage_state()?
+            .latest_transaction_version
+            .ok_or_else(|| anyhow!("No transaction backup found."))
+    }
+
+    async fn get_version_by_account_sequence(
+        &self,
+        _account: AccountAddress,
+        _seq: u64,
+    ) -> Result<Option<Version>> {
+        bail!("Looking up transactions by account sequence is not supported when replaying from a backup.")
+    }
+}
@@ -0,0 +1,410 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod schema;
+
+use crate::{
+    cache_interface::schema::{
+        column_families, AccountSequenceSchema, AccountStateSchema, StateValueSchema,
+        TransactionSchema,
+    },
+    AptosValidatorInterface,
+};
+use anyhow::{anyhow, ensure, Result};
+use aptos_config::config::RocksdbConfig;
+use aptos_rocksdb_options::gen_rocksdb_options;
+use aptos_schemadb::{SchemaBatch, DB};
+use aptos_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
+};
+use std::{
+    fmt,
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
+
+pub const DEBUGGER_CACHE_DB_NAME: &str = "debugger_cache_db";
+
+/// Hits and misses of a [`CachedDebuggerInterface`] since it was opened.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct CacheStats {
+    pub state_value_hits: u64,
+    pub state_value_misses: u64,
+    pub transaction_hits: u64,
+    pub transaction_misses: u64,
+}
+
+impl fmt::Display for CacheStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hit_rate = |hits: u64, misses: u64| {
+            if hits + misses == 0 {
+                0.0
+            } else {
+                hits as f64 / (hits + misses) as f64 * 100.0
+            }
+        };
+        writeln!(
+            f,
+            "State values: {} hits, {} misses ({:.2}% hit rate)",
+            self.state_value_hits,
+            self.state_value_misses,
+            hit_rate(self.state_value_hits, self.state_value_misses),
+        )?;
+        write!(
+            f,
+            "Transactions: {} hits, {} misses ({:.2}% hit rate)",
+            self.transaction_hits,
+            self.transaction_misses,
+            hit_rate(self.transaction_hits, self.transaction_misses),
+        )
+    }
+}
+
+#[derive(Default)]
+struct Counters {
+    state_value_hits: AtomicU64,
+    state_value_misses: AtomicU64,
+    transaction_hits: AtomicU64,
+    transaction_misses: AtomicU64,
+}
+
+/// An interface that persists the state values and committed transactions fetched through another
+/// interface, usually a [`crate::RestDebuggerInterface`], in a local RocksDB. Repeated replays of
+/// the same versions are then served from disk.
+///
+/// Entries are keyed by `(StateKey, Version)`, by transaction version and by account, so they never
+/// become stale. Without an inner interface, the cache is offline and fails on any miss.
+pub struct CachedDebuggerInterface {
+    db: DB,
+    inner: Option<Arc<dyn AptosValidatorInterface + Send>>,
+    counters: Counters,
+}
+
+impl CachedDebuggerInterface {
+    /// Opens (or creates) the cache in `cache_dir`. Misses are fetched through `inner`, or fail
+    /// if it is None.
+    pub fn open(
+        cache_dir: impl AsRef<Path>,
+        inner: Option<Arc<dyn AptosValidatorInterface + Send>>,
+    ) -> Result<Self> {
+        let db = DB::open(
+            cache_dir.as_ref().join(DEBUGGER_CACHE_DB_NAME),
+            "debugger_cache_db",
+            column_families(),
+            &gen_rocksdb_options(&RocksdbConfig::default(), false),
+        )?;
+
+        Ok(Self {
+            db,
+            inner,
+            counters: Counters::default(),
+        })
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            state_value_hits: self.counters.state_value_hits.load(Ordering::Relaxed),
+            state_value_misses: self.counters.state_value_misses.load(Ordering::Relaxed),
+            transaction_hits: self.counters.transaction_hits.load(Ordering::Relaxed),
+            transaction_misses: self.counters.transaction_misses.load(Ordering::Relaxed),
+        }
+    }
+
+    fn inner(&self) -> Result<&(dyn AptosValidatorInterface + Send)> {
+        self.inner
+            .as_deref()
+            .ok_or_else(|| anyhow!("Cache miss in offline mode."))
+    }
+
+    fn put_transactions(
+        &self,
+        begin: Version,
+        txns: Vec<Transaction>,
+        txn_infos: Vec<TransactionInfo>,
+    ) -> Result<()> {
+        ensure!(txns.len() == txn_infos.len());
+        let batch = SchemaBatch::new();
+        for (version, value) in (begin..).zip(txns.into_iter().zip(txn_infos)) {
+            batch.put::<TransactionSchema>(&version, &value)?;
+        }
+        self.db.write_schemas(batch)
+    }
+}
+
+#[async_trait::async_trait]
+impl AptosValidatorInterface for CachedDebuggerInterface {
+    async fn get_account_state_by_version(
+        &self,
+        account: AccountAddress,
+        version: Version,
+    ) -> Result<Option<AccountState>> {
+        let key = (account, version);
+        if let Some(account_state) = self.db.get::<AccountStateSchema>(&key)? {
+            return Ok(account_state);
+        }
+
+        let account_state = self
+            .inner()?
+            .get_account_state_by_version(account, version)
+            .await?;
+        self.db.put::<AccountStateSchema>(&key, &account_state)?;
+        Ok(account_state)
+    }
+
+    async fn get_state_value_by_version(
+        &self,
+        state_key: &StateKey,
+        version: Version,
+    ) -> Result<Option<StateValue>> {
+        let key = (state_key.clone(), version);
+        if let Some(value) = self.db.get::<StateValueSchema>(&key)? {
+            self.counters
+                .state_value_hits
+                .fetch_add(1, Ordering::Relaxed);
+            return Ok(value);
+        }
+
+        self.counters
+            .state_value_misses
+            .fetch_add(1, Ordering::Relaxed);
+        let value = self
+            .inner()?
+            .get_state_value_by_version(state_key, version)
+            .await?;
+        self.db.put::<StateValueSchema>(&key, &value)?;
+        Ok(value)
+    }
+
+    async fn get_committed_transactions(
+        &self,
+        start: Version,
+        limit: u64,
+    ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+        let mut txns = Vec::with_capacity(limit as usize);
+        let mut txn_infos = Vec::with_capacity(limit as usize);
+        for version in start..start + limit {
+            match self.db.get::<TransactionSchema>(&version)? {
+                Some((txn, txn_info)) => {
+                    txns.push(txn);
+                    txn_infos.push(txn_info);
+                },
+                None => break,
+            }
+        }
+
+        let num_cached = txns.len() as u64;
+        self.counters
+            .transaction_hits
+            .fetch_add(num_cached, Ordering::Relaxed);
+        if num_cached < limit {
+            self.counters
+                .transaction_misses
+                .fetch_add(limit - num_cached, Ordering::Relaxed);
+            let (mut new_txns, mut new_txn_infos) = self
+                .inner()?
+                .get_committed_transactions(start + num_cached, limit - num_cached)
+                .await?;
+            self.put_transactions(start + num_cached, new_txns.clone(), new_txn_infos.clone())?;
+            txns.append(&mut new_txns);
+            txn_infos.append(&mut new_txn_infos);
+        }
+
+        Ok((txns, txn_infos))
+    }
+
+    async fn get_committed_write_sets(&self, start: Version, limit: u64) -> Result<Vec<WriteSet>> {
+        self.inner()?.get_committed_write_sets(start, limit).await
+    }
+
+    async fn get_latest_version(&self) -> Result<Version> {
+        self.inner()?.get_latest_version().await
+    }
+
+    async fn get_version_by_account_sequence(
+        &self,
+        account: AccountAddress,
+        seq: u64,
+    ) -> Result<Option<Version>> {
+        let key = (account, seq);
+        if let Some(version) = self.db.get::<AccountSequenceSchema>(&key)? {
+            return Ok(Some(version));
+        }
+
+        // A sequence number that is not committed yet may be committed later, so only hits are
+        // cached.
+        let version = self
+            .inner()?
+            .get_version_by_account_sequence(account, seq)
+            .await?;
+        if let Some(version) = version {
+            self.db.put::<AccountSequenceSchema>(&key, &version)?;
+        }
+        Ok(version)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aptos_crypto::HashValue;
+    use aptos_temppath::TempPath;
+    use aptos_types::{account_config::CORE_CODE_ADDRESS, transaction::ExecutionStatus};
+    use std::collections::BTreeMap;
+
+    /// Serves made up data derived from the requested versions.
+    struct MockInterface;
+
+    #[async_trait::async_trait]
+    impl AptosValidatorInterface for MockInterface {
+        async fn get_account_state_by_version(
+            &self,
+            account: AccountAddress,
+            version: Version,
+        ) -> Result<Option<AccountState>> {
+            let data = BTreeMap::from([(b"version".to_vec(), version.to_be_bytes().to_vec())]);
+            Ok(Some(AccountState::new(account, data)))
+        }
+
+        async fn get_state_value_by_version(
+            &self,
+            _state_key: &StateKey,
+            version: Version,
+        ) -> Result<Option<StateValue>> {
+            Ok(Some(StateValue::new_legacy(
+                version.to_be_bytes().to_vec().into(),
+            )))
+        }
+
+        async fn get_committed_transactions(
+            &self,
+            start: Version,
+            limit: u64,
+        ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)> {
+            Ok((start..start + limit)
+                .map(|version| {
+                    let txn = Transaction::StateCheckpoint(HashValue::sha3_256_of(
+                        &version.to_be_bytes(),
+                    ));
+                    let txn_info =
+                        TransactionInfo::new_placeholder(version, None, ExecutionStatus::Success);
+                    (txn, txn_info)
+                })
+                .unzip())
+        }
+
+        async fn get_committed_write_sets(
+            &self,
+            start: Version,
+            limit: u64,
+        ) -> Result<Vec<WriteSet>> {
+            Ok((start..start + limit)
+                .map(|_| WriteSet::default())
+                .collect())
+        }
+
+        async fn get_latest_version(&self) -> Result<Version> {
+            Ok(100)
+        }
+
+        async fn get_version_by_account_sequence(
+            &self,
+            _account: AccountAddress,
+            seq: u64,
+        ) -> Result<Option<Version>> {
+            Ok(Some(seq * 10))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_offline_hits_and_misses() {
+        let cache_dir = TempPath::new();
+        cache_dir.create_as_dir().unwrap();
+        let state_key = StateKey::raw(b"key".to_vec());
+        let (txns, txn_infos, state_value, account_state, version) = {
+            let cache =
+                CachedDebuggerInterface::open(&cache_dir, Some(Arc::new(MockInterface))).unwrap();
+            let (txns, txn_infos) = cache.get_committed_transactions(10, 5).await.unwrap();
+            let state_value = cache
+                .get_state_value_by_version(&state_key, 12)
+                .await
+                .unwrap();
+            let account_state = cache
+                .get_account_state_by_version(CORE_CODE_ADDRESS, 12)
+                .await
+                .unwrap();
+            let version = cache
+                .get_version_by_account_sequence(CORE_CODE_ADDRESS, 3)
+                .await
+                .unwrap();
+            assert_eq!(cache.stats(), CacheStats {
+                state_value_hits: 0,
+                state_value_misses: 1,
+                transaction_hits: 0,
+                transaction_misses: 5,
+            });
+            (txns, txn_infos, state_value, account_state, version)
+        };
+
+        // Everything fetched above is served from disk without an inner interface.
+        let cache = CachedDebuggerInterface::open(&cache_dir, None).unwrap();
+        assert_eq!(
+            cache.get_committed_transactions(10, 5).await.unwrap(),
+            (txns, txn_infos)
+        );
+        assert_eq!(
+            cache
+                .get_state_value_by_version(&state_key, 12)
+                .await
+                .unwrap(),
+            state_value
+        );
+        assert_eq!(
+            cache
+                .get_account_state_by_version(CORE_CODE_ADDRESS, 12)
+                .await
+                .unwrap(),
+            account_state
+        );
+        assert_eq!(
+            cache
+                .get_version_by_account_sequence(CORE_CODE_ADDRESS, 3)
+                .await
+                .unwrap(),
+            version
+        );
+        assert_eq!(cache.stats(), CacheStats {
+            state_value_hits: 1,
+            state_value_misses: 0,
+            transaction_hits: 5,
+            transaction_misses: 0,
+        });
+
+        // Anything else is a miss, which fails.
+        assert!(cache.get_committed_transactions(10, 6).await.is_err());
+        assert!(cache
+            .get_state_value_by_version(&state_key, 13)
+            .await
+            .is_err());
+        assert!(cache
+            .get_account_state_by_version(CORE_CODE_ADDRESS, 13)
+            .await
+            .is_err());
+        assert!(cache
+            .get_version_by_account_sequence(CORE_CODE_ADDRESS, 4)
+            .await
+            .is_err());
+        assert_eq!(cache.stats(), CacheStats {
+            state_value_hits: 1,
+            state_value_misses: 1,
+            transaction_hits: 10,
+            transaction_misses: 1,
+        });
+    }
+}
@@ -0,0 +1,199 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Physical schemas of the debugger cache.
+//!
+//! ```text
+//! state_value:  |<------ key ------>|<------ value ------>|
+//!               | state key | version | Option<StateValue> |
+//!
+//! transaction:  |<- key ->|<------------- value ------------->|
+//!               | version | (Transaction, TransactionInfo)    |
+//!
+//! account_state:    |<------- key ------->|<------ value ------>|
+//!                   | address | version   | Option<AccountState> |
+//!
+//! account_sequence: |<------- key ------->|<- value ->|
+//!                   | address | seq_num   |  version  |
+//! ```
+
+use anyhow::{ensure, Result};
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+    ColumnFamilyName,
+};
+use aptos_types::{
+    account_address::AccountAddress,
+    account_state::AccountState,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{Transaction, TransactionInfo, Version},
+};
+use std::{convert::TryInto, mem::size_of};
+
+pub const DEFAULT_COLUMN_FAMILY_NAME: ColumnFamilyName = "default";
+pub const STATE_VALUE_CF_NAME: ColumnFamilyName = "state_value";
+pub const TRANSACTION_CF_NAME: ColumnFamilyName = "transaction";
+pub const ACCOUNT_STATE_CF_NAME: ColumnFamilyName = "account_state";
+pub const ACCOUNT_SEQUENCE_CF_NAME: ColumnFamilyName = "account_sequence";
+
+pub fn column_families() -> Vec<ColumnFamilyName> {
+    vec![
+        /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
+        STATE_VALUE_CF_NAME,
+        TRANSACTION_CF_NAME,
+        ACCOUNT_STATE_CF_NAME,
+        ACCOUNT_SEQUENCE_CF_NAME,
+    ]
+}
+
+type StateValueKey = (StateKey, Version);
+
+define_schema!(
+    StateValueSchema,
+    StateValueKey,
+    Option<StateValue>,
+    STATE_VALUE_CF_NAME
+);
+
+impl KeyCodec<StateValueSchema> for StateValueKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let mut encoded = self.0.encode()?;
+        encoded.extend_from_slice(&self.1.to_be_bytes());
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const VERSION_SIZE: usize = size_of::<Version>();
+
+        ensure!(
+            data.len() > VERSION_SIZE,
+            "Unexpected state value key length {}",
+            data.len()
+        );
+        let state_key_len = data.len() - VERSION_SIZE;
+        let state_key = StateKey::decode(&data[..state_key_len])?;
+        let version = Version::from_be_bytes(data[state_key_len..].try_into()?);
+        Ok((state_key, version))
+    }
+}
+
+impl ValueCodec<StateValueSchema> for Option<StateValue> {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+type TransactionValue = (Transaction, TransactionInfo);
+
+define_schema!(
+    TransactionSchema,
+    Version,
+    TransactionValue,
+    TRANSACTION_CF_NAME
+);
+
+impl KeyCodec<TransactionSchema> for Version {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        Ok(Version::from_be_bytes(data.try_into()?))
+    }
+}
+
+impl ValueCodec<TransactionSchema> for TransactionValue {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+/// Encodes an account address followed by a big endian number, i.e. a version or a sequence number.
+fn encode_account_key(address: &AccountAddress, number: u64) -> Vec<u8> {
+    let mut encoded = address.to_vec();
+    encoded.extend_from_slice(&number.to_be_bytes());
+    encoded
+}
+
+fn decode_account_key(data: &[u8]) -> Result<(AccountAddress, u64)> {
+    const ADDRESS_SIZE: usize = AccountAddress::LENGTH;
+
+    ensure!(
+        data.len() == ADDRESS_SIZE + size_of::<u64>(),
+        "Unexpected account key length {}",
+        data.len()
+    );
+    let address = AccountAddress::try_from(&data[..ADDRESS_SIZE])?;
+    let number = u64::from_be_bytes(data[ADDRESS_SIZE..].try_into()?);
+    Ok((address, number))
+}
+
+type AccountStateKey = (AccountAddress, Version);
+
+define_schema!(
+    AccountStateSchema,
+    AccountStateKey,
+    Option<AccountState>,
+    ACCOUNT_STATE_CF_NAME
+);
+
+impl KeyCodec<AccountStateSchema> for AccountStateKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(encode_account_key(&self.0, self.1))
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        decode_account_key(data)
+    }
+}
+
+impl ValueCodec<AccountStateSchema> for Option<AccountState> {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        bcs::to_bytes(self).map_err(Into::into)
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        bcs::from_bytes(data).map_err(Into::into)
+    }
+}
+
+type AccountSequenceKey = (AccountAddress, u64);
+
+define_schema!(
+    AccountSequenceSchema,
+    AccountSequenceKey,
+    Version,
+    ACCOUNT_SEQUENCE_CF_NAME
+);
+
+impl KeyCodec<AccountSequenceSchema> for AccountSequenceKey {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        Ok(encode_account_key(&self.0, self.1))
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        decode_account_key(data)
+    }
+}
+
+impl ValueCodec<AccountSequenceSchema> for Version {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(self.to_be_bytes().to_vec())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        Ok(Version::from_be_bytes(data.try_into()?))
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,86 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod state_value {
+    use super::super::*;
+    use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_encode_decode(
+            state_key in any::<StateKey>(),
+            version in any::<Version>(),
+            v in any::<Option<StateValue>>(),
+        ) {
+            assert_encode_decode::<StateValueSchema>(&(state_key, version), &v);
+        }
+    }
+
+    test_no_panic_decoding!(StateValueSchema);
+}
+
+mod transaction {
+    use super::super::*;
+    use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_encode_decode(
+            version in any::<Version>(),
+            txn in any::<Transaction>(),
+            txn_info in any::<TransactionInfo>(),
+        ) {
+            assert_encode_decode::<TransactionSchema>(&version, &(txn, txn_info));
+        }
+    }
+
+    test_no_panic_decoding!(TransactionSchema);
+}
+
+mod account_state {
+    use super::super::*;
+    use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+    use proptest::{collection::btree_map, option, prelude::*};
+
+    fn arb_account_state() -> impl Strategy<Value = AccountState> {
+        (
+            any::<AccountAddress>(),
+            btree_map(any::<Vec<u8>>(), any::<Vec<u8>>(), 0..10),
+        )
+            .prop_map(|(address, data)| AccountState::new(address, data))
+    }
+
+    proptest! {
+        #[test]
+        fn test_encode_decode(
+            address in any::<AccountAddress>(),
+            version in any::<Version>(),
+            account_state in option::of(arb_account_state()),
+        ) {
+            assert_encode_decode::<AccountStateSchema>(&(address, version), &account_state);
+        }
+    }
+
+    test_no_panic_decoding!(AccountStateSchema);
+}
+
+mod account_sequence {
+    use super::super::*;
+    use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn test_encode_decode(
+            address in any::<AccountAddress>(),
+            seq_num in any::<u64>(),
+            version in any::<Version>(),
+        ) {
+            assert_encode_decode::<AccountSequenceSchema>(&(address, seq_num), &version);
+        }
+    }
+
+    test_no_panic_decoding!(AccountSequenceSchema);
+}
@@ -2,10 +2,17 @@
 // Parts of the project are originally copyright © Meta Platforms, Inc.
 // SPDX-License-Identifier: Apache-2.0
 
+mod backup_interface;
+mod cache_interface;
 mod rest_interface;
 mod storage_interface;
 
-pub use crate::{rest_interface::RestDebuggerInterface, storage_interface::DBDebuggerInterface};
+pub use crate::{
+    backup_interface::BackupDebuggerInterface,
+    cache_interface::{CacheStats, CachedDebuggerInterface},
+    rest_interface::RestDebuggerInterface,
+    storage_interface::DBDebuggerInterface,
+};
 use anyhow::{anyhow, Result};
 use aptos_state_view::TStateView;
 use aptos_types::{
@@ -18,6 +25,7 @@ use aptos_types::{
         state_key::StateKey, state_storage_usage::StateStorageUsage, state_value::StateValue,
     },
     transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
 };
 use lru::LruCache;
 use move_binary_format::file_format::CompiledModule;
@@ -46,6 +54,8 @@ pub trait AptosValidatorInterface: Sync {
         limit: u64,
     ) -> Result<(Vec<Transaction>, Vec<TransactionInfo>)>;
 
+    async fn get_committed_write_sets(&self, start: Version, limit: u64) -> Result<Vec<WriteSet>>;
+
     async fn get_latest_version(&self) -> Result<Version>;
 
     async fn get_version_by_account_sequence(
@@ -13,6 +13,7 @@ use aptos_types::{
     account_state::AccountState,
     state_store::{state_key::StateKey, state_value::StateValue},
     transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
 };
 use std::collections::BTreeMap;
 
@@ -92,6 +93,24 @@ impl AptosValidatorInterface for RestDebuggerInterface {
         Ok((txns, txn_infos))
     }
 
+    async fn get_committed_write_sets(&self, start: Version, limit: u64) -> Result<Vec<WriteSet>> {
+        let mut write_sets = Vec::with_capacity(limit as usize);
+
+        while write_sets.len() < limit as usize {
+            self.0
+                .get_transactions_bcs(
+                    Some(start + write_sets.len() as u64),
+                    Some(limit as u16 - write_sets.len() as u16),
+                )
+                .await?
+                .into_inner()
+                .into_iter()
+                .for_each(|txn| write_sets.push(txn.changes));
+        }
+
+        Ok(write_sets)
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         Ok(self.0.get_ledger_information().await?.into_inner().version)
     }
@@ -15,6 +15,7 @@ use aptos_types::{
     account_state::AccountState,
     state_store::{state_key::StateKey, state_key_prefix::StateKeyPrefix, state_value::StateValue},
     transaction::{Transaction, TransactionInfo, Version},
+    write_set::WriteSet,
 };
 use std::{path::Path, sync::Arc};
 
@@ -79,6 +80,12 @@ impl AptosValidatorInterface for DBDebuggerInterface {
         Ok((txns, txn_infos))
     }
 
+    async fn get_committed_write_sets(&self, start: Version, limit: u64) -> Result<Vec<WriteSet>> {
+        self.0
+            .get_write_set_iterator(start, start + limit)?
+            .collect::<Result<Vec<_>>>()
+    }
+
     async fn get_latest_version(&self) -> Result<Version> {
         self.0.get_latest_version()
     }
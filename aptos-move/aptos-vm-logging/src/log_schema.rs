@@ -53,7 +53,7 @@ impl AdapterLogSchema {
                 base_version: Some(base_version),
                 txn_idx,
             },
-            StateViewId::Miscellaneous => Self {
+            StateViewId::Miscellaneous | StateViewId::CodeOverrides { .. } => Self {
                 name: LogEntry::Miscellaneous,
                 block_id: None,
                 first_version: None,
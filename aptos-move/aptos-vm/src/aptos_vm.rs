@@ -60,6 +60,7 @@ use move_binary_format::{
 };
 use move_core_types::{
     account_address::AccountAddress,
+    call_trace::{CallTraces, InternalCallTrace},
     ident_str,
     identifier::Identifier,
     language_storage::{ModuleId, TypeTag},
@@ -80,7 +81,6 @@ use std::{
         Arc,
     },
 };
-use move_core_types::call_trace::CallTraces;
 
 static EXECUTION_CONCURRENCY_LEVEL: OnceCell<usize> = OnceCell::new();
 static NUM_EXECUTION_SHARD: OnceCell<usize> = OnceCell::new();
@@ -107,6 +107,12 @@ pub fn allow_module_bundle_for_test() {
 
 pub struct AptosVM(pub(crate) AptosVMImpl);
 
+/// The output of a view function, and the gas it used.
+pub struct ViewFunctionOutput {
+    pub values: Result<Vec<Vec<u8>>>,
+    pub gas_used: u64,
+}
+
 struct AptosSimulationVM(AptosVM);
 
 macro_rules! unwrap_or_discard {
@@ -398,6 +404,7 @@ impl AptosVM {
         gas_meter: &mut impl AptosGasMeter,
         senders: Vec<AccountAddress>,
         script_fn: &EntryFunction,
+        call_traces: Option<&mut CallTraces>,
     ) -> Result<SerializedReturnValues, VMStatus> {
         let function = session.load_function(
             script_fn.module(),
@@ -415,13 +422,23 @@ impl AptosVM {
             &function,
             struct_constructors,
         )?;
-        Ok(session.execute_entry_function(
-            script_fn.module(),
-            script_fn.function(),
-            script_fn.ty_args().to_vec(),
-            args,
-            gas_meter,
-        )?)
+        Ok(match call_traces {
+            Some(call_traces) => session.execute_entry_function_with_call_trace(
+                script_fn.module(),
+                script_fn.function(),
+                script_fn.ty_args().to_vec(),
+                args,
+                gas_meter,
+                call_traces,
+            )?,
+            None => session.execute_entry_function(
+                script_fn.module(),
+                script_fn.function(),
+                script_fn.ty_args().to_vec(),
+                args,
+                gas_meter,
+            )?,
+        })
     }
 
     fn execute_script_or_entry_function(
@@ -434,6 +451,7 @@ impl AptosVM {
         log_context: &AdapterLogSchema,
         new_published_modules_loaded: &mut bool,
         change_set_configs: &ChangeSetConfigs,
+        mut call_traces: Option<&mut CallTraces>,
     ) -> Result<(VMStatus, VMOutput), VMStatus> {
         fail_point!("move_adapter::execute_script_or_entry_function", |_| {
             Err(VMStatus::Error {
@@ -467,12 +485,21 @@ impl AptosVM {
                                 .get_features()
                                 .is_enabled(FeatureFlag::STRUCT_CONSTRUCTORS),
                         )?;
-                    session.execute_script(
-                        script.code(),
-                        script.ty_args().to_vec(),
-                        args,
-                        gas_meter,
-                    )?;
+                    match call_traces.as_deref_mut() {
+                        Some(call_traces) => session.execute_script_with_call_trace(
+                            script.code(),
+                            script.ty_args().to_vec(),
+                            args,
+                            gas_meter,
+                            call_traces,
+                        )?,
+                        None => session.execute_script(
+                            script.code(),
+                            script.ty_args().to_vec(),
+                            args,
+                            gas_meter,
+                        )?,
+                    };
                 },
                 TransactionPayload::EntryFunction(script_fn) => {
                     self.validate_and_execute_entry_function(
@@ -480,6 +507,7 @@ impl AptosVM {
                         gas_meter,
                         txn_data.senders(),
                         script_fn,
+                        call_traces.as_deref_mut(),
                     )?;
                 },
 
@@ -494,6 +522,7 @@ impl AptosVM {
                 &mut session,
                 gas_meter,
                 new_published_modules_loaded,
+                call_traces,
             )?;
 
             let respawned_session = self.charge_change_set_and_respawn_session(
@@ -559,6 +588,7 @@ impl AptosVM {
         log_context: &AdapterLogSchema,
         new_published_modules_loaded: &mut bool,
         change_set_configs: &ChangeSetConfigs,
+        call_traces: Option<&mut CallTraces>,
     ) -> Result<(VMStatus, VMOutput), VMStatus> {
         fail_point!("move_adapter::execute_multisig_transaction", |_| {
             Err(VMStatus::error(
@@ -630,6 +660,7 @@ impl AptosVM {
                     txn_payload.multisig_address,
                     &entry_function,
                     new_published_modules_loaded,
+                    call_traces,
                 ),
         };
 
@@ -685,6 +716,7 @@ impl AptosVM {
         multisig_address: AccountAddress,
         payload: &EntryFunction,
         new_published_modules_loaded: &mut bool,
+        mut call_traces: Option<&mut CallTraces>,
     ) -> Result<(), VMStatus> {
         // If txn args are not valid, we'd still consider the transaction as executed but
         // failed. This is primarily because it's unrecoverable at this point.
@@ -693,11 +725,17 @@ impl AptosVM {
             gas_meter,
             vec![multisig_address],
             payload,
+            call_traces.as_deref_mut(),
         )?;
 
         // Resolve any pending module publishes in case the multisig transaction is deploying
         // modules.
-        self.resolve_pending_code_publish(session, gas_meter, new_published_modules_loaded)?;
+        self.resolve_pending_code_publish(
+            session,
+            gas_meter,
+            new_published_modules_loaded,
+            call_traces,
+        )?;
         Ok(())
     }
 
@@ -801,6 +839,7 @@ impl AptosVM {
         exists: BTreeSet<ModuleId>,
         senders: &[AccountAddress],
         new_published_modules_loaded: &mut bool,
+        mut call_traces: Option<&mut CallTraces>,
     ) -> VMResult<()> {
         let init_func_name = ident_str!("init_module");
         for module in modules {
@@ -821,13 +860,24 @@ impl AptosVM {
                         .iter()
                         .map(|s| MoveValue::Signer(*s).simple_serialize().unwrap())
                         .collect();
-                    session.execute_function_bypass_visibility(
-                        &module.self_id(),
-                        init_func_name,
-                        vec![],
-                        args,
-                        gas_meter,
-                    )?;
+                    match call_traces.as_deref_mut() {
+                        Some(call_traces) => session
+                            .execute_function_bypass_visibility_with_call_trace(
+                                &module.self_id(),
+                                init_func_name,
+                                vec![],
+                                args,
+                                gas_meter,
+                                call_traces,
+                            )?,
+                        None => session.execute_function_bypass_visibility(
+                            &module.self_id(),
+                            init_func_name,
+                            vec![],
+                            args,
+                            gas_meter,
+                        )?,
+                    };
                 } else {
                     return Err(PartialVMError::new(StatusCode::CONSTRAINT_NOT_SATISFIED)
                         .finish(Location::Undefined));
@@ -876,6 +926,7 @@ impl AptosVM {
         log_context: &AdapterLogSchema,
         new_published_modules_loaded: &mut bool,
         change_set_configs: &ChangeSetConfigs,
+        mut call_traces: Option<&mut CallTraces>,
     ) -> Result<(VMStatus, VMOutput), VMStatus> {
         if MODULE_BUNDLE_DISALLOWED.load(Ordering::Relaxed) {
             return Err(VMStatus::error(StatusCode::FEATURE_UNDER_GATING, None));
@@ -889,7 +940,77 @@ impl AptosVM {
 
         gas_meter.charge_intrinsic_gas_for_transaction(txn_data.transaction_size())?;
 
-        Self::verify_module_bundle(&mut session, modules)?;
+        // Publishing and the initializers of a bundle are traced as a frame standing for the
+        // bundle itself, charged the gas of both, with the initializer calls as its sub traces.
+        let balance_before_publish = gas_meter.balance_internal();
+        let bundle_frame_depth = match call_traces.as_deref_mut() {
+            Some(call_traces) => {
+                call_traces
+                    .push(InternalCallTrace {
+                        module_id: txn_data.sender().to_string(),
+                        func_name: "module_bundle".to_string(),
+                        ..Default::default()
+                    })
+                    .map_err(|_| {
+                        PartialVMError::new(StatusCode::CALL_STACK_OVERFLOW)
+                            .finish(Location::Undefined)
+                    })?;
+                call_traces.len()
+            },
+            None => 0,
+        };
+
+        let publish_result = self.publish_module_bundle_and_initialize(
+            &mut session,
+            gas_meter,
+            txn_data,
+            modules,
+            new_published_modules_loaded,
+            call_traces.as_deref_mut(),
+        );
+
+        if let Some(call_traces) = call_traces {
+            // Frames left open by a failing initializer are folded into the bundle frame
+            while call_traces.len() > bundle_frame_depth {
+                let top_call = call_traces.pop().unwrap();
+                call_traces.push_call_trace(top_call);
+            }
+            let gas_used = balance_before_publish
+                .checked_sub(gas_meter.balance_internal())
+                .unwrap_or_else(|| 0.into());
+            call_traces.set_gas_used(gas_used.into());
+        }
+        publish_result?;
+
+        let respawned_session = self.charge_change_set_and_respawn_session(
+            session,
+            resolver,
+            gas_meter,
+            change_set_configs,
+            txn_data,
+        )?;
+
+        self.success_transaction_cleanup(
+            respawned_session,
+            gas_meter,
+            txn_data,
+            log_context,
+            change_set_configs,
+        )
+    }
+
+    /// Publishes a module bundle on behalf of the sender and calls the initializers of the
+    /// published modules.
+    fn publish_module_bundle_and_initialize(
+        &self,
+        session: &mut SessionExt,
+        gas_meter: &mut impl AptosGasMeter,
+        txn_data: &TransactionMetadata,
+        modules: &ModuleBundle,
+        new_published_modules_loaded: &mut bool,
+        call_traces: Option<&mut CallTraces>,
+    ) -> VMResult<()> {
+        Self::verify_module_bundle(session, modules)?;
         session.publish_module_bundle_with_compat_config(
             modules.clone().into_inner(),
             txn_data.sender(),
@@ -906,28 +1027,13 @@ impl AptosVM {
 
         // call init function of the each module
         self.execute_module_initialization(
-            &mut session,
+            session,
             gas_meter,
             &self.deserialize_module_bundle(modules)?,
             BTreeSet::new(),
             &[txn_data.sender()],
             new_published_modules_loaded,
-        )?;
-
-        let respawned_session = self.charge_change_set_and_respawn_session(
-            session,
-            resolver,
-            gas_meter,
-            change_set_configs,
-            txn_data,
-        )?;
-
-        self.success_transaction_cleanup(
-            respawned_session,
-            gas_meter,
-            txn_data,
-            log_context,
-            change_set_configs,
+            call_traces,
         )
     }
 
@@ -937,6 +1043,7 @@ impl AptosVM {
         session: &mut SessionExt,
         gas_meter: &mut impl AptosGasMeter,
         new_published_modules_loaded: &mut bool,
+        call_traces: Option<&mut CallTraces>,
     ) -> VMResult<()> {
         if let Some(PublishRequest {
             destination,
@@ -988,6 +1095,7 @@ impl AptosVM {
                 exists,
                 &[destination],
                 new_published_modules_loaded,
+                call_traces,
             )
         } else {
             Ok(())
@@ -1076,6 +1184,7 @@ impl AptosVM {
         txn: &SignatureCheckedTransaction,
         log_context: &AdapterLogSchema,
         gas_meter: &mut impl AptosGasMeter,
+        call_traces: Option<&mut CallTraces>,
     ) -> (VMStatus, VMOutput) {
         // Revalidate the transaction.
         let mut session = self.0.new_session(resolver, SessionId::prologue(txn));
@@ -1120,6 +1229,7 @@ impl AptosVM {
                     log_context,
                     &mut new_published_modules_loaded,
                     &storage_gas_params.change_set_configs,
+                    call_traces,
                 ),
             TransactionPayload::Multisig(payload) => self.execute_multisig_transaction(
                 resolver,
@@ -1130,6 +1240,7 @@ impl AptosVM {
                 log_context,
                 &mut new_published_modules_loaded,
                 &storage_gas_params.change_set_configs,
+                call_traces,
             ),
 
             // Deprecated. Will be removed in the future.
@@ -1142,6 +1253,7 @@ impl AptosVM {
                 log_context,
                 &mut new_published_modules_loaded,
                 &storage_gas_params.change_set_configs,
+                call_traces,
             ),
         };
 
@@ -1195,7 +1307,7 @@ impl AptosVM {
         // TODO: would we end up having a diverging behavior by creating the gas meter at an earlier time?
         let mut gas_meter = unwrap_or_discard!(self.make_standard_gas_meter(balance, log_context));
 
-        self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter)
+        self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter, None)
     }
 
     pub fn execute_user_transaction_with_custom_gas_meter<G, F>(
@@ -1218,7 +1330,7 @@ impl AptosVM {
             balance,
         )?;
         let (status, output) =
-            self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter);
+            self.execute_user_transaction_impl(resolver, txn, log_context, &mut gas_meter, None);
 
         Ok((status, output, gas_meter))
     }
@@ -1346,6 +1458,7 @@ impl AptosVM {
         resolver: &impl AptosMoveResolver,
         block_metadata: BlockMetadata,
         log_context: &AdapterLogSchema,
+        call_traces: Option<&mut CallTraces>,
     ) -> Result<(VMStatus, VMOutput), VMStatus> {
         fail_point!("move_adapter::process_block_prologue", |_| {
             Err(VMStatus::error(
@@ -1365,18 +1478,25 @@ impl AptosVM {
             .new_session(resolver, SessionId::block_meta(&block_metadata));
 
         let args = serialize_values(&block_metadata.get_prologue_move_args(txn_data.sender));
-        session
-            .execute_function_bypass_visibility(
+        match call_traces {
+            Some(call_traces) => session.execute_function_bypass_visibility_with_call_trace(
                 &BLOCK_MODULE,
                 BLOCK_PROLOGUE,
                 vec![],
                 args,
                 &mut gas_meter,
-            )
-            .map(|_return_vals| ())
-            .or_else(|e| {
-                expect_only_successful_execution(e, BLOCK_PROLOGUE.as_str(), log_context)
-            })?;
+                call_traces,
+            ),
+            None => session.execute_function_bypass_visibility(
+                &BLOCK_MODULE,
+                BLOCK_PROLOGUE,
+                vec![],
+                args,
+                &mut gas_meter,
+            ),
+        }
+        .map(|_return_vals| ())
+        .or_else(|e| expect_only_successful_execution(e, BLOCK_PROLOGUE.as_str(), log_context))?;
         SYSTEM_TRANSACTIONS_EXECUTED.inc();
 
         let output = get_transaction_output(
@@ -1403,7 +1523,7 @@ impl AptosVM {
 
         let resolver = simulation_vm.0.as_move_resolver(executor_view);
         let (vm_status, vm_output) =
-            simulation_vm.simulate_signed_transaction(&resolver, txn, &log_context);
+            simulation_vm.simulate_signed_transaction(&resolver, txn, &log_context, None);
         (
             vm_status,
             vm_output
@@ -1412,34 +1532,87 @@ impl AptosVM {
         )
     }
 
-    pub fn get_call_trace(
+    /// Same as `simulate_signed_transaction`, but also records the call trace of the payload.
+    pub fn simulate_signed_transaction_with_call_trace(
+        txn: &SignedTransaction,
         executor_view: &impl ExecutorView,
-        module_id: ModuleId,
-        func_name: Identifier,
-        type_args: Vec<TypeTag>,
-        arguments: Vec<Vec<u8>>,
-        gas_budget: u64,
-    ) -> Result<CallTraces> {
+    ) -> (VMStatus, TransactionOutput, CallTraces) {
         let vm = AptosVM::new_from_executor_view(executor_view);
+        let simulation_vm = AptosSimulationVM(vm);
         let log_context = AdapterLogSchema::new(executor_view.id(), 0);
-        let mut gas_meter =
-            MemoryTrackedGasMeter::new(StandardGasMeter::new(StandardGasAlgebra::new(
-                vm.0.get_gas_feature_version(),
-                vm.0.get_gas_parameters(&log_context)?.vm.clone(),
-                vm.0.get_storage_gas_parameters(&log_context)?.clone(),
-                gas_budget,
-            )));
+
+        let resolver = simulation_vm.0.as_move_resolver(executor_view);
+        let mut call_traces = CallTraces::new();
+        let (vm_status, vm_output) = simulation_vm.simulate_signed_transaction(
+            &resolver,
+            txn,
+            &log_context,
+            Some(&mut call_traces),
+        );
+        (
+            vm_status,
+            vm_output
+                .try_into_transaction_output(&resolver)
+                .expect("Simulation cannot fail"),
+            call_traces,
+        )
+    }
+
+    /// Executes a SignedTransaction through the regular user transaction flow (prologue,
+    /// payload, epilogue and gas charging) and records the call trace of its payload.
+    ///
+    /// Signatures are checked as usual, so this is intended for replaying committed transactions
+    /// against the state they were originally executed on.
+    pub fn execute_user_transaction_with_call_trace(
+        txn: &SignedTransaction,
+        executor_view: &impl ExecutorView,
+    ) -> Result<(VMStatus, TransactionOutput, CallTraces)> {
+        let vm = AptosVM::new_from_executor_view(executor_view);
+        let log_context = AdapterLogSchema::new(executor_view.id(), 0);
+        let txn = txn.clone().check_signature()?;
+
         let resolver = vm.as_move_resolver(executor_view);
-        let mut session = vm.new_session(&resolver, SessionId::Void);
-        let call_trace_res = session.call_trace(&module_id, &func_name, type_args, arguments, &mut gas_meter);
-        match call_trace_res {
-            Ok(call_trace) => {
-                Ok(call_trace)
-            }
-            Err(err) => {
-                Err(anyhow::Error::msg(err.into_vm_status()))
-            }
-        }
+        let balance = TransactionMetadata::new(&txn).max_gas_amount();
+        let mut gas_meter = vm
+            .make_standard_gas_meter(balance, &log_context)
+            .map_err(|err| anyhow!("Failed to create gas meter: {:?}", err))?;
+        let mut call_traces = CallTraces::new();
+        let (vm_status, vm_output) = vm.execute_user_transaction_impl(
+            &resolver,
+            &txn,
+            &log_context,
+            &mut gas_meter,
+            Some(&mut call_traces),
+        );
+        let output = vm_output
+            .try_into_transaction_output(&resolver)
+            .map_err(|err| anyhow!("Failed to materialize transaction output: {:?}", err))?;
+        Ok((vm_status, output, call_traces))
+    }
+
+    /// Executes the block prologue of a BlockMetadata transaction and records the call trace of
+    /// the framework calls it makes.
+    pub fn execute_block_metadata_with_call_trace(
+        block_metadata: BlockMetadata,
+        executor_view: &impl ExecutorView,
+    ) -> Result<(VMStatus, TransactionOutput, CallTraces)> {
+        let vm = AptosVM::new_from_executor_view(executor_view);
+        let log_context = AdapterLogSchema::new(executor_view.id(), 0);
+
+        let resolver = vm.as_move_resolver(executor_view);
+        let mut call_traces = CallTraces::new();
+        let (vm_status, vm_output) = vm
+            .process_block_prologue(
+                &resolver,
+                block_metadata,
+                &log_context,
+                Some(&mut call_traces),
+            )
+            .map_err(|err| anyhow!("Failed to execute block prologue: {:?}", err))?;
+        let output = vm_output
+            .try_into_transaction_output(&resolver)
+            .map_err(|err| anyhow!("Failed to materialize transaction output: {:?}", err))?;
+        Ok((vm_status, output, call_traces))
     }
 
     pub fn execute_view_function(
@@ -1450,16 +1623,68 @@ impl AptosVM {
         arguments: Vec<Vec<u8>>,
         gas_budget: u64,
     ) -> Result<Vec<Vec<u8>>> {
+        Self::execute_view_function_with_gas_used(
+            state_view, module_id, func_name, type_args, arguments, gas_budget,
+        )
+        .values
+    }
+
+    /// Like [`AptosVM::execute_view_function`], but also returns the gas used, including by a
+    /// call that failed.
+    pub fn execute_view_function_with_gas_used(
+        state_view: &impl StateView,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_budget: u64,
+    ) -> ViewFunctionOutput {
         let vm = AptosVM::new_from_state_view(state_view);
         let log_context = AdapterLogSchema::new(state_view.id(), 0);
-        let mut gas_meter =
-            MemoryTrackedGasMeter::new(StandardGasMeter::new(StandardGasAlgebra::new(
-                vm.0.get_gas_feature_version(),
-                vm.0.get_gas_parameters(&log_context)?.vm.clone(),
-                vm.0.get_storage_gas_parameters(&log_context)?.clone(),
-                gas_budget,
-            )));
+        let mut gas_meter = match (
+            vm.0.get_gas_parameters(&log_context),
+            vm.0.get_storage_gas_parameters(&log_context),
+        ) {
+            (Ok(gas_params), Ok(storage_gas_params)) => {
+                MemoryTrackedGasMeter::new(StandardGasMeter::new(StandardGasAlgebra::new(
+                    vm.0.get_gas_feature_version(),
+                    gas_params.vm.clone(),
+                    storage_gas_params.clone(),
+                    gas_budget,
+                )))
+            },
+            (Err(err), _) | (_, Err(err)) => {
+                return ViewFunctionOutput {
+                    values: Err(err.into()),
+                    gas_used: 0,
+                }
+            },
+        };
 
+        let values = Self::execute_view_function_in_vm(
+            &vm,
+            state_view,
+            module_id,
+            func_name,
+            type_args,
+            arguments,
+            &mut gas_meter,
+        );
+        ViewFunctionOutput {
+            values,
+            gas_used: gas_budget.saturating_sub(u64::from(gas_meter.balance())),
+        }
+    }
+
+    fn execute_view_function_in_vm(
+        vm: &AptosVM,
+        state_view: &impl StateView,
+        module_id: ModuleId,
+        func_name: Identifier,
+        type_args: Vec<TypeTag>,
+        arguments: Vec<Vec<u8>>,
+        gas_meter: &mut impl AptosGasMeter,
+    ) -> Result<Vec<Vec<u8>>> {
         let executor_view = state_view.as_executor_view();
         let resolver = vm.as_move_resolver(&executor_view);
         let mut session = vm.new_session(&resolver, SessionId::Void);
@@ -1482,7 +1707,7 @@ impl AptosVM {
                 func_name.as_ident_str(),
                 type_args,
                 arguments,
-                &mut gas_meter,
+                gas_meter,
             )
             .map_err(|err| anyhow!("Failed to execute function: {:?}", err))?
             .return_values
@@ -1741,8 +1966,12 @@ impl VMAdapter for AptosVM {
         Ok(match txn {
             PreprocessedTransaction::BlockMetadata(block_metadata) => {
                 fail_point!("aptos_vm::execution::block_metadata");
-                let (vm_status, output) =
-                    self.process_block_prologue(resolver, block_metadata.clone(), log_context)?;
+                let (vm_status, output) = self.process_block_prologue(
+                    resolver,
+                    block_metadata.clone(),
+                    log_context,
+                    None,
+                )?;
                 (vm_status, output, Some("block_prologue".to_string()))
             },
             PreprocessedTransaction::WaypointWriteSet(write_set_payload) => {
@@ -1867,6 +2096,7 @@ impl AptosSimulationVM {
         resolver: &impl AptosMoveResolver,
         txn: &SignedTransaction,
         log_context: &AdapterLogSchema,
+        call_traces: Option<&mut CallTraces>,
     ) -> (VMStatus, VMOutput) {
         // simulation transactions should not carry valid signatures, otherwise malicious fullnodes
         // may execute them without user's explicit permission.
@@ -1913,6 +2143,7 @@ impl AptosSimulationVM {
                     log_context,
                     &mut new_published_modules_loaded,
                     &storage_gas_params.change_set_configs,
+                    call_traces,
                 )
             },
             TransactionPayload::Multisig(multisig) => {
@@ -1926,6 +2157,7 @@ impl AptosSimulationVM {
                                     multisig.multisig_address,
                                     &entry_function,
                                     &mut new_published_modules_loaded,
+                                    call_traces,
                                 ));
                                 // TODO: Deduplicate this against execute_multisig_transaction
                                 // A bit tricky since we need to skip success/failure cleanups,
@@ -1965,6 +2197,7 @@ impl AptosSimulationVM {
                 log_context,
                 &mut new_published_modules_loaded,
                 &storage_gas_params.change_set_configs,
+                call_traces,
             ),
         };
 
@@ -121,7 +121,7 @@ pub mod transaction_metadata;
 mod transaction_validation;
 mod verifier;
 
-pub use crate::aptos_vm::AptosVM;
+pub use crate::aptos_vm::{AptosVM, ViewFunctionOutput};
 use crate::sharded_block_executor::{executor_client::ExecutorClient, ShardedBlockExecutor};
 use aptos_state_view::StateView;
 use aptos_types::{
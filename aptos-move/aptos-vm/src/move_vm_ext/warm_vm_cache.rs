@@ -4,10 +4,12 @@
 #![forbid(unsafe_code)]
 
 use crate::{counters::TIMER, move_vm_ext::AptosMoveResolver, natives::aptos_natives_with_builder};
+use aptos_crypto::HashValue;
 use aptos_framework::natives::code::PackageRegistry;
 use aptos_infallible::RwLock;
 use aptos_metrics_core::TimerHelper;
 use aptos_native_interface::SafeNativeBuilder;
+use aptos_state_view::StateViewId;
 use aptos_types::on_chain_config::OnChainConfig;
 use bytes::Bytes;
 use move_binary_format::errors::{Location, PartialVMError, VMResult};
@@ -101,6 +103,9 @@ struct WarmVmId {
     natives: Bytes,
     vm_config: Bytes,
     core_packages_registry: Option<Bytes>,
+    /// Modules overridden by the state view. A VM warmed up on storage would keep running the
+    /// original modules once they are loaded.
+    code_overrides: Option<HashValue>,
 }
 
 impl WarmVmId {
@@ -117,6 +122,10 @@ impl WarmVmId {
             natives,
             vm_config: Self::vm_config_bytes(vm_config),
             core_packages_registry: Self::core_packages_id_bytes(resolver)?,
+            code_overrides: match resolver.id() {
+                StateViewId::CodeOverrides { code_hash } => Some(code_hash),
+                _ => None,
+            },
         })
     }
 
@@ -564,6 +564,17 @@ pub fn setup_environment_and_start_node(
         consensus_reconfig_subscription,
     ) = state_sync::create_event_subscription_service(&node_config, &db_rw);
 
+    // Subscribe the API to ledger updates, which drive its streaming endpoints
+    let api_ledger_update_listener = if node_config.api.enabled {
+        Some(
+            event_subscription_service
+                .subscribe_to_ledger_updates()
+                .expect("API must subscribe to ledger updates"),
+        )
+    } else {
+        None
+    };
+
     // Set up the networks and gather the application network handles
     let (
         network_runtimes,
@@ -597,7 +608,12 @@ pub fn setup_environment_and_start_node(
 
     // Bootstrap the API and indexer
     let (mempool_client_receiver, api_runtime, indexer_runtime, indexer_grpc_runtime) =
-        services::bootstrap_api_and_indexer(&node_config, aptos_db, chain_id)?;
+        services::bootstrap_api_and_indexer(
+            &node_config,
+            aptos_db,
+            chain_id,
+            api_ledger_update_listener,
+        )?;
 
     // Create mempool and get the consensus to mempool sender
     let (mempool_runtime, consensus_to_mempool_sender) =
@@ -6,7 +6,9 @@ use aptos_build_info::build_information;
 use aptos_config::config::NodeConfig;
 use aptos_consensus::network_interface::ConsensusMsg;
 use aptos_consensus_notifications::ConsensusNotifier;
-use aptos_event_notifications::{DbBackedOnChainConfig, ReconfigNotificationListener};
+use aptos_event_notifications::{
+    DbBackedOnChainConfig, LedgerUpdateNotificationListener, ReconfigNotificationListener,
+};
 use aptos_indexer_grpc_fullnode::runtime::bootstrap as bootstrap_indexer_grpc;
 use aptos_logger::{debug, telemetry_log_writer::TelemetryLog, LoggerFilterUpdater};
 use aptos_mempool::{network::MempoolSyncMsg, MempoolClientRequest, QuorumStoreRequest};
@@ -34,6 +36,7 @@ pub fn bootstrap_api_and_indexer(
     node_config: &NodeConfig,
     aptos_db: Arc<dyn DbReader>,
     chain_id: ChainId,
+    api_ledger_update_listener: Option<LedgerUpdateNotificationListener>,
 ) -> anyhow::Result<(
     Receiver<MempoolClientRequest>,
     Option<Runtime>,
@@ -51,6 +54,7 @@ pub fn bootstrap_api_and_indexer(
             chain_id,
             aptos_db.clone(),
             mempool_client_sender.clone(),
+            api_ledger_update_listener,
         )?)
     } else {
         None
@@ -15,6 +15,8 @@ pub const OWNER_ACCOUNT: &str = "owner_account";
 
 /// Definitions of global data items (e.g., as held in secure storage)
 pub const SAFETY_DATA: &str = "safety_data";
+pub const SAFETY_DATA_EXPORTED: &str = "safety_data_exported";
+pub const SAFETY_DATA_IMPORT: &str = "safety_data_import";
 pub const WAYPOINT: &str = "waypoint";
 pub const GENESIS_WAYPOINT: &str = "genesis-waypoint";
 
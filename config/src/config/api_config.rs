@@ -4,13 +4,15 @@
 
 use crate::{
     config::{
-        config_sanitizer::ConfigSanitizer, gas_estimation_config::GasEstimationConfig,
-        node_config_loader::NodeType, Error, NodeConfig,
+        config_optimizer::ConfigOptimizer, config_sanitizer::ConfigSanitizer,
+        gas_estimation_config::GasEstimationConfig, node_config_loader::NodeType, Error,
+        NodeConfig,
     },
     utils,
 };
 use aptos_types::chain_id::ChainId;
 use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
 use std::net::SocketAddr;
 
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
@@ -48,6 +50,14 @@ pub struct ApiConfig {
     /// Enables transaction simulation
     #[serde(default = "default_enabled")]
     pub transaction_simulation_enabled: bool,
+    /// Enables the call trace APIs, which re-execute the block of the traced transaction up to it
+    #[serde(default = "default_disabled")]
+    pub call_trace_enabled: bool,
+    /// Enables the mempool APIs, exposing the transactions waiting in the mempool of the node
+    ///
+    /// If not set, this is disabled on public fullnodes.
+    #[serde(default = "default_enabled")]
+    pub mempool_api_enabled: bool,
     /// Maximum number of transactions that can be sent with the Batch submit API
     pub max_submit_transaction_batch_size: usize,
     /// Maximum page size for transaction paginated APIs
@@ -62,6 +72,31 @@ pub struct ApiConfig {
     ///
     /// This limits the execution length of a view function to the given gas used.
     pub max_gas_view_function: u64,
+    /// Multiplier of `max_gas_view_function` giving the gas limit of a whole view function batch
+    ///
+    /// Each call of the batch is still limited to `max_gas_view_function`.
+    pub view_function_batch_gas_multiplier: u64,
+    /// Minimum gas counted against the gas limit of a view function batch for each call
+    ///
+    /// This is also counted for calls that fail before they are executed, e.g. because they
+    /// can't be converted.
+    pub min_gas_view_function_batch_call: u64,
+    /// Maximum number of calls in a view function batch
+    pub max_view_function_batch_size: usize,
+    /// Maximum number of requests waiting on a transaction by hash at the same time
+    ///
+    /// Requests beyond this limit return the current state of the transaction right away.
+    pub wait_by_hash_max_active_connections: usize,
+    /// Maximum time in seconds a request can wait on a transaction by hash
+    pub wait_by_hash_max_timeout_secs: u64,
+    /// Maximum number of open transaction streams
+    ///
+    /// Requests for a new stream beyond this limit are rejected with a 503.
+    pub transaction_stream_max_active_connections: usize,
+    /// Maximum number of call trace requests re-executing their block at the same time
+    ///
+    /// Requests beyond this limit are rejected with a 503.
+    pub call_trace_max_active_replays: usize,
     /// Optional: Maximum number of worker threads for the API.
     ///
     /// If not set, `runtime_worker_multiplier` will multiply times the number of CPU cores on the machine
@@ -82,6 +117,13 @@ pub const DEFAULT_MAX_PAGE_SIZE: u16 = 100;
 const DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE: u16 = 9999;
 const DEFAULT_MAX_VIEW_GAS: u64 = 2_000_000; // We keep this value the same as the max number of gas allowed for one single transaction defined in aptos-gas.
+const DEFAULT_VIEW_FUNCTION_BATCH_GAS_MULTIPLIER: u64 = 10;
+const DEFAULT_MIN_GAS_VIEW_FUNCTION_BATCH_CALL: u64 = 1_000;
+const DEFAULT_MAX_VIEW_FUNCTION_BATCH_SIZE: usize = 100;
+const DEFAULT_WAIT_BY_HASH_MAX_ACTIVE_CONNECTIONS: usize = 100;
+const DEFAULT_WAIT_BY_HASH_MAX_TIMEOUT_SECS: u64 = 30;
+const DEFAULT_TRANSACTION_STREAM_MAX_ACTIVE_CONNECTIONS: usize = 100;
+const DEFAULT_CALL_TRACE_MAX_ACTIVE_REPLAYS: usize = 4;
 
 fn default_enabled() -> bool {
     true
@@ -107,12 +149,22 @@ impl Default for ApiConfig {
             encode_submission_enabled: default_enabled(),
             transaction_submission_enabled: default_enabled(),
             transaction_simulation_enabled: default_enabled(),
+            call_trace_enabled: default_disabled(),
+            mempool_api_enabled: default_enabled(),
             max_submit_transaction_batch_size: DEFAULT_MAX_SUBMIT_TRANSACTION_BATCH_SIZE,
             max_transactions_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_events_page_size: DEFAULT_MAX_PAGE_SIZE,
             max_account_resources_page_size: DEFAULT_MAX_ACCOUNT_RESOURCES_PAGE_SIZE,
             max_account_modules_page_size: DEFAULT_MAX_ACCOUNT_MODULES_PAGE_SIZE,
             max_gas_view_function: DEFAULT_MAX_VIEW_GAS,
+            view_function_batch_gas_multiplier: DEFAULT_VIEW_FUNCTION_BATCH_GAS_MULTIPLIER,
+            min_gas_view_function_batch_call: DEFAULT_MIN_GAS_VIEW_FUNCTION_BATCH_CALL,
+            max_view_function_batch_size: DEFAULT_MAX_VIEW_FUNCTION_BATCH_SIZE,
+            wait_by_hash_max_active_connections: DEFAULT_WAIT_BY_HASH_MAX_ACTIVE_CONNECTIONS,
+            wait_by_hash_max_timeout_secs: DEFAULT_WAIT_BY_HASH_MAX_TIMEOUT_SECS,
+            transaction_stream_max_active_connections:
+                DEFAULT_TRANSACTION_STREAM_MAX_ACTIVE_CONNECTIONS,
+            call_trace_max_active_replays: DEFAULT_CALL_TRACE_MAX_ACTIVE_REPLAYS,
             max_runtime_workers: None,
             runtime_worker_multiplier: 2,
             gas_estimation: GasEstimationConfig::default(),
@@ -169,6 +221,30 @@ impl ConfigSanitizer for ApiConfig {
     }
 }
 
+impl ConfigOptimizer for ApiConfig {
+    fn optimize(
+        node_config: &mut NodeConfig,
+        local_config_yaml: &Value,
+        node_type: NodeType,
+        _chain_id: ChainId,
+    ) -> Result<bool, Error> {
+        let api_config = &mut node_config.api;
+        let local_api_config_yaml = &local_config_yaml["api"];
+
+        // Disable the mempool APIs for public fullnodes (they expose the pending transactions
+        // of every account, and the order in which the node will broadcast them)
+        let mut modified_config = false;
+        if node_type == NodeType::PublicFullnode
+            && local_api_config_yaml["mempool_api_enabled"].is_null()
+        {
+            api_config.mempool_api_enabled = false;
+            modified_config = true;
+        }
+
+        Ok(modified_config)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -227,4 +303,59 @@ mod tests {
             ApiConfig::sanitize(&node_config, NodeType::Validator, ChainId::mainnet()).unwrap_err();
         assert!(matches!(error, Error::ConfigSanitizerFailed(_, _)));
     }
+
+    #[test]
+    fn test_optimize_mempool_api_public_fullnode() {
+        // Create a node config with the mempool APIs enabled
+        let mut node_config = NodeConfig::default();
+        assert!(node_config.api.mempool_api_enabled);
+
+        // Optimize the config for a validator fullnode and verify no modifications are made
+        let modified_config = ApiConfig::optimize(
+            &mut node_config,
+            &serde_yaml::from_str("{}").unwrap(), // An empty local config,
+            NodeType::ValidatorFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap();
+        assert!(!modified_config);
+        assert!(node_config.api.mempool_api_enabled);
+
+        // Optimize the config for a public fullnode and verify the mempool APIs are disabled
+        let modified_config = ApiConfig::optimize(
+            &mut node_config,
+            &serde_yaml::from_str("{}").unwrap(), // An empty local config,
+            NodeType::PublicFullnode,
+            ChainId::testnet(),
+        )
+        .unwrap();
+        assert!(modified_config);
+        assert!(!node_config.api.mempool_api_enabled);
+    }
+
+    #[test]
+    fn test_optimize_mempool_api_public_fullnode_local_override() {
+        // Create a node config with the mempool APIs enabled
+        let mut node_config = NodeConfig::default();
+
+        // Create a local config that enables the mempool APIs
+        let local_config_yaml = serde_yaml::from_str(
+            r#"
+            api:
+                mempool_api_enabled: true
+            "#,
+        )
+        .unwrap();
+
+        // Optimize the config for a public fullnode and verify no modifications are made
+        let modified_config = ApiConfig::optimize(
+            &mut node_config,
+            &local_config_yaml,
+            NodeType::PublicFullnode,
+            ChainId::mainnet(),
+        )
+        .unwrap();
+        assert!(!modified_config);
+        assert!(node_config.api.mempool_api_enabled);
+    }
 }
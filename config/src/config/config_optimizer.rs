@@ -3,7 +3,7 @@
 
 use crate::{
     config::{
-        node_config_loader::NodeType, utils::get_config_name, Error, IndexerConfig,
+        node_config_loader::NodeType, utils::get_config_name, ApiConfig, Error, IndexerConfig,
         InspectionServiceConfig, LoggerConfig, MempoolConfig, NodeConfig, Peer,
         PeerMonitoringServiceConfig, PeerRole, PeerSet, StateSyncConfig,
     },
@@ -97,6 +97,9 @@ impl ConfigOptimizer for NodeConfig {
     ) -> Result<bool, Error> {
         // Optimize only the relevant sub-configs
         let mut optimizers_with_modifications = vec![];
+        if ApiConfig::optimize(node_config, local_config_yaml, node_type, chain_id)? {
+            optimizers_with_modifications.push(ApiConfig::get_optimizer_name());
+        }
         if IndexerConfig::optimize(node_config, local_config_yaml, node_type, chain_id)? {
             optimizers_with_modifications.push(IndexerConfig::get_optimizer_name());
         }
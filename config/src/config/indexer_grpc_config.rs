@@ -36,6 +36,10 @@ pub struct IndexerGrpcConfig {
 
     /// Number of transactions returned in a single stream response
     pub output_batch_size: u16,
+
+    /// If true, every transaction that executed Move code is re-executed and streamed with its
+    /// call trace. This is expensive and should only be enabled for nodes serving trace consumers.
+    pub enable_call_traces: bool,
 }
 
 // Reminder, #[serde(default)] on IndexerGrpcConfig means that the default values for
@@ -53,6 +57,7 @@ impl Default for IndexerGrpcConfig {
             processor_task_count: DEFAULT_PROCESSOR_TASK_COUNT,
             processor_batch_size: DEFAULT_PROCESSOR_BATCH_SIZE,
             output_batch_size: DEFAULT_OUTPUT_BATCH_SIZE,
+            enable_call_traces: false,
         }
     }
 }
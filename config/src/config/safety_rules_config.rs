@@ -31,6 +31,10 @@ pub struct SafetyRulesConfig {
     pub network_timeout_ms: u64,
     pub enable_cached_safety_data: bool,
     pub initial_safety_rules_config: InitialSafetyRulesConfig,
+    // Path to a safety data export to import on startup, e.g. when the validator moves to
+    // another machine. Safety rules refuse to start if the export is older than the safety
+    // data in storage.
+    pub safety_data_import_file: Option<PathBuf>,
 }
 
 impl Default for SafetyRulesConfig {
@@ -44,14 +48,17 @@ impl Default for SafetyRulesConfig {
             network_timeout_ms: 30_000,
             enable_cached_safety_data: true,
             initial_safety_rules_config: InitialSafetyRulesConfig::None,
+            safety_data_import_file: None,
         }
     }
 }
 
 impl SafetyRulesConfig {
     pub fn set_data_dir(&mut self, data_dir: PathBuf) {
-        if let SecureBackend::OnDiskStorage(backend) = &mut self.backend {
-            backend.set_data_dir(data_dir);
+        match &mut self.backend {
+            SecureBackend::OnDiskStorage(backend) => backend.set_data_dir(data_dir),
+            SecureBackend::EncryptedOnDiskStorage(backend) => backend.set_data_dir(data_dir),
+            SecureBackend::InMemoryStorage | SecureBackend::Vault(_) => {},
         }
     }
 
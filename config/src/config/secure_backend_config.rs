@@ -3,7 +3,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::config::Error;
-use aptos_secure_storage::{InMemoryStorage, Namespaced, OnDiskStorage, Storage, VaultStorage};
+use aptos_secure_storage::{
+    EncryptedOnDiskStorage, EncryptionKey, InMemoryStorage, Namespaced, OnDiskStorage, Storage,
+    VaultStorage,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     fs::File,
@@ -17,15 +20,18 @@ pub enum SecureBackend {
     InMemoryStorage,
     Vault(VaultConfig),
     OnDiskStorage(OnDiskStorageConfig),
+    EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig),
 }
 
 impl SecureBackend {
     pub fn namespace(&self) -> Option<&str> {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
-                namespace.as_deref()
-            },
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            }) => namespace.as_deref(),
             SecureBackend::InMemoryStorage => None,
         }
     }
@@ -33,7 +39,11 @@ impl SecureBackend {
     pub fn clear_namespace(&mut self) {
         match self {
             SecureBackend::Vault(VaultConfig { namespace, .. })
-            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. }) => {
+            | SecureBackend::OnDiskStorage(OnDiskStorageConfig { namespace, .. })
+            | SecureBackend::EncryptedOnDiskStorage(EncryptedOnDiskStorageConfig {
+                namespace,
+                ..
+            }) => {
                 *namespace = None;
             },
             SecureBackend::InMemoryStorage => {},
@@ -94,6 +104,83 @@ pub struct OnDiskStorageConfig {
     data_dir: PathBuf,
 }
 
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EncryptedOnDiskStorageConfig {
+    // Required path for encrypted on disk storage
+    pub path: PathBuf,
+    /// A namespace is an optional portion of the path to a key stored within
+    /// EncryptedOnDiskStorage, as for OnDiskStorage.
+    pub namespace: Option<String>,
+    /// The secret the encryption key of the file is derived from
+    pub encryption_key: EncryptionKeySource,
+    /// The secret the file was encrypted with before a key rotation. If set, the file is
+    /// re-encrypted with `encryption_key` the first time it is read, after which this can be
+    /// removed.
+    pub previous_encryption_key: Option<EncryptionKeySource>,
+    #[serde(skip)]
+    data_dir: PathBuf,
+}
+
+impl EncryptedOnDiskStorageConfig {
+    pub fn new(path: PathBuf, encryption_key: EncryptionKeySource) -> Self {
+        Self {
+            path,
+            namespace: None,
+            encryption_key,
+            previous_encryption_key: None,
+            data_dir: PathBuf::from("/opt/aptos/data"),
+        }
+    }
+
+    pub fn path(&self) -> PathBuf {
+        if self.path.is_relative() {
+            self.data_dir.join(&self.path)
+        } else {
+            self.path.clone()
+        }
+    }
+
+    pub fn set_data_dir(&mut self, data_dir: PathBuf) {
+        self.data_dir = data_dir;
+    }
+}
+
+/// Encryption keys are never stored within the config: they're either a passphrase stored on
+/// disk, or a base64 encoded 32 byte key held by an environment variable.
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EncryptionKeySource {
+    /// This is an absolute path and not relative to data_dir
+    PassphraseFromDisk(PathBuf),
+    /// The name of the environment variable
+    FromEnv(String),
+}
+
+impl EncryptionKeySource {
+    pub fn read_key(&self) -> Result<EncryptionKey, Error> {
+        match self {
+            EncryptionKeySource::PassphraseFromDisk(path) => {
+                let passphrase = read_file(path)?;
+                // Editors tend to append a newline to the files they save.
+                let passphrase = passphrase.trim_end_matches(&['\r', '\n'][..]);
+                if passphrase.is_empty() {
+                    return Err(Error::Missing("passphrase"));
+                }
+                Ok(EncryptionKey::Passphrase(passphrase.as_bytes().to_vec()))
+            },
+            EncryptionKeySource::FromEnv(name) => {
+                let key = std::env::var(name).map_err(|e| {
+                    Error::Unexpected(format!("Unable to read env variable {}: {}", name, e))
+                })?;
+                EncryptionKey::from_base64(&key).map_err(|e| {
+                    Error::Unexpected(format!("Invalid key in env variable {}: {}", name, e))
+                })
+            },
+        }
+    }
+}
+
 /// Tokens can either be directly within this config or stored somewhere on disk.
 #[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "snake_case")]
@@ -169,6 +256,24 @@ impl From<&SecureBackend> for Storage {
                     storage
                 }
             },
+            SecureBackend::EncryptedOnDiskStorage(config) => {
+                let storage = Storage::from(EncryptedOnDiskStorage::new(
+                    config.path(),
+                    config
+                        .encryption_key
+                        .read_key()
+                        .expect("Unable to read encryption key"),
+                    config.previous_encryption_key.as_ref().map(|key| {
+                        key.read_key()
+                            .expect("Unable to read previous encryption key")
+                    }),
+                ));
+                if let Some(namespace) = &config.namespace {
+                    Storage::from(Namespaced::new(namespace, Box::new(storage)))
+                } else {
+                    storage
+                }
+            },
             SecureBackend::Vault(config) => {
                 let storage = Storage::from(VaultStorage::new(
                     config.server.clone(),
@@ -300,4 +405,54 @@ vault:
         let config = Token::FromConfig("config_token".to_string());
         assert_eq!("config_token", config.read_token().unwrap());
     }
+
+    #[test]
+    fn test_encrypted_on_disk_storage_config_parsing() {
+        let text = r#"
+type: "encrypted_on_disk_storage"
+path: secure_storage.json
+encryption_key:
+    passphrase_from_disk: "/passphrase"
+previous_encryption_key:
+    from_env: "APTOS_STORAGE_KEY"
+        "#;
+
+        let backend: SecureBackend = serde_yaml::from_str(text).unwrap();
+        let config = match backend {
+            SecureBackend::EncryptedOnDiskStorage(config) => config,
+            _ => panic!("Unexpected backend: {:?}", backend),
+        };
+        assert_eq!(config.path, PathBuf::from("secure_storage.json"));
+        assert_eq!(
+            config.encryption_key,
+            EncryptionKeySource::PassphraseFromDisk(PathBuf::from("/passphrase"))
+        );
+        assert_eq!(
+            config.previous_encryption_key,
+            Some(EncryptionKeySource::FromEnv(
+                "APTOS_STORAGE_KEY".to_string()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_encryption_key_reading() {
+        let temppath = aptos_temppath::TempPath::new();
+        temppath.create_as_file().unwrap();
+        let mut file = File::create(temppath.path()).unwrap();
+        file.write_all(b"passphrase\n").unwrap();
+        let disk = EncryptionKeySource::PassphraseFromDisk(temppath.path().to_path_buf());
+        assert!(matches!(
+            disk.read_key().unwrap(),
+            EncryptionKey::Passphrase(passphrase) if passphrase == b"passphrase"
+        ));
+
+        let name = "TEST_ENCRYPTION_KEY_READING";
+        let env = EncryptionKeySource::FromEnv(name.to_string());
+        assert!(env.read_key().is_err());
+        std::env::set_var(name, "BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=");
+        assert!(matches!(env.read_key().unwrap(), EncryptionKey::Raw(key) if key == [7u8; 32]));
+        std::env::set_var(name, "BwcHBwcHBwcHBwcHBwcHBw==");
+        assert!(env.read_key().is_err());
+    }
 }
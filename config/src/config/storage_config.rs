@@ -72,6 +72,15 @@ pub struct RocksdbConfigs {
     pub split_ledger_db: bool,
     // Note: Not ready for production use yet.
     pub skip_index_and_usage: bool,
+    /// Maintain an index of the versions at which each state key was written, which serves the
+    /// history of a resource or table item. Off by default since it grows with every write.
+    pub enable_state_value_history_index: bool,
+    /// Maintain an index of the events by Move type, which serves the events of a type across
+    /// all accounts, including module events. Off by default.
+    pub enable_event_by_type_index: bool,
+    /// Maintain an index of the user transactions by the entry function they call, which serves
+    /// the transactions calling a function. Off by default.
+    pub enable_transaction_by_function_index: bool,
     pub state_kv_db_config: RocksdbConfig,
     pub index_db_config: RocksdbConfig,
 }
@@ -84,6 +93,9 @@ impl Default for RocksdbConfigs {
             use_sharded_state_merkle_db: false,
             split_ledger_db: false,
             skip_index_and_usage: false,
+            enable_state_value_history_index: false,
+            enable_event_by_type_index: false,
+            enable_transaction_by_function_index: false,
             state_kv_db_config: RocksdbConfig::default(),
             index_db_config: RocksdbConfig {
                 max_open_files: 1000,
@@ -56,6 +56,12 @@ pub enum Error {
     WaypointOutOfDate(u64, u64, u64, u64),
     #[error("Invalid Timeout: {0}")]
     InvalidTimeout(String),
+    #[error("Invalid safety data export: {0}")]
+    InvalidSafetyDataExport(String),
+    #[error("Safety data export is older than the safety data in storage: exported epoch {0}, last voted round {1}, stored epoch {2}, last voted round {3}")]
+    StaleSafetyDataExport(u64, u64, u64, u64),
+    #[error("The safety data was exported, this validator must not sign anymore")]
+    SafetyDataExported,
 }
 
 impl From<serde_json::Error> for Error {
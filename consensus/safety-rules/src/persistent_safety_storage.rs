@@ -5,11 +5,15 @@
 use crate::{
     counters,
     logging::{self, LogEntry, LogEvent},
+    safety_data_export::{SafetyDataExport, SignedSafetyDataExport},
     Error,
 };
 use aptos_consensus_types::{common::Author, safety_data::SafetyData};
-use aptos_crypto::{bls12381, PrivateKey};
-use aptos_global_constants::{CONSENSUS_KEY, OWNER_ACCOUNT, SAFETY_DATA, WAYPOINT};
+use aptos_crypto::{bls12381, HashValue, PrivateKey};
+use aptos_global_constants::{
+    CONSENSUS_KEY, OWNER_ACCOUNT, SAFETY_DATA, SAFETY_DATA_EXPORTED, SAFETY_DATA_IMPORT, WAYPOINT,
+};
+use aptos_infallible::duration_since_epoch;
 use aptos_logger::prelude::*;
 use aptos_secure_storage::{KVStorage, Storage};
 use aptos_types::waypoint::Waypoint;
@@ -160,6 +164,86 @@ impl PersistentSafetyStorage {
         Ok(())
     }
 
+    /// Exports the safety data and the waypoint, signed with the consensus key. The validator
+    /// must not vote anymore once exported, or the export is stale, so the storage is marked
+    /// as exported and safety rules refuse to initialize from it until an export is imported.
+    pub fn export_safety_data(&mut self) -> Result<SignedSafetyDataExport, Error> {
+        let export = SafetyDataExport {
+            author: self.author()?,
+            safety_data: self.safety_data()?,
+            waypoint: self.waypoint()?,
+            timestamp_secs: duration_since_epoch().as_secs(),
+        };
+        let _timer = counters::start_timer("get", CONSENSUS_KEY);
+        let consensus_key: bls12381::PrivateKey =
+            self.internal_store.get(CONSENSUS_KEY).map(|v| v.value)?;
+        let signed = SignedSafetyDataExport::new(export, &consensus_key)?;
+        self.internal_store.set(SAFETY_DATA_EXPORTED, true)?;
+        Ok(signed)
+    }
+
+    /// Whether the safety data was exported since the last import, if any
+    pub fn safety_data_exported(&self) -> Result<bool, Error> {
+        match self.internal_store.get(SAFETY_DATA_EXPORTED) {
+            Ok(response) => Ok(response.value),
+            Err(aptos_secure_storage::Error::KeyNotSet(_)) => Ok(false),
+            Err(error) => Err(error.into()),
+        }
+    }
+
+    /// Imports an export of the same validator. Returns false if the export was already the
+    /// last one imported, and refuses an export older than the safety data in storage, which
+    /// would let the validator vote twice in a round.
+    pub fn import_safety_data(&mut self, signed: &SignedSafetyDataExport) -> Result<bool, Error> {
+        signed.verify()?;
+        let export = &signed.export;
+        let author = self.author()?;
+        if export.author != author {
+            return Err(Error::InvalidSafetyDataExport(format!(
+                "exported by {}, not by {}",
+                export.author, author
+            )));
+        }
+        self.consensus_key_for_version(signed.public_key.clone())
+            .map_err(|_| {
+                Error::InvalidSafetyDataExport("not signed by the consensus key in storage".into())
+            })?;
+
+        let last_import: Option<HashValue> = match self.internal_store.get(SAFETY_DATA_IMPORT) {
+            Ok(response) => Some(response.value),
+            Err(aptos_secure_storage::Error::KeyNotSet(_)) => None,
+            Err(error) => return Err(error.into()),
+        };
+        if last_import == Some(signed.checksum) {
+            return Ok(false);
+        }
+
+        let safety_data = self.safety_data()?;
+        if export.is_older_than(&safety_data) {
+            return Err(Error::StaleSafetyDataExport(
+                export.safety_data.epoch,
+                export.safety_data.last_voted_round,
+                safety_data.epoch,
+                safety_data.last_voted_round,
+            ));
+        }
+        self.set_safety_data(export.safety_data.clone())?;
+        if export.waypoint.version() > self.waypoint()?.version() {
+            self.set_waypoint(&export.waypoint)?;
+        }
+        self.internal_store
+            .set(SAFETY_DATA_IMPORT, signed.checksum)?;
+        // The validator holds the latest safety data again, e.g. when it's moved back.
+        self.internal_store.set(SAFETY_DATA_EXPORTED, false)?;
+        info!(
+            epoch = export.safety_data.epoch,
+            last_voted_round = export.safety_data.last_voted_round,
+            "Imported safety data exported at {}",
+            export.timestamp_secs
+        );
+        Ok(true)
+    }
+
     #[cfg(any(test, feature = "testing"))]
     pub fn internal_store(&mut self) -> &mut Storage {
         &mut self.internal_store
@@ -0,0 +1,76 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::Error;
+use aptos_consensus_types::{common::Author, safety_data::SafetyData};
+use aptos_crypto::{bls12381, hash::CryptoHash, HashValue, PrivateKey, Signature, SigningKey};
+use aptos_crypto_derive::{BCSCryptoHash, CryptoHasher};
+use aptos_types::waypoint::Waypoint;
+use serde::{Deserialize, Serialize};
+
+/// The content of PersistentSafetyStorage that guarantees a validator never votes twice for a
+/// round, exported to move the validator to another machine.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize, CryptoHasher, BCSCryptoHash)]
+pub struct SafetyDataExport {
+    pub author: Author,
+    pub safety_data: SafetyData,
+    pub waypoint: Waypoint,
+    /// Time of the export, in seconds since the Unix epoch
+    pub timestamp_secs: u64,
+}
+
+impl SafetyDataExport {
+    /// Returns true if `self` is older than `other`, i.e. importing it over `other` could let the
+    /// validator vote again in a round it already voted in.
+    pub fn is_older_than(&self, other: &SafetyData) -> bool {
+        let safety_data = &self.safety_data;
+        safety_data.epoch < other.epoch
+            || (safety_data.epoch == other.epoch
+                && (safety_data.last_voted_round < other.last_voted_round
+                    || safety_data.preferred_round < other.preferred_round
+                    || safety_data.one_chain_round < other.one_chain_round))
+    }
+}
+
+/// A SafetyDataExport signed with the consensus key of the validator, as written to the export
+/// file
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub struct SignedSafetyDataExport {
+    pub export: SafetyDataExport,
+    /// Hash of `export`, which tells a corrupted file apart from a forged one
+    pub checksum: HashValue,
+    pub public_key: bls12381::PublicKey,
+    pub signature: bls12381::Signature,
+}
+
+impl SignedSafetyDataExport {
+    pub fn new(
+        export: SafetyDataExport,
+        consensus_key: &bls12381::PrivateKey,
+    ) -> Result<Self, Error> {
+        let signature = consensus_key
+            .sign(&export)
+            .map_err(|error| Error::InternalError(error.to_string()))?;
+        Ok(Self {
+            checksum: export.hash(),
+            public_key: consensus_key.public_key(),
+            signature,
+            export,
+        })
+    }
+
+    /// Verifies the checksum and the signature. The caller still has to check that the public
+    /// key is the consensus key of the validator.
+    pub fn verify(&self) -> Result<(), Error> {
+        if self.export.hash() != self.checksum {
+            return Err(Error::InvalidSafetyDataExport(
+                "checksum mismatch, the export is corrupted".into(),
+            ));
+        }
+        self.signature
+            .verify(&self.export, &self.public_key)
+            .map_err(|error| {
+                Error::InvalidSafetyDataExport(format!("invalid signature: {}", error))
+            })
+    }
+}
@@ -214,6 +214,10 @@ impl SafetyRules {
     }
 
     fn guarded_initialize(&mut self, proof: &EpochChangeProof) -> Result<(), Error> {
+        if self.persistent_storage.safety_data_exported()? {
+            self.validator_signer = None;
+            return Err(Error::SafetyDataExported);
+        }
         let waypoint = self.persistent_storage.waypoint()?;
         let last_li = proof
             .verify(&waypoint)
@@ -9,14 +9,41 @@ use crate::{
     remote_service::RemoteService,
     serializer::{SerializerClient, SerializerService},
     thread::ThreadService,
-    SafetyRules, TSafetyRules,
+    Error, SafetyRules, SignedSafetyDataExport, TSafetyRules,
 };
 use aptos_config::config::{InitialSafetyRulesConfig, SafetyRulesConfig, SafetyRulesService};
 use aptos_infallible::RwLock;
+use aptos_logger::info;
 use aptos_secure_storage::{KVStorage, Storage};
-use std::{convert::TryInto, net::SocketAddr, sync::Arc};
+use std::{convert::TryInto, fs, net::SocketAddr, path::Path, sync::Arc};
 
 pub fn storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
+    let mut storage = initialize_storage(config);
+    if let Some(path) = &config.safety_data_import_file {
+        import_safety_data(&mut storage, path)
+            .unwrap_or_else(|error| panic!("Refusing to start safety rules: {}", error));
+    }
+    storage
+}
+
+/// Imports the safety data export at `path`, unless it's the last one imported already
+fn import_safety_data(storage: &mut PersistentSafetyStorage, path: &Path) -> Result<(), Error> {
+    let contents = fs::read(path).map_err(|error| {
+        Error::InternalError(format!(
+            "Unable to read safety data export {:?}: {}",
+            path, error
+        ))
+    })?;
+    let export: SignedSafetyDataExport = serde_json::from_slice(&contents)?;
+    if !storage.import_safety_data(&export)? {
+        info!("Safety data export {:?} was already imported", path);
+    }
+    Ok(())
+}
+
+/// Opens the safety rules storage, initializing it from the test config or, if it's not
+/// initialized yet, from the initial safety rules config
+pub fn initialize_storage(config: &SafetyRulesConfig) -> PersistentSafetyStorage {
     let backend = &config.backend;
     let internal_storage: Storage = backend.try_into().expect("Unable to initialize storage");
     if let Err(error) = internal_storage.available() {
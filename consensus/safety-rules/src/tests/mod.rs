@@ -4,6 +4,7 @@
 
 mod local;
 mod networking;
+mod safety_data_export;
 mod safety_rules;
 mod serializer;
 mod suite;
@@ -0,0 +1,141 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    safety_rules_manager, test_utils, Error, SafetyRules, SignedSafetyDataExport, TSafetyRules,
+};
+use aptos_config::config::{SafetyRulesConfig, SafetyRulesTestConfig};
+use aptos_consensus_types::safety_data::SafetyData;
+use aptos_temppath::TempPath;
+use aptos_types::validator_signer::ValidatorSigner;
+
+#[test]
+fn test_export_and_import() {
+    let signer = ValidatorSigner::from_int(0);
+    let mut old_storage = test_utils::test_storage(&signer);
+    let safety_data = SafetyData::new(2, 10, 8, 9, None);
+    old_storage.set_safety_data(safety_data.clone()).unwrap();
+    let export = old_storage.export_safety_data().unwrap();
+    assert_eq!(export.export.author, signer.author());
+    assert_eq!(export.export.waypoint, old_storage.waypoint().unwrap());
+
+    let mut new_storage = test_utils::test_storage(&signer);
+    assert!(new_storage.import_safety_data(&export).unwrap());
+    assert_eq!(new_storage.safety_data().unwrap(), safety_data);
+    // Importing the same export again, e.g. on every restart, is a no-op.
+    assert!(!new_storage.import_safety_data(&export).unwrap());
+    assert!(!new_storage.safety_data_exported().unwrap());
+}
+
+#[test]
+fn test_exported_storage_refuses_to_initialize() {
+    let signer = ValidatorSigner::from_int(0);
+    let mut storage = test_utils::test_storage(&signer);
+    assert!(!storage.safety_data_exported().unwrap());
+    let export = storage.export_safety_data().unwrap();
+    assert!(storage.safety_data_exported().unwrap());
+
+    let (proof, _) = test_utils::make_genesis(&signer);
+    let mut safety_rules = SafetyRules::new(storage);
+    assert_eq!(
+        safety_rules.initialize(&proof).unwrap_err(),
+        Error::SafetyDataExported
+    );
+
+    // Importing an export, e.g. when moving the validator back, lets it sign again.
+    let mut storage = test_utils::test_storage(&signer);
+    storage.export_safety_data().unwrap();
+    assert!(storage.import_safety_data(&export).unwrap());
+    assert!(!storage.safety_data_exported().unwrap());
+    let mut safety_rules = SafetyRules::new(storage);
+    safety_rules.initialize(&proof).unwrap();
+}
+
+#[test]
+fn test_import_refuses_older_safety_data() {
+    let signer = ValidatorSigner::from_int(0);
+    let mut old_storage = test_utils::test_storage(&signer);
+    old_storage
+        .set_safety_data(SafetyData::new(2, 10, 8, 9, None))
+        .unwrap();
+    let export = old_storage.export_safety_data().unwrap();
+
+    let mut new_storage = test_utils::test_storage(&signer);
+    let newer_safety_data = SafetyData::new(2, 11, 8, 9, None);
+    new_storage
+        .set_safety_data(newer_safety_data.clone())
+        .unwrap();
+    assert_eq!(
+        new_storage.import_safety_data(&export).unwrap_err(),
+        Error::StaleSafetyDataExport(2, 10, 2, 11)
+    );
+    assert_eq!(new_storage.safety_data().unwrap(), newer_safety_data);
+
+    new_storage
+        .set_safety_data(SafetyData::new(3, 0, 0, 0, None))
+        .unwrap();
+    assert!(matches!(
+        new_storage.import_safety_data(&export).unwrap_err(),
+        Error::StaleSafetyDataExport(..)
+    ));
+}
+
+#[test]
+fn test_import_refuses_invalid_exports() {
+    let signer = ValidatorSigner::from_int(0);
+    let mut old_storage = test_utils::test_storage(&signer);
+    old_storage
+        .set_safety_data(SafetyData::new(2, 10, 8, 9, None))
+        .unwrap();
+    let export = old_storage.export_safety_data().unwrap();
+    let mut new_storage = test_utils::test_storage(&signer);
+
+    let mut corrupted = export.clone();
+    corrupted.export.safety_data.last_voted_round = 100;
+    assert!(matches!(
+        new_storage.import_safety_data(&corrupted).unwrap_err(),
+        Error::InvalidSafetyDataExport(_)
+    ));
+
+    // A consistent checksum doesn't make up for the signature.
+    let other_signer = ValidatorSigner::from_int(1);
+    let forged =
+        SignedSafetyDataExport::new(corrupted.export.clone(), other_signer.private_key()).unwrap();
+    assert!(matches!(
+        new_storage.import_safety_data(&forged).unwrap_err(),
+        Error::InvalidSafetyDataExport(_)
+    ));
+
+    // Exports of other validators are refused.
+    let mut other_storage = test_utils::test_storage(&other_signer);
+    let other_export = other_storage.export_safety_data().unwrap();
+    assert!(matches!(
+        new_storage.import_safety_data(&other_export).unwrap_err(),
+        Error::InvalidSafetyDataExport(_)
+    ));
+    assert_eq!(new_storage.safety_data().unwrap().epoch, 1);
+}
+
+#[test]
+#[should_panic(expected = "Refusing to start safety rules")]
+fn test_startup_refuses_older_safety_data() {
+    let signer = ValidatorSigner::from_int(0);
+    let mut old_storage = test_utils::test_storage(&signer);
+    old_storage
+        .set_safety_data(SafetyData::new(0, 10, 8, 9, None))
+        .unwrap();
+    let export = old_storage.export_safety_data().unwrap();
+    let path = TempPath::new();
+    std::fs::write(path.path(), serde_json::to_vec(&export).unwrap()).unwrap();
+
+    // The storage initialized from the test config is at epoch 1.
+    let mut test_config = SafetyRulesTestConfig::new(signer.author());
+    test_config.consensus_key(signer.private_key().clone());
+    test_config.waypoint = Some(export.export.waypoint);
+    let config = SafetyRulesConfig {
+        test: Some(test_config),
+        safety_data_import_file: Some(path.path().to_path_buf()),
+        ..Default::default()
+    };
+    safety_rules_manager::storage(&config);
+}
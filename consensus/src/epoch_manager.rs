@@ -19,8 +19,8 @@ use crate::{
     liveness::{
         cached_proposer_election::CachedProposerElection,
         leader_reputation::{
-            extract_epoch_to_proposers, AptosDBBackend, LeaderReputation,
-            ProposerAndVoterHeuristic, ReputationHeuristic,
+            create_reputation_heuristic, extract_epoch_to_proposers, first_epoch_to_consider,
+            AptosDBBackend, LeaderReputation,
         },
         proposal_generator::{
             ChainHealthBackoffConfig, PipelineBackpressureConfig, ProposalGenerator,
@@ -75,7 +75,7 @@ use aptos_types::{
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
     on_chain_config::{
-        LeaderReputationType, OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig,
+        OnChainConfigPayload, OnChainConfigProvider, OnChainConsensusConfig,
         OnChainExecutionConfig, ProposerElectionType, ValidatorSet,
     },
     validator_signer::ValidatorSigner,
@@ -254,32 +254,11 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
                     window_size,
                     weight_by_voting_power,
                     use_history_from_previous_epoch_max_count,
-                ) = match &leader_reputation_type {
-                    LeaderReputationType::ProposerAndVoter(proposer_and_voter_config)
-                    | LeaderReputationType::ProposerAndVoterV2(proposer_and_voter_config) => {
-                        let proposer_window_size = proposers.len()
-                            * proposer_and_voter_config.proposer_window_num_validators_multiplier;
-                        let voter_window_size = proposers.len()
-                            * proposer_and_voter_config.voter_window_num_validators_multiplier;
-                        let heuristic: Box<dyn ReputationHeuristic> =
-                            Box::new(ProposerAndVoterHeuristic::new(
-                                self.author,
-                                proposer_and_voter_config.active_weight,
-                                proposer_and_voter_config.inactive_weight,
-                                proposer_and_voter_config.failed_weight,
-                                proposer_and_voter_config.failure_threshold_percent,
-                                voter_window_size,
-                                proposer_window_size,
-                                leader_reputation_type.use_reputation_window_from_stale_end(),
-                            ));
-                        (
-                            heuristic,
-                            std::cmp::max(proposer_window_size, voter_window_size),
-                            proposer_and_voter_config.weight_by_voting_power,
-                            proposer_and_voter_config.use_history_from_previous_epoch_max_count,
-                        )
-                    },
-                };
+                ) = create_reputation_heuristic(
+                    self.author,
+                    proposers.len(),
+                    leader_reputation_type,
+                );
 
                 let seek_len = onchain_config.leader_reputation_exclude_round() as usize
                     + onchain_config.max_failed_authors_to_store()
@@ -352,16 +331,8 @@ impl<P: OnChainConfigProvider> EpochManager<P> {
         proposers: Vec<AccountAddress>,
         needed_rounds: u64,
     ) -> HashMap<u64, Vec<AccountAddress>> {
-        // Genesis is epoch=0
-        // First block (after genesis) is epoch=1, and is the only block in that epoch.
-        // It has no votes, so we skip it unless we are in epoch 1, as otherwise it will
-        // skew leader elections for exclude_round number of rounds.
-        let first_epoch_to_consider = std::cmp::max(
-            if epoch_state.epoch == 1 { 1 } else { 2 },
-            epoch_state
-                .epoch
-                .saturating_sub(use_history_from_previous_epoch_max_count as u64),
-        );
+        let first_epoch_to_consider =
+            first_epoch_to_consider(epoch_state.epoch, use_history_from_previous_epoch_max_count);
         // If we are considering beyond the current epoch, we need to fetch validators for those epochs
         let epoch_to_proposers = if epoch_state.epoch > first_epoch_to_consider {
             self.storage
@@ -0,0 +1,631 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    counters::{NUM_SENDERS_IN_BLOCK, TXN_SHUFFLE_SECONDS},
+    transaction_shuffler::TransactionShuffler,
+};
+use aptos_types::transaction::{MultisigTransactionPayload, SignedTransaction, TransactionPayload};
+use move_core_types::{account_address::AccountAddress, language_storage::ModuleId};
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, VecDeque},
+};
+
+/// An implementation of transaction shuffler, which spreads transactions that are likely to
+/// conflict during parallel execution, to reduce the number of Block-STM aborts. Two transactions
+/// are considered conflicting if they have the same sender, or if they call an entry function of
+/// the same module, which is a cheap proxy for their read and write sets: transactions calling a
+/// hot module, e.g. a popular mint, mostly touch the same resources.
+///
+/// A transaction placed at position `p` keeps the other transactions of its sender out of the
+/// positions up to `p + sender_conflict_window_size`, and the other transactions calling its
+/// module out of the positions up to `p + module_conflict_window_size`. At each position, the
+/// shuffler picks, among the first remaining transaction of each sender, the one that doesn't
+/// conflict with the previous ones and has the highest gas unit price, ties going to the earliest
+/// transaction of the input. If they all conflict, it picks the one whose conflict ends first,
+/// with the same tie-breakers. It maintains the following invariants:
+/// 1. Relative ordering of all transactions from the same sender is the same before and after
+/// shuffling.
+/// 2. The output only depends on the input transactions and the window sizes, so that all the
+/// validators compute the same block.
+///
+/// Transactions are picked through two heaps. Once their sender conflict ends, the transactions
+/// calling a module are grouped, and only the preferred one of each group is in the heaps, so
+/// that shuffling takes O(n log(n)) even when most transactions call the same module.
+pub struct FeeAndConflictAwareShuffler {
+    sender_conflict_window_size: usize,
+    module_conflict_window_size: usize,
+}
+
+impl TransactionShuffler for FeeAndConflictAwareShuffler {
+    fn shuffle(&self, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction> {
+        let _timer = TXN_SHUFFLE_SECONDS.start_timer();
+
+        // Early return for performance reason if there are no conflicts to avoid
+        if txns.is_empty()
+            || (self.sender_conflict_window_size == 0 && self.module_conflict_window_size == 0)
+        {
+            return txns;
+        }
+
+        let num_txns = txns.len();
+        let mut state = ShufflingState::new(
+            self.sender_conflict_window_size,
+            self.module_conflict_window_size,
+            txns,
+        );
+        let mut shuffled_txns = Vec::with_capacity(num_txns);
+        while let Some(index) = state.next_candidate() {
+            shuffled_txns.push(state.place(index));
+        }
+        NUM_SENDERS_IN_BLOCK.set(state.txns_by_sender.len() as f64);
+        shuffled_txns
+    }
+}
+
+impl FeeAndConflictAwareShuffler {
+    pub fn new(sender_conflict_window_size: usize, module_conflict_window_size: usize) -> Self {
+        Self {
+            sender_conflict_window_size,
+            module_conflict_window_size,
+        }
+    }
+}
+
+/// The module of the entry function the transaction calls, if any
+fn entry_function_module(txn: &SignedTransaction) -> Option<ModuleId> {
+    match txn.payload() {
+        TransactionPayload::EntryFunction(entry_function) => Some(entry_function.module().clone()),
+        TransactionPayload::Multisig(multisig) => match &multisig.transaction_payload {
+            Some(MultisigTransactionPayload::EntryFunction(entry_function)) => {
+                Some(entry_function.module().clone())
+            },
+            None => None,
+        },
+        TransactionPayload::Script(_) | TransactionPayload::ModuleBundle(_) => None,
+    }
+}
+
+/// A transaction that can be placed next, i.e. the first remaining transaction of its sender
+#[derive(Clone, Copy)]
+struct Candidate {
+    /// Position from which the transaction doesn't conflict with the placed transactions. It
+    /// only grows as transactions get placed, so a stale value is a lower bound.
+    available_at: usize,
+    gas_unit_price: u64,
+    index: usize,
+}
+
+impl Candidate {
+    /// Order of preference among candidates available at the same position
+    fn priority(&self) -> (u64, Reverse<usize>) {
+        (self.gas_unit_price, Reverse(self.index))
+    }
+}
+
+/// Candidates that are available, with the preferred one first
+struct Ready(Candidate);
+
+impl Ord for Ready {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.priority().cmp(&other.0.priority())
+    }
+}
+
+impl PartialOrd for Ready {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ready {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ready {}
+
+/// Candidates that conflict, with the one available first, then the preferred one, first
+struct Waiting(Candidate);
+
+impl Ord for Waiting {
+    fn cmp(&self, other: &Self) -> Ordering {
+        Reverse(self.0.available_at)
+            .cmp(&Reverse(other.0.available_at))
+            .then_with(|| self.0.priority().cmp(&other.0.priority()))
+    }
+}
+
+impl PartialOrd for Waiting {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Waiting {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Waiting {}
+
+/// A stateful data structure maintained by the transaction shuffler during shuffling. Placed
+/// transactions are taken out of `txns`, and the position of the last placed transaction of each
+/// sender and module gives when their conflicts end.
+///
+/// A candidate is grouped with the other candidates calling its module once its sender conflict
+/// can't outlast the module conflict. From then on, all the candidates of the group are available
+/// at the same time, and only the preferred one is in `ready` or `waiting`. Each candidate has at
+/// most one entry in the heaps, which may be stale and is checked again when popped.
+struct ShufflingState {
+    sender_conflict_window_size: usize,
+    module_conflict_window_size: usize,
+    txns: Vec<Option<SignedTransaction>>,
+    modules: Vec<Option<ModuleId>>,
+    // Indexes of the transactions not yet placed of each sender, in the original order
+    txns_by_sender: HashMap<AccountAddress, VecDeque<usize>>,
+    last_sender_positions: HashMap<AccountAddress, usize>,
+    last_module_positions: HashMap<ModuleId, usize>,
+    grouped: Vec<bool>,
+    groups: HashMap<ModuleId, BinaryHeap<Ready>>,
+    ready: BinaryHeap<Ready>,
+    waiting: BinaryHeap<Waiting>,
+    // Whether each candidate has an entry in `ready` or `waiting`
+    queued: Vec<bool>,
+    position: usize,
+    // Number of times a popped candidate was checked again
+    num_refreshes: usize,
+}
+
+impl ShufflingState {
+    pub fn new(
+        sender_conflict_window_size: usize,
+        module_conflict_window_size: usize,
+        txns: Vec<SignedTransaction>,
+    ) -> Self {
+        let mut txns_by_sender: HashMap<_, VecDeque<_>> = HashMap::new();
+        for (index, txn) in txns.iter().enumerate() {
+            txns_by_sender
+                .entry(txn.sender())
+                .or_default()
+                .push_back(index);
+        }
+        let mut state = Self {
+            sender_conflict_window_size,
+            module_conflict_window_size,
+            grouped: vec![false; txns.len()],
+            queued: vec![false; txns.len()],
+            modules: txns.iter().map(entry_function_module).collect(),
+            txns: txns.into_iter().map(Some).collect(),
+            txns_by_sender,
+            last_sender_positions: HashMap::new(),
+            last_module_positions: HashMap::new(),
+            groups: HashMap::new(),
+            ready: BinaryHeap::new(),
+            waiting: BinaryHeap::new(),
+            position: 0,
+            num_refreshes: 0,
+        };
+        let first_txns: Vec<_> = state
+            .txns_by_sender
+            .values()
+            .filter_map(|indexes| indexes.front().copied())
+            .collect();
+        for index in first_txns {
+            let candidate = state.candidate(index);
+            state.queued[index] = true;
+            state.ready.push(Ready(candidate));
+        }
+        state
+    }
+
+    fn txn(&self, index: usize) -> &SignedTransaction {
+        self.txns[index]
+            .as_ref()
+            .expect("Candidates are not placed yet")
+    }
+
+    fn sender_available_at(&self, index: usize) -> usize {
+        self.last_sender_positions
+            .get(&self.txn(index).sender())
+            .map_or(0, |position| {
+                position + self.sender_conflict_window_size + 1
+            })
+    }
+
+    fn module_available_at(&self, index: usize) -> usize {
+        self.modules[index]
+            .as_ref()
+            .and_then(|module| self.last_module_positions.get(module))
+            .map_or(0, |position| {
+                position + self.module_conflict_window_size + 1
+            })
+    }
+
+    fn candidate(&self, index: usize) -> Candidate {
+        Candidate {
+            available_at: self
+                .sender_available_at(index)
+                .max(self.module_available_at(index)),
+            gas_unit_price: self.txn(index).gas_unit_price(),
+            index,
+        }
+    }
+
+    fn wait(&mut self, candidate: Candidate) {
+        self.queued[candidate.index] = true;
+        self.waiting.push(Waiting(candidate));
+    }
+
+    /// Checks the conflicts of a popped candidate again, grouping it with the candidates calling
+    /// its module if its sender conflict allows it. Returns `None` if the candidate is placed, or
+    /// if it isn't the preferred one of its group.
+    fn refresh(&mut self, index: usize) -> Option<Candidate> {
+        self.queued[index] = false;
+        self.num_refreshes += 1;
+        if self.txns[index].is_none() {
+            return None;
+        }
+        let candidate = self.candidate(index);
+        let module = match &self.modules[index] {
+            Some(module) => module,
+            None => return Some(candidate),
+        };
+        if !self.grouped[index] {
+            let module_or_position_available_at =
+                self.module_available_at(index).max(self.position);
+            if self.sender_available_at(index) > module_or_position_available_at {
+                return Some(candidate);
+            }
+            self.grouped[index] = true;
+            self.groups
+                .entry(module.clone())
+                .or_default()
+                .push(Ready(candidate));
+        }
+        let preferred = self.groups.get(module).and_then(|group| group.peek());
+        (preferred.map(|Ready(preferred)| preferred.index) == Some(index)).then_some(candidate)
+    }
+
+    /// Returns the index of the transaction to place at the current position
+    pub fn next_candidate(&mut self) -> Option<usize> {
+        // Candidates whose conflicts ended are ready
+        while self.waiting.peek().map_or(false, |Waiting(candidate)| {
+            candidate.available_at <= self.position
+        }) {
+            let Waiting(candidate) = self.waiting.pop().expect("Peeked candidate");
+            self.ready.push(Ready(candidate));
+        }
+        // The preferred ready candidate, once its conflicts are checked again
+        while let Some(Ready(candidate)) = self.ready.pop() {
+            if let Some(candidate) = self.refresh(candidate.index) {
+                if candidate.available_at <= self.position {
+                    return Some(candidate.index);
+                }
+                self.wait(candidate);
+            }
+        }
+        // Otherwise, the candidate whose conflicts end first
+        while let Some(Waiting(candidate)) = self.waiting.pop() {
+            if let Some(updated) = self.refresh(candidate.index) {
+                if updated.available_at == candidate.available_at {
+                    return Some(candidate.index);
+                }
+                self.wait(updated);
+            }
+        }
+        None
+    }
+
+    /// Places the transaction at the current position, and makes the next transaction of its
+    /// sender a candidate
+    pub fn place(&mut self, index: usize) -> SignedTransaction {
+        let txn = self.txns[index].take().expect("Transaction placed twice");
+        let sender_txns = self
+            .txns_by_sender
+            .get_mut(&txn.sender())
+            .expect("Sender of the transaction must be known");
+        assert_eq!(sender_txns.pop_front(), Some(index));
+        let next_index = sender_txns.front().copied();
+
+        self.last_sender_positions
+            .insert(txn.sender(), self.position);
+        let mut next_preferred_index = None;
+        if let Some(module) = self.modules[index].take() {
+            if self.grouped[index] {
+                let group = self
+                    .groups
+                    .get_mut(&module)
+                    .expect("Group of the transaction must be known");
+                assert_eq!(
+                    group.pop().map(|Ready(preferred)| preferred.index),
+                    Some(index)
+                );
+                next_preferred_index = group.peek().map(|Ready(preferred)| preferred.index);
+            }
+            self.last_module_positions.insert(module, self.position);
+        }
+        self.position += 1;
+
+        for candidate_index in next_index.into_iter().chain(next_preferred_index) {
+            if !self.queued[candidate_index] {
+                let candidate = self.candidate(candidate_index);
+                self.wait(candidate);
+            }
+        }
+        txn
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        fee_and_conflict_aware_shuffler::{
+            entry_function_module, FeeAndConflictAwareShuffler, ShufflingState,
+        },
+        transaction_shuffler::TransactionShuffler,
+    };
+    use aptos_crypto::{ed25519::Ed25519PrivateKey, PrivateKey, SigningKey, Uniform};
+    use aptos_types::{
+        chain_id::ChainId,
+        transaction::{EntryFunction, RawTransaction, SignedTransaction, TransactionPayload},
+    };
+    use move_core_types::{
+        account_address::AccountAddress, identifier::Identifier, language_storage::ModuleId,
+    };
+    use rand::{rngs::OsRng, Rng};
+    use std::{
+        cmp::Reverse,
+        collections::{HashMap, HashSet},
+    };
+
+    fn module(name: &str) -> ModuleId {
+        ModuleId::new(AccountAddress::ONE, Identifier::new(name).unwrap())
+    }
+
+    /// Transactions of a new sender, calling the module with the gas unit prices
+    fn create_signed_transactions(
+        module: &ModuleId,
+        gas_unit_prices: &[u64],
+    ) -> Vec<SignedTransaction> {
+        let private_key = Ed25519PrivateKey::generate_for_testing();
+        let public_key = private_key.public_key();
+        let sender = AccountAddress::random();
+
+        gas_unit_prices
+            .iter()
+            .enumerate()
+            .map(|(i, gas_unit_price)| {
+                let transaction_payload = TransactionPayload::EntryFunction(EntryFunction::new(
+                    module.clone(),
+                    Identifier::new("f").unwrap(),
+                    vec![],
+                    vec![],
+                ));
+                let raw_transaction = RawTransaction::new(
+                    sender,
+                    i as u64,
+                    transaction_payload,
+                    0,
+                    *gas_unit_price,
+                    0,
+                    ChainId::new(10),
+                );
+                SignedTransaction::new(
+                    raw_transaction.clone(),
+                    public_key.clone(),
+                    private_key.sign(&raw_transaction).unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    /// Shuffles by comparing every candidate at each position, as the shuffler is specified
+    fn reference_shuffle(
+        sender_conflict_window_size: usize,
+        module_conflict_window_size: usize,
+        txns: Vec<SignedTransaction>,
+    ) -> Vec<SignedTransaction> {
+        let mut remaining_txns: Vec<_> = txns.into_iter().enumerate().collect();
+        let mut last_sender_positions = HashMap::new();
+        let mut last_module_positions = HashMap::new();
+        let mut shuffled_txns = Vec::new();
+        while !remaining_txns.is_empty() {
+            let position = shuffled_txns.len();
+            let mut senders = HashSet::new();
+            let (next, _) = remaining_txns
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, txn))| senders.insert(txn.sender()))
+                .map(|(next, (index, txn))| {
+                    let sender_available_at = last_sender_positions
+                        .get(&txn.sender())
+                        .map_or(0, |last| last + sender_conflict_window_size + 1);
+                    let module_available_at = entry_function_module(txn)
+                        .and_then(|module| last_module_positions.get(&module))
+                        .map_or(0, |last| last + module_conflict_window_size + 1);
+                    let available_at = sender_available_at.max(module_available_at).max(position);
+                    (next, (available_at, Reverse(txn.gas_unit_price()), *index))
+                })
+                .min_by_key(|(_, key)| *key)
+                .unwrap();
+            let (_, txn) = remaining_txns.remove(next);
+            last_sender_positions.insert(txn.sender(), position);
+            if let Some(module) = entry_function_module(&txn) {
+                last_module_positions.insert(module, position);
+            }
+            shuffled_txns.push(txn);
+        }
+        shuffled_txns
+    }
+
+    #[test]
+    fn test_single_user_txns() {
+        let txns = create_signed_transactions(&module("m"), &[1, 5, 3, 2, 4]);
+        let optimized_txns = FeeAndConflictAwareShuffler::new(3, 3).shuffle(txns.clone());
+        // Assert that ordering is unchanged in case of single sender block, whatever the fees
+        assert_eq!(txns, optimized_txns)
+    }
+
+    #[test]
+    fn test_unique_sender_and_module_txns() {
+        let mut txns = Vec::new();
+        for index in 0..50 {
+            let name = format!("m{}", index);
+            txns.extend(create_signed_transactions(&module(&name), &[1]));
+        }
+        let optimized_txns = FeeAndConflictAwareShuffler::new(10, 10).shuffle(txns.clone());
+        // Assert that the ordering is unchanged without conflicts and with the same fees
+        assert_eq!(txns, optimized_txns)
+    }
+
+    #[test]
+    // M1_1, M1_2, M1_3, M2_1, M2_2, M2_3, each from a different sender
+    // with module_conflict_window_size=1, should return:
+    // M1_1, M2_1, M1_2, M2_2, M1_3, M2_3
+    fn test_hot_module_spreading() {
+        let mut orig_txns = Vec::new();
+        for name in ["m1", "m2"] {
+            for _ in 0..3 {
+                orig_txns.extend(create_signed_transactions(&module(name), &[1]));
+            }
+        }
+        let optimized_txns = FeeAndConflictAwareShuffler::new(0, 1).shuffle(orig_txns.clone());
+        let expected_txns: Vec<_> = [0, 3, 1, 4, 2, 5]
+            .iter()
+            .map(|index| orig_txns[*index].clone())
+            .collect();
+        assert_eq!(optimized_txns, expected_txns);
+    }
+
+    #[test]
+    // S1_1(1), S2_1(1), S3_1(5), S4_1(3), all calling different modules
+    // should return the non-conflicting transactions by decreasing gas unit price:
+    // S3_1, S4_1, S1_1, S2_1
+    fn test_fee_priority() {
+        let mut orig_txns = Vec::new();
+        for (name, gas_unit_price) in [("m1", 1), ("m2", 1), ("m3", 5), ("m4", 3)] {
+            orig_txns.extend(create_signed_transactions(&module(name), &[gas_unit_price]));
+        }
+        let optimized_txns = FeeAndConflictAwareShuffler::new(3, 3).shuffle(orig_txns.clone());
+        let expected_txns: Vec<_> = [2, 3, 0, 1]
+            .iter()
+            .map(|index| orig_txns[*index].clone())
+            .collect();
+        assert_eq!(optimized_txns, expected_txns);
+    }
+
+    #[test]
+    fn test_same_sender_relative_order_and_determinism() {
+        let mut rng = OsRng;
+        let modules: Vec<_> = (0..5).map(|index| module(&format!("m{}", index))).collect();
+        let mut orig_txns = Vec::new();
+        let mut orig_txns_by_sender = HashMap::new();
+        for _ in 0..100 {
+            let gas_unit_prices: Vec<_> = (0..rng.gen_range(1, 20))
+                .map(|_| rng.gen_range(100, 110))
+                .collect();
+            let sender_txns = create_signed_transactions(
+                &modules[rng.gen_range(0, modules.len())],
+                &gas_unit_prices,
+            );
+            orig_txns_by_sender.insert(sender_txns[0].sender(), sender_txns.clone());
+            orig_txns.extend(sender_txns);
+        }
+        let txn_shuffler = FeeAndConflictAwareShuffler::new(32, 4);
+        let optimized_txns = txn_shuffler.shuffle(orig_txns.clone());
+        assert_eq!(orig_txns.len(), optimized_txns.len());
+        assert_eq!(txn_shuffler.shuffle(orig_txns), optimized_txns);
+
+        let mut optimized_txns_by_sender = HashMap::new();
+        for txn in optimized_txns {
+            optimized_txns_by_sender
+                .entry(txn.sender())
+                .or_insert_with(Vec::new)
+                .push(txn);
+        }
+        for (sender, orig_txns) in orig_txns_by_sender {
+            assert_eq!(optimized_txns_by_sender.get(&sender).unwrap(), &orig_txns)
+        }
+    }
+
+    #[test]
+    fn test_matches_reference_shuffle() {
+        let mut rng = OsRng;
+        let modules: Vec<_> = (0..3).map(|index| module(&format!("m{}", index))).collect();
+        let mut orig_txns = Vec::new();
+        for _ in 0..50 {
+            let gas_unit_prices: Vec<_> = (0..rng.gen_range(1, 6))
+                .map(|_| rng.gen_range(1, 4))
+                .collect();
+            orig_txns.extend(create_signed_transactions(
+                &modules[rng.gen_range(0, modules.len())],
+                &gas_unit_prices,
+            ));
+        }
+        for (sender_conflict_window_size, module_conflict_window_size) in
+            [(0, 1), (2, 3), (5, 1), (1, 8)]
+        {
+            let txn_shuffler = FeeAndConflictAwareShuffler::new(
+                sender_conflict_window_size,
+                module_conflict_window_size,
+            );
+            assert_eq!(
+                txn_shuffler.shuffle(orig_txns.clone()),
+                reference_shuffle(
+                    sender_conflict_window_size,
+                    module_conflict_window_size,
+                    orig_txns.clone()
+                )
+            );
+        }
+    }
+
+    /// Shuffles the transactions, also returning the number of times candidates were checked again
+    fn shuffle_and_count_refreshes(
+        txns: Vec<SignedTransaction>,
+    ) -> (Vec<SignedTransaction>, usize) {
+        let mut state = ShufflingState::new(32, 32, txns);
+        let mut shuffled_txns = vec![];
+        while let Some(index) = state.next_candidate() {
+            shuffled_txns.push(state.place(index));
+        }
+        (shuffled_txns, state.num_refreshes)
+    }
+
+    #[test]
+    fn test_hot_module_refreshes() {
+        let hot_module = module("m");
+        let orig_txns: Vec<_> = (0..10_000)
+            .flat_map(|_| create_signed_transactions(&hot_module, &[1]))
+            .collect();
+        let (optimized_txns, num_refreshes) = shuffle_and_count_refreshes(orig_txns.clone());
+        // Checking every waiting transaction again at each position would be quadratic
+        assert!(num_refreshes <= 3 * orig_txns.len());
+        // Assert that the ordering is unchanged when all the transactions conflict
+        assert_eq!(orig_txns, optimized_txns);
+
+        let gas_unit_prices: Vec<_> = (0..100).map(|i| 1 + i % 3).collect();
+        let orig_txns: Vec<_> = (0..100)
+            .flat_map(|_| create_signed_transactions(&hot_module, &gas_unit_prices))
+            .collect();
+        let (optimized_txns, num_refreshes) = shuffle_and_count_refreshes(orig_txns.clone());
+        assert!(num_refreshes <= 3 * orig_txns.len());
+        assert_eq!(optimized_txns.len(), orig_txns.len());
+    }
+
+    #[test]
+    fn test_shuffling_zero_conflict_windows() {
+        let mut orig_txns = Vec::new();
+        for gas_unit_price in [1, 3, 2] {
+            let sender_txns = create_signed_transactions(&module("m"), &[gas_unit_price, 1]);
+            orig_txns.extend(sender_txns);
+        }
+        let optimized_txns = FeeAndConflictAwareShuffler::new(0, 0).shuffle(orig_txns.clone());
+        // Assert that the ordering is unchanged without conflict windows.
+        assert_eq!(orig_txns, optimized_txns);
+    }
+}
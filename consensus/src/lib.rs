@@ -50,6 +50,7 @@ pub mod consensus_provider;
 /// Required by the telemetry service
 pub mod counters;
 mod execution_pipeline;
+mod fee_and_conflict_aware_shuffler;
 /// AptosNet interface.
 pub mod network_interface;
 mod payload_manager;
@@ -62,9 +63,13 @@ use aptos_metrics_core::IntGauge;
 pub use consensusdb::create_checkpoint;
 /// Required by the smoke tests
 pub use consensusdb::CONSENSUS_DB_NAME;
+/// Required by the db tool, to replay proposer election over committed history
+pub use liveness::leader_reputation_simulator;
 pub use quorum_store::quorum_store_db::QUORUM_STORE_DB_NAME;
 #[cfg(feature = "fuzzing")]
 pub use round_manager::round_manager_fuzzing;
+/// Required by the executor benchmark, to measure the effect of shuffling on execution
+pub use transaction_shuffler::{create_transaction_shuffler, TransactionShuffler};
 
 struct IntGaugeGuard {
     gauge: IntGauge,
@@ -24,6 +24,7 @@ use aptos_types::{
     account_config::{new_block_event_key, NewBlockEvent},
     epoch_change::EpochChangeProof,
     epoch_state::EpochState,
+    on_chain_config::LeaderReputationType,
 };
 use std::{
     cmp::max,
@@ -550,6 +551,57 @@ impl ReputationHeuristic for ProposerAndVoterHeuristic {
     }
 }
 
+/// Creates the reputation heuristic of a LeaderReputation config, for an epoch with the given
+/// number of proposers. Also returns the size of the history window the heuristic needs,
+/// whether weights are multiplied by voting power, and the number of previous epochs whose
+/// history is used.
+pub(crate) fn create_reputation_heuristic(
+    author: Author,
+    num_proposers: usize,
+    leader_reputation_type: &LeaderReputationType,
+) -> (Box<dyn ReputationHeuristic>, usize, bool, u32) {
+    match leader_reputation_type {
+        LeaderReputationType::ProposerAndVoter(proposer_and_voter_config)
+        | LeaderReputationType::ProposerAndVoterV2(proposer_and_voter_config) => {
+            let proposer_window_size =
+                num_proposers * proposer_and_voter_config.proposer_window_num_validators_multiplier;
+            let voter_window_size =
+                num_proposers * proposer_and_voter_config.voter_window_num_validators_multiplier;
+            let heuristic: Box<dyn ReputationHeuristic> = Box::new(ProposerAndVoterHeuristic::new(
+                author,
+                proposer_and_voter_config.active_weight,
+                proposer_and_voter_config.inactive_weight,
+                proposer_and_voter_config.failed_weight,
+                proposer_and_voter_config.failure_threshold_percent,
+                voter_window_size,
+                proposer_window_size,
+                leader_reputation_type.use_reputation_window_from_stale_end(),
+            ));
+            (
+                heuristic,
+                max(proposer_window_size, voter_window_size),
+                proposer_and_voter_config.weight_by_voting_power,
+                proposer_and_voter_config.use_history_from_previous_epoch_max_count,
+            )
+        },
+    }
+}
+
+/// Returns the first epoch whose history is used to elect the leaders of `epoch`.
+pub(crate) fn first_epoch_to_consider(
+    epoch: u64,
+    use_history_from_previous_epoch_max_count: u32,
+) -> u64 {
+    // Genesis is epoch=0
+    // First block (after genesis) is epoch=1, and is the only block in that epoch.
+    // It has no votes, so we skip it unless we are in epoch 1, as otherwise it will
+    // skew leader elections for exclude_round number of rounds.
+    max(
+        if epoch == 1 { 1 } else { 2 },
+        epoch.saturating_sub(use_history_from_previous_epoch_max_count as u64),
+    )
+}
+
 /// Committed history based proposer election implementation that could help bias towards
 /// successful leaders to help improve performance.
 pub struct LeaderReputation {
@@ -681,20 +733,18 @@ impl LeaderReputation {
             )
         })
     }
-}
 
-impl ProposerElection for LeaderReputation {
-    fn get_valid_proposer_and_voting_power_participation_ratio(
+    /// Returns the weights the candidates are elected with at the round, i.e. their reputation
+    /// multiplied by their voting power, and the index of the elected candidate.
+    fn elect(
         &self,
         round: Round,
-    ) -> (Author, f64) {
-        let target_round = round.saturating_sub(self.exclude_round);
-        let (sliding_window, root_hash) = self.backend.get_block_metadata(self.epoch, target_round);
-        let voting_power_participation_ratio =
-            self.compute_chain_health_and_add_metrics(&sliding_window, round);
+        sliding_window: &[NewBlockEvent],
+        root_hash: HashValue,
+    ) -> (Vec<u128>, usize) {
         let mut weights =
             self.heuristic
-                .get_weights(self.epoch, &self.epoch_to_proposers, &sliding_window);
+                .get_weights(self.epoch, &self.epoch_to_proposers, sliding_window);
         let proposers = &self.epoch_to_proposers[&self.epoch];
         assert_eq!(weights.len(), proposers.len());
 
@@ -720,7 +770,30 @@ impl ProposerElection for LeaderReputation {
             .concat()
         };
 
-        let chosen_index = choose_index(stake_weights, state);
+        let chosen_index = choose_index(stake_weights.clone(), state);
+        (stake_weights, chosen_index)
+    }
+
+    /// Elects the proposer of the round as `get_valid_proposer` does, without updating the
+    /// chain health metrics, and returns it with the weights of all the candidates.
+    pub(crate) fn simulate_election(&self, round: Round) -> (Vec<u128>, usize) {
+        let target_round = round.saturating_sub(self.exclude_round);
+        let (sliding_window, root_hash) = self.backend.get_block_metadata(self.epoch, target_round);
+        self.elect(round, &sliding_window, root_hash)
+    }
+}
+
+impl ProposerElection for LeaderReputation {
+    fn get_valid_proposer_and_voting_power_participation_ratio(
+        &self,
+        round: Round,
+    ) -> (Author, f64) {
+        let target_round = round.saturating_sub(self.exclude_round);
+        let (sliding_window, root_hash) = self.backend.get_block_metadata(self.epoch, target_round);
+        let voting_power_participation_ratio =
+            self.compute_chain_health_and_add_metrics(&sliding_window, round);
+        let (_, chosen_index) = self.elect(round, &sliding_window, root_hash);
+        let proposers = &self.epoch_to_proposers[&self.epoch];
         (proposers[chosen_index], voting_power_participation_ratio)
     }
 
@@ -0,0 +1,366 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Offline replay of proposer election over the committed block history, to compare proposer
+//! election configs before changing them on chain.
+//!
+//! Every round of the history is elected again with the simulated config, from the reputation
+//! the actual history gives. The replay doesn't model how the history itself would have changed
+//! under the simulated config.
+
+use crate::liveness::{
+    leader_reputation::{
+        create_reputation_heuristic, first_epoch_to_consider, LeaderReputation, MetadataBackend,
+    },
+    proposer_election::ProposerElection,
+    rotating_proposer_election::{choose_leader, RotatingProposer},
+    round_proposer_election::RoundProposer,
+};
+use anyhow::{format_err, Result};
+use aptos_config::config::ConsensusConfig;
+use aptos_consensus_types::common::{Author, Round};
+use aptos_crypto::HashValue;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::NewBlockEvent,
+    on_chain_config::{OnChainConsensusConfig, ProposerElectionType},
+    transaction::Version,
+};
+use serde::Serialize;
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+/// A committed block of the history
+#[derive(Clone, Debug)]
+pub struct HistoricalBlock {
+    /// NewBlockEvent of the block
+    pub event: NewBlockEvent,
+    /// Version of the block metadata transaction
+    pub version: Version,
+    /// Accumulator root hash at `version`, which seeds the election of the configs using it
+    pub root_hash: HashValue,
+}
+
+/// Committed block history to replay proposer election over
+#[derive(Clone, Debug, Default)]
+pub struct BlockHistory {
+    /// Committed blocks, in commit order
+    pub blocks: Vec<HistoricalBlock>,
+    /// Number of blocks at the start of `blocks` preceding the replayed history. They aren't
+    /// replayed, only read by the elections of the first replayed rounds.
+    pub num_preceding_blocks: usize,
+    /// Validators of each epoch of the history and their voting power, in validator set order
+    pub epoch_to_validators: HashMap<u64, Vec<(Author, u64)>>,
+}
+
+impl BlockHistory {
+    /// The blocks whose rounds are replayed
+    pub fn replayed_blocks(&self) -> &[HistoricalBlock] {
+        self.blocks
+            .get(self.num_preceding_blocks..)
+            .unwrap_or_default()
+    }
+
+    fn validators(&self, epoch: u64) -> Result<&[(Author, u64)]> {
+        self.epoch_to_validators
+            .get(&epoch)
+            .filter(|validators| !validators.is_empty())
+            .map(Vec::as_slice)
+            .ok_or_else(|| format_err!("No validator set for epoch {}", epoch))
+    }
+}
+
+/// Leader distribution of a proposer election config over the history
+#[derive(Clone, Debug, Serialize)]
+pub struct SimulationReport {
+    /// Number of rounds replayed
+    pub num_rounds: u64,
+    /// Number of rounds of the history whose proposal wasn't committed
+    pub actual_failed_rounds: u64,
+    /// Number of rounds expected to fail with the simulated config, if each validator failed
+    /// as a leader as often as it did in the history
+    pub expected_failed_rounds: f64,
+    /// Validators, by decreasing expected number of rounds led
+    pub validators: Vec<ValidatorReport>,
+}
+
+/// Leader statistics of a validator over the history
+#[derive(Clone, Debug, Serialize)]
+pub struct ValidatorReport {
+    /// The validator
+    pub author: Author,
+    /// Rounds the validator led in the history, with its proposal committed
+    pub actual_proposals: u64,
+    /// Rounds the validator led in the history, without its proposal committed
+    pub actual_failed_proposals: u64,
+    /// Rounds the simulated config elected the validator in
+    pub elected_rounds: u64,
+    /// Sum of the probabilities of the validator to be elected over all the rounds
+    pub expected_elected_rounds: f64,
+    /// Mean probability of the validator to be elected in a round
+    pub selection_probability: f64,
+}
+
+/// Serves the history preceding a round, as AptosDBBackend serves the latest history
+struct HistoryBackend {
+    blocks: Arc<Vec<HistoricalBlock>>,
+    window_size: usize,
+}
+
+impl MetadataBackend for HistoryBackend {
+    fn get_block_metadata(
+        &self,
+        target_epoch: u64,
+        target_round: Round,
+    ) -> (Vec<NewBlockEvent>, HashValue) {
+        let end = self.blocks.partition_point(|block| {
+            (block.event.epoch(), block.event.round()) <= (target_epoch, target_round)
+        });
+        let start = end.saturating_sub(self.window_size);
+        let root_hash = self.blocks[..end]
+            .last()
+            .map_or_else(HashValue::zero, |block| block.root_hash);
+        (
+            self.blocks[start..end]
+                .iter()
+                .rev()
+                .map(|block| block.event.clone())
+                .collect(),
+            root_hash,
+        )
+    }
+}
+
+enum SimulatedElection {
+    Reputation(LeaderReputation),
+    /// Elections that don't depend on the history, with their candidates
+    Static(Box<dyn ProposerElection>, Vec<Author>),
+}
+
+impl SimulatedElection {
+    fn new(
+        epoch: u64,
+        history: &BlockHistory,
+        blocks: Arc<Vec<HistoricalBlock>>,
+        config: &OnChainConsensusConfig,
+    ) -> Result<Self> {
+        let validators = history.validators(epoch)?;
+        let proposers: Vec<_> = validators.iter().map(|(author, _)| *author).collect();
+        Ok(match config.proposer_election_type() {
+            ProposerElectionType::RotatingProposer(contiguous_rounds) => Self::Static(
+                Box::new(RotatingProposer::new(proposers.clone(), *contiguous_rounds)),
+                proposers,
+            ),
+            ProposerElectionType::FixedProposer(contiguous_rounds) => {
+                let proposer = choose_leader(proposers.clone());
+                Self::Static(
+                    Box::new(RotatingProposer::new(vec![proposer], *contiguous_rounds)),
+                    proposers,
+                )
+            },
+            ProposerElectionType::RoundProposer(round_proposers) => Self::Static(
+                Box::new(RoundProposer::new(round_proposers.clone(), proposers[0])),
+                proposers,
+            ),
+            ProposerElectionType::LeaderReputation(leader_reputation_type) => {
+                // The author of the heuristic only selects the metrics it reports
+                let (heuristic, window_size, weight_by_voting_power, max_previous_epochs) =
+                    create_reputation_heuristic(
+                        AccountAddress::ZERO,
+                        proposers.len(),
+                        leader_reputation_type,
+                    );
+                let voting_powers = if weight_by_voting_power {
+                    validators.iter().map(|(_, power)| *power).collect()
+                } else {
+                    vec![1; proposers.len()]
+                };
+                let epoch_to_proposers = (first_epoch_to_consider(epoch, max_previous_epochs)
+                    ..epoch)
+                    .filter_map(|previous_epoch| {
+                        let validators = history.epoch_to_validators.get(&previous_epoch)?;
+                        Some((
+                            previous_epoch,
+                            validators.iter().map(|(author, _)| *author).collect(),
+                        ))
+                    })
+                    .chain([(epoch, proposers)])
+                    .collect();
+                Self::Reputation(LeaderReputation::new(
+                    epoch,
+                    epoch_to_proposers,
+                    voting_powers,
+                    Box::new(HistoryBackend {
+                        blocks,
+                        window_size,
+                    }),
+                    heuristic,
+                    config.leader_reputation_exclude_round(),
+                    leader_reputation_type.use_root_hash_for_seed(),
+                    ConsensusConfig::default().window_for_chain_health,
+                ))
+            },
+        })
+    }
+
+    /// Returns the probability of each candidate to be elected at the round, and the index of
+    /// the elected candidate
+    fn elect(&self, round: Round) -> (Vec<f64>, usize) {
+        match self {
+            Self::Reputation(election) => {
+                let (weights, index) = election.simulate_election(round);
+                let total_weight: f64 = weights.iter().map(|weight| *weight as f64).sum();
+                let probabilities = weights
+                    .iter()
+                    .map(|weight| *weight as f64 / total_weight)
+                    .collect();
+                (probabilities, index)
+            },
+            Self::Static(election, proposers) => {
+                let proposer = election.get_valid_proposer(round);
+                let index = proposers
+                    .iter()
+                    .position(|candidate| *candidate == proposer)
+                    .expect("Elected proposer must be a candidate");
+                let mut probabilities = vec![0.0; proposers.len()];
+                probabilities[index] = 1.0;
+                (probabilities, index)
+            },
+        }
+    }
+}
+
+fn validator_report(
+    validators: &mut BTreeMap<Author, ValidatorReport>,
+    author: Author,
+) -> &mut ValidatorReport {
+    validators.entry(author).or_insert_with(|| ValidatorReport {
+        author,
+        actual_proposals: 0,
+        actual_failed_proposals: 0,
+        elected_rounds: 0,
+        expected_elected_rounds: 0.0,
+        selection_probability: 0.0,
+    })
+}
+
+fn failure_rate(proposals: u64, failed_proposals: u64) -> Option<f64> {
+    let total = proposals + failed_proposals;
+    (total > 0).then(|| failed_proposals as f64 / total as f64)
+}
+
+/// The first round accounted for by the block: its own round is preceded by one failed round
+/// per failed proposer
+fn first_round(event: &NewBlockEvent) -> Round {
+    event
+        .round()
+        .saturating_sub(event.failed_proposer_indices().len() as u64)
+        .max(1)
+}
+
+/// Number of blocks preceding a round the election of the config reads, in an epoch of
+/// `num_validators` validators. Zero for the elections that don't depend on the history.
+pub fn history_window(config: &OnChainConsensusConfig, num_validators: usize) -> usize {
+    match config.proposer_election_type() {
+        ProposerElectionType::LeaderReputation(leader_reputation_type) => {
+            let (_, window_size, _, _) = create_reputation_heuristic(
+                AccountAddress::ZERO,
+                num_validators,
+                leader_reputation_type,
+            );
+            // The window ends before the excluded rounds, which have at most one block each
+            window_size + config.leader_reputation_exclude_round() as usize
+        },
+        _ => 0,
+    }
+}
+
+/// Replays proposer election with the config over every round of the history
+pub fn simulate_proposer_election(
+    history: &BlockHistory,
+    config: &OnChainConsensusConfig,
+) -> Result<SimulationReport> {
+    let mut validators = BTreeMap::new();
+    let mut actual_failed_rounds = 0;
+    // First and last round of each epoch, including the failed rounds before the first block
+    let mut epoch_rounds: BTreeMap<u64, (Round, Round)> = BTreeMap::new();
+    for block in history.replayed_blocks() {
+        let event = &block.event;
+        let epoch_validators = history.validators(event.epoch())?;
+        // NIL blocks have no proposer, their failed leader is in the failed proposers
+        if event.proposer() != AccountAddress::ZERO {
+            validator_report(&mut validators, event.proposer()).actual_proposals += 1;
+        }
+        for index in event.failed_proposer_indices() {
+            let (author, _) = epoch_validators.get(*index as usize).ok_or_else(|| {
+                format_err!(
+                    "Failed proposer index {} out of the validator set of epoch {}",
+                    index,
+                    event.epoch()
+                )
+            })?;
+            validator_report(&mut validators, *author).actual_failed_proposals += 1;
+            actual_failed_rounds += 1;
+        }
+
+        let first_round = first_round(event);
+        let rounds = epoch_rounds
+            .entry(event.epoch())
+            .or_insert((first_round, event.round()));
+        rounds.0 = rounds.0.min(first_round);
+        rounds.1 = rounds.1.max(event.round());
+    }
+
+    // Validators that never led fail as often as the average leader
+    let default_failure_rate = failure_rate(
+        validators.values().map(|v| v.actual_proposals).sum(),
+        validators.values().map(|v| v.actual_failed_proposals).sum(),
+    )
+    .unwrap_or(0.0);
+    let failure_rates: HashMap<_, _> = validators
+        .values()
+        .filter_map(|v| {
+            Some((
+                v.author,
+                failure_rate(v.actual_proposals, v.actual_failed_proposals)?,
+            ))
+        })
+        .collect();
+
+    let blocks = Arc::new(history.blocks.clone());
+    let mut num_rounds = 0;
+    let mut expected_failed_rounds = 0.0;
+    for (epoch, (first_round, last_round)) in epoch_rounds {
+        let candidates = history.validators(epoch)?;
+        let election = SimulatedElection::new(epoch, history, blocks.clone(), config)?;
+        for round in first_round..=last_round {
+            let (probabilities, index) = election.elect(round);
+            num_rounds += 1;
+            validator_report(&mut validators, candidates[index].0).elected_rounds += 1;
+            for ((author, _), probability) in candidates.iter().zip(probabilities) {
+                validator_report(&mut validators, *author).expected_elected_rounds += probability;
+                expected_failed_rounds +=
+                    probability * failure_rates.get(author).unwrap_or(&default_failure_rate);
+            }
+        }
+    }
+
+    let mut validators: Vec<_> = validators.into_values().collect();
+    for validator in &mut validators {
+        if num_rounds > 0 {
+            validator.selection_probability = validator.expected_elected_rounds / num_rounds as f64;
+        }
+    }
+    validators.sort_by(|a, b| {
+        b.expected_elected_rounds
+            .total_cmp(&a.expected_elected_rounds)
+    });
+    Ok(SimulationReport {
+        num_rounds,
+        actual_failed_rounds,
+        expected_failed_rounds,
+        validators,
+    })
+}
@@ -0,0 +1,158 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::liveness::leader_reputation_simulator::{
+    history_window, simulate_proposer_election, BlockHistory, HistoricalBlock,
+};
+use aptos_bitvec::BitVec;
+use aptos_consensus_types::common::Author;
+use aptos_crypto::HashValue;
+use aptos_types::{
+    account_address::AccountAddress,
+    account_config::NewBlockEvent,
+    on_chain_config::{ConsensusConfigV1, OnChainConsensusConfig, ProposerElectionType},
+};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+const EPOCH: u64 = 2;
+
+/// History of `num_rounds` rounds led in turn by 4 validators, where the last validator always
+/// fails to get its proposals committed.
+fn history_with_failing_validator(num_rounds: u64) -> (Vec<Author>, BlockHistory) {
+    let validators: Vec<Author> = (0..4).map(|_| Author::random()).sorted().collect();
+    let mut votes = BitVec::with_num_bits(validators.len() as u16);
+    for index in 0..validators.len() as u16 {
+        votes.set(index);
+    }
+
+    let mut blocks = vec![];
+    let mut failed_proposers = vec![];
+    for round in 1..=num_rounds {
+        let leader = (round % validators.len() as u64) as usize;
+        if leader == validators.len() - 1 {
+            failed_proposers.push(leader as u64);
+            continue;
+        }
+        blocks.push(HistoricalBlock {
+            event: NewBlockEvent::new(
+                AccountAddress::random(),
+                EPOCH,
+                round,
+                blocks.len() as u64,
+                votes.clone().into(),
+                validators[leader],
+                std::mem::take(&mut failed_proposers),
+                round * 100,
+            ),
+            version: round,
+            root_hash: HashValue::zero(),
+        });
+    }
+    let history = BlockHistory {
+        blocks,
+        num_preceding_blocks: 0,
+        epoch_to_validators: HashMap::from([(
+            EPOCH,
+            validators.iter().map(|author| (*author, 1)).collect(),
+        )]),
+    };
+    (validators, history)
+}
+
+fn config(proposer_election_type: ProposerElectionType) -> OnChainConsensusConfig {
+    OnChainConsensusConfig::V2(ConsensusConfigV1 {
+        proposer_election_type,
+        ..ConsensusConfigV1::default()
+    })
+}
+
+#[test]
+fn test_replay_rotating_proposer() {
+    let (validators, history) = history_with_failing_validator(400);
+    let report =
+        simulate_proposer_election(&history, &config(ProposerElectionType::RotatingProposer(1)))
+            .unwrap();
+
+    // The history is the one of a rotating proposer, so the replay matches it.
+    assert_eq!(report.num_rounds, 400);
+    assert_eq!(report.actual_failed_rounds, 100);
+    assert_eq!(report.expected_failed_rounds, 100.0);
+    for validator in &report.validators {
+        let actual_rounds = validator.actual_proposals + validator.actual_failed_proposals;
+        assert_eq!(validator.elected_rounds, actual_rounds);
+        assert_eq!(validator.expected_elected_rounds, actual_rounds as f64);
+    }
+    let failing = report
+        .validators
+        .iter()
+        .find(|validator| validator.author == validators[3])
+        .unwrap();
+    assert_eq!(failing.actual_proposals, 0);
+    assert_eq!(failing.actual_failed_proposals, 100);
+}
+
+#[test]
+fn test_replay_leader_reputation() {
+    let (validators, history) = history_with_failing_validator(400);
+    let report = simulate_proposer_election(&history, &OnChainConsensusConfig::default()).unwrap();
+
+    assert_eq!(report.num_rounds, 400);
+    assert_eq!(report.actual_failed_rounds, 100);
+    // Once the failures are in the reputation window, the failing validator is rarely elected.
+    assert!(report.expected_failed_rounds < 100.0 / 4.0);
+    let failing = report.validators.last().unwrap();
+    assert_eq!(failing.author, validators[3]);
+    assert!(failing.selection_probability < 0.05);
+    let total_probability: f64 = report
+        .validators
+        .iter()
+        .map(|validator| validator.selection_probability)
+        .sum();
+    assert!((total_probability - 1.0).abs() < 1e-9);
+    assert_eq!(
+        report
+            .validators
+            .iter()
+            .map(|validator| validator.elected_rounds)
+            .sum::<u64>(),
+        400
+    );
+}
+
+#[test]
+fn test_replay_reads_preceding_blocks() {
+    let (_, mut history) = history_with_failing_validator(400);
+    let num_preceding_blocks = history
+        .blocks
+        .iter()
+        .filter(|block| block.event.round() <= 200)
+        .count();
+    let mut truncated = history.clone();
+    truncated.blocks.drain(..num_preceding_blocks);
+    history.num_preceding_blocks = num_preceding_blocks;
+
+    let config = OnChainConsensusConfig::default();
+    assert!(history_window(&config, 4) <= num_preceding_blocks);
+    let report = simulate_proposer_election(&history, &config).unwrap();
+    let truncated_report = simulate_proposer_election(&truncated, &config).unwrap();
+
+    // The preceding blocks aren't replayed...
+    assert_eq!(report.num_rounds, 200);
+    assert_eq!(report.num_rounds, truncated_report.num_rounds);
+    assert_eq!(report.actual_failed_rounds, 50);
+    // ...but the failures in them are in the reputation window from the first replayed round.
+    assert!(report.expected_failed_rounds < truncated_report.expected_failed_rounds);
+
+    assert_eq!(
+        history_window(&config(ProposerElectionType::RotatingProposer(1)), 4),
+        0
+    );
+}
+
+#[test]
+fn test_replay_requires_validator_sets() {
+    let (_, mut history) = history_with_failing_validator(10);
+    history.epoch_to_validators.clear();
+    assert!(simulate_proposer_election(&history, &OnChainConsensusConfig::default()).is_err());
+}
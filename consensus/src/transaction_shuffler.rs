@@ -1,12 +1,17 @@
 // Copyright © Aptos Foundation
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::sender_aware_shuffler::SenderAwareShuffler;
+use crate::{
+    fee_and_conflict_aware_shuffler::FeeAndConflictAwareShuffler,
+    sender_aware_shuffler::SenderAwareShuffler,
+};
 use aptos_logger::info;
 use aptos_types::{
     on_chain_config::{
         TransactionShufflerType,
-        TransactionShufflerType::{DeprecatedSenderAwareV1, NoShuffling, SenderAwareV2},
+        TransactionShufflerType::{
+            DeprecatedSenderAwareV1, FeeAndConflictAwareV1, NoShuffling, SenderAwareV2,
+        },
     },
     transaction::SignedTransaction,
 };
@@ -14,6 +19,7 @@ use std::sync::Arc;
 
 /// Interface to shuffle transactions
 pub trait TransactionShuffler: Send + Sync {
+    /// Reorders the transactions of a block before execution
     fn shuffle(&self, txns: Vec<SignedTransaction>) -> Vec<SignedTransaction>;
 }
 
@@ -26,6 +32,7 @@ impl TransactionShuffler for NoOpShuffler {
     }
 }
 
+/// Creates the transaction shuffler of the on-chain shuffler type
 pub fn create_transaction_shuffler(
     shuffler_type: TransactionShufflerType,
 ) -> Arc<dyn TransactionShuffler> {
@@ -45,5 +52,18 @@ pub fn create_transaction_shuffler(
             );
             Arc::new(SenderAwareShuffler::new(confict_window_size as usize))
         },
+        FeeAndConflictAwareV1 {
+            sender_conflict_window_size,
+            module_conflict_window_size,
+        } => {
+            info!(
+                "Using fee and conflict aware transaction shuffling with sender conflict window size {} and module conflict window size {}",
+                sender_conflict_window_size, module_conflict_window_size
+            );
+            Arc::new(FeeAndConflictAwareShuffler::new(
+                sender_conflict_window_size as usize,
+                module_conflict_window_size as usize,
+            ))
+        },
     }
 }
@@ -0,0 +1,221 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::twins::scenario::{Expectations, NodeRef, RoundSchedule, TwinsScenario};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// The leader and the network partitions of a single round
+type Arrangement = (usize, Vec<Vec<NodeRef>>);
+
+/// Enumerates Twins scenarios systematically, as in the Twins paper: every split of the nodes
+/// and twins into at most `max_partitions` partitions is combined with every leader into a
+/// round arrangement, and the scenarios are all the sequences of `num_rounds` arrangements.
+///
+/// The number of scenarios grows exponentially with the number of rounds, so large suites are
+/// better run through `sample`.
+pub struct ScenarioGenerator {
+    num_nodes: usize,
+    twins: Vec<usize>,
+    num_rounds: usize,
+    max_partitions: usize,
+    leaders: Vec<usize>,
+    duration_ms: u64,
+}
+
+impl ScenarioGenerator {
+    pub fn new(num_nodes: usize, twins: Vec<usize>, num_rounds: usize) -> Self {
+        Self {
+            num_nodes,
+            twins,
+            num_rounds,
+            max_partitions: 2,
+            leaders: (0..num_nodes).collect(),
+            duration_ms: 5_000,
+        }
+    }
+
+    pub fn max_partitions(mut self, max_partitions: usize) -> Self {
+        self.max_partitions = max_partitions;
+        self
+    }
+
+    /// Restricts the leaders of the generated rounds, by node index
+    pub fn leaders(mut self, leaders: Vec<usize>) -> Self {
+        self.leaders = leaders;
+        self
+    }
+
+    pub fn duration_ms(mut self, duration_ms: u64) -> Self {
+        self.duration_ms = duration_ms;
+        self
+    }
+
+    /// Every split of the nodes and twins into at most `max_partitions` partitions. The split
+    /// with a single partition, i.e. a fully connected round, is empty.
+    pub fn partitions(&self) -> Vec<Vec<Vec<NodeRef>>> {
+        let mut splits: Vec<Vec<Vec<NodeRef>>> = vec![vec![]];
+        // Each node either joins one of the partitions of the previous nodes, or starts a new
+        // one, which enumerates every split exactly once.
+        for node in self.scenario(0, vec![]).node_refs() {
+            splits = splits
+                .into_iter()
+                .flat_map(|split| {
+                    let mut next: Vec<_> = (0..split.len())
+                        .map(|index| {
+                            let mut joined = split.clone();
+                            joined[index].push(node);
+                            joined
+                        })
+                        .collect();
+                    if split.len() < self.max_partitions {
+                        let mut started = split;
+                        started.push(vec![node]);
+                        next.push(started);
+                    }
+                    next
+                })
+                .collect();
+        }
+        splits
+            .into_iter()
+            .map(|split| if split.len() > 1 { split } else { vec![] })
+            .collect()
+    }
+
+    fn arrangements(&self) -> Vec<Arrangement> {
+        let partitions = self.partitions();
+        self.leaders
+            .iter()
+            .flat_map(|leader| {
+                partitions
+                    .iter()
+                    .map(move |partitions| (*leader, partitions.clone()))
+            })
+            .collect()
+    }
+
+    /// The number of scenarios `scenarios` enumerates, if it fits in a usize
+    pub fn num_scenarios(&self) -> Option<usize> {
+        self.arrangements()
+            .len()
+            .checked_pow(self.num_rounds as u32)
+    }
+
+    /// Lazily enumerates all the scenarios. Consecutive scenarios differ in their last rounds.
+    pub fn scenarios(&self) -> impl Iterator<Item = TwinsScenario> + '_ {
+        let arrangements = self.arrangements();
+        let mut counter = vec![0; self.num_rounds];
+        let mut done = arrangements.is_empty();
+        let mut index = 0;
+        std::iter::from_fn(move || {
+            if done {
+                return None;
+            }
+            let scenario = self.scenario(
+                index,
+                counter
+                    .iter()
+                    .map(|position| arrangements[*position].clone())
+                    .collect(),
+            );
+            index += 1;
+            // Increments the counter, in base `arrangements.len()`
+            done = true;
+            for position in counter.iter_mut().rev() {
+                *position += 1;
+                if *position < arrangements.len() {
+                    done = false;
+                    break;
+                }
+                *position = 0;
+            }
+            Some(scenario)
+        })
+    }
+
+    /// Picks `count` scenarios at random, reproducibly for a given seed
+    pub fn sample(&self, count: usize, seed: u64) -> Vec<TwinsScenario> {
+        let arrangements = self.arrangements();
+        let mut rng = StdRng::seed_from_u64(seed);
+        (0..count)
+            .map(|index| {
+                let rounds = (0..self.num_rounds)
+                    .map(|_| arrangements[rng.gen_range(0, arrangements.len())].clone())
+                    .collect();
+                self.scenario(index, rounds)
+            })
+            .collect()
+    }
+
+    fn scenario(&self, index: usize, arrangements: Vec<Arrangement>) -> TwinsScenario {
+        TwinsScenario {
+            name: format!("generated-{}", index),
+            num_nodes: self.num_nodes,
+            twins: self.twins.clone(),
+            rounds: arrangements
+                .into_iter()
+                .zip(1..)
+                .map(|((leader, partitions), round)| RoundSchedule {
+                    round,
+                    leader: Some(leader),
+                    partitions,
+                })
+                .collect(),
+            expect: Expectations::default(),
+            duration_ms: self.duration_ms,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::twins::runner::assert_scenario;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_partitions() {
+        // 4 nodes and a twin: S(5, 1) + S(5, 2) splits into at most 2 partitions
+        let generator = ScenarioGenerator::new(4, vec![0], 1);
+        let partitions = generator.partitions();
+        assert_eq!(partitions.len(), 1 + 15);
+        assert_eq!(partitions.iter().collect::<HashSet<_>>().len(), 16);
+        assert_eq!(generator.max_partitions(3).partitions().len(), 1 + 15 + 25);
+    }
+
+    #[test]
+    fn test_scenarios() {
+        let generator = ScenarioGenerator::new(2, vec![], 2);
+        // 2 splits of 2 nodes, times 2 leaders, for each of the 2 rounds
+        assert_eq!(generator.num_scenarios(), Some(16));
+        let scenarios: Vec<_> = generator.scenarios().collect();
+        assert_eq!(scenarios.len(), 16);
+        assert_eq!(
+            scenarios
+                .iter()
+                .map(|scenario| &scenario.rounds)
+                .collect::<HashSet<_>>()
+                .len(),
+            16
+        );
+        for scenario in &scenarios {
+            scenario.validate().unwrap();
+        }
+
+        let generator = ScenarioGenerator::new(4, vec![0], 3).leaders(vec![0, 1]);
+        assert_eq!(generator.num_scenarios(), Some(32 * 32 * 32));
+        assert_eq!(generator.sample(5, 7), generator.sample(5, 7));
+        for scenario in generator.sample(5, 7) {
+            scenario.validate().unwrap();
+        }
+    }
+
+    #[test]
+    #[ignore] // Long running, timing dependent suite
+    fn generated_twins_suite() {
+        let generator = ScenarioGenerator::new(4, vec![0], 4).duration_ms(3_000);
+        for scenario in generator.sample(20, 0) {
+            assert_scenario(&scenario);
+        }
+    }
+}
@@ -3,4 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 mod basic_twins_test;
+mod generator;
+mod runner;
+mod scenario;
 mod twins_node;
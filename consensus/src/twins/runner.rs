@@ -0,0 +1,513 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    network_tests::{NetworkPlayground, TwinId},
+    test_utils::consensus_runtime,
+    twins::{
+        scenario::{NodeRef, RoundSchedule, TwinsScenario},
+        twins_node::{CommittedBlock, SMRNode},
+    },
+};
+use aptos_consensus_types::common::Round;
+use aptos_crypto::HashValue;
+use aptos_types::on_chain_config::ProposerElectionType::RoundProposer;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// A scenario expectation that didn't hold
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Violation {
+    /// Two nodes committed conflicting blocks: `second` committed a block at `round`, which
+    /// isn't in the chain committed by `first`.
+    Safety {
+        round: Round,
+        first: NodeRef,
+        first_block: HashValue,
+        second: NodeRef,
+        second_block: HashValue,
+    },
+    Liveness {
+        node: NodeRef,
+        commits: usize,
+        min_commits: usize,
+    },
+}
+
+impl Violation {
+    pub fn is_safety(&self) -> bool {
+        matches!(self, Violation::Safety { .. })
+    }
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Violation::Safety {
+                round,
+                first,
+                first_block,
+                second,
+                second_block,
+            } => write!(
+                f,
+                "commit safety violated at round {}: {} committed block {} and {} committed \
+                 conflicting block {}",
+                round, first, first_block, second, second_block
+            ),
+            Violation::Liveness {
+                node,
+                commits,
+                min_commits,
+            } => write!(
+                f,
+                "liveness violated: {} committed {} blocks, expected at least {}",
+                node, commits, min_commits
+            ),
+        }
+    }
+}
+
+/// The blocks committed by each node of a scenario run
+pub struct TwinsOutcome {
+    pub commits: HashMap<NodeRef, Vec<CommittedBlock>>,
+}
+
+impl TwinsOutcome {
+    /// Returns the violated expectations of the scenario
+    pub fn violations(&self, scenario: &TwinsScenario) -> Vec<Violation> {
+        let mut violations = vec![];
+        if scenario.expect.safety {
+            violations.extend(check_commit_safety(
+                &scenario
+                    .honest_nodes()
+                    .into_iter()
+                    .map(|node| (node, self.commits[&node].as_slice()))
+                    .collect::<Vec<_>>(),
+            ));
+        }
+        if let Some(liveness) = &scenario.expect.liveness {
+            violations.extend(
+                liveness_nodes(scenario)
+                    .into_iter()
+                    .filter(|node| self.commits[node].len() < liveness.min_commits)
+                    .map(|node| Violation::Liveness {
+                        node,
+                        commits: self.commits[&node].len(),
+                        min_commits: liveness.min_commits,
+                    }),
+            );
+        }
+        violations
+    }
+}
+
+fn liveness_nodes(scenario: &TwinsScenario) -> Vec<NodeRef> {
+    match &scenario.expect.liveness {
+        Some(liveness) if !liveness.nodes.is_empty() => liveness.nodes.clone(),
+        Some(_) => scenario.honest_nodes(),
+        None => vec![],
+    }
+}
+
+/// Checks that the chains committed by the given nodes don't conflict, and returns the first
+/// conflict found otherwise.
+///
+/// A node that commits a block also commits its ancestors, even the ones it only learns through
+/// state sync and never sees in a commit. So a block `b` committed by one node conflicts with
+/// the chain of another node if the other node committed a different block at the round of `b`
+/// or of its parent, or any block in between.
+pub fn check_commit_safety(commits: &[(NodeRef, &[CommittedBlock])]) -> Option<Violation> {
+    let by_round: Vec<BTreeMap<Round, HashValue>> = commits
+        .iter()
+        .map(|(_, blocks)| blocks.iter().map(|block| (block.round, block.id)).collect())
+        .collect();
+
+    for (first, (first_node, first_blocks)) in commits.iter().enumerate() {
+        for (second, (second_node, _)) in commits.iter().enumerate() {
+            if first == second {
+                continue;
+            }
+            for block in first_blocks.iter() {
+                let conflict = by_round[second]
+                    .range(block.parent_round..=block.round)
+                    .find(|(round, id)| {
+                        (**round == block.parent_round && **id != block.parent_id)
+                            || (**round == block.round && **id != block.id)
+                            || (**round > block.parent_round && **round < block.round)
+                    });
+                if let Some((round, id)) = conflict {
+                    return Some(Violation::Safety {
+                        round: *round,
+                        first: *first_node,
+                        first_block: if *round == block.parent_round {
+                            block.parent_id
+                        } else {
+                            block.id
+                        },
+                        second: *second_node,
+                        second_block: *id,
+                    });
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Runs the scenario until its duration elapses, or until its liveness expectation is met
+pub fn run_scenario(scenario: &TwinsScenario) -> TwinsOutcome {
+    let runtime = consensus_runtime();
+    let mut playground = NetworkPlayground::new(runtime.handle().clone());
+    let nodes = SMRNode::start_nodes_with_twins(
+        scenario.num_nodes,
+        &scenario.twins,
+        &mut playground,
+        RoundProposer(HashMap::new()),
+        Some(scenario.leaders()),
+    );
+    let node_refs = scenario.node_refs();
+    let twin_ids: HashMap<NodeRef, TwinId> = node_refs
+        .iter()
+        .zip(nodes.iter())
+        .map(|(node_ref, node)| (*node_ref, node.id))
+        .collect();
+
+    let round_partitions: HashMap<u64, Vec<Vec<TwinId>>> = scenario
+        .rounds
+        .iter()
+        .filter(|schedule| !schedule.partitions.is_empty())
+        .map(|schedule| {
+            let partitions = schedule
+                .partitions
+                .iter()
+                .map(|partition| partition.iter().map(|node| twin_ids[node]).collect())
+                .collect();
+            (schedule.round, partitions)
+        })
+        .collect();
+    assert!(playground.split_network_round(&round_partitions));
+    runtime.spawn(playground.start());
+
+    let expected_live: Vec<_> = liveness_nodes(scenario)
+        .into_iter()
+        .map(|node| &nodes[node_refs.iter().position(|n| *n == node).unwrap()])
+        .collect();
+    let min_commits = scenario
+        .expect
+        .liveness
+        .as_ref()
+        .map_or(0, |liveness| liveness.min_commits);
+    let deadline = Instant::now() + Duration::from_millis(scenario.duration_ms);
+    runtime.block_on(async {
+        while Instant::now() < deadline {
+            if !expected_live.is_empty()
+                && expected_live
+                    .iter()
+                    .all(|node| node.committed_blocks.lock().len() >= min_commits)
+            {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+    });
+
+    TwinsOutcome {
+        commits: node_refs
+            .into_iter()
+            .zip(nodes.iter())
+            .map(|(node_ref, node)| (node_ref, node.committed_blocks.lock().clone()))
+            .collect(),
+    }
+}
+
+/// Number of runs in a row a candidate schedule has to violate commit safety in, to be accepted
+/// while minimizing a schedule
+const MINIMIZE_CONFIRMATION_RUNS: usize = 2;
+
+/// Shrinks the schedule of a scenario that violated commit safety, while `reproduce` still
+/// finds a safety violation. Rounds after the violation are dropped first. Then each remaining
+/// round, from the last one, is dropped, or else reduced by dropping its leader, dropping its
+/// partitions or merging two of its partitions, until no reduction reproduces the violation.
+///
+/// Runs are not deterministic, so a candidate is only accepted if it violates commit safety in
+/// `MINIMIZE_CONFIRMATION_RUNS` runs in a row. The result is still not guaranteed to
+/// reproduce the violation on every run.
+pub fn minimize_schedule<F>(
+    scenario: &TwinsScenario,
+    violation: Violation,
+    mut reproduce: F,
+) -> (TwinsScenario, Violation)
+where
+    F: FnMut(&TwinsScenario) -> Option<Violation>,
+{
+    let mut minimal = scenario.clone();
+    let mut violation = violation;
+    let mut attempt = |candidate: &TwinsScenario| {
+        let mut found = None;
+        for _ in 0..MINIMIZE_CONFIRMATION_RUNS {
+            found = Some(reproduce(candidate).filter(Violation::is_safety)?);
+        }
+        found
+    };
+
+    if let Violation::Safety { round, .. } = violation {
+        let mut candidate = minimal.clone();
+        candidate.rounds.retain(|schedule| schedule.round <= round);
+        if candidate.rounds.len() < minimal.rounds.len() {
+            if let Some(found) = attempt(&candidate) {
+                minimal = candidate;
+                violation = found;
+            }
+        }
+    }
+
+    let mut index = minimal.rounds.len();
+    while index > 0 {
+        index -= 1;
+        let mut candidate = minimal.clone();
+        candidate.rounds.remove(index);
+        if let Some(found) = attempt(&candidate) {
+            minimal = candidate;
+            violation = found;
+            continue;
+        }
+
+        // The round is needed, reduce its schedule instead while the violation reproduces.
+        loop {
+            let reduced = schedule_reductions(&minimal.rounds[index])
+                .into_iter()
+                .find_map(|schedule| {
+                    let mut candidate = minimal.clone();
+                    candidate.rounds[index] = schedule;
+                    attempt(&candidate).map(|found| (candidate, found))
+                });
+            match reduced {
+                Some((candidate, found)) => {
+                    minimal = candidate;
+                    violation = found;
+                },
+                None => break,
+            }
+        }
+    }
+    minimal.name = format!("{}-minimized", scenario.name);
+    (minimal, violation)
+}
+
+/// Smaller variants of a round schedule: without its leader, without its partitions, or with
+/// two of its partitions merged
+fn schedule_reductions(schedule: &RoundSchedule) -> Vec<RoundSchedule> {
+    let mut reductions = vec![];
+    if schedule.leader.is_some() {
+        reductions.push(RoundSchedule {
+            leader: None,
+            ..schedule.clone()
+        });
+    }
+    if !schedule.partitions.is_empty() {
+        reductions.push(RoundSchedule {
+            partitions: vec![],
+            ..schedule.clone()
+        });
+    }
+    // Merging the last two partitions is the same as dropping them.
+    let num_partitions = schedule.partitions.len();
+    if num_partitions > 2 {
+        for first in 0..num_partitions {
+            for second in first + 1..num_partitions {
+                let mut partitions = schedule.partitions.clone();
+                let merged = partitions.remove(second);
+                partitions[first].extend(merged);
+                reductions.push(RoundSchedule {
+                    partitions,
+                    ..schedule.clone()
+                });
+            }
+        }
+    }
+    reductions
+}
+
+/// Runs the scenario and panics if an expectation doesn't hold. Commit safety violations are
+/// reported with a minimal schedule reproducing them.
+pub fn assert_scenario(scenario: &TwinsScenario) {
+    let violations = run_scenario(scenario).violations(scenario);
+    if let Some(violation) = violations.iter().find(|violation| violation.is_safety()) {
+        let (minimal, violation) = minimize_schedule(scenario, violation.clone(), |candidate| {
+            run_scenario(candidate)
+                .violations(candidate)
+                .into_iter()
+                .find(Violation::is_safety)
+        });
+        panic!(
+            "[TwinsTest] Scenario {} failed: {}\nMinimal reproducing schedule:\n{}",
+            scenario.name,
+            violation,
+            minimal.to_yaml()
+        );
+    }
+    assert!(
+        violations.is_empty(),
+        "[TwinsTest] Scenario {} failed: {}",
+        scenario.name,
+        violations
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn block(round: Round, id: u8, parent_round: Round, parent_id: u8) -> CommittedBlock {
+        CommittedBlock {
+            round,
+            id: HashValue::new([id; HashValue::LENGTH]),
+            parent_round,
+            parent_id: HashValue::new([parent_id; HashValue::LENGTH]),
+        }
+    }
+
+    #[test]
+    fn test_check_commit_safety() {
+        let n0 = NodeRef::Node(0);
+        let n1 = NodeRef::Node(1);
+        let chain = [block(1, 1, 0, 0), block(2, 2, 1, 1), block(4, 4, 2, 2)];
+
+        // A node that synced past some blocks and one that is behind are consistent.
+        let synced = [block(4, 4, 2, 2)];
+        let behind = [block(1, 1, 0, 0)];
+        let commits = [
+            (n0, &chain[..]),
+            (n1, &synced[..]),
+            (NodeRef::Node(2), &behind[..]),
+        ];
+        assert_eq!(check_commit_safety(&commits), None);
+
+        let fork = [block(1, 1, 0, 0), block(2, 5, 1, 1)];
+        assert!(matches!(
+            check_commit_safety(&[(n0, &chain[..]), (n1, &fork[..])]),
+            Some(Violation::Safety { round: 2, .. })
+        ));
+
+        // n1 committed round 3, which the chain of n0 skips from round 2 to 4.
+        let skipped = [block(3, 3, 2, 2)];
+        assert!(matches!(
+            check_commit_safety(&[(n0, &chain[..]), (n1, &skipped[..])]),
+            Some(Violation::Safety { round: 3, .. })
+        ));
+    }
+
+    fn minimize_test_scenario() -> TwinsScenario {
+        let mut scenario = TwinsScenario {
+            name: "test".to_string(),
+            num_nodes: 4,
+            twins: vec![0],
+            rounds: (1..=6)
+                .map(|round| RoundSchedule {
+                    round,
+                    leader: Some(0),
+                    partitions: vec![],
+                })
+                .collect(),
+            expect: Default::default(),
+            duration_ms: 0,
+        };
+        scenario.rounds[2].partitions = vec![
+            vec![NodeRef::Node(0), NodeRef::Node(1)],
+            vec![NodeRef::Node(2)],
+            vec![NodeRef::Node(3)],
+            vec![NodeRef::Twin(0)],
+        ];
+        scenario
+    }
+
+    fn safety_violation(round: Round) -> Violation {
+        Violation::Safety {
+            round,
+            first: NodeRef::Node(1),
+            first_block: HashValue::zero(),
+            second: NodeRef::Node(2),
+            second_block: HashValue::zero(),
+        }
+    }
+
+    #[test]
+    fn test_minimize_schedule() {
+        let scenario = minimize_test_scenario();
+
+        // Only the leader of round 2 and the separation of n1 and n2 in round 3 matter.
+        let mut runs = 0;
+        let (minimal, found) = minimize_schedule(&scenario, safety_violation(4), |candidate| {
+            runs += 1;
+            let schedule_of = |round| candidate.rounds.iter().find(|s| s.round == round);
+            let leader_2 = schedule_of(2).and_then(|schedule| schedule.leader);
+            let separated_3 = schedule_of(3).map_or(false, |schedule| {
+                schedule.partitions.iter().any(|partition| {
+                    partition.contains(&NodeRef::Node(1)) != partition.contains(&NodeRef::Node(2))
+                })
+            });
+            (leader_2 == Some(0) && separated_3).then(|| safety_violation(3))
+        });
+        assert_eq!(minimal.rounds, vec![
+            RoundSchedule {
+                round: 2,
+                leader: Some(0),
+                partitions: vec![],
+            },
+            RoundSchedule {
+                round: 3,
+                leader: None,
+                partitions: vec![
+                    vec![
+                        NodeRef::Node(0),
+                        NodeRef::Node(1),
+                        NodeRef::Node(3),
+                        NodeRef::Twin(0)
+                    ],
+                    vec![NodeRef::Node(2)],
+                ],
+            },
+        ]);
+        assert_eq!(found, safety_violation(3));
+        assert_eq!(minimal.name, "test-minimized");
+        // Accepted candidates run twice, rejected ones until their first run without violation.
+        assert_eq!(runs, 20);
+    }
+
+    #[test]
+    fn test_minimize_schedule_reruns_candidates() {
+        let scenario = minimize_test_scenario();
+
+        // Every other run violates safety, so no candidate reproduces twice in a row.
+        let mut runs = 0;
+        let (minimal, found) = minimize_schedule(&scenario, safety_violation(4), |_| {
+            runs += 1;
+            (runs % 2 == 1).then(|| safety_violation(3))
+        });
+        assert_eq!(minimal.rounds, scenario.rounds);
+        assert_eq!(found, safety_violation(4));
+    }
+
+    #[test]
+    fn test_honest_nodes_scenario() {
+        let scenario =
+            TwinsScenario::from_yaml(include_str!("scenarios/honest_nodes.yaml")).unwrap();
+        assert_scenario(&scenario);
+    }
+
+    #[test]
+    #[ignore] // Twins runs with leader twins are timing dependent, as twins_proposer_test
+    fn test_twin_in_minority_scenario() {
+        let scenario =
+            TwinsScenario::from_yaml(include_str!("scenarios/twin_in_minority.yaml")).unwrap();
+        assert_scenario(&scenario);
+    }
+}
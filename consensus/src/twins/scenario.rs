@@ -0,0 +1,313 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! Declarative Twins scenarios.
+//!
+//! A scenario places twins, and schedules the leader and the network partitions of each round.
+//! Nodes are named `n<index>`, and the twin of node `i` is named `t<i>`:
+//!
+//! ```yaml
+//! name: twin-in-minority
+//! num_nodes: 4
+//! twins: [0]
+//! rounds:
+//!   - round: 1
+//!     leader: 0
+//!     partitions: [[n0, n1, n2], [t0, n3]]
+//! expect:
+//!   safety: true
+//!   liveness:
+//!     nodes: [n1, n2]
+//!     min_commits: 1
+//! duration_ms: 10000
+//! ```
+
+use anyhow::{bail, ensure, format_err};
+use aptos_consensus_types::common::Round;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    str::FromStr,
+};
+
+/// A node of a scenario, either a validator or the twin of a validator
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum NodeRef {
+    Node(usize),
+    Twin(usize),
+}
+
+impl fmt::Display for NodeRef {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NodeRef::Node(index) => write!(f, "n{}", index),
+            NodeRef::Twin(index) => write!(f, "t{}", index),
+        }
+    }
+}
+
+impl FromStr for NodeRef {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        let parse_index = |index: &str| {
+            index
+                .parse()
+                .map_err(|_| format_err!("Invalid node name '{}'", s))
+        };
+        if let Some(index) = s.strip_prefix('n') {
+            Ok(NodeRef::Node(parse_index(index)?))
+        } else if let Some(index) = s.strip_prefix('t') {
+            Ok(NodeRef::Twin(parse_index(index)?))
+        } else {
+            bail!("Invalid node name '{}', expected n<index> or t<index>", s)
+        }
+    }
+}
+
+impl TryFrom<String> for NodeRef {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> anyhow::Result<Self> {
+        s.parse()
+    }
+}
+
+impl From<NodeRef> for String {
+    fn from(node: NodeRef) -> Self {
+        node.to_string()
+    }
+}
+
+/// The leader and the network partitions of a round
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoundSchedule {
+    pub round: Round,
+    /// Index of the leader, whose twin, if any, also proposes. Rounds without a leader are led
+    /// by node 0.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub leader: Option<usize>,
+    /// Messages of the round are only delivered within a partition. Either empty, for a fully
+    /// connected round, or placing every node and twin in exactly one partition.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub partitions: Vec<Vec<NodeRef>>,
+}
+
+/// Minimum number of blocks the given nodes have to commit
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Liveness {
+    /// Nodes expected to make progress, all the nodes without a twin if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<NodeRef>,
+    pub min_commits: usize,
+}
+
+/// The properties checked at the end of a scenario
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Expectations {
+    /// Whether the nodes without a twin must never commit conflicting blocks. Scenarios with
+    /// more twins than the network tolerates faults can legitimately break it.
+    #[serde(default = "default_safety")]
+    pub safety: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub liveness: Option<Liveness>,
+}
+
+impl Default for Expectations {
+    fn default() -> Self {
+        Self {
+            safety: default_safety(),
+            liveness: None,
+        }
+    }
+}
+
+fn default_safety() -> bool {
+    true
+}
+
+fn default_duration_ms() -> u64 {
+    10_000
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TwinsScenario {
+    pub name: String,
+    pub num_nodes: usize,
+    /// Indexes of the nodes that get a twin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub twins: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rounds: Vec<RoundSchedule>,
+    #[serde(default)]
+    pub expect: Expectations,
+    /// How long the scenario runs at most. It stops earlier once the liveness expectation is met.
+    #[serde(default = "default_duration_ms")]
+    pub duration_ms: u64,
+}
+
+impl TwinsScenario {
+    pub fn from_yaml(yaml: &str) -> anyhow::Result<Self> {
+        let scenario: Self = serde_yaml::from_str(yaml)?;
+        scenario.validate()?;
+        Ok(scenario)
+    }
+
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(self).expect("Twins scenarios are always serializable")
+    }
+
+    /// All the nodes of the scenario, in the order the harness starts them: the validators
+    /// first, then the twins.
+    pub fn node_refs(&self) -> Vec<NodeRef> {
+        (0..self.num_nodes)
+            .map(NodeRef::Node)
+            .chain(self.twins.iter().map(|index| NodeRef::Twin(*index)))
+            .collect()
+    }
+
+    /// The validators without a twin
+    pub fn honest_nodes(&self) -> Vec<NodeRef> {
+        (0..self.num_nodes)
+            .filter(|index| !self.twins.contains(index))
+            .map(NodeRef::Node)
+            .collect()
+    }
+
+    /// The leader schedule, as the node index of the leader of each round
+    pub fn leaders(&self) -> HashMap<Round, usize> {
+        self.rounds
+            .iter()
+            .filter_map(|schedule| Some((schedule.round, schedule.leader?)))
+            .collect()
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        ensure!(self.num_nodes > 0, "A scenario needs at least one node");
+        let mut twins = HashSet::new();
+        for index in &self.twins {
+            ensure!(
+                *index < self.num_nodes,
+                "Twin of node {} out of {} nodes",
+                index,
+                self.num_nodes
+            );
+            ensure!(twins.insert(*index), "Node {} has several twins", index);
+        }
+
+        let nodes: HashSet<_> = self.node_refs().into_iter().collect();
+        let mut rounds = HashSet::new();
+        for schedule in &self.rounds {
+            ensure!(schedule.round > 0, "Rounds start at 1");
+            ensure!(
+                rounds.insert(schedule.round),
+                "Round {} is scheduled several times",
+                schedule.round
+            );
+            if let Some(leader) = schedule.leader {
+                ensure!(
+                    leader < self.num_nodes,
+                    "Leader {} of round {} out of {} nodes",
+                    leader,
+                    schedule.round,
+                    self.num_nodes
+                );
+            }
+            if schedule.partitions.is_empty() {
+                continue;
+            }
+            let mut placed = HashSet::new();
+            for node in schedule.partitions.iter().flatten() {
+                ensure!(
+                    nodes.contains(node),
+                    "Unknown node {} in round {}",
+                    node,
+                    schedule.round
+                );
+                ensure!(
+                    placed.insert(*node),
+                    "Node {} is in several partitions in round {}",
+                    node,
+                    schedule.round
+                );
+            }
+            ensure!(
+                placed.len() == nodes.len(),
+                "The partitions of round {} don't place every node",
+                schedule.round
+            );
+        }
+
+        if let Some(liveness) = &self.expect.liveness {
+            for node in &liveness.nodes {
+                ensure!(nodes.contains(node), "Unknown node {} in liveness", node);
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCENARIO: &str = r#"
+name: twin-in-minority
+num_nodes: 4
+twins: [0]
+rounds:
+  - round: 1
+    leader: 0
+    partitions: [[n0, n1, n2], [t0, n3]]
+  - round: 2
+    leader: 1
+expect:
+  liveness:
+    nodes: [n1, n2]
+    min_commits: 1
+"#;
+
+    #[test]
+    fn test_parse_scenario() {
+        let scenario = TwinsScenario::from_yaml(SCENARIO).unwrap();
+        let (n0, n1, n2, n3, t0) = (
+            NodeRef::Node(0),
+            NodeRef::Node(1),
+            NodeRef::Node(2),
+            NodeRef::Node(3),
+            NodeRef::Twin(0),
+        );
+        assert_eq!(scenario.node_refs(), vec![n0, n1, n2, n3, t0]);
+        assert_eq!(scenario.honest_nodes(), vec![n1, n2, n3]);
+        assert_eq!(scenario.rounds[0].partitions[1], vec![t0, n3]);
+        assert!(scenario.rounds[1].partitions.is_empty());
+        assert_eq!(scenario.leaders(), HashMap::from([(1, 0), (2, 1)]));
+        assert!(scenario.expect.safety);
+        assert_eq!(scenario.duration_ms, default_duration_ms());
+
+        let round_trip = TwinsScenario::from_yaml(&scenario.to_yaml()).unwrap();
+        assert_eq!(round_trip, scenario);
+    }
+
+    #[test]
+    fn test_invalid_scenarios() {
+        let invalid = [
+            ("[[n0, n1, n2], [t0, n3]]", "[[n0, n1, n2], [t1, n3]]"),
+            ("[[n0, n1, n2], [t0, n3]]", "[[n0, n1, n2], [t0, n3, n1]]"),
+            ("[[n0, n1, n2], [t0, n3]]", "[[n0, n1, n2], [n3]]"),
+            ("[[n0, n1, n2], [t0, n3]]", "[[n0, n1, n2], [t0, x3]]"),
+            ("leader: 1", "leader: 4"),
+            ("twins: [0]", "twins: [0, 0]"),
+        ];
+        for (from, to) in invalid {
+            let yaml = SCENARIO.replacen(from, to, 1);
+            assert!(TwinsScenario::from_yaml(&yaml).is_err(), "{}", yaml);
+        }
+    }
+}
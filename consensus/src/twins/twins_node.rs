@@ -21,8 +21,13 @@ use aptos_config::{
     generator::{self, ValidatorSwarm},
     network_id::{NetworkId, PeerNetworkId},
 };
-use aptos_consensus_types::common::{Author, Round};
+use aptos_consensus_types::{
+    common::{Author, Round},
+    executed_block::ExecutedBlock,
+};
+use aptos_crypto::HashValue;
 use aptos_event_notifications::{ReconfigNotification, ReconfigNotificationListener};
+use aptos_infallible::Mutex;
 use aptos_mempool::mocks::MockSharedMempool;
 use aptos_network::{
     application::interface::{NetworkClient, NetworkServiceEvents},
@@ -52,11 +57,34 @@ use maplit::hashmap;
 use std::{collections::HashMap, iter::FromIterator, sync::Arc};
 use tokio::runtime::Runtime;
 
+/// A block committed by a node, as recorded by the harness
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CommittedBlock {
+    pub round: Round,
+    pub id: HashValue,
+    pub parent_round: Round,
+    pub parent_id: HashValue,
+}
+
+impl From<&ExecutedBlock> for CommittedBlock {
+    fn from(block: &ExecutedBlock) -> Self {
+        Self {
+            round: block.round(),
+            id: block.id(),
+            parent_round: block.quorum_cert().certified_block().round(),
+            parent_id: block.parent_id(),
+        }
+    }
+}
+
 /// Auxiliary struct that is preparing SMR for the test
 pub struct SMRNode {
     pub id: TwinId,
     pub storage: Arc<MockStorage>,
     pub commit_cb_receiver: mpsc::UnboundedReceiver<LedgerInfoWithSignatures>,
+    /// Every block committed by the node, in commit order. Unlike the commit callback, which
+    /// only carries the last block of each commit, this includes all the committed blocks.
+    pub committed_blocks: Arc<Mutex<Vec<CommittedBlock>>>,
     _runtime: Runtime,
     _shared_mempool: MockSharedMempool,
     _state_sync: mpsc::UnboundedReceiver<Vec<SignedTransaction>>,
@@ -166,10 +194,18 @@ impl SMRNode {
         runtime.spawn(epoch_mgr.start(timeout_receiver, network_receiver));
 
         let (commit_cb_sender, commit_cb_receiver) = mpsc::unbounded::<LedgerInfoWithSignatures>();
+        let committed_blocks = Arc::new(Mutex::new(vec![]));
+        let committed_blocks_clone = committed_blocks.clone();
         runtime.spawn(async move {
             loop {
                 let ordered_blocks = ordered_blocks_events.next().await.unwrap();
                 let commit = ordered_blocks.ordered_proof.clone();
+                committed_blocks_clone.lock().extend(
+                    ordered_blocks
+                        .ordered_blocks
+                        .iter()
+                        .map(CommittedBlock::from),
+                );
                 state_computer
                     .commit_to_storage(ordered_blocks)
                     .await
@@ -183,6 +219,7 @@ impl SMRNode {
             id: twin_id,
             _runtime: runtime,
             commit_cb_receiver,
+            committed_blocks,
             storage,
             _shared_mempool: shared_mempool,
             _state_sync: state_sync,
@@ -198,6 +235,26 @@ impl SMRNode {
         round_proposers_idx: Option<HashMap<Round, usize>>,
     ) -> Vec<Self> {
         assert!(num_nodes >= num_twins);
+        let twin_of: Vec<_> = (0..num_twins).collect();
+        Self::start_nodes_with_twins(
+            num_nodes,
+            &twin_of,
+            playground,
+            proposer_type,
+            round_proposers_idx,
+        )
+    }
+
+    /// Starts a given number of nodes, and a twin for each node index in `twin_of`. The twin of
+    /// `twin_of[i]` gets the id `num_nodes + i`.
+    pub fn start_nodes_with_twins(
+        num_nodes: usize,
+        twin_of: &[usize],
+        playground: &mut NetworkPlayground,
+        proposer_type: ProposerElectionType,
+        round_proposers_idx: Option<HashMap<Round, usize>>,
+    ) -> Vec<Self> {
+        assert!(twin_of.iter().all(|index| *index < num_nodes));
         let ValidatorSwarm {
             nodes: mut node_configs,
         } = generator::validator_swarm_for_testing(num_nodes);
@@ -251,8 +308,8 @@ impl SMRNode {
         // We don't add twins to ValidatorSet or round_proposers above
         // because a node with twins should be treated the same at the
         // consensus level
-        for i in 0..num_twins {
-            let twin = node_configs[i].clone();
+        for index in twin_of {
+            let twin = node_configs[*index].clone();
             node_configs.push(twin);
         }
 
@@ -113,6 +113,7 @@ async fn check_view_account_balance(
                 function: "0x1::coin::balance".parse()?,
                 type_arguments: vec!["0x1::aptos_coin::AptosCoin".parse()?],
                 arguments: vec![serde_json::Value::String(address.to_hex_literal())],
+                state_overrides: None,
             },
             None,
         )
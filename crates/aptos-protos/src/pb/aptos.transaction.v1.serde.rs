@@ -555,6 +555,743 @@ impl<'de> serde::Deserialize<'de> for BlockMetadataTransaction {
         deserializer.deserialize_struct("aptos.transaction.v1.BlockMetadataTransaction", FIELDS, GeneratedVisitor)
     }
 }
+impl serde::Serialize for CallTrace {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if self.payload_kind != 0 {
+            len += 1;
+        }
+        if !self.module_id.is_empty() {
+            len += 1;
+        }
+        if !self.func_name.is_empty() {
+            len += 1;
+        }
+        if !self.inputs.is_empty() {
+            len += 1;
+        }
+        if !self.outputs.is_empty() {
+            len += 1;
+        }
+        if !self.type_args.is_empty() {
+            len += 1;
+        }
+        if !self.events.is_empty() {
+            len += 1;
+        }
+        if !self.resources.is_empty() {
+            len += 1;
+        }
+        if self.inclusive_internal_gas != 0 {
+            len += 1;
+        }
+        if self.exclusive_internal_gas != 0 {
+            len += 1;
+        }
+        if self.abort.is_some() {
+            len += 1;
+        }
+        if !self.sub_traces.is_empty() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.CallTrace", len)?;
+        if self.payload_kind != 0 {
+            let v = call_trace::PayloadKind::from_i32(self.payload_kind)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.payload_kind)))?;
+            struct_ser.serialize_field("payloadKind", &v)?;
+        }
+        if !self.module_id.is_empty() {
+            struct_ser.serialize_field("moduleId", &self.module_id)?;
+        }
+        if !self.func_name.is_empty() {
+            struct_ser.serialize_field("funcName", &self.func_name)?;
+        }
+        if !self.inputs.is_empty() {
+            struct_ser.serialize_field("inputs", &self.inputs)?;
+        }
+        if !self.outputs.is_empty() {
+            struct_ser.serialize_field("outputs", &self.outputs)?;
+        }
+        if !self.type_args.is_empty() {
+            struct_ser.serialize_field("typeArgs", &self.type_args)?;
+        }
+        if !self.events.is_empty() {
+            struct_ser.serialize_field("events", &self.events)?;
+        }
+        if !self.resources.is_empty() {
+            struct_ser.serialize_field("resources", &self.resources)?;
+        }
+        if self.inclusive_internal_gas != 0 {
+            struct_ser.serialize_field("inclusiveInternalGas", ToString::to_string(&self.inclusive_internal_gas).as_str())?;
+        }
+        if self.exclusive_internal_gas != 0 {
+            struct_ser.serialize_field("exclusiveInternalGas", ToString::to_string(&self.exclusive_internal_gas).as_str())?;
+        }
+        if let Some(v) = self.abort.as_ref() {
+            struct_ser.serialize_field("abort", v)?;
+        }
+        if !self.sub_traces.is_empty() {
+            struct_ser.serialize_field("subTraces", &self.sub_traces)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for CallTrace {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "payload_kind",
+            "payloadKind",
+            "module_id",
+            "moduleId",
+            "func_name",
+            "funcName",
+            "inputs",
+            "outputs",
+            "type_args",
+            "typeArgs",
+            "events",
+            "resources",
+            "inclusive_internal_gas",
+            "inclusiveInternalGas",
+            "exclusive_internal_gas",
+            "exclusiveInternalGas",
+            "abort",
+            "sub_traces",
+            "subTraces",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            PayloadKind,
+            ModuleId,
+            FuncName,
+            Inputs,
+            Outputs,
+            TypeArgs,
+            Events,
+            Resources,
+            InclusiveInternalGas,
+            ExclusiveInternalGas,
+            Abort,
+            SubTraces,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "payloadKind" | "payload_kind" => Ok(GeneratedField::PayloadKind),
+                            "moduleId" | "module_id" => Ok(GeneratedField::ModuleId),
+                            "funcName" | "func_name" => Ok(GeneratedField::FuncName),
+                            "inputs" => Ok(GeneratedField::Inputs),
+                            "outputs" => Ok(GeneratedField::Outputs),
+                            "typeArgs" | "type_args" => Ok(GeneratedField::TypeArgs),
+                            "events" => Ok(GeneratedField::Events),
+                            "resources" => Ok(GeneratedField::Resources),
+                            "inclusiveInternalGas" | "inclusive_internal_gas" => Ok(GeneratedField::InclusiveInternalGas),
+                            "exclusiveInternalGas" | "exclusive_internal_gas" => Ok(GeneratedField::ExclusiveInternalGas),
+                            "abort" => Ok(GeneratedField::Abort),
+                            "subTraces" | "sub_traces" => Ok(GeneratedField::SubTraces),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = CallTrace;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.CallTrace")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<CallTrace, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut payload_kind__ = None;
+                let mut module_id__ = None;
+                let mut func_name__ = None;
+                let mut inputs__ = None;
+                let mut outputs__ = None;
+                let mut type_args__ = None;
+                let mut events__ = None;
+                let mut resources__ = None;
+                let mut inclusive_internal_gas__ = None;
+                let mut exclusive_internal_gas__ = None;
+                let mut abort__ = None;
+                let mut sub_traces__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::PayloadKind => {
+                            if payload_kind__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("payloadKind"));
+                            }
+                            payload_kind__ = Some(map.next_value::<call_trace::PayloadKind>()? as i32);
+                        }
+                        GeneratedField::ModuleId => {
+                            if module_id__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("moduleId"));
+                            }
+                            module_id__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::FuncName => {
+                            if func_name__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("funcName"));
+                            }
+                            func_name__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Inputs => {
+                            if inputs__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inputs"));
+                            }
+                            inputs__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Outputs => {
+                            if outputs__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("outputs"));
+                            }
+                            outputs__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::TypeArgs => {
+                            if type_args__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("typeArgs"));
+                            }
+                            type_args__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Events => {
+                            if events__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("events"));
+                            }
+                            events__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Resources => {
+                            if resources__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("resources"));
+                            }
+                            resources__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::InclusiveInternalGas => {
+                            if inclusive_internal_gas__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("inclusiveInternalGas"));
+                            }
+                            inclusive_internal_gas__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::ExclusiveInternalGas => {
+                            if exclusive_internal_gas__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("exclusiveInternalGas"));
+                            }
+                            exclusive_internal_gas__ =
+                                Some(map.next_value::<::pbjson::private::NumberDeserialize<_>>()?.0)
+                            ;
+                        }
+                        GeneratedField::Abort => {
+                            if abort__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("abort"));
+                            }
+                            abort__ = map.next_value()?;
+                        }
+                        GeneratedField::SubTraces => {
+                            if sub_traces__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("subTraces"));
+                            }
+                            sub_traces__ = Some(map.next_value()?);
+                        }
+                    }
+                }
+                Ok(CallTrace {
+                    payload_kind: payload_kind__.unwrap_or_default(),
+                    module_id: module_id__.unwrap_or_default(),
+                    func_name: func_name__.unwrap_or_default(),
+                    inputs: inputs__.unwrap_or_default(),
+                    outputs: outputs__.unwrap_or_default(),
+                    type_args: type_args__.unwrap_or_default(),
+                    events: events__.unwrap_or_default(),
+                    resources: resources__.unwrap_or_default(),
+                    inclusive_internal_gas: inclusive_internal_gas__.unwrap_or_default(),
+                    exclusive_internal_gas: exclusive_internal_gas__.unwrap_or_default(),
+                    abort: abort__,
+                    sub_traces: sub_traces__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.CallTrace", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for call_trace::PayloadKind {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "PAYLOAD_KIND_UNSPECIFIED",
+            Self::EntryFunction => "PAYLOAD_KIND_ENTRY_FUNCTION",
+            Self::Script => "PAYLOAD_KIND_SCRIPT",
+            Self::Multisig => "PAYLOAD_KIND_MULTISIG",
+            Self::ModuleBundle => "PAYLOAD_KIND_MODULE_BUNDLE",
+            Self::BlockMetadata => "PAYLOAD_KIND_BLOCK_METADATA",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for call_trace::PayloadKind {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "PAYLOAD_KIND_UNSPECIFIED",
+            "PAYLOAD_KIND_ENTRY_FUNCTION",
+            "PAYLOAD_KIND_SCRIPT",
+            "PAYLOAD_KIND_MULTISIG",
+            "PAYLOAD_KIND_MODULE_BUNDLE",
+            "PAYLOAD_KIND_BLOCK_METADATA",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = call_trace::PayloadKind;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(call_trace::PayloadKind::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(call_trace::PayloadKind::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "PAYLOAD_KIND_UNSPECIFIED" => Ok(call_trace::PayloadKind::Unspecified),
+                    "PAYLOAD_KIND_ENTRY_FUNCTION" => Ok(call_trace::PayloadKind::EntryFunction),
+                    "PAYLOAD_KIND_SCRIPT" => Ok(call_trace::PayloadKind::Script),
+                    "PAYLOAD_KIND_MULTISIG" => Ok(call_trace::PayloadKind::Multisig),
+                    "PAYLOAD_KIND_MODULE_BUNDLE" => Ok(call_trace::PayloadKind::ModuleBundle),
+                    "PAYLOAD_KIND_BLOCK_METADATA" => Ok(call_trace::PayloadKind::BlockMetadata),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
+impl serde::Serialize for CallTraceAbort {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.status_code.is_empty() {
+            len += 1;
+        }
+        if self.abort_code.is_some() {
+            len += 1;
+        }
+        if self.location.is_some() {
+            len += 1;
+        }
+        if self.code_offset.is_some() {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.CallTraceAbort", len)?;
+        if !self.status_code.is_empty() {
+            struct_ser.serialize_field("statusCode", &self.status_code)?;
+        }
+        if let Some(v) = self.abort_code.as_ref() {
+            struct_ser.serialize_field("abortCode", ToString::to_string(&v).as_str())?;
+        }
+        if let Some(v) = self.location.as_ref() {
+            struct_ser.serialize_field("location", v)?;
+        }
+        if let Some(v) = self.code_offset.as_ref() {
+            struct_ser.serialize_field("codeOffset", v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for CallTraceAbort {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "status_code",
+            "statusCode",
+            "abort_code",
+            "abortCode",
+            "location",
+            "code_offset",
+            "codeOffset",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            StatusCode,
+            AbortCode,
+            Location,
+            CodeOffset,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "statusCode" | "status_code" => Ok(GeneratedField::StatusCode),
+                            "abortCode" | "abort_code" => Ok(GeneratedField::AbortCode),
+                            "location" => Ok(GeneratedField::Location),
+                            "codeOffset" | "code_offset" => Ok(GeneratedField::CodeOffset),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = CallTraceAbort;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.CallTraceAbort")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<CallTraceAbort, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut status_code__ = None;
+                let mut abort_code__ = None;
+                let mut location__ = None;
+                let mut code_offset__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::StatusCode => {
+                            if status_code__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("statusCode"));
+                            }
+                            status_code__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::AbortCode => {
+                            if abort_code__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("abortCode"));
+                            }
+                            abort_code__ =
+                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                        GeneratedField::Location => {
+                            if location__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("location"));
+                            }
+                            location__ = map.next_value()?;
+                        }
+                        GeneratedField::CodeOffset => {
+                            if code_offset__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("codeOffset"));
+                            }
+                            code_offset__ =
+                                map.next_value::<::std::option::Option<::pbjson::private::NumberDeserialize<_>>>()?.map(|x| x.0)
+                            ;
+                        }
+                    }
+                }
+                Ok(CallTraceAbort {
+                    status_code: status_code__.unwrap_or_default(),
+                    abort_code: abort_code__,
+                    location: location__,
+                    code_offset: code_offset__,
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.CallTraceAbort", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for CallTraceResource {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+        let mut len = 0;
+        if !self.address.is_empty() {
+            len += 1;
+        }
+        if self.r#type.is_some() {
+            len += 1;
+        }
+        if self.access != 0 {
+            len += 1;
+        }
+        let mut struct_ser = serializer.serialize_struct("aptos.transaction.v1.CallTraceResource", len)?;
+        if !self.address.is_empty() {
+            struct_ser.serialize_field("address", &self.address)?;
+        }
+        if let Some(v) = self.r#type.as_ref() {
+            struct_ser.serialize_field("type", v)?;
+        }
+        if self.access != 0 {
+            let v = call_trace_resource::Access::from_i32(self.access)
+                .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.access)))?;
+            struct_ser.serialize_field("access", &v)?;
+        }
+        struct_ser.end()
+    }
+}
+impl<'de> serde::Deserialize<'de> for CallTraceResource {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "address",
+            "type",
+            "access",
+        ];
+
+        #[allow(clippy::enum_variant_names)]
+        enum GeneratedField {
+            Address,
+            Type,
+            Access,
+        }
+        impl<'de> serde::Deserialize<'de> for GeneratedField {
+            fn deserialize<D>(deserializer: D) -> std::result::Result<GeneratedField, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                struct GeneratedVisitor;
+
+                impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+                    type Value = GeneratedField;
+
+                    fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                        write!(formatter, "expected one of: {:?}", &FIELDS)
+                    }
+
+                    #[allow(unused_variables)]
+                    fn visit_str<E>(self, value: &str) -> std::result::Result<GeneratedField, E>
+                    where
+                        E: serde::de::Error,
+                    {
+                        match value {
+                            "address" => Ok(GeneratedField::Address),
+                            "type" => Ok(GeneratedField::Type),
+                            "access" => Ok(GeneratedField::Access),
+                            _ => Err(serde::de::Error::unknown_field(value, FIELDS)),
+                        }
+                    }
+                }
+                deserializer.deserialize_identifier(GeneratedVisitor)
+            }
+        }
+        struct GeneratedVisitor;
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = CallTraceResource;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                formatter.write_str("struct aptos.transaction.v1.CallTraceResource")
+            }
+
+            fn visit_map<V>(self, mut map: V) -> std::result::Result<CallTraceResource, V::Error>
+                where
+                    V: serde::de::MapAccess<'de>,
+            {
+                let mut address__ = None;
+                let mut r#type__ = None;
+                let mut access__ = None;
+                while let Some(k) = map.next_key()? {
+                    match k {
+                        GeneratedField::Address => {
+                            if address__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("address"));
+                            }
+                            address__ = Some(map.next_value()?);
+                        }
+                        GeneratedField::Type => {
+                            if r#type__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("type"));
+                            }
+                            r#type__ = map.next_value()?;
+                        }
+                        GeneratedField::Access => {
+                            if access__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("access"));
+                            }
+                            access__ = Some(map.next_value::<call_trace_resource::Access>()? as i32);
+                        }
+                    }
+                }
+                Ok(CallTraceResource {
+                    address: address__.unwrap_or_default(),
+                    r#type: r#type__,
+                    access: access__.unwrap_or_default(),
+                })
+            }
+        }
+        deserializer.deserialize_struct("aptos.transaction.v1.CallTraceResource", FIELDS, GeneratedVisitor)
+    }
+}
+impl serde::Serialize for call_trace_resource::Access {
+    #[allow(deprecated)]
+    fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let variant = match self {
+            Self::Unspecified => "ACCESS_UNSPECIFIED",
+            Self::Exists => "ACCESS_EXISTS",
+            Self::Read => "ACCESS_READ",
+            Self::Write => "ACCESS_WRITE",
+            Self::MoveFrom => "ACCESS_MOVE_FROM",
+            Self::MoveTo => "ACCESS_MOVE_TO",
+        };
+        serializer.serialize_str(variant)
+    }
+}
+impl<'de> serde::Deserialize<'de> for call_trace_resource::Access {
+    #[allow(deprecated)]
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        const FIELDS: &[&str] = &[
+            "ACCESS_UNSPECIFIED",
+            "ACCESS_EXISTS",
+            "ACCESS_READ",
+            "ACCESS_WRITE",
+            "ACCESS_MOVE_FROM",
+            "ACCESS_MOVE_TO",
+        ];
+
+        struct GeneratedVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for GeneratedVisitor {
+            type Value = call_trace_resource::Access;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                write!(formatter, "expected one of: {:?}", &FIELDS)
+            }
+
+            fn visit_i64<E>(self, v: i64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(call_trace_resource::Access::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Signed(v), &self)
+                    })
+            }
+
+            fn visit_u64<E>(self, v: u64) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                use std::convert::TryFrom;
+                i32::try_from(v)
+                    .ok()
+                    .and_then(call_trace_resource::Access::from_i32)
+                    .ok_or_else(|| {
+                        serde::de::Error::invalid_value(serde::de::Unexpected::Unsigned(v), &self)
+                    })
+            }
+
+            fn visit_str<E>(self, value: &str) -> std::result::Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                match value {
+                    "ACCESS_UNSPECIFIED" => Ok(call_trace_resource::Access::Unspecified),
+                    "ACCESS_EXISTS" => Ok(call_trace_resource::Access::Exists),
+                    "ACCESS_READ" => Ok(call_trace_resource::Access::Read),
+                    "ACCESS_WRITE" => Ok(call_trace_resource::Access::Write),
+                    "ACCESS_MOVE_FROM" => Ok(call_trace_resource::Access::MoveFrom),
+                    "ACCESS_MOVE_TO" => Ok(call_trace_resource::Access::MoveTo),
+                    _ => Err(serde::de::Error::unknown_variant(value, FIELDS)),
+                }
+            }
+        }
+        deserializer.deserialize_any(GeneratedVisitor)
+    }
+}
 impl serde::Serialize for DeleteModule {
     #[allow(deprecated)]
     fn serialize<S>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error>
@@ -5315,6 +6052,9 @@ impl serde::Serialize for Transaction {
         if self.r#type != 0 {
             len += 1;
         }
+        if self.call_trace.is_some() {
+            len += 1;
+        }
         if self.txn_data.is_some() {
             len += 1;
         }
@@ -5339,6 +6079,9 @@ impl serde::Serialize for Transaction {
                 .ok_or_else(|| serde::ser::Error::custom(format!("Invalid variant {}", self.r#type)))?;
             struct_ser.serialize_field("type", &v)?;
         }
+        if let Some(v) = self.call_trace.as_ref() {
+            struct_ser.serialize_field("callTrace", v)?;
+        }
         if let Some(v) = self.txn_data.as_ref() {
             match v {
                 transaction::TxnData::BlockMetadata(v) => {
@@ -5372,6 +6115,8 @@ impl<'de> serde::Deserialize<'de> for Transaction {
             "block_height",
             "blockHeight",
             "type",
+            "call_trace",
+            "callTrace",
             "block_metadata",
             "blockMetadata",
             "genesis",
@@ -5388,6 +6133,7 @@ impl<'de> serde::Deserialize<'de> for Transaction {
             Epoch,
             BlockHeight,
             Type,
+            CallTrace,
             BlockMetadata,
             Genesis,
             StateCheckpoint,
@@ -5419,6 +6165,7 @@ impl<'de> serde::Deserialize<'de> for Transaction {
                             "epoch" => Ok(GeneratedField::Epoch),
                             "blockHeight" | "block_height" => Ok(GeneratedField::BlockHeight),
                             "type" => Ok(GeneratedField::Type),
+                            "callTrace" | "call_trace" => Ok(GeneratedField::CallTrace),
                             "blockMetadata" | "block_metadata" => Ok(GeneratedField::BlockMetadata),
                             "genesis" => Ok(GeneratedField::Genesis),
                             "stateCheckpoint" | "state_checkpoint" => Ok(GeneratedField::StateCheckpoint),
@@ -5448,6 +6195,7 @@ impl<'de> serde::Deserialize<'de> for Transaction {
                 let mut epoch__ = None;
                 let mut block_height__ = None;
                 let mut r#type__ = None;
+                let mut call_trace__ = None;
                 let mut txn_data__ = None;
                 while let Some(k) = map.next_key()? {
                     match k {
@@ -5493,6 +6241,12 @@ impl<'de> serde::Deserialize<'de> for Transaction {
                             }
                             r#type__ = Some(map.next_value::<transaction::TransactionType>()? as i32);
                         }
+                        GeneratedField::CallTrace => {
+                            if call_trace__.is_some() {
+                                return Err(serde::de::Error::duplicate_field("callTrace"));
+                            }
+                            call_trace__ = map.next_value()?;
+                        }
                         GeneratedField::BlockMetadata => {
                             if txn_data__.is_some() {
                                 return Err(serde::de::Error::duplicate_field("blockMetadata"));
@@ -5530,6 +6284,7 @@ impl<'de> serde::Deserialize<'de> for Transaction {
                     epoch: epoch__.unwrap_or_default(),
                     block_height: block_height__.unwrap_or_default(),
                     r#type: r#type__.unwrap_or_default(),
+                    call_trace: call_trace__,
                     txn_data: txn_data__,
                 })
             }
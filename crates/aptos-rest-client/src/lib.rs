@@ -13,11 +13,12 @@ pub use response::Response;
 pub mod client_builder;
 pub mod state;
 pub mod types;
+pub mod verifying;
 
 pub use crate::client_builder::{AptosBaseUrl, ClientBuilder};
 use crate::{
     aptos::{AptosVersion, Balance},
-    error::RestError,
+    error::{AptosErrorResponse, RestError},
 };
 use anyhow::{anyhow, Result};
 pub use aptos_api_types::{
@@ -27,8 +28,8 @@ use aptos_api_types::{
     deserialize_from_string,
     mime_types::{BCS, BCS_SIGNED_TRANSACTION as BCS_CONTENT_TYPE, JSON},
     AptosError, BcsBlock, Block, GasEstimation, HexEncodedBytes, IndexResponse, MoveModuleId,
-    TransactionData, TransactionOnChainData, TransactionsBatchSubmissionResult, UserTransaction,
-    VersionedEvent, ViewRequest,
+    StateValueWithProofBcs, TransactionData, TransactionOnChainData, TransactionWithProofBcs,
+    TransactionsBatchSubmissionResult, UserTransaction, VersionedEvent, ViewRequest,
 };
 use aptos_crypto::HashValue;
 use aptos_logger::{debug, info, sample, sample::SampleRate};
@@ -36,6 +37,7 @@ use aptos_types::{
     account_address::AccountAddress,
     account_config::{AccountResource, CoinStoreResource, NewBlockEvent, CORE_CODE_ADDRESS},
     contract_event::EventWithVersion,
+    state_proof::StateProof,
     state_store::state_key::StateKey,
     transaction::SignedTransaction,
 };
@@ -58,6 +60,8 @@ const DEFAULT_INTERVAL_MS: u64 = 1000;
 static DEFAULT_MAX_WAIT_DURATION: Duration = Duration::from_millis(DEFAULT_MAX_WAIT_MS);
 static DEFAULT_INTERVAL_DURATION: Duration = Duration::from_millis(DEFAULT_INTERVAL_MS);
 const DEFAULT_MAX_SERVER_LAG_WAIT_DURATION: Duration = Duration::from_secs(60);
+/// How long the node holds a wait by hash request, below the default request timeout
+const WAIT_BY_HASH_TIMEOUT: Duration = Duration::from_secs(5);
 const RESOURCES_PER_CALL_PAGINATION: u64 = 9999;
 const MODULES_PER_CALL_PAGINATION: u64 = 1000;
 const X_APTOS_SDK_HEADER_VALUE: &str = concat!("aptos-rust-sdk/", env!("CARGO_PKG_VERSION"));
@@ -689,7 +693,25 @@ impl Client {
             max_server_lag_wait,
             timeout_from_call,
             |hash| async move {
-                let resp = self.get_transaction_by_hash_inner(hash).await?;
+                let resp = self.wait_transaction_by_hash_inner(hash).await?;
+                let resp = match resp.status() {
+                    // The node is at its limit of waiting requests, doesn't serve the wait
+                    // endpoint, or doesn't know the transaction. Look it up instead.
+                    StatusCode::SERVICE_UNAVAILABLE | StatusCode::NOT_FOUND => {
+                        self.get_transaction_by_hash_inner(hash).await?
+                    },
+                    StatusCode::GONE => {
+                        // The transaction expired while pending, which the caller detects
+                        // from the ledger timestamp.
+                        return match parse_error(resp).await {
+                            RestError::Api(AptosErrorResponse {
+                                state: Some(state), ..
+                            }) => Ok(WaitForTransactionResult::Pending(state)),
+                            error => Ok(WaitForTransactionResult::NotFound(error)),
+                        };
+                    },
+                    _ => resp,
+                };
                 if resp.status() != StatusCode::NOT_FOUND {
                     let txn_resp: Response<Transaction> = self.json(resp).await?;
                     let (transaction, state) = txn_resp.into_parts();
@@ -848,6 +870,22 @@ impl Client {
         Ok(self.inner.get(url).send().await?)
     }
 
+    async fn wait_transaction_by_hash_inner(
+        &self,
+        hash: HashValue,
+    ) -> AptosResult<reqwest::Response> {
+        let url = self.build_path(&format!(
+            "transactions/wait_by_hash/{}",
+            hash.to_hex_literal()
+        ))?;
+        Ok(self
+            .inner
+            .get(url)
+            .query(&[("timeout_secs", WAIT_BY_HASH_TIMEOUT.as_secs())])
+            .send()
+            .await?)
+    }
+
     pub async fn get_transaction_by_version(
         &self,
         version: u64,
@@ -1310,6 +1348,53 @@ impl Client {
         Ok(response.map(|inner| inner.to_vec()))
     }
 
+    /// Gets the latest ledger info with signatures, and the epoch changes since `known_version`
+    pub async fn get_ledger_info_with_proof_bcs(
+        &self,
+        known_version: u64,
+    ) -> AptosResult<Response<StateProof>> {
+        let url = self.build_path(&format!(
+            "proofs/ledger_info?known_version={}",
+            known_version
+        ))?;
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
+    /// Gets a state value from the latest state snapshot, with the proofs to verify it from
+    /// `known_version`
+    pub async fn get_state_value_with_proof_bcs(
+        &self,
+        state_key: &StateKey,
+        known_version: u64,
+    ) -> AptosResult<Response<StateValueWithProofBcs>> {
+        let url = self.build_path(&format!(
+            "proofs/state_value?known_version={}",
+            known_version
+        ))?;
+        let data = json!({
+            "key": hex::encode(bcs::to_bytes(state_key)?),
+        });
+
+        let response = self.post_bcs(url, data).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
+    /// Gets a transaction and its events by version, with the proofs to verify them from
+    /// `known_version`
+    pub async fn get_transaction_with_proof_bcs(
+        &self,
+        version: u64,
+        known_version: u64,
+    ) -> AptosResult<Response<TransactionWithProofBcs>> {
+        let url = self.build_path(&format!(
+            "proofs/transactions/by_version/{}?known_version={}",
+            version, known_version
+        ))?;
+        let response = self.get_bcs(url).await?;
+        Ok(response.and_then(|inner| bcs::from_bytes(&inner))?)
+    }
+
     pub async fn get_account(&self, address: AccountAddress) -> AptosResult<Response<Account>> {
         let url = self.build_path(&format!("accounts/{}", address.to_hex()))?;
         let response = self.inner.get(url).send().await?;
@@ -0,0 +1,181 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{error::RestError, AptosResult, Client, Response};
+use anyhow::{anyhow, Context};
+use aptos_api_types::StateValueWithProofBcs;
+use aptos_crypto::hash::CryptoHash;
+use aptos_types::{
+    access_path::AccessPath,
+    account_address::AccountAddress,
+    ledger_info::LedgerInfoWithSignatures,
+    state_proof::StateProof,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{TransactionWithProof, Version},
+    trusted_state::TrustedState,
+    waypoint::Waypoint,
+};
+use move_core_types::language_storage::StructTag;
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+
+/// A client that verifies the data it reads against a trusted waypoint
+///
+/// Each response is checked against the latest ledger info returned with it, which is itself
+/// verified through the epoch changes since the last verified ledger info. The trusted state
+/// ratchets forward with every response, so that only the epoch changes since the previous
+/// request have to be verified.
+pub struct VerifyingClient {
+    inner: Client,
+    trusted_state: Mutex<TrustedState>,
+}
+
+impl VerifyingClient {
+    /// Creates a client trusting the epoch waypoint, e.g. the genesis waypoint
+    pub fn new(inner: Client, waypoint: Waypoint) -> Self {
+        Self::new_with_trusted_state(inner, TrustedState::from_epoch_waypoint(waypoint))
+    }
+
+    /// Creates a client resuming from a trusted state, e.g. from a previous run
+    pub fn new_with_trusted_state(inner: Client, trusted_state: TrustedState) -> Self {
+        Self {
+            inner,
+            trusted_state: Mutex::new(trusted_state),
+        }
+    }
+
+    /// The underlying client, which doesn't verify anything
+    pub fn inner(&self) -> &Client {
+        &self.inner
+    }
+
+    /// The latest verified state
+    pub async fn trusted_state(&self) -> TrustedState {
+        self.trusted_state.lock().await.clone()
+    }
+
+    /// Ratchets the trusted state to the latest ledger info of the node, and returns it
+    pub async fn get_ledger_info(&self) -> AptosResult<Response<LedgerInfoWithSignatures>> {
+        let mut trusted_state = self.trusted_state.lock().await;
+        loop {
+            let (state_proof, state) = self
+                .inner
+                .get_ledger_info_with_proof_bcs(trusted_state.version())
+                .await?
+                .into_parts();
+            if ratchet(&mut trusted_state, &state_proof)? {
+                return Ok(Response::new(
+                    state_proof.latest_ledger_info_w_sigs().clone(),
+                    state,
+                ));
+            }
+        }
+    }
+
+    /// Gets a resource from the latest state snapshot, `None` if it doesn't exist
+    pub async fn get_account_resource_bcs<T: DeserializeOwned>(
+        &self,
+        address: AccountAddress,
+        resource_type: StructTag,
+    ) -> AptosResult<Response<Option<T>>> {
+        let access_path = AccessPath::resource_access_path(address, resource_type)?;
+        let response = self
+            .get_state_value(&StateKey::access_path(access_path))
+            .await?;
+        response.and_then(|state_value| {
+            state_value
+                .map(|state_value| bcs::from_bytes(state_value.bytes()))
+                .transpose()
+                .map_err(RestError::from)
+        })
+    }
+
+    /// Gets a state value from the latest state snapshot, `None` if it doesn't exist
+    pub async fn get_state_value(
+        &self,
+        state_key: &StateKey,
+    ) -> AptosResult<Response<Option<StateValue>>> {
+        let mut trusted_state = self.trusted_state.lock().await;
+        loop {
+            let (state_value_with_proof, state) = self
+                .inner
+                .get_state_value_with_proof_bcs(state_key, trusted_state.version())
+                .await?
+                .into_parts();
+            if ratchet(&mut trusted_state, &state_value_with_proof.state_proof)? {
+                verify_state_value(state_key, &state_value_with_proof)?;
+                return Ok(Response::new(state_value_with_proof.state_value, state));
+            }
+        }
+    }
+
+    /// Gets a transaction and its events by version
+    pub async fn get_transaction_by_version(
+        &self,
+        version: Version,
+    ) -> AptosResult<Response<TransactionWithProof>> {
+        let mut trusted_state = self.trusted_state.lock().await;
+        loop {
+            let (transaction_with_proof, state) = self
+                .inner
+                .get_transaction_with_proof_bcs(version, trusted_state.version())
+                .await?
+                .into_parts();
+            let state_proof = &transaction_with_proof.state_proof;
+            if ratchet(&mut trusted_state, state_proof)? {
+                let transaction_with_proof = transaction_with_proof.transaction_with_proof;
+                if transaction_with_proof.events.is_none() {
+                    return Err(anyhow!("Events of transaction {} are missing", version).into());
+                }
+                transaction_with_proof
+                    .verify(state_proof.latest_ledger_info(), version)
+                    .context("Failed to verify the transaction")?;
+                return Ok(Response::new(transaction_with_proof, state));
+            }
+        }
+    }
+}
+
+/// Verifies the state proof and ratchets the trusted state with it.
+///
+/// Returns whether the latest ledger info of the proof is now trusted. It isn't when the proof
+/// only covers part of the epoch changes since the trusted state, in which case the request has
+/// to be sent again from the ratcheted trusted state.
+fn ratchet(trusted_state: &mut TrustedState, state_proof: &StateProof) -> AptosResult<bool> {
+    let change = trusted_state
+        .verify_and_ratchet(state_proof)
+        .context("Failed to verify the state proof")?;
+    if let Some(new_state) = change.new_state() {
+        *trusted_state = new_state;
+    }
+    Ok(trusted_state.version() == state_proof.latest_ledger_info().version())
+}
+
+/// Verifies the state value against the latest ledger info of the proof, which must be trusted
+fn verify_state_value(
+    state_key: &StateKey,
+    state_value_with_proof: &StateValueWithProofBcs,
+) -> anyhow::Result<()> {
+    let StateValueWithProofBcs {
+        version,
+        state_value,
+        state_value_proof,
+        transaction_info_with_proof,
+        state_proof,
+    } = state_value_with_proof;
+
+    transaction_info_with_proof
+        .verify(state_proof.latest_ledger_info(), *version)
+        .context("Failed to verify the transaction info")?;
+    let state_checkpoint_hash = transaction_info_with_proof
+        .transaction_info()
+        .state_checkpoint_hash()
+        .ok_or_else(|| anyhow!("Version {} is not a state checkpoint", version))?;
+    state_value_proof
+        .verify(
+            state_checkpoint_hash,
+            state_key.hash(),
+            state_value.as_ref(),
+        )
+        .context("Failed to verify the state value")
+}
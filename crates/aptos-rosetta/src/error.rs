@@ -49,6 +49,8 @@ pub enum ApiError {
     SequenceNumberTooOld(Option<String>),
     VmError(Option<String>),
     MempoolIsFull(Option<String>),
+    TooManyConnections(Option<String>),
+    TransactionExpired(Option<String>),
 }
 
 impl std::fmt::Display for ApiError {
@@ -97,6 +99,8 @@ impl ApiError {
             SequenceNumberTooOld(None),
             VmError(None),
             MempoolIsFull(None),
+            TooManyConnections(None),
+            TransactionExpired(None),
         ]
     }
 
@@ -137,6 +141,8 @@ impl ApiError {
             MempoolIsFull(_) => 32,
             CoinTypeFailedToBeFetched(_) => 33,
             StateValueNotFound(_) => 34,
+            TooManyConnections(_) => 35,
+            TransactionExpired(_) => 36,
         }
     }
 
@@ -147,6 +153,7 @@ impl ApiError {
             AccountNotFound(_)
                 | BlockNotFound(_)
                 | MempoolIsFull(_)
+                | TooManyConnections(_)
                 | GasEstimationFailed(_)
                 | CoinTypeFailedToBeFetched(_)
         )
@@ -194,6 +201,8 @@ impl ApiError {
             ApiError::VmError(_) => "Transaction submission failed due to VM error",
             ApiError::MempoolIsFull(_) => "Mempool is full all accounts",
             ApiError::GasEstimationFailed(_) => "Gas estimation failed",
+            ApiError::TooManyConnections(_) => "Too many connections to the node, please retry",
+            ApiError::TransactionExpired(_) => "Transaction expired before it was committed",
         }
     }
 
@@ -222,6 +231,8 @@ impl ApiError {
             ApiError::SequenceNumberTooOld(inner) => inner,
             ApiError::VmError(inner) => inner,
             ApiError::MempoolIsFull(inner) => inner,
+            ApiError::TooManyConnections(inner) => inner,
+            ApiError::TransactionExpired(inner) => inner,
             ApiError::GasEstimationFailed(inner) => inner,
             ApiError::MaxGasFeeTooLow(inner) => inner,
             _ => None,
@@ -282,6 +293,9 @@ impl From<RestError> for ApiError {
                 },
                 AptosErrorCode::VersionPruned => ApiError::VersionPruned(Some(err.error.message)),
                 AptosErrorCode::BlockPruned => ApiError::BlockPruned(Some(err.error.message)),
+                AptosErrorCode::TransactionExpired => {
+                    ApiError::TransactionExpired(Some(err.error.message))
+                },
                 AptosErrorCode::InvalidInput => ApiError::InvalidInput(Some(err.error.message)),
                 AptosErrorCode::InvalidTransactionUpdate => {
                     ApiError::InvalidInput(Some(err.error.message))
@@ -294,6 +308,9 @@ impl From<RestError> for ApiError {
                     ApiError::InternalError(Some(err.error.message))
                 },
                 AptosErrorCode::MempoolIsFull => ApiError::MempoolIsFull(Some(err.error.message)),
+                AptosErrorCode::TooManyConnections => {
+                    ApiError::TooManyConnections(Some(err.error.message))
+                },
                 AptosErrorCode::WebFrameworkError => {
                     ApiError::InternalError(Some(err.error.message))
                 },
@@ -349,6 +349,7 @@ pub async fn get_delegation_stake_balances(
                     serde_json::Value::String(pool_address.to_string()),
                     serde_json::Value::String(owner_address.to_string()),
                 ],
+                state_overrides: None,
             },
             Some(version),
         )
@@ -364,6 +365,7 @@ pub async fn get_delegation_stake_balances(
                 function: STAKE_GET_LOCKUP_SECS_FUNCTION.clone(),
                 type_arguments: vec![],
                 arguments: vec![serde_json::Value::String(pool_address.to_string())],
+                state_overrides: None,
             },
             Some(version),
         )
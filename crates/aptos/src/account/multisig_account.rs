@@ -187,6 +187,7 @@ impl CliCommand<serde_json::Value> for VerifyProposal {
                             .to_string(),
                     ),
                 ],
+                state_overrides: None,
             })
             .await?[0];
         // Get expected multisig transaction payload hash hex from provided entry function.
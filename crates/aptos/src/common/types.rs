@@ -23,7 +23,7 @@ use aptos_crypto::{
     x25519, PrivateKey, ValidCryptoMaterial, ValidCryptoMaterialStringExt,
 };
 use aptos_debugger::AptosDebugger;
-use aptos_gas_profiling::FrameName;
+use aptos_gas_profiling::{FrameName, GasProfileFormat};
 use aptos_global_constants::adjust_gas_headroom;
 use aptos_keygen::KeyGen;
 use aptos_logger::Level;
@@ -1524,9 +1524,16 @@ pub struct TransactionOptions {
     pub(crate) prompt_options: PromptOptions,
 
     /// If this option is set, simulate the transaction locally using the debugger and generate
-    /// flamegraphs that reflect the gas usage.
+    /// a profile of the gas usage, as flamegraphs by default.
     #[clap(long)]
     pub(crate) profile_gas: bool,
+
+    /// Format of the gas profile generated by `--profile-gas`
+    ///
+    /// `flamegraph` generates SVG flamegraphs, `json` the full gas log as versioned JSON and
+    /// `speedscope` a profile that can be opened in https://www.speedscope.app
+    #[clap(long, default_value_t = GasProfileFormat::Flamegraph)]
+    pub(crate) profile_gas_format: GasProfileFormat,
 }
 
 impl TransactionOptions {
@@ -1796,8 +1803,13 @@ impl TransactionOptions {
 
         // Execute the transaction using the debugger
         let debugger = AptosDebugger::rest_client(client).unwrap();
-        let res = debugger.execute_transaction_at_version_with_gas_profiler(version, transaction);
-        let (vm_status, output, gas_log) = res.map_err(|err| {
+        let res = debugger.execute_transaction_at_version_with_gas_profiler_in_format(
+            version,
+            transaction,
+            self.profile_gas_format,
+            &format!("Transaction {}", hash),
+        );
+        let (vm_status, output, gas_log, files) = res.map_err(|err| {
             CliError::UnexpectedError(format!("failed to simulate txn with gas profiler: {}", err))
         })?;
 
@@ -1836,55 +1848,27 @@ impl TransactionOptions {
             };
         }
 
-        // Generate the execution & IO flamegraph.
+        // Save the gas profile generated in the requested format.
         println!();
-        match gas_log
-            .exec_io
-            .to_flamegraph(format!("Transaction {} -- Execution & IO", hash))?
-        {
-            Some(graph_bytes) => {
-                create_dir!();
-                let graph_file_path = Path::join(dir, format!("{}.exec_io.svg", raw_file_name));
-                std::fs::write(&graph_file_path, graph_bytes).map_err(|err| {
-                    CliError::UnexpectedError(format!(
-                        "Failed to write flamegraph to file {} : {:?}",
-                        graph_file_path.display(),
-                        err
-                    ))
-                })?;
-                println!(
-                    "Execution & IO Gas flamegraph saved to {}",
-                    graph_file_path.display()
-                );
-            },
-            None => {
-                println!("Skipped generating execution & IO flamegraph");
-            },
-        }
-
-        // Generate the storage fee flamegraph.
-        match gas_log
-            .storage
-            .to_flamegraph(format!("Transaction {} -- Storage Fee", hash))?
-        {
-            Some(graph_bytes) => {
-                create_dir!();
-                let graph_file_path = Path::join(dir, format!("{}.storage.svg", raw_file_name));
-                std::fs::write(&graph_file_path, graph_bytes).map_err(|err| {
-                    CliError::UnexpectedError(format!(
-                        "Failed to write flamegraph to file {} : {:?}",
-                        graph_file_path.display(),
-                        err
-                    ))
-                })?;
-                println!(
-                    "Storage fee flamegraph saved to {}",
-                    graph_file_path.display()
-                );
-            },
-            None => {
-                println!("Skipped generating storage fee flamegraph");
-            },
+        for file in files {
+            let content = match file.content {
+                Some(content) => content,
+                None => {
+                    println!("Skipped generating {}", file.name);
+                    continue;
+                },
+            };
+            create_dir!();
+            let file_path = Path::join(dir, format!("{}.{}", raw_file_name, file.suffix));
+            std::fs::write(&file_path, content).map_err(|err| {
+                CliError::UnexpectedError(format!(
+                    "Failed to write {} to file {} : {:?}",
+                    file.name,
+                    file_path.display(),
+                    err
+                ))
+            })?;
+            println!("{} saved to {}", file.description, file_path.display());
         }
 
         println!();
@@ -216,6 +216,7 @@ async fn is_partial_governance_voting_enabled_for_delegation_pool(
                     .unwrap(),
                 type_arguments: vec![],
                 arguments: vec![serde_json::Value::String(pool_address.to_string())],
+                state_overrides: None,
             },
             None,
         )
@@ -247,6 +248,7 @@ async fn get_remaining_voting_power(
                     serde_json::Value::String(voter_address.to_string()),
                     serde_json::Value::String(proposal_id.to_string()),
                 ],
+                state_overrides: None,
             },
             None,
         )
@@ -614,6 +614,7 @@ impl SubmitVote {
                     serde_json::Value::String("0x1".to_string()),
                     serde_json::Value::String(proposal_id.to_string()),
                 ],
+                state_overrides: None,
             })
             .await?[0]
             .as_bool()
@@ -639,6 +640,7 @@ impl SubmitVote {
                         serde_json::Value::String(pool_address.to_string()),
                         serde_json::Value::String(proposal_id.to_string()),
                     ],
+                    state_overrides: None,
                 })
                 .await?[0]
                 .as_str()
@@ -0,0 +1,108 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::common::types::{CliCommand, CliError, CliTypedResult, ProfileOptions, RestOptions};
+use aptos_debugger::AptosDebugger;
+use aptos_gas_profiling::{GasLogDiff, JsonGasLog};
+use aptos_types::transaction::Version;
+use async_trait::async_trait;
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+/// Compare the gas usage of two transactions
+///
+/// Each side is either a gas log generated with `--profile-gas --profile-gas-format json`, or
+/// a committed transaction, which is replayed locally with the gas profiler.
+///
+/// Reports the changes of the totals, call paths, functions, instructions, storage reads &
+/// writes and storage fees, including new and removed items. For example, to compare a
+/// transaction against the same call made after upgrading a package:
+///
+/// aptos move gas-diff --before-version 123456 --after gas-profiling/txn-6a1c...gas_log.json
+#[derive(Parser)]
+pub struct GasDiff {
+    /// Gas log to compare against
+    #[clap(
+        long,
+        required_unless_present = "before_version",
+        conflicts_with = "before_version"
+    )]
+    pub(crate) before: Option<PathBuf>,
+
+    /// Version of a committed transaction to compare against
+    #[clap(long)]
+    pub(crate) before_version: Option<Version>,
+
+    /// Gas log to compare
+    #[clap(
+        long,
+        required_unless_present = "after_version",
+        conflicts_with = "after_version"
+    )]
+    pub(crate) after: Option<PathBuf>,
+
+    /// Version of a committed transaction to compare
+    #[clap(long)]
+    pub(crate) after_version: Option<Version>,
+
+    #[clap(flatten)]
+    pub(crate) rest_options: RestOptions,
+    #[clap(flatten)]
+    pub(crate) profile_options: ProfileOptions,
+}
+
+impl GasDiff {
+    async fn load_gas_log(
+        &self,
+        path: Option<&Path>,
+        version: Option<Version>,
+    ) -> CliTypedResult<JsonGasLog> {
+        if let Some(path) = path {
+            let bytes = std::fs::read(path).map_err(|err| {
+                CliError::IO(format!("Unable to read gas log {}", path.display()), err)
+            })?;
+            return JsonGasLog::from_bytes(&bytes).map_err(|err| {
+                CliError::UnableToParse("gas log", format!("{}: {}", path.display(), err))
+            });
+        }
+
+        let version = version.expect("either a gas log or a version should be given");
+        let client = self.rest_options.client(&self.profile_options)?;
+        let debugger = AptosDebugger::rest_client(client)?;
+        let (_, _, gas_log) = debugger
+            .execute_past_transaction_with_gas_profiler(version)
+            .await
+            .map_err(|err| {
+                CliError::UnexpectedError(format!(
+                    "failed to replay txn {} with gas profiler: {}",
+                    version, err
+                ))
+            })?;
+        Ok(gas_log.to_json())
+    }
+}
+
+#[async_trait]
+impl CliCommand<GasLogDiff> for GasDiff {
+    fn command_name(&self) -> &'static str {
+        "GasDiff"
+    }
+
+    async fn execute(self) -> CliTypedResult<GasLogDiff> {
+        let before = self
+            .load_gas_log(self.before.as_deref(), self.before_version)
+            .await?;
+        let after = self
+            .load_gas_log(self.after.as_deref(), self.after_version)
+            .await?;
+        let diff = before.diff(&after)?;
+
+        // The diff is returned as JSON on stdout, so the table goes to stderr
+        let mut text = String::new();
+        diff.textualize(&mut text)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        eprintln!("{}", text);
+
+        Ok(diff)
+    }
+}
@@ -4,6 +4,7 @@
 mod aptos_debug_natives;
 pub mod coverage;
 mod disassembler;
+mod gas_diff;
 mod manifest;
 pub mod package_hooks;
 mod show;
@@ -27,6 +28,7 @@ use crate::{
     move_tool::{
         coverage::SummaryCoverage,
         disassembler::Disassemble,
+        gas_diff::GasDiff,
         manifest::{Dependency, ManifestNamedAddress, MovePackageManifest, PackageInfo},
     },
     CliCommand, CliResult,
@@ -83,6 +85,7 @@ pub enum MoveTool {
     Disassemble(Disassemble),
     Document(DocumentPackage),
     Download(DownloadPackage),
+    GasDiff(GasDiff),
     Init(InitPackage),
     List(ListPackage),
     Prove(ProvePackage),
@@ -110,6 +113,7 @@ impl MoveTool {
             MoveTool::Disassemble(tool) => tool.execute_serialized().await,
             MoveTool::Document(tool) => tool.execute_serialized().await,
             MoveTool::Download(tool) => tool.execute_serialized().await,
+            MoveTool::GasDiff(tool) => tool.execute_serialized().await,
             MoveTool::Init(tool) => tool.execute_serialized_success().await,
             MoveTool::List(tool) => tool.execute_serialized().await,
             MoveTool::Prove(tool) => tool.execute_serialized().await,
@@ -9,9 +9,10 @@ use crate::{
     common::{
         types::{
             CliCommand, CliError, CliResult, CliTypedResult, OptionalPoolAddressArgs,
-            PoolAddressArgs, ProfileOptions, RestOptions, TransactionOptions, TransactionSummary,
+            PoolAddressArgs, ProfileOptions, PromptOptions, RestOptions, TransactionOptions,
+            TransactionSummary,
         },
-        utils::read_from_file,
+        utils::{check_if_file_exists, read_from_file, write_to_user_only_file},
     },
     genesis::git::from_yaml,
     node::analyze::{
@@ -25,6 +26,7 @@ use aptos_backup_cli::{
     utils::GlobalRestoreOpt,
 };
 use aptos_cached_packages::aptos_stdlib;
+use aptos_config::config::{EncryptionKeySource, InitialSafetyRulesConfig, NodeConfig};
 use aptos_crypto::{bls12381, bls12381::PublicKey, x25519, ValidCryptoMaterialStringExt};
 use aptos_genesis::config::{HostAndPort, OperatorConfiguration};
 use aptos_logger::Level;
@@ -32,6 +34,10 @@ use aptos_network_checker::args::{
     validate_address, CheckEndpointArgs, HandshakeArgs, NodeAddressArgs,
 };
 use aptos_rest_client::{aptos_api_types::VersionedEvent, Client, State};
+use aptos_safety_rules::{
+    initialize_storage, PersistentSafetyStorage, SafetyDataExport, SignedSafetyDataExport,
+};
+use aptos_secure_storage::{EncryptedOnDiskStorage, OnDiskStorage, Storage};
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{BlockResource, CORE_CODE_ADDRESS},
@@ -52,7 +58,7 @@ use serde::{Deserialize, Serialize};
 use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
-    path::PathBuf,
+    path::{Path, PathBuf},
     time::Duration,
 };
 
@@ -67,8 +73,11 @@ pub enum NodeTool {
     AnalyzeValidatorPerformance(AnalyzeValidatorPerformance),
     BootstrapDb(BootstrapDb),
     CheckNetworkConnectivity(CheckNetworkConnectivity),
+    EncryptSecureStorage(EncryptSecureStorage),
+    ExportSafetyData(ExportSafetyData),
     GetPerformance(GetPerformance),
     GetStakePool(GetStakePool),
+    ImportSafetyData(ImportSafetyData),
     InitializeValidator(InitializeValidator),
     JoinValidatorSet(JoinValidatorSet),
     LeaveValidatorSet(LeaveValidatorSet),
@@ -91,8 +100,11 @@ impl NodeTool {
                     .await
             },
             CheckNetworkConnectivity(tool) => tool.execute_serialized().await,
+            EncryptSecureStorage(tool) => tool.execute_serialized().await,
+            ExportSafetyData(tool) => tool.execute_serialized().await,
             GetPerformance(tool) => tool.execute_serialized().await,
             GetStakePool(tool) => tool.execute_serialized().await,
+            ImportSafetyData(tool) => tool.execute_serialized().await,
             InitializeValidator(tool) => tool.execute_serialized().await,
             JoinValidatorSet(tool) => tool.execute_serialized().await,
             LeaveValidatorSet(tool) => tool.execute_serialized().await,
@@ -1366,6 +1378,185 @@ impl CliCommand<String> for CheckNetworkConnectivity {
     }
 }
 
+/// Encrypt an on-disk secure storage
+///
+/// Copies the content of a plaintext `on_disk_storage` secure backend file, e.g. the consensus
+/// key and the safety data of a validator, into a new `encrypted_on_disk_storage` file. Stop the
+/// node before running this, then point its secure backend config to the new file with the same
+/// encryption key, and delete the plaintext file once the node runs with it.
+#[derive(Parser)]
+pub struct EncryptSecureStorage {
+    /// Path to the plaintext secure storage file
+    #[clap(long, value_parser)]
+    pub(crate) input_file: PathBuf,
+
+    /// Path to the encrypted secure storage file to create
+    #[clap(long, value_parser)]
+    pub(crate) output_file: PathBuf,
+
+    /// Path to a file holding the passphrase to encrypt with
+    #[clap(
+        long,
+        value_parser,
+        required_unless_present = "encryption_key_env",
+        conflicts_with = "encryption_key_env"
+    )]
+    pub(crate) passphrase_file: Option<PathBuf>,
+
+    /// Name of an environment variable holding a base64 encoded 32 byte key to encrypt with
+    #[clap(long)]
+    pub(crate) encryption_key_env: Option<String>,
+}
+
+#[async_trait]
+impl CliCommand<String> for EncryptSecureStorage {
+    fn command_name(&self) -> &'static str {
+        "EncryptSecureStorage"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        if !self.input_file.exists() {
+            return Err(CliError::CommandArgumentError(format!(
+                "Secure storage file {:?} doesn't exist",
+                self.input_file
+            )));
+        }
+        if self.output_file.exists() {
+            return Err(CliError::CommandArgumentError(format!(
+                "{:?} already exists, refusing to overwrite it",
+                self.output_file
+            )));
+        }
+        let key_source = match (self.passphrase_file, self.encryption_key_env) {
+            (Some(path), _) => EncryptionKeySource::PassphraseFromDisk(path),
+            (None, Some(name)) => EncryptionKeySource::FromEnv(name),
+            (None, None) => unreachable!("Enforced by clap"),
+        };
+        let key = key_source.read_key()?;
+
+        let plaintext = OnDiskStorage::new(self.input_file);
+        let mut encrypted = EncryptedOnDiskStorage::new(self.output_file.clone(), key, None);
+        let num_entries = encrypted
+            .import_on_disk_storage(&plaintext)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        Ok(format!(
+            "Encrypted {} entries into {:?}",
+            num_entries, self.output_file
+        ))
+    }
+}
+
+/// Export the safety data of a validator
+///
+/// Writes the safety data of the validator (epoch, last voted round, preferred round) and its
+/// waypoint to a file, signed with its consensus key, to move the validator to another machine.
+/// Stop the validator before exporting. The secure storage is marked as exported, and the
+/// validator refuses to sign anything on this machine until an export is imported back.
+#[derive(Parser)]
+pub struct ExportSafetyData {
+    /// Path to the config of the validator, to find its secure storage
+    #[clap(long, value_parser)]
+    pub(crate) node_config: PathBuf,
+
+    /// Path to the export file to create
+    #[clap(long, value_parser)]
+    pub(crate) output_file: PathBuf,
+
+    #[clap(flatten)]
+    pub(crate) prompt_options: PromptOptions,
+}
+
+#[async_trait]
+impl CliCommand<SafetyDataExport> for ExportSafetyData {
+    fn command_name(&self) -> &'static str {
+        "ExportSafetyData"
+    }
+
+    async fn execute(self) -> CliTypedResult<SafetyDataExport> {
+        check_if_file_exists(self.output_file.as_path(), self.prompt_options)?;
+        let mut storage = safety_storage(&self.node_config, false)?;
+        let signed_export = storage
+            .export_safety_data()
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        let bytes = serde_json::to_vec_pretty(&signed_export)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        write_to_user_only_file(self.output_file.as_path(), "Safety data export", &bytes)?;
+        Ok(signed_export.export)
+    }
+}
+
+/// Import the safety data of a validator
+///
+/// Imports a file written by `aptos node export-safety-data` into the secure storage of the
+/// validator on its new machine. If the secure storage is empty, it's first initialized from
+/// `consensus.safety_rules.initial_safety_rules_config`, which must then hold the same consensus
+/// key. The import is refused if the exported safety data is older than the one in storage.
+/// Alternatively, set `consensus.safety_rules.safety_data_import_file` in the node config to
+/// import it on startup.
+#[derive(Parser)]
+pub struct ImportSafetyData {
+    /// Path to the config of the validator, to find its secure storage
+    #[clap(long, value_parser)]
+    pub(crate) node_config: PathBuf,
+
+    /// Path to the export file
+    #[clap(long, value_parser)]
+    pub(crate) input_file: PathBuf,
+}
+
+#[async_trait]
+impl CliCommand<String> for ImportSafetyData {
+    fn command_name(&self) -> &'static str {
+        "ImportSafetyData"
+    }
+
+    async fn execute(self) -> CliTypedResult<String> {
+        let bytes = read_from_file(self.input_file.as_path())?;
+        let signed_export: SignedSafetyDataExport = serde_json::from_slice(&bytes)
+            .map_err(|err| CliError::UnableToParse("Safety data export", err.to_string()))?;
+        let mut storage = safety_storage(&self.node_config, true)?;
+        let imported = storage
+            .import_safety_data(&signed_export)
+            .map_err(|err| CliError::UnexpectedError(err.to_string()))?;
+        Ok(if imported {
+            format!(
+                "Imported safety data of epoch {}, last voted round {}",
+                signed_export.export.safety_data.epoch,
+                signed_export.export.safety_data.last_voted_round
+            )
+        } else {
+            "The safety data export was already imported".to_string()
+        })
+    }
+}
+
+/// Opens the safety rules storage of the secure backend of the node. If `initialize` is set and
+/// the storage is empty, it's initialized like on startup of the node.
+fn safety_storage(node_config: &Path, initialize: bool) -> CliTypedResult<PersistentSafetyStorage> {
+    let config = NodeConfig::load_from_path(node_config)?;
+    let safety_rules_config = &config.consensus.safety_rules;
+    let storage = PersistentSafetyStorage::new(
+        Storage::from(&safety_rules_config.backend),
+        safety_rules_config.enable_cached_safety_data,
+    );
+    if storage.author().is_ok() {
+        return Ok(storage);
+    }
+    if !initialize
+        || (safety_rules_config.test.is_none()
+            && matches!(
+                safety_rules_config.initial_safety_rules_config,
+                InitialSafetyRulesConfig::None
+            ))
+    {
+        return Err(CliError::UnexpectedError(
+            "Safety rules storage is not initialized, provide an initial safety rules config"
+                .to_string(),
+        ));
+    }
+    Ok(initialize_storage(safety_rules_config))
+}
+
 /// Show epoch information
 ///
 /// Displays the current epoch, the epoch length, and the estimated time of the next epoch
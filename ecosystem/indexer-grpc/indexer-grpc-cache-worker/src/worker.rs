@@ -9,7 +9,7 @@ use anyhow::{bail, Context, Result};
 use aptos_indexer_grpc_utils::{
     cache_operator::CacheOperator,
     config::IndexerGrpcFileStoreConfig,
-    create_grpc_client,
+    create_grpc_client, encode_transaction,
     file_store_operator::{
         FileStoreMetadata, FileStoreOperator, GcsFileStoreOperator, LocalFileStoreOperator,
     },
@@ -22,7 +22,6 @@ use aptos_protos::internal::fullnode::v1::{
     GetTransactionsFromNodeRequest, TransactionsFromNodeResponse,
 };
 use futures::{self, StreamExt};
-use prost::Message;
 use tracing::{error, info};
 use url::Url;
 
@@ -183,13 +182,9 @@ async fn process_transactions_from_node_response(
                         Some(ref timestamp) => timestamp.seconds as u64,
                         None => 0,
                     };
-                    let mut encoded_proto_data = vec![];
-                    tx.encode(&mut encoded_proto_data)
-                        .context("Encode transaction failed.")?;
-                    let base64_encoded_proto_data = base64::encode(encoded_proto_data);
-                    Ok((tx.version, base64_encoded_proto_data, timestamp_in_seconds))
+                    (tx.version, encode_transaction(&tx), timestamp_in_seconds)
                 })
-                .collect::<Result<Vec<(u64, String, u64)>>>()?;
+                .collect::<Vec<(u64, String, u64)>>();
 
             // Push to cache.
             match cache_operator.update_cache_transactions(transactions).await {
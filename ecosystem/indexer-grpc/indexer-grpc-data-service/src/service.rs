@@ -15,6 +15,7 @@ use aptos_indexer_grpc_utils::{
     constants::{
         BLOB_STORAGE_SIZE, GRPC_AUTH_TOKEN_HEADER, GRPC_REQUEST_NAME_HEADER, MESSAGE_SIZE_LIMIT,
     },
+    decode_transaction,
     file_store_operator::{FileStoreOperator, GcsFileStoreOperator, LocalFileStoreOperator},
     time_diff_since_pb_timestamp_in_secs,
     types::RedisUrl,
@@ -27,7 +28,6 @@ use aptos_protos::{
     transaction::v1::Transaction,
 };
 use futures::Stream;
-use prost::Message;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, pin::Pin, sync::Arc, time::Duration};
 use tokio::sync::mpsc::{channel, error::SendTimeoutError};
@@ -399,11 +399,7 @@ fn get_transactions_responses_builder(
 ) -> Vec<TransactionsResponse> {
     let transactions: Vec<Transaction> = data
         .into_iter()
-        .map(|(encoded, _)| {
-            let decoded_transaction = base64::decode(encoded).unwrap();
-            let transaction = Transaction::decode(&*decoded_transaction);
-            transaction.unwrap()
-        })
+        .map(|(encoded, _)| decode_transaction(&encoded).unwrap())
         .collect();
     let chunks = chunk_transactions(transactions, MESSAGE_SIZE_LIMIT);
     chunks
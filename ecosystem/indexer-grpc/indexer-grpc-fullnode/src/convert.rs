@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use aptos_api_types::{
+    call_trace::{CallTrace, CallTraceAbort, CallTracePayloadKind, CallTraceResourceAccess},
     AccountSignature, DeleteModule, DeleteResource, Ed25519Signature, EntryFunctionId,
     EntryFunctionPayload, Event, GenesisPayload, MoveAbility, MoveFunction,
     MoveFunctionGenericTypeParam, MoveFunctionVisibility, MoveModule, MoveModuleBytecode,
@@ -758,6 +759,74 @@ pub fn convert_transaction(
         epoch,
         block_height,
         r#type: txn_type as i32,
+        call_trace: None,
         txn_data: Some(txn_data),
     }
 }
+
+pub fn convert_call_trace_payload_kind(
+    payload_kind: &CallTracePayloadKind,
+) -> transaction::call_trace::PayloadKind {
+    match payload_kind {
+        CallTracePayloadKind::EntryFunction => transaction::call_trace::PayloadKind::EntryFunction,
+        CallTracePayloadKind::Script => transaction::call_trace::PayloadKind::Script,
+        CallTracePayloadKind::Multisig => transaction::call_trace::PayloadKind::Multisig,
+        CallTracePayloadKind::ModuleBundle => transaction::call_trace::PayloadKind::ModuleBundle,
+        CallTracePayloadKind::BlockMetadata => transaction::call_trace::PayloadKind::BlockMetadata,
+    }
+}
+
+pub fn convert_call_trace_resource_access(
+    access: &CallTraceResourceAccess,
+) -> transaction::call_trace_resource::Access {
+    match access {
+        CallTraceResourceAccess::Exists => transaction::call_trace_resource::Access::Exists,
+        CallTraceResourceAccess::Read => transaction::call_trace_resource::Access::Read,
+        CallTraceResourceAccess::Write => transaction::call_trace_resource::Access::Write,
+        CallTraceResourceAccess::MoveFrom => transaction::call_trace_resource::Access::MoveFrom,
+        CallTraceResourceAccess::MoveTo => transaction::call_trace_resource::Access::MoveTo,
+    }
+}
+
+pub fn convert_call_trace_abort(abort: &CallTraceAbort) -> transaction::CallTraceAbort {
+    transaction::CallTraceAbort {
+        status_code: abort.status_code.clone(),
+        abort_code: abort.abort_code.map(|abort_code| abort_code.0),
+        location: abort.location.as_ref().map(convert_move_module_id),
+        code_offset: abort.code_offset.map(u32::from),
+    }
+}
+
+pub fn convert_call_trace(call_trace: &CallTrace) -> transaction::CallTrace {
+    transaction::CallTrace {
+        payload_kind: call_trace
+            .payload_kind
+            .as_ref()
+            .map(convert_call_trace_payload_kind)
+            .unwrap_or(transaction::call_trace::PayloadKind::Unspecified)
+            as i32,
+        module_id: call_trace.module_id.clone(),
+        func_name: call_trace.func_name.clone(),
+        inputs: call_trace.inputs.iter().map(|v| v.to_string()).collect(),
+        outputs: call_trace.outputs.iter().map(|v| v.to_string()).collect(),
+        type_args: call_trace.type_args.clone(),
+        events: convert_events(&call_trace.events),
+        resources: call_trace
+            .resources
+            .iter()
+            .map(|resource| transaction::CallTraceResource {
+                address: resource.address.to_string(),
+                r#type: Some(convert_move_struct_tag(&resource.typ)),
+                access: convert_call_trace_resource_access(&resource.access) as i32,
+            })
+            .collect(),
+        inclusive_internal_gas: call_trace.inclusive_internal_gas.0,
+        exclusive_internal_gas: call_trace.exclusive_internal_gas.0,
+        abort: call_trace.abort.as_ref().map(convert_call_trace_abort),
+        sub_traces: call_trace
+            .sub_traces
+            .iter()
+            .map(convert_call_trace)
+            .collect(),
+    }
+}
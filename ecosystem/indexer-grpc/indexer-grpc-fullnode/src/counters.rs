@@ -21,6 +21,15 @@ pub static UNABLE_TO_FETCH_TRANSACTION: Lazy<IntCounter> = Lazy::new(|| {
     .unwrap()
 });
 
+/// Number of times the indexer has been unable to trace a batch of transactions. Ideally zero.
+pub static UNABLE_TO_TRACE_TRANSACTIONS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "indexer_grpc_unable_to_trace_transactions_count",
+        "Number of times the indexer has been unable to compute the call traces of a batch of transactions"
+    )
+    .unwrap()
+});
+
 /// Number of times the indexer has been able to fetch a transaction
 pub static FETCHED_TRANSACTION: Lazy<IntCounter> = Lazy::new(|| {
     register_int_counter!(
@@ -47,6 +47,7 @@ impl FullnodeData for FullnodeDataService {
         let processor_task_count = self.service_context.processor_task_count;
         let processor_batch_size = self.service_context.processor_batch_size;
         let output_batch_size = self.service_context.output_batch_size;
+        let enable_call_traces = self.service_context.enable_call_traces;
 
         // Some node metadata
         let context = self.service_context.context.clone();
@@ -67,6 +68,7 @@ impl FullnodeData for FullnodeDataService {
                 processor_task_count,
                 processor_batch_size,
                 output_batch_size,
+                enable_call_traces,
                 tx.clone(),
             );
             // Sends init message (one time per request) to the client in the with chain id and starting version. Basically a handshake
@@ -42,6 +42,7 @@ impl RawData for LocalnetDataService {
         let starting_version = r.starting_version.expect("Starting version must be set");
         let processor_batch_size = self.service_context.processor_batch_size;
         let output_batch_size = self.service_context.output_batch_size;
+        let enable_call_traces = self.service_context.enable_call_traces;
         let ledger_chain_id = context.chain_id().id();
         let transactions_count = r.transactions_count;
         // Creates a channel to send the stream to the client
@@ -58,6 +59,7 @@ impl RawData for LocalnetDataService {
                 1,
                 processor_batch_size,
                 output_batch_size,
+                enable_call_traces,
                 tx.clone(),
             );
             loop {
@@ -48,6 +48,7 @@ pub fn bootstrap(
     let processor_task_count = node_config.indexer_grpc.processor_task_count;
     let processor_batch_size = node_config.indexer_grpc.processor_batch_size;
     let output_batch_size = node_config.indexer_grpc.output_batch_size;
+    let enable_call_traces = node_config.indexer_grpc.enable_call_traces;
 
     runtime.spawn(async move {
         let context = Arc::new(Context::new(chain_id, db, mp_sender, node_config));
@@ -56,6 +57,7 @@ pub fn bootstrap(
             processor_task_count,
             processor_batch_size,
             output_batch_size,
+            enable_call_traces,
         };
         // If we are here, we know indexer grpc is enabled.
         let server = FullnodeDataService {
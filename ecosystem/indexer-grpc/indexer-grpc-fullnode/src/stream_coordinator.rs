@@ -2,12 +2,17 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::{
-    convert::convert_transaction,
-    counters::{FETCHED_LATENCY_IN_SECS, FETCHED_TRANSACTION, UNABLE_TO_FETCH_TRANSACTION},
+    convert::{convert_call_trace, convert_transaction},
+    counters::{
+        FETCHED_LATENCY_IN_SECS, FETCHED_TRANSACTION, UNABLE_TO_FETCH_TRANSACTION,
+        UNABLE_TO_TRACE_TRANSACTIONS,
+    },
     runtime::{DEFAULT_NUM_RETRIES, RETRY_TIME_MILLIS},
 };
 use aptos_api::context::Context;
-use aptos_api_types::{AsConverter, Transaction as APITransaction, TransactionOnChainData};
+use aptos_api_types::{
+    call_trace::CallTrace, AsConverter, Transaction as APITransaction, TransactionOnChainData,
+};
 use aptos_indexer_grpc_utils::{chunk_transactions, constants::MESSAGE_SIZE_LIMIT};
 use aptos_logger::{error, info, sample, sample::SampleRate};
 use aptos_protos::{
@@ -32,6 +37,7 @@ pub struct IndexerStreamCoordinator {
     pub processor_task_count: u16,
     pub processor_batch_size: u16,
     pub output_batch_size: u16,
+    pub enable_call_traces: bool,
     pub highest_known_version: u64,
     pub context: Arc<Context>,
     pub transactions_sender: mpsc::Sender<Result<TransactionsFromNodeResponse, tonic::Status>>,
@@ -51,6 +57,7 @@ impl IndexerStreamCoordinator {
         processor_task_count: u16,
         processor_batch_size: u16,
         output_batch_size: u16,
+        enable_call_traces: bool,
         transactions_sender: mpsc::Sender<Result<TransactionsFromNodeResponse, tonic::Status>>,
     ) -> Self {
         Self {
@@ -58,6 +65,7 @@ impl IndexerStreamCoordinator {
             processor_task_count,
             processor_batch_size,
             output_batch_size,
+            enable_call_traces,
             highest_known_version: 0,
             context,
             transactions_sender,
@@ -70,13 +78,14 @@ impl IndexerStreamCoordinator {
     /// Processing transactions in 4 stages:
     /// 1. Fetch transactions from storage
     /// 2. Convert transactions to rust objects (for example stringifying move structs into json)
-    /// 3. Convert into protobuf objects
+    /// 3. Convert into protobuf objects, attaching call traces if enabled
     /// 4. Encode protobuf objects (base64)
     pub async fn process_next_batch(&mut self) -> Vec<Result<EndVersion, Status>> {
         let ledger_chain_id = self.context.chain_id().id();
         let mut tasks = vec![];
         let batches = self.get_batches().await;
         let output_batch_size = self.output_batch_size;
+        let enable_call_traces = self.enable_call_traces;
 
         for batch in batches {
             let context = self.context.clone();
@@ -87,9 +96,25 @@ impl IndexerStreamCoordinator {
                 // Fetch and convert transactions from API
                 let raw_txns =
                     Self::fetch_raw_txns_with_retries(context.clone(), ledger_version, batch).await;
+                let (raw_txns, call_traces) = if enable_call_traces {
+                    match Self::get_call_traces(context.clone(), raw_txns).await {
+                        Ok(traced) => traced,
+                        Err(status) => {
+                            // Streaming the batch without its traces would look like the
+                            // transactions executed no Move code, so fail the stream instead.
+                            let _ = transaction_sender.send(Err(status.clone())).await;
+                            return Err(status);
+                        },
+                    }
+                } else {
+                    (raw_txns, vec![])
+                };
                 let api_txns = Self::convert_to_api_txns(context, raw_txns).await;
                 api_txns.last().map(record_fetched_transaction_latency);
-                let pb_txns = Self::convert_to_pb_txns(api_txns);
+                let mut pb_txns = Self::convert_to_pb_txns(api_txns);
+                for (pb_txn, call_trace) in pb_txns.iter_mut().zip(call_traces) {
+                    pb_txn.call_trace = call_trace.as_ref().map(convert_call_trace);
+                }
                 // Wrap in stream response object and send to channel
                 for chunk in pb_txns.chunks(output_batch_size as usize) {
                     for chunk in chunk_transactions(chunk.to_vec(), MESSAGE_SIZE_LIMIT) {
@@ -323,6 +348,39 @@ impl IndexerStreamCoordinator {
         transactions
     }
 
+    /// Re-executes the transactions to compute their call traces, on the blocking thread pool
+    /// since replaying blocks is CPU bound. Fails if the transactions can't be traced, e.g.
+    /// because the state the batch starts from has been pruned.
+    async fn get_call_traces(
+        context: Arc<Context>,
+        raw_txns: Vec<TransactionOnChainData>,
+    ) -> Result<(Vec<TransactionOnChainData>, Vec<Option<CallTrace>>), Status> {
+        let (raw_txns, result) = tokio::task::spawn_blocking(move || {
+            let result = context.get_call_traces(&raw_txns);
+            (raw_txns, result)
+        })
+        .await
+        .map_err(|err| Status::internal(format!("Call tracing task failed: {}", err)))?;
+        match result {
+            Ok(call_traces) => Ok((raw_txns, call_traces)),
+            Err(err) => {
+                UNABLE_TO_TRACE_TRANSACTIONS.inc();
+                error!(
+                    starting_version = raw_txns.first().map(|txn| txn.version),
+                    num_transactions = raw_txns.len(),
+                    error = format!("{:?}", err),
+                    "Could not compute call traces",
+                );
+                Err(Status::unavailable(format!(
+                    "Could not compute the call traces of the transactions starting at version \
+                     {:?}: {}",
+                    raw_txns.first().map(|txn| txn.version),
+                    err
+                )))
+            },
+        }
+    }
+
     fn convert_to_pb_txns(api_txns: Vec<APITransaction>) -> Vec<TransactionPB> {
         api_txns
             .iter()
@@ -2,5 +2,6 @@
 // SPDX-License-Identifier: Apache-2.0
 
 // mod proto_converter_tests;
+mod stream_coordinator_tests;
 
 pub use aptos_api_test_context::{new_test_context as super_new_test_context, TestContext};
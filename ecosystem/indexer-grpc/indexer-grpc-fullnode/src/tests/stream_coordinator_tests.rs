@@ -0,0 +1,71 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{stream_coordinator::IndexerStreamCoordinator, tests::super_new_test_context};
+use aptos_api_test_context::current_function_name;
+use aptos_config::config::NodeConfig;
+use aptos_protos::{
+    internal::fullnode::v1::transactions_from_node_response::Response,
+    transaction::v1::{call_trace::PayloadKind, Transaction},
+};
+use std::sync::Arc;
+use tokio::sync::mpsc;
+
+/// Streams every committed transaction from version 1 on, skipping genesis.
+async fn stream_transactions(
+    context: Arc<aptos_api::context::Context>,
+    enable_call_traces: bool,
+) -> Vec<Transaction> {
+    let (sender, mut receiver) = mpsc::channel(100);
+    let mut coordinator =
+        IndexerStreamCoordinator::new(context, 1, 1, 100, 100, enable_call_traces, sender);
+    for result in coordinator.process_next_batch().await {
+        result.unwrap();
+    }
+    let mut transactions = vec![];
+    while let Ok(response) = receiver.try_recv() {
+        if let Some(Response::Data(data)) = response.unwrap().response {
+            transactions.extend(data.transactions);
+        }
+    }
+    transactions
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_call_traces() {
+    let mut test_context =
+        super_new_test_context(current_function_name!(), NodeConfig::default(), false);
+    let account = test_context.gen_account();
+    let txn = test_context.create_user_account(&account).await;
+    test_context.commit_block(&vec![txn]).await;
+
+    let transactions = stream_transactions(Arc::new(test_context.context), true).await;
+    let versions: Vec<_> = transactions.iter().map(|txn| txn.version).collect();
+    assert_eq!(versions, vec![1, 2, 3]);
+
+    let block_metadata_trace = transactions[0].call_trace.as_ref().unwrap();
+    assert_eq!(
+        block_metadata_trace.payload_kind(),
+        PayloadKind::BlockMetadata
+    );
+    assert_eq!(block_metadata_trace.func_name, "block_prologue");
+    let user_trace = transactions[1].call_trace.as_ref().unwrap();
+    assert_eq!(user_trace.payload_kind(), PayloadKind::EntryFunction);
+    assert_eq!(user_trace.func_name, "create_account");
+    assert!(!user_trace.sub_traces.is_empty());
+    // The state checkpoint executes no Move code
+    assert!(transactions[2].call_trace.is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_stream_without_call_traces() {
+    let mut test_context =
+        super_new_test_context(current_function_name!(), NodeConfig::default(), false);
+    let account = test_context.gen_account();
+    let txn = test_context.create_user_account(&account).await;
+    test_context.commit_block(&vec![txn]).await;
+
+    let transactions = stream_transactions(Arc::new(test_context.context), false).await;
+    assert_eq!(transactions.len(), 3);
+    assert!(transactions.iter().all(|txn| txn.call_trace.is_none()));
+}
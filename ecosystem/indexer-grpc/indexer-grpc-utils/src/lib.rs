@@ -93,6 +93,19 @@ pub fn build_protobuf_encoded_transaction_wrappers(
         .collect()
 }
 
+/// Encodes a transaction the way it is stored in the cache and the file store. The whole
+/// protobuf message is kept, so that optional fields, e.g. the call trace, pass through to the
+/// data service.
+pub fn encode_transaction(transaction: &Transaction) -> String {
+    base64::encode(transaction.encode_to_vec())
+}
+
+/// Decodes a transaction encoded by `encode_transaction`.
+pub fn decode_transaction(encoded_transaction: &str) -> anyhow::Result<Transaction> {
+    let bytes = base64::decode(encoded_transaction)?;
+    Ok(Transaction::decode(&*bytes)?)
+}
+
 pub fn time_diff_since_pb_timestamp_in_secs(timestamp: &Timestamp) -> f64 {
     let current_timestamp = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -132,6 +145,7 @@ pub fn chunk_transactions(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aptos_protos::transaction::v1::CallTrace;
 
     #[test]
     fn test_chunk_the_transactions_correctly_with_large_transaction() {
@@ -150,6 +164,26 @@ mod tests {
         assert_eq!(chunked_transactions.len(), 10);
     }
 
+    #[test]
+    fn test_encoded_transaction_keeps_call_trace() {
+        let t = Transaction {
+            version: 2,
+            call_trace: Some(CallTrace {
+                module_id: "0x1::aptos_account".to_string(),
+                func_name: "transfer".to_string(),
+                sub_traces: vec![CallTrace {
+                    module_id: "0x1::coin".to_string(),
+                    func_name: "transfer".to_string(),
+                    ..CallTrace::default()
+                }],
+                ..CallTrace::default()
+            }),
+            ..Transaction::default()
+        };
+        let decoded = decode_transaction(&encode_transaction(&t)).unwrap();
+        assert_eq!(decoded, t);
+    }
+
     #[test]
     fn test_chunk_the_transactions_correctly() {
         let t = Transaction {
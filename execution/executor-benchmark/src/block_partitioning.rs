@@ -2,22 +2,29 @@
 
 use crate::{metrics::TIMER, pipeline::ExecuteBlockMessage};
 use aptos_block_partitioner::{BlockPartitioner, PartitionerConfig};
+use aptos_consensus::{create_transaction_shuffler, TransactionShuffler};
 use aptos_crypto::HashValue;
 use aptos_logger::info;
 use aptos_types::{
     block_executor::partitioner::{ExecutableBlock, ExecutableTransactions},
+    on_chain_config::TransactionShufflerType,
     transaction::Transaction,
 };
-use std::time::Instant;
+use std::{sync::Arc, time::Instant};
 
 pub(crate) struct BlockPartitioningStage {
     num_executor_shards: usize,
     num_blocks_processed: usize,
     maybe_partitioner: Option<Box<dyn BlockPartitioner>>,
+    transaction_shuffler: Arc<dyn TransactionShuffler>,
 }
 
 impl BlockPartitioningStage {
-    pub fn new(num_shards: usize, partitioner_config: &dyn PartitionerConfig) -> Self {
+    pub fn new(
+        num_shards: usize,
+        partitioner_config: &dyn PartitionerConfig,
+        transaction_shuffler_type: TransactionShufflerType,
+    ) -> Self {
         let maybe_partitioner = if num_shards <= 1 {
             None
         } else {
@@ -29,16 +36,37 @@ impl BlockPartitioningStage {
             num_executor_shards: num_shards,
             num_blocks_processed: 0,
             maybe_partitioner,
+            transaction_shuffler: create_transaction_shuffler(transaction_shuffler_type),
         }
     }
 
-    pub fn process(&mut self, mut txns: Vec<Transaction>) -> ExecuteBlockMessage {
+    /// Shuffles the user transactions as consensus does before execution. The other
+    /// transactions, i.e. the state checkpoint ending the block, stay at the end.
+    fn shuffle(&self, txns: Vec<Transaction>) -> Vec<Transaction> {
+        let mut user_txns = vec![];
+        let mut other_txns = vec![];
+        for txn in txns {
+            match txn {
+                Transaction::UserTransaction(txn) => user_txns.push(txn),
+                txn => other_txns.push(txn),
+            }
+        }
+        self.transaction_shuffler
+            .shuffle(user_txns)
+            .into_iter()
+            .map(Transaction::UserTransaction)
+            .chain(other_txns)
+            .collect()
+    }
+
+    pub fn process(&mut self, txns: Vec<Transaction>) -> ExecuteBlockMessage {
         let current_block_start_time = Instant::now();
         info!(
             "In iteration {}, received {:?} transactions.",
             self.num_blocks_processed,
             txns.len()
         );
+        let mut txns = self.shuffle(txns);
         let block_id = HashValue::random();
         let block: ExecutableBlock = match &self.maybe_partitioner {
             None => (block_id, txns).into(),
@@ -203,6 +203,7 @@ pub fn run_benchmark<V>(
 
     let mut start_time = Instant::now();
     let start_gas_measurement = GasMesurement::start();
+    let start_aborts = block_executor_counters::SPECULATIVE_ABORT_COUNT.get();
 
     let start_partitioning_total = BLOCK_PARTITIONING_SECONDS.get_sample_sum();
     let start_execution_total = APTOS_EXECUTOR_EXECUTE_BLOCK_SECONDS.get_sample_sum();
@@ -260,6 +261,7 @@ pub fn run_benchmark<V>(
     let elapsed = start_time.elapsed().as_secs_f64();
     let delta_v = (db.reader.get_latest_version().unwrap() - version) as f64;
     let (delta_gas, delta_gas_count) = start_gas_measurement.end();
+    let delta_aborts = block_executor_counters::SPECULATIVE_ABORT_COUNT.get() - start_aborts;
 
     let delta_vm_time = APTOS_EXECUTOR_VM_EXECUTE_BLOCK_SECONDS.get_sample_sum() - start_vm_time;
     info!(
@@ -280,6 +282,12 @@ pub fn run_benchmark<V>(
         "Overall GPT: {} gas/txn",
         delta_gas / (delta_gas_count as f64).max(1.0)
     );
+    // Compare runs with different --transaction-shuffler on the same workload
+    info!(
+        "Overall abort rate: {} aborts/txn (transaction shuffler: {:?})",
+        delta_aborts as f64 / delta_v.max(1.0),
+        pipeline_config.transaction_shuffler_type
+    );
 
     let time_in_partitioning =
         BLOCK_PARTITIONING_SECONDS.get_sample_sum() - start_partitioning_total;
@@ -562,11 +570,13 @@ mod tests {
     use aptos_executor::block_executor::TransactionBlockExecutor;
     use aptos_temppath::TempPath;
     use aptos_transaction_generator_lib::args::TransactionTypeArg;
+    use aptos_types::on_chain_config::TransactionShufflerType;
     use aptos_vm::AptosVM;
 
     fn test_generic_benchmark<E>(
         transaction_type: Option<TransactionTypeArg>,
         verify_sequence_numbers: bool,
+        transaction_shuffler_type: TransactionShufflerType,
     ) where
         E: TransactionBlockExecutor + 'static,
     {
@@ -610,24 +620,52 @@ mod tests {
             false,
             false,
             false,
-            PipelineConfig::default(),
+            PipelineConfig {
+                transaction_shuffler_type,
+                ..PipelineConfig::default()
+            },
         );
     }
 
     #[test]
     fn test_benchmark() {
-        test_generic_benchmark::<AptosVM>(None, true);
+        test_generic_benchmark::<AptosVM>(None, true, TransactionShufflerType::NoShuffling);
     }
 
     #[test]
     fn test_benchmark_transaction() {
-        test_generic_benchmark::<AptosVM>(Some(TransactionTypeArg::TokenV2AmbassadorMint), true);
+        test_generic_benchmark::<AptosVM>(
+            Some(TransactionTypeArg::TokenV2AmbassadorMint),
+            true,
+            TransactionShufflerType::NoShuffling,
+        );
+    }
+
+    #[test]
+    fn test_benchmark_sender_aware_shuffling() {
+        test_generic_benchmark::<AptosVM>(
+            Some(TransactionTypeArg::TokenV2AmbassadorMint),
+            true,
+            TransactionShufflerType::SenderAwareV2(32),
+        );
+    }
+
+    #[test]
+    fn test_benchmark_fee_and_conflict_aware_shuffling() {
+        test_generic_benchmark::<AptosVM>(
+            Some(TransactionTypeArg::TokenV2AmbassadorMint),
+            true,
+            TransactionShufflerType::FeeAndConflictAwareV1 {
+                sender_conflict_window_size: 32,
+                module_conflict_window_size: 4,
+            },
+        );
     }
 
     #[test]
     fn test_native_benchmark() {
         // correct execution not yet implemented, so cannot be checked for validity
-        test_generic_benchmark::<NativeExecutor>(None, false);
+        test_generic_benchmark::<NativeExecutor>(None, false, TransactionShufflerType::NoShuffling);
     }
 }
 
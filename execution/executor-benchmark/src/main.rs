@@ -22,8 +22,9 @@ use aptos_metrics_core::{register_int_gauge, IntGauge};
 use aptos_profiler::{ProfilerConfig, ProfilerHandler};
 use aptos_push_metrics::MetricsPusher;
 use aptos_transaction_generator_lib::args::TransactionTypeArg;
+use aptos_types::on_chain_config::TransactionShufflerType;
 use aptos_vm::AptosVM;
-use clap::{ArgGroup, Parser, Subcommand};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 use once_cell::sync::Lazy;
 use std::{
     path::PathBuf,
@@ -92,6 +93,13 @@ impl PrunerOpt {
     }
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq, ValueEnum)]
+enum TransactionShufflerArg {
+    NoShuffling,
+    SenderAware,
+    FeeAndConflictAware,
+}
+
 #[derive(Debug, Parser)]
 pub struct PipelineOpt {
     #[clap(long)]
@@ -124,6 +132,12 @@ pub struct PipelineOpt {
     partitioner_v2_num_threads: usize,
     #[clap(long, default_value = "64")]
     partitioner_v2_dashmap_num_shards: usize,
+    #[clap(long, value_enum, default_value_t = TransactionShufflerArg::NoShuffling)]
+    transaction_shuffler: TransactionShufflerArg,
+    #[clap(long, default_value = "32")]
+    sender_conflict_window_size: u32,
+    #[clap(long, default_value = "4")]
+    module_conflict_window_size: u32,
 }
 
 impl PipelineOpt {
@@ -138,6 +152,22 @@ impl PipelineOpt {
             use_global_executor: self.use_global_executor,
             num_generator_workers: self.num_generator_workers,
             partitioner_config: self.partitioner_config(),
+            transaction_shuffler_type: self.transaction_shuffler_type(),
+        }
+    }
+
+    fn transaction_shuffler_type(&self) -> TransactionShufflerType {
+        match self.transaction_shuffler {
+            TransactionShufflerArg::NoShuffling => TransactionShufflerType::NoShuffling,
+            TransactionShufflerArg::SenderAware => {
+                TransactionShufflerType::SenderAwareV2(self.sender_conflict_window_size)
+            },
+            TransactionShufflerArg::FeeAndConflictAware => {
+                TransactionShufflerType::FeeAndConflictAwareV1 {
+                    sender_conflict_window_size: self.sender_conflict_window_size,
+                    module_conflict_window_size: self.module_conflict_window_size,
+                }
+            },
         }
     }
 
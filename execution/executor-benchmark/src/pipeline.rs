@@ -12,6 +12,7 @@ use aptos_executor_types::{state_checkpoint_output::StateCheckpointOutput, Block
 use aptos_logger::info;
 use aptos_types::{
     block_executor::partitioner::ExecutableBlock,
+    on_chain_config::TransactionShufflerType,
     transaction::{Transaction, Version},
 };
 use derivative::Derivative;
@@ -39,6 +40,8 @@ pub struct PipelineConfig {
     #[derivative(Default(value = "4"))]
     pub num_generator_workers: usize,
     pub partitioner_config: PartitionerV2Config,
+    #[derivative(Default(value = "TransactionShufflerType::NoShuffling"))]
+    pub transaction_shuffler_type: TransactionShufflerType,
 }
 
 pub struct Pipeline<V> {
@@ -107,8 +110,11 @@ where
 
         let mut join_handles = vec![];
 
-        let mut partitioning_stage =
-            BlockPartitioningStage::new(num_partitioner_shards, &config.partitioner_config);
+        let mut partitioning_stage = BlockPartitioningStage::new(
+            num_partitioner_shards,
+            &config.partitioner_config,
+            config.transaction_shuffler_type.clone(),
+        );
 
         let mut exe = TransactionExecutor::new(executor_1, parent_block_id, ledger_update_sender);
 
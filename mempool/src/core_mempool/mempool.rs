@@ -12,7 +12,9 @@ use crate::{
     },
     counters,
     logging::{LogEntry, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{
+        MempoolSummary, MultiBucketTimelineIndexIds, PendingTransactionStatus,
+    },
 };
 use aptos_config::config::NodeConfig;
 use aptos_consensus_types::common::TransactionInProgress;
@@ -407,6 +409,19 @@ impl Mempool {
         self.transactions.gen_snapshot()
     }
 
+    pub(crate) fn get_account_transaction_statuses(
+        &self,
+        address: &AccountAddress,
+        max_queue_position: usize,
+    ) -> Vec<PendingTransactionStatus> {
+        self.transactions
+            .get_account_transaction_statuses(address, max_queue_position)
+    }
+
+    pub(crate) fn gen_summary(&self) -> MempoolSummary {
+        self.transactions.gen_summary()
+    }
+
     #[cfg(test)]
     pub fn get_parking_lot_size(&self) -> usize {
         self.transactions.get_parking_lot_size()
@@ -15,7 +15,9 @@ use crate::{
     counters,
     counters::{BROADCAST_BATCHED_LABEL, BROADCAST_READY_LABEL, CONSENSUS_READY_LABEL},
     logging::{LogEntry, LogEvent, LogSchema, TxnsLog},
-    shared_mempool::types::MultiBucketTimelineIndexIds,
+    shared_mempool::types::{
+        MempoolSummary, MultiBucketTimelineIndexIds, PendingTransactionStatus,
+    },
 };
 use aptos_config::config::MempoolConfig;
 use aptos_crypto::HashValue;
@@ -27,7 +29,7 @@ use aptos_types::{
 };
 use std::{
     cmp::max,
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     mem::size_of,
     ops::Bound,
     time::{Duration, SystemTime},
@@ -69,6 +71,8 @@ pub struct TransactionStore {
     size_bytes: usize,
     // keeps track of txns that were resubmitted with higher gas
     gas_upgraded_index: HashMap<TxnPointer, u64>,
+    // number of txns removed since startup, by reason
+    removal_counts: BTreeMap<&'static str, u64>,
 
     // configuration
     capacity: usize,
@@ -101,6 +105,7 @@ impl TransactionStore {
             // estimated size in bytes
             size_bytes: 0,
             gas_upgraded_index: HashMap::new(),
+            removal_counts: BTreeMap::new(),
 
             // configuration
             capacity: config.capacity,
@@ -224,7 +229,7 @@ impl TransactionStore {
                 } else if current_version.get_gas_price() < txn.get_gas_price() {
                     // Update txn if gas unit price is a larger value than before
                     if let Some(txn) = txns.remove(&txn_seq_num) {
-                        self.index_remove(&txn);
+                        self.index_remove(&txn, counters::REMOVED_GAS_UPGRADED_LABEL);
                     };
                     gas_upgraded = true;
                 } else if current_version.get_gas_price() > txn.get_gas_price() {
@@ -335,7 +340,7 @@ impl TransactionStore {
                             txn.sequence_info.transaction_sequence_number
                         ))
                     );
-                    self.index_remove(&txn);
+                    self.index_remove(&txn, counters::REMOVED_MEMPOOL_FULL_LABEL);
                 }
             }
         }
@@ -494,7 +499,7 @@ impl TransactionStore {
                     transaction.get_sender(),
                     transaction.sequence_info.transaction_sequence_number,
                 );
-                self.index_remove(transaction);
+                self.index_remove(transaction, counters::REMOVED_COMMITTED_LABEL);
             }
             trace!(
                 LogSchema::new(LogEntry::CleanCommittedTxn).txns(rm_txns),
@@ -532,7 +537,7 @@ impl TransactionStore {
             if let Some(txns) = self.transactions.get_mut(account) {
                 txns.remove(&sequence_number);
             }
-            self.index_remove(&txn_to_remove);
+            self.index_remove(&txn_to_remove, counters::REMOVED_REJECTED_LABEL);
 
             if aptos_logger::enabled!(Level::Trace) {
                 let mut txns_log = TxnsLog::new();
@@ -546,8 +551,10 @@ impl TransactionStore {
     }
 
     /// Removes transaction from all indexes. Only call after removing from main transactions DS.
-    fn index_remove(&mut self, txn: &MempoolTransaction) {
+    /// `reason` is the label the removal is counted under in the summary.
+    fn index_remove(&mut self, txn: &MempoolTransaction, reason: &'static str) {
         counters::CORE_MEMPOOL_REMOVED_TXNS.inc();
+        *self.removal_counts.entry(reason).or_insert(0) += 1;
         self.system_ttl_index.remove(txn);
         self.expiration_time_index.remove(txn);
         self.priority_index.remove(txn);
@@ -742,7 +749,7 @@ impl TransactionStore {
                     }
 
                     // remove txn
-                    self.index_remove(&txn);
+                    self.index_remove(&txn, metric_label);
                 }
             }
         }
@@ -779,6 +786,77 @@ impl TransactionStore {
         txns_log
     }
 
+    /// Status of the transactions of the account, in sequence number order. Only the first
+    /// `max_queue_position` transactions of the queue are searched for their queue position.
+    pub(crate) fn get_account_transaction_statuses(
+        &self,
+        address: &AccountAddress,
+        max_queue_position: usize,
+    ) -> Vec<PendingTransactionStatus> {
+        let txns = match self.transactions.get(address) {
+            Some(txns) => txns,
+            None => return vec![],
+        };
+
+        // Walk the front of the queue until all the ready transactions of the account are found.
+        let num_ready = txns
+            .values()
+            .filter(|txn| self.priority_index.contains(txn))
+            .count();
+        let mut queue_positions = HashMap::new();
+        if num_ready > 0 {
+            for (position, key) in self
+                .priority_index
+                .iter()
+                .take(max_queue_position)
+                .enumerate()
+            {
+                if key.address == *address {
+                    queue_positions
+                        .insert(key.sequence_number.transaction_sequence_number, position);
+                    if queue_positions.len() == num_ready {
+                        break;
+                    }
+                }
+            }
+        }
+
+        txns.iter()
+            .map(|(seq_num, txn)| PendingTransactionStatus {
+                txn: txn.txn.clone(),
+                ready: self.priority_index.contains(txn),
+                queue_position: queue_positions.get(seq_num).cloned(),
+                ranking_score: txn.ranking_score,
+                bucket: self.get_bucket(txn.ranking_score).to_string(),
+                insertion_time: txn.insertion_info.insertion_time,
+                timeline_state: txn.timeline_state,
+                submitted_by: txn.insertion_info.submitted_by,
+            })
+            .collect()
+    }
+
+    pub(crate) fn gen_summary(&self) -> MempoolSummary {
+        MempoolSummary {
+            num_transactions: self.system_ttl_index.size(),
+            size_bytes: self.size_bytes,
+            capacity: self.capacity,
+            capacity_bytes: self.capacity_bytes,
+            num_ready_transactions: self.priority_index.size(),
+            parking_lot_size: self.parking_lot_index.size(),
+            bucket_sizes: self
+                .timeline_index
+                .get_sizes()
+                .into_iter()
+                .map(|(bucket, size)| (bucket.to_string(), size))
+                .collect(),
+            removal_counts: self
+                .removal_counts
+                .iter()
+                .map(|(reason, count)| (reason.to_string(), *count))
+                .collect(),
+        }
+    }
+
     #[cfg(test)]
     pub(crate) fn get_parking_lot_size(&self) -> usize {
         self.parking_lot_index.size()
@@ -36,6 +36,12 @@ pub const BROADCAST_BATCHED_LABEL: &str = "broadcast_batched";
 pub const GC_SYSTEM_TTL_LABEL: &str = "system_ttl";
 pub const GC_CLIENT_EXP_LABEL: &str = "client_expiration";
 
+// Core mempool removal reason labels, besides the GC type labels
+pub const REMOVED_COMMITTED_LABEL: &str = "committed";
+pub const REMOVED_REJECTED_LABEL: &str = "rejected";
+pub const REMOVED_GAS_UPGRADED_LABEL: &str = "gas_upgraded";
+pub const REMOVED_MEMPOOL_FULL_LABEL: &str = "mempool_full";
+
 // Core mempool GC txn status label
 pub const GC_ACTIVE_TXN_LABEL: &str = "active";
 pub const GC_PARKED_TXN_LABEL: &str = "parked";
@@ -63,6 +69,8 @@ pub const SUCCESS_LABEL: &str = "success";
 // Bounded executor task labels
 pub const CLIENT_EVENT_LABEL: &str = "client_event";
 pub const CLIENT_EVENT_GET_TXN_LABEL: &str = "client_event_get_txn";
+pub const CLIENT_EVENT_GET_ACCOUNT_TXNS_LABEL: &str = "client_event_get_account_txns";
+pub const CLIENT_EVENT_GET_SUMMARY_LABEL: &str = "client_event_get_summary";
 pub const RECONFIG_EVENT_LABEL: &str = "reconfig";
 pub const PEER_BROADCAST_EVENT_LABEL: &str = "peer_broadcast";
 
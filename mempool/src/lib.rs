@@ -58,12 +58,14 @@
 
 #[cfg(any(test, feature = "fuzzing"))]
 mod tests;
+pub use core_mempool::{SubmittedBy, TimelineState};
 pub use shared_mempool::{
     bootstrap, network,
     network::MempoolSyncMsg,
     types::{
-        MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver, QuorumStoreRequest,
-        QuorumStoreResponse, SubmissionStatus,
+        CommittedTransactions, MempoolClientRequest, MempoolClientSender, MempoolEventsReceiver,
+        MempoolSummary, PendingTransactionStatus, QuorumStoreRequest, QuorumStoreResponse,
+        SubmissionStatus,
     },
 };
 #[cfg(any(test, feature = "fuzzing"))]
@@ -156,6 +156,9 @@ pub enum LogEntry {
     ReconfigUpdate,
     JsonRpc,
     GetTransaction,
+    GetAccountTransactions,
+    GetSummary,
+    SubscribeToCommits,
     GetBlock,
     QuorumStore,
     StateSyncCommit,
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 //! Processes that are directly spawned by shared mempool runtime initialization
-use super::types::MempoolClientRequest;
+use super::types::{CommittedTransactions, MempoolClientRequest};
 use crate::{
     core_mempool::{CoreMempool, TimelineState},
     counters,
@@ -177,6 +177,55 @@ async fn handle_client_request<NetworkClient, TransactionValidator>(
                 ))
                 .await;
         },
+        MempoolClientRequest::GetAccountTransactions(address, callback) => {
+            // This timer measures how long it took for the bounded executor to *schedule* the
+            // task.
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_GET_ACCOUNT_TXNS_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            // This timer measures how long it took for the task to go from scheduled to started.
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_GET_ACCOUNT_TXNS_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_get_account_transactions(
+                    smp.clone(),
+                    address,
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        },
+        MempoolClientRequest::GetSummary(callback) => {
+            // This timer measures how long it took for the bounded executor to *schedule* the
+            // task.
+            let _timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_GET_SUMMARY_LABEL,
+                counters::SPAWN_LABEL,
+            );
+            // This timer measures how long it took for the task to go from scheduled to started.
+            let task_start_timer = counters::task_spawn_latency_timer(
+                counters::CLIENT_EVENT_GET_SUMMARY_LABEL,
+                counters::START_LABEL,
+            );
+            bounded_executor
+                .spawn(tasks::process_client_get_summary(
+                    smp.clone(),
+                    callback,
+                    task_start_timer,
+                ))
+                .await;
+        },
+        MempoolClientRequest::SubscribeToCommits(callback) => {
+            if callback.send(smp.commit_notifications.subscribe()).is_err() {
+                warn!(LogSchema::event_log(
+                    LogEntry::SubscribeToCommits,
+                    LogEvent::CallbackFail
+                ));
+            }
+        },
     }
 }
 
@@ -213,6 +262,13 @@ fn handle_commit_notification<NetworkClient, TransactionValidator>(
             .collect(),
         msg.block_timestamp_usecs,
     );
+    // Sending only fails if nobody is subscribed.
+    let _ = smp
+        .commit_notifications
+        .send(Arc::new(CommittedTransactions {
+            transactions: msg.transactions.clone(),
+            block_timestamp_usecs: msg.block_timestamp_usecs,
+        }));
     smp.validator.write().notify_commit();
     let counter_result = if mempool_listener.ack_commit_notification(msg).is_err() {
         error!(LogSchema::event_log(
@@ -4,7 +4,7 @@
 
 //! Objects used by/related to shared mempool
 use crate::{
-    core_mempool::CoreMempool,
+    core_mempool::{CoreMempool, SubmittedBy, TimelineState},
     network::{MempoolNetworkInterface, MempoolSyncMsg},
 };
 use anyhow::Result;
@@ -15,12 +15,14 @@ use aptos_config::{
 use aptos_consensus_types::common::{RejectedTransactionSummary, TransactionInProgress};
 use aptos_crypto::HashValue;
 use aptos_infallible::{Mutex, RwLock};
+use aptos_mempool_notifications::CommittedTransaction;
 use aptos_network::{
     application::interface::NetworkClientInterface, transport::ConnectionMetadata,
 };
 use aptos_storage_interface::DbReader;
 use aptos_types::{
-    mempool_status::MempoolStatus, transaction::SignedTransaction, vm_status::DiscardedVMStatus,
+    account_address::AccountAddress, mempool_status::MempoolStatus, transaction::SignedTransaction,
+    vm_status::DiscardedVMStatus,
 };
 use aptos_vm_validator::vm_validator::TransactionValidation;
 use futures::{
@@ -38,7 +40,10 @@ use std::{
     task::Waker,
     time::{Instant, SystemTime},
 };
-use tokio::runtime::Handle;
+use tokio::{runtime::Handle, sync::broadcast};
+
+/// Number of commit notifications buffered for subscribers that fall behind
+const COMMIT_NOTIFICATIONS_CAPACITY: usize = 256;
 
 /// Struct that owns all dependencies required by shared mempool routines.
 #[derive(Clone)]
@@ -50,6 +55,7 @@ pub(crate) struct SharedMempool<NetworkClient, TransactionValidator> {
     pub validator: Arc<RwLock<TransactionValidator>>,
     pub subscribers: Vec<UnboundedSender<SharedMempoolNotification>>,
     pub broadcast_within_validator_network: Arc<RwLock<bool>>,
+    pub commit_notifications: broadcast::Sender<Arc<CommittedTransactions>>,
 }
 
 impl<
@@ -75,6 +81,7 @@ impl<
             validator,
             subscribers,
             broadcast_within_validator_network: Arc::new(RwLock::new(true)),
+            commit_notifications: broadcast::channel(COMMIT_NOTIFICATIONS_CAPACITY).0,
         }
     }
 
@@ -230,6 +237,59 @@ pub type SubmissionStatusBundle = (SignedTransaction, SubmissionStatus);
 pub enum MempoolClientRequest {
     SubmitTransaction(SignedTransaction, oneshot::Sender<Result<SubmissionStatus>>),
     GetTransactionByHash(HashValue, oneshot::Sender<Option<SignedTransaction>>),
+    GetAccountTransactions(
+        AccountAddress,
+        oneshot::Sender<Vec<PendingTransactionStatus>>,
+    ),
+    GetSummary(oneshot::Sender<MempoolSummary>),
+    SubscribeToCommits(oneshot::Sender<broadcast::Receiver<Arc<CommittedTransactions>>>),
+}
+
+/// User transactions committed in a block, as notified to mempool by state sync
+#[derive(Clone, Debug)]
+pub struct CommittedTransactions {
+    pub transactions: Vec<CommittedTransaction>,
+    pub block_timestamp_usecs: u64,
+}
+
+/// Number of transactions at the front of the queue of ready transactions searched for the
+/// queue position of the transactions of an account. Transactions further back in the queue
+/// are reported without a position, so that a request doesn't walk the whole queue while
+/// holding the mempool lock.
+pub const MAX_QUEUE_POSITION: usize = 10_000;
+
+/// Status of a transaction waiting in mempool
+#[derive(Clone, Debug)]
+pub struct PendingTransactionStatus {
+    pub txn: SignedTransaction,
+    /// Whether the transaction can be pulled into the next block. Otherwise it's parked until
+    /// the sequence number gap before it is filled.
+    pub ready: bool,
+    /// Position in the queue of ready transactions consensus pulls from, starting at 0. Not
+    /// set if the transaction isn't ready, or is further back than the positions searched.
+    pub queue_position: Option<usize>,
+    pub ranking_score: u64,
+    /// Broadcast bucket of the ranking score
+    pub bucket: String,
+    pub insertion_time: SystemTime,
+    pub timeline_state: TimelineState,
+    pub submitted_by: SubmittedBy,
+}
+
+/// Node-wide summary of the content of mempool
+#[derive(Clone, Debug)]
+pub struct MempoolSummary {
+    pub num_transactions: usize,
+    pub size_bytes: usize,
+    pub capacity: usize,
+    pub capacity_bytes: usize,
+    /// Number of transactions consensus can pull
+    pub num_ready_transactions: usize,
+    pub parking_lot_size: usize,
+    /// Number of transactions ready for broadcast in each broadcast bucket
+    pub bucket_sizes: Vec<(String, usize)>,
+    /// Number of transactions removed since the node started, by reason
+    pub removal_counts: BTreeMap<String, u64>,
 }
 
 pub type MempoolClientSender = mpsc::Sender<MempoolClientRequest>;
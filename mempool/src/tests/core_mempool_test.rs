@@ -917,3 +917,59 @@ fn test_include_gas_upgraded() {
     let batch = pool.get_batch(10, 10240, true, true, vec![high_gas_txn, low_gas_txn]);
     assert_eq!(batch.len(), 0);
 }
+
+#[test]
+fn test_account_transaction_statuses_and_summary() {
+    let (mut pool, _) = setup_mempool_with_broadcast_buckets(vec![0, 10]);
+    // The transaction with sequence number 3 is parked behind the gap at 2.
+    add_txns_to_mempool(&mut pool, vec![
+        TestTransaction::new(0, 0, 1),
+        TestTransaction::new(0, 1, 1),
+        TestTransaction::new(0, 3, 1),
+        TestTransaction::new(1, 0, 20),
+    ]);
+
+    let statuses = pool.get_account_transaction_statuses(&TestTransaction::get_address(0), 10);
+    let view: Vec<_> = statuses
+        .iter()
+        .map(|status| {
+            (
+                status.txn.sequence_number(),
+                status.ready,
+                status.queue_position,
+            )
+        })
+        .collect();
+    // The transaction of the other account is ahead in the queue with a higher gas price.
+    assert_eq!(view, vec![(0, true, Some(1)), (1, true, Some(2)), (3, false, None)]);
+    assert_eq!(statuses[0].bucket, "0");
+    assert!(matches!(statuses[0].timeline_state, TimelineState::Ready(_)));
+    assert_eq!(statuses[2].timeline_state, TimelineState::NotReady);
+    assert_eq!(statuses[0].submitted_by, SubmittedBy::Downstream);
+    assert!(pool
+        .get_account_transaction_statuses(&TestTransaction::get_address(2), 10)
+        .is_empty());
+
+    // Only the front of the queue is searched for the queue positions.
+    let queue_positions: Vec<_> = pool
+        .get_account_transaction_statuses(&TestTransaction::get_address(0), 2)
+        .iter()
+        .map(|status| status.queue_position)
+        .collect();
+    assert_eq!(queue_positions, vec![Some(1), None, None]);
+
+    let summary = pool.gen_summary();
+    assert_eq!(summary.num_transactions, 4);
+    assert_eq!(summary.num_ready_transactions, 3);
+    assert_eq!(summary.parking_lot_size, 1);
+    assert_eq!(summary.bucket_sizes, vec![
+        ("0".to_string(), 2),
+        ("10".to_string(), 1)
+    ]);
+    assert!(summary.removal_counts.is_empty());
+
+    pool.commit_transaction(&TestTransaction::get_address(0), 0);
+    let summary = pool.gen_summary();
+    assert_eq!(summary.num_transactions, 3);
+    assert_eq!(summary.removal_counts.get("committed"), Some(&1));
+}
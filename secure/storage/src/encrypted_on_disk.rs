@@ -0,0 +1,374 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    from_base64, to_base64, CryptoKVStorage, Error, GetResponse, KVStorage, OnDiskStorage,
+};
+use aptos_infallible::Mutex;
+use aptos_logger::info;
+use aptos_temppath::TempPath;
+use aptos_time_service::{TimeService, TimeServiceTrait};
+use rand::{rngs::OsRng, RngCore};
+use ring::{
+    aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN},
+    hkdf, pbkdf2,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    fmt,
+    fs::{self, File, OpenOptions},
+    io::Write,
+    num::NonZeroU32,
+    path::{Path, PathBuf},
+};
+
+/// Version of the format of the encrypted file
+const FILE_VERSION: u8 = 1;
+/// Length of the AES-256 keys derived from the encryption key
+const KEY_LEN: usize = 32;
+/// Length of the salt the keys are derived with
+const SALT_LEN: usize = 32;
+/// PBKDF2 iterations for passphrases, as recommended by OWASP for PBKDF2-HMAC-SHA256
+const PBKDF2_ITERATIONS: u32 = 600_000;
+/// HKDF info binding the keys derived from raw keys to this storage
+const HKDF_INFO: &[u8] = b"APTOS_ENCRYPTED_ON_DISK_STORAGE";
+
+/// The secret EncryptedOnDiskStorage derives its encryption keys from
+#[derive(Clone)]
+pub enum EncryptionKey {
+    /// A passphrase, stretched with PBKDF2-HMAC-SHA256
+    Passphrase(Vec<u8>),
+    /// A uniformly random 32 byte key, expanded with HKDF-SHA256
+    Raw([u8; KEY_LEN]),
+}
+
+impl EncryptionKey {
+    /// Parses a base64 encoded 32 byte key
+    pub fn from_base64(encoded: &str) -> Result<Self, Error> {
+        let bytes = base64::decode(encoded.trim())?;
+        let key = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            Error::SerializationError(format!(
+                "Expected a {} byte encryption key, got {} bytes",
+                KEY_LEN,
+                bytes.len()
+            ))
+        })?;
+        Ok(EncryptionKey::Raw(key))
+    }
+
+    fn kdf(&self) -> Kdf {
+        match self {
+            EncryptionKey::Passphrase(_) => Kdf::Pbkdf2Sha256 {
+                iterations: PBKDF2_ITERATIONS,
+            },
+            EncryptionKey::Raw(_) => Kdf::HkdfSha256,
+        }
+    }
+
+    fn derive(&self, kdf: &Kdf, salt: &[u8]) -> Result<[u8; KEY_LEN], Error> {
+        let mut key = [0u8; KEY_LEN];
+        match (self, kdf) {
+            (EncryptionKey::Passphrase(passphrase), Kdf::Pbkdf2Sha256 { iterations }) => {
+                let iterations = NonZeroU32::new(*iterations).ok_or_else(|| {
+                    Error::SerializationError("PBKDF2 iterations must not be 0".into())
+                })?;
+                pbkdf2::derive(
+                    pbkdf2::PBKDF2_HMAC_SHA256,
+                    iterations,
+                    salt,
+                    passphrase,
+                    &mut key,
+                );
+            },
+            (EncryptionKey::Raw(raw_key), Kdf::HkdfSha256) => {
+                hkdf::Salt::new(hkdf::HKDF_SHA256, salt)
+                    .extract(raw_key)
+                    .expand(&[HKDF_INFO], hkdf::HKDF_SHA256)
+                    .and_then(|okm| okm.fill(&mut key))
+                    .map_err(|_| Error::InternalError("Unable to derive the key".into()))?;
+            },
+            (_, kdf) => {
+                return Err(Error::DecryptionError(format!(
+                    "The storage was encrypted with a key derived by {:?}",
+                    kdf
+                )));
+            },
+        }
+        Ok(key)
+    }
+}
+
+// The key material must not end up in logs.
+impl fmt::Debug for EncryptionKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncryptionKey::Passphrase(_) => write!(f, "EncryptionKey::Passphrase(<elided>)"),
+            EncryptionKey::Raw(_) => write!(f, "EncryptionKey::Raw(<elided>)"),
+        }
+    }
+}
+
+/// How the key encrypting the file is derived from the EncryptionKey
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case", tag = "type")]
+enum Kdf {
+    Pbkdf2Sha256 { iterations: u32 },
+    HkdfSha256,
+}
+
+/// The content of the file: the JSON of OnDiskStorage encrypted with AES-256-GCM, with the salt
+/// as associated data.
+#[derive(Deserialize, Serialize)]
+struct EncryptedFile {
+    version: u8,
+    kdf: Kdf,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    salt: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    nonce: Vec<u8>,
+    #[serde(serialize_with = "to_base64", deserialize_with = "from_base64")]
+    ciphertext: Vec<u8>,
+}
+
+/// A key derived from the current EncryptionKey, cached as PBKDF2 is slow by design
+struct DerivedKey {
+    kdf: Kdf,
+    salt: Vec<u8>,
+    key: [u8; KEY_LEN],
+}
+
+/// EncryptedOnDiskStorage is an OnDiskStorage whose file is encrypted at rest with AES-256-GCM,
+/// under a key derived from a passphrase or from a raw key. Like OnDiskStorage, it is intended
+/// for single threads, and every write replaces the whole file atomically.
+///
+/// The encryption key can be rotated either with `rotate_encryption_key`, or by restarting with
+/// the new key as the current key and the old one as the previous key: the file is then
+/// re-encrypted with the current key the first time it is read.
+pub struct EncryptedOnDiskStorage {
+    file_path: PathBuf,
+    temp_path: TempPath,
+    key: EncryptionKey,
+    previous_key: Option<EncryptionKey>,
+    derived_key: Mutex<Option<DerivedKey>>,
+    time_service: TimeService,
+}
+
+impl EncryptedOnDiskStorage {
+    pub fn new(
+        file_path: PathBuf,
+        key: EncryptionKey,
+        previous_key: Option<EncryptionKey>,
+    ) -> Self {
+        Self::new_with_time_service(file_path, key, previous_key, TimeService::real())
+    }
+
+    fn new_with_time_service(
+        file_path: PathBuf,
+        key: EncryptionKey,
+        previous_key: Option<EncryptionKey>,
+        time_service: TimeService,
+    ) -> Self {
+        if !file_path.exists() {
+            create_file(&file_path)
+                .unwrap_or_else(|_| panic!("Unable to create storage at path: {:?}", file_path));
+        }
+
+        // The parent will be one when only a filename is supplied. Therefore use the current
+        // working directory provided by PathBuf::new().
+        let file_dir = file_path
+            .parent()
+            .map_or(PathBuf::new(), |p| p.to_path_buf());
+
+        Self {
+            file_path,
+            temp_path: TempPath::new_with_temp_dir(file_dir),
+            key,
+            previous_key,
+            derived_key: Mutex::new(None),
+            time_service,
+        }
+    }
+
+    /// Re-encrypts the storage with a new key. The previous key is no longer needed afterwards.
+    pub fn rotate_encryption_key(&mut self, key: EncryptionKey) -> Result<(), Error> {
+        let data = self.read()?;
+        self.key = key;
+        self.previous_key = None;
+        *self.derived_key.lock() = None;
+        self.write(&data)
+    }
+
+    /// Copies all the entries of a plaintext OnDiskStorage, with their last update time. Fails
+    /// without writing anything if any of the entries is already set, and returns the number of
+    /// entries copied otherwise.
+    pub fn import_on_disk_storage(&mut self, storage: &OnDiskStorage) -> Result<usize, Error> {
+        let entries = storage.read()?;
+        let mut data = self.read()?;
+        if let Some(key) = entries.keys().find(|key| data.contains_key(*key)) {
+            return Err(Error::KeyAlreadyExists(key.clone()));
+        }
+        let num_entries = entries.len();
+        data.extend(entries);
+        self.write(&data)?;
+        Ok(num_entries)
+    }
+
+    fn read(&self) -> Result<HashMap<String, Value>, Error> {
+        let contents = fs::read(&self.file_path)?;
+        if contents.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let file: EncryptedFile = serde_json::from_slice(&contents)?;
+        if file.version != FILE_VERSION {
+            return Err(Error::SerializationError(format!(
+                "Unsupported encrypted storage version: {}",
+                file.version
+            )));
+        }
+
+        let error = match self
+            .current_key(&file.kdf, &file.salt)
+            .and_then(|key| decrypt(&key, &file))
+        {
+            Ok(data) => return Ok(data),
+            Err(error) => error,
+        };
+        let previous_key = match &self.previous_key {
+            Some(previous_key) => previous_key,
+            None => return Err(error),
+        };
+        let key = previous_key.derive(&file.kdf, &file.salt)?;
+        let data = decrypt(&key, &file)?;
+        info!(
+            "Re-encrypting the secure storage at {:?} with the current key",
+            self.file_path
+        );
+        self.write(&data)?;
+        Ok(data)
+    }
+
+    fn write(&self, data: &HashMap<String, Value>) -> Result<(), Error> {
+        let (kdf, salt, key) = self.encryption_key()?;
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+        let mut ciphertext = serde_json::to_vec(data)?;
+        aead_key(&key)?
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(&salt),
+                &mut ciphertext,
+            )
+            .map_err(|_| Error::InternalError("Unable to encrypt the storage".into()))?;
+        let contents = serde_json::to_vec(&EncryptedFile {
+            version: FILE_VERSION,
+            kdf,
+            salt,
+            nonce: nonce.to_vec(),
+            ciphertext,
+        })?;
+
+        let mut file = create_file(self.temp_path.path())?;
+        file.write_all(&contents)?;
+        file.sync_all()?;
+        fs::rename(&self.temp_path, &self.file_path)?;
+        Ok(())
+    }
+
+    /// The key derived from the current EncryptionKey with the given parameters
+    fn current_key(&self, kdf: &Kdf, salt: &[u8]) -> Result<[u8; KEY_LEN], Error> {
+        let mut derived_key = self.derived_key.lock();
+        if let Some(derived_key) = derived_key.as_ref() {
+            if &derived_key.kdf == kdf && derived_key.salt == salt {
+                return Ok(derived_key.key);
+            }
+        }
+        let key = self.key.derive(kdf, salt)?;
+        *derived_key = Some(DerivedKey {
+            kdf: kdf.clone(),
+            salt: salt.to_vec(),
+            key,
+        });
+        Ok(key)
+    }
+
+    /// The parameters and key to encrypt with: the last ones derived from the current
+    /// EncryptionKey, or new ones with a fresh salt.
+    fn encryption_key(&self) -> Result<(Kdf, Vec<u8>, [u8; KEY_LEN]), Error> {
+        if let Some(derived_key) = self.derived_key.lock().as_ref() {
+            return Ok((
+                derived_key.kdf.clone(),
+                derived_key.salt.clone(),
+                derived_key.key,
+            ));
+        }
+        let kdf = self.key.kdf();
+        let mut salt = vec![0u8; SALT_LEN];
+        OsRng.fill_bytes(&mut salt);
+        let key = self.current_key(&kdf, &salt)?;
+        Ok((kdf, salt, key))
+    }
+}
+
+impl KVStorage for EncryptedOnDiskStorage {
+    fn available(&self) -> Result<(), Error> {
+        Ok(())
+    }
+
+    fn get<V: DeserializeOwned>(&self, key: &str) -> Result<GetResponse<V>, Error> {
+        let mut data = self.read()?;
+        data.remove(key)
+            .ok_or_else(|| Error::KeyNotSet(key.to_string()))
+            .and_then(|value| serde_json::from_value(value).map_err(|e| e.into()))
+    }
+
+    fn set<V: Serialize>(&mut self, key: &str, value: V) -> Result<(), Error> {
+        let now = self.time_service.now_secs();
+        let mut data = self.read()?;
+        data.insert(
+            key.to_string(),
+            serde_json::to_value(&GetResponse::new(value, now))?,
+        );
+        self.write(&data)
+    }
+
+    #[cfg(any(test, feature = "testing"))]
+    fn reset_and_clear(&mut self) -> Result<(), Error> {
+        self.write(&HashMap::new())
+    }
+}
+
+impl CryptoKVStorage for EncryptedOnDiskStorage {}
+
+fn aead_key(key: &[u8; KEY_LEN]) -> Result<LessSafeKey, Error> {
+    UnboundKey::new(&AES_256_GCM, key)
+        .map(LessSafeKey::new)
+        .map_err(|_| Error::InternalError("Invalid AES-256-GCM key".into()))
+}
+
+fn decrypt(key: &[u8; KEY_LEN], file: &EncryptedFile) -> Result<HashMap<String, Value>, Error> {
+    let nonce = Nonce::try_assume_unique_for_key(&file.nonce)
+        .map_err(|_| Error::SerializationError("Invalid nonce".into()))?;
+    let mut in_out = file.ciphertext.clone();
+    let plaintext = aead_key(key)?
+        .open_in_place(nonce, Aad::from(&file.salt), &mut in_out)
+        .map_err(|_| {
+            Error::DecryptionError(
+                "Wrong encryption key, or the storage has been tampered with".into(),
+            )
+        })?;
+    Ok(serde_json::from_slice(plaintext)?)
+}
+
+/// Creates or truncates a file only readable by its owner
+fn create_file(path: &Path) -> std::io::Result<File> {
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)
+}
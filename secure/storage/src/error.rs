@@ -22,6 +22,8 @@ pub enum Error {
     SerializationError(String),
     #[error("Key version not found, key name: {0}, version: {1}")]
     KeyVersionNotFound(String, String),
+    #[error("Decryption error: {0}")]
+    DecryptionError(String),
 }
 
 impl From<base64::DecodeError> for Error {
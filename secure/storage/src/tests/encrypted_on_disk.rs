@@ -0,0 +1,100 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    tests::suite, EncryptedOnDiskStorage, EncryptionKey, Error, KVStorage, OnDiskStorage, Storage,
+};
+use aptos_temppath::TempPath;
+use std::fs;
+
+const U64_KEY: &str = "U64_Key";
+
+fn raw_key(byte: u8) -> EncryptionKey {
+    EncryptionKey::Raw([byte; 32])
+}
+
+#[test]
+fn encrypted_on_disk() {
+    let path_buf = TempPath::new().path().to_path_buf();
+    let mut storage = Storage::from(EncryptedOnDiskStorage::new(path_buf, raw_key(1), None));
+    suite::execute_all_storage_tests(&mut storage);
+}
+
+#[test]
+fn test_encrypted_at_rest() {
+    let temp_path = TempPath::new();
+    let path_buf = temp_path.path().to_path_buf();
+    let passphrase = EncryptionKey::Passphrase(b"correct horse battery staple".to_vec());
+    let mut storage = EncryptedOnDiskStorage::new(path_buf.clone(), passphrase.clone(), None);
+    storage.set(U64_KEY, 0xDEADBEEF_u64).unwrap();
+
+    let contents = fs::read_to_string(&path_buf).unwrap();
+    assert!(!contents.contains(U64_KEY));
+    assert!(!contents.contains(&0xDEADBEEF_u64.to_string()));
+
+    let storage = EncryptedOnDiskStorage::new(path_buf.clone(), passphrase, None);
+    assert_eq!(storage.get::<u64>(U64_KEY).unwrap().value, 0xDEADBEEF);
+
+    let wrong_passphrase = EncryptionKey::Passphrase(b"wrong".to_vec());
+    let storage = EncryptedOnDiskStorage::new(path_buf.clone(), wrong_passphrase, None);
+    assert!(matches!(
+        storage.get::<u64>(U64_KEY).unwrap_err(),
+        Error::DecryptionError(_)
+    ));
+    let storage = EncryptedOnDiskStorage::new(path_buf, raw_key(1), None);
+    assert!(matches!(
+        storage.get::<u64>(U64_KEY).unwrap_err(),
+        Error::DecryptionError(_)
+    ));
+}
+
+#[test]
+fn test_rotate_encryption_key() {
+    let temp_path = TempPath::new();
+    let path_buf = temp_path.path().to_path_buf();
+    let mut storage = EncryptedOnDiskStorage::new(path_buf.clone(), raw_key(1), None);
+    storage.set(U64_KEY, 1_u64).unwrap();
+    storage.rotate_encryption_key(raw_key(2)).unwrap();
+    assert_eq!(storage.get::<u64>(U64_KEY).unwrap().value, 1);
+
+    let storage = EncryptedOnDiskStorage::new(path_buf.clone(), raw_key(1), None);
+    assert!(storage.get::<u64>(U64_KEY).is_err());
+    let storage = EncryptedOnDiskStorage::new(path_buf, raw_key(2), None);
+    assert_eq!(storage.get::<u64>(U64_KEY).unwrap().value, 1);
+}
+
+#[test]
+fn test_rotate_encryption_key_on_restart() {
+    let temp_path = TempPath::new();
+    let path_buf = temp_path.path().to_path_buf();
+    let mut storage = EncryptedOnDiskStorage::new(path_buf.clone(), raw_key(1), None);
+    storage.set(U64_KEY, 1_u64).unwrap();
+
+    // The first read with the previous key re-encrypts the file with the current key.
+    let storage = EncryptedOnDiskStorage::new(path_buf.clone(), raw_key(2), Some(raw_key(1)));
+    assert_eq!(storage.get::<u64>(U64_KEY).unwrap().value, 1);
+    let storage = EncryptedOnDiskStorage::new(path_buf, raw_key(2), None);
+    assert_eq!(storage.get::<u64>(U64_KEY).unwrap().value, 1);
+}
+
+#[test]
+fn test_import_on_disk_storage() {
+    let plaintext_path = TempPath::new();
+    let mut plaintext = OnDiskStorage::new(plaintext_path.path().to_path_buf());
+    plaintext.set(U64_KEY, 1_u64).unwrap();
+    let last_update = plaintext.get::<u64>(U64_KEY).unwrap().last_update;
+
+    let encrypted_path = TempPath::new();
+    let mut storage =
+        EncryptedOnDiskStorage::new(encrypted_path.path().to_path_buf(), raw_key(1), None);
+    assert_eq!(storage.import_on_disk_storage(&plaintext).unwrap(), 1);
+    let response = storage.get::<u64>(U64_KEY).unwrap();
+    assert_eq!(response.value, 1);
+    assert_eq!(response.last_update, last_update);
+
+    // Existing entries are never overwritten.
+    assert_eq!(
+        storage.import_on_disk_storage(&plaintext).unwrap_err(),
+        Error::KeyAlreadyExists(U64_KEY.to_string())
+    );
+}
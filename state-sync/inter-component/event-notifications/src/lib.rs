@@ -7,6 +7,7 @@ use anyhow::Result;
 use aptos_channels::{aptos_channel, message_queues::QueueStyle};
 use aptos_id_generator::{IdGenerator, U64IdGenerator};
 use aptos_infallible::RwLock;
+use aptos_logger::prelude::*;
 use aptos_state_view::account_with_state_view::AsAccountWithStateView;
 use aptos_storage_interface::{state_view::DbStateViewAtVersion, DbReader, DbReaderWriter};
 use aptos_types::{
@@ -40,6 +41,7 @@ mod tests;
 // will be retrieved using FIFO ordering.
 const EVENT_NOTIFICATION_CHANNEL_SIZE: usize = 100;
 const RECONFIG_NOTIFICATION_CHANNEL_SIZE: usize = 1;
+const LEDGER_UPDATE_NOTIFICATION_CHANNEL_SIZE: usize = 1;
 
 #[derive(Clone, Debug, Deserialize, Error, PartialEq, Eq, Serialize)]
 pub enum Error {
@@ -82,6 +84,9 @@ pub struct EventSubscriptionService {
     // Reconfig subscription registry
     reconfig_subscriptions: HashMap<SubscriptionId, ReconfigSubscription>,
 
+    // Ledger update subscription registry
+    ledger_update_subscriptions: HashMap<SubscriptionId, LedgerUpdateSubscription>,
+
     // Database to fetch on-chain configuration data
     storage: Arc<RwLock<DbReaderWriter>>,
 
@@ -95,6 +100,7 @@ impl EventSubscriptionService {
             event_key_subscriptions: HashMap::new(),
             subscription_id_to_event_subscription: HashMap::new(),
             reconfig_subscriptions: HashMap::new(),
+            ledger_update_subscriptions: HashMap::new(),
             storage,
             subscription_id_generator: U64IdGenerator::new(),
         }
@@ -186,6 +192,43 @@ impl EventSubscriptionService {
         })
     }
 
+    /// Returns a LedgerUpdateNotificationListener that is sent a notification
+    /// containing the latest synced version every time new transactions are
+    /// committed. Only the latest notification is kept: if the listener falls
+    /// behind, older notifications are dropped, so subscribers should read
+    /// everything up to the notified version from storage.
+    pub fn subscribe_to_ledger_updates(
+        &mut self,
+    ) -> Result<LedgerUpdateNotificationListener, Error> {
+        let (notification_sender, notification_receiver) = aptos_channel::new(
+            QueueStyle::KLAST,
+            LEDGER_UPDATE_NOTIFICATION_CHANNEL_SIZE,
+            None,
+        );
+
+        // Create a new ledger update subscription
+        let subscription_id = self.get_new_subscription_id();
+        let ledger_update_subscription = LedgerUpdateSubscription {
+            notification_sender,
+        };
+
+        // Store the new subscription
+        if self
+            .ledger_update_subscriptions
+            .insert(subscription_id, ledger_update_subscription)
+            .is_some()
+        {
+            return Err(Error::UnexpectedErrorEncountered(format!(
+                "Duplicate ledger update subscription found! This should not occur! ID: {}",
+                subscription_id,
+            )));
+        }
+
+        Ok(LedgerUpdateNotificationListener {
+            notification_receiver,
+        })
+    }
+
     fn get_new_subscription_id(&mut self) -> u64 {
         self.subscription_id_generator.next()
     }
@@ -246,6 +289,21 @@ impl EventSubscriptionService {
         Ok(reconfig_event_found)
     }
 
+    /// This notifies all the ledger update subscribers of the latest synced version.
+    /// Ledger updates are best effort, so failures are logged instead of returned.
+    fn notify_ledger_update_subscribers(&mut self, version: Version) {
+        for (subscription_id, ledger_update_subscription) in
+            self.ledger_update_subscriptions.iter_mut()
+        {
+            if let Err(error) = ledger_update_subscription.notify_subscriber_of_version(version) {
+                warn!(
+                    "Failed to notify ledger update subscription {} of version {}: {:?}",
+                    subscription_id, version, error
+                );
+            }
+        }
+    }
+
     /// This notifies all the reconfiguration subscribers of the on-chain
     /// configurations at the specified version.
     fn notify_reconfiguration_subscribers(&mut self, version: Version) -> Result<(), Error> {
@@ -306,20 +364,21 @@ impl EventSubscriptionService {
 
 impl EventNotificationSender for EventSubscriptionService {
     fn notify_events(&mut self, version: Version, events: Vec<ContractEvent>) -> Result<(), Error> {
-        if events.is_empty() {
-            return Ok(()); // No events!
+        if !events.is_empty() {
+            // Notify event subscribers and check if a reconfiguration event was processed
+            let reconfig_event_processed = self.notify_event_subscribers(version, events)?;
+
+            // If a reconfiguration event was found, also notify the reconfig subscribers
+            // of the new configuration values.
+            if reconfig_event_processed {
+                self.notify_reconfiguration_subscribers(version)?;
+            }
         }
 
-        // Notify event subscribers and check if a reconfiguration event was processed
-        let reconfig_event_processed = self.notify_event_subscribers(version, events)?;
-
-        // If a reconfiguration event was found, also notify the reconfig subscribers
-        // of the new configuration values.
-        if reconfig_event_processed {
-            self.notify_reconfiguration_subscribers(version)
-        } else {
-            Ok(())
-        }
+        // Ledger update subscribers are notified last, of every commit, even one without
+        // events. They can't delay or fail the notifications consensus depends on.
+        self.notify_ledger_update_subscribers(version);
+        Ok(())
     }
 
     fn notify_initial_configs(&mut self, version: Version) -> Result<(), Error> {
@@ -379,6 +438,20 @@ impl ReconfigSubscription {
     }
 }
 
+/// A single ledger update subscription, holding the channel to send the
+/// corresponding notifications.
+struct LedgerUpdateSubscription {
+    pub notification_sender: aptos_channels::aptos_channel::Sender<(), LedgerUpdateNotification>,
+}
+
+impl LedgerUpdateSubscription {
+    fn notify_subscriber_of_version(&mut self, version: Version) -> Result<(), Error> {
+        self.notification_sender
+            .push((), LedgerUpdateNotification { version })
+            .map_err(|error| Error::UnexpectedErrorEncountered(format!("{:?}", error)))
+    }
+}
+
 #[derive(Clone)]
 pub struct DbBackedOnChainConfig {
     pub reader: Arc<dyn DbReader>,
@@ -421,12 +494,21 @@ pub struct ReconfigNotification<P: OnChainConfigProvider> {
     pub on_chain_configs: OnChainConfigPayload<P>,
 }
 
+/// A notification for newly committed transactions.
+#[derive(Debug)]
+pub struct LedgerUpdateNotification {
+    pub version: Version,
+}
+
 /// A subscription listener for on-chain events.
 pub type EventNotificationListener = NotificationListener<EventNotification>;
 
 /// A subscription listener for reconfigurations.
 pub type ReconfigNotificationListener<P> = NotificationListener<ReconfigNotification<P>>;
 
+/// A subscription listener for ledger updates.
+pub type LedgerUpdateNotificationListener = NotificationListener<LedgerUpdateNotification>;
+
 /// The component responsible for listening to subscription notifications.
 #[derive(Debug)]
 pub struct NotificationListener<T> {
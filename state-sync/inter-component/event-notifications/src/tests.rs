@@ -6,7 +6,7 @@
 
 use crate::{
     DbBackedOnChainConfig, Error, EventNotificationListener, EventNotificationSender,
-    EventSubscriptionService, ReconfigNotificationListener,
+    EventSubscriptionService, LedgerUpdateNotificationListener, ReconfigNotificationListener,
 };
 use aptos_db::AptosDB;
 use aptos_executor_test_helpers::bootstrap_genesis;
@@ -348,6 +348,50 @@ fn test_event_subscribers() {
     verify_no_event_notifications(vec![&mut listener_1]);
 }
 
+#[test]
+fn test_ledger_update_subscribers() {
+    // Create subscription service and mock database
+    let mut event_service = create_event_subscription_service();
+
+    // Create ledger update subscribers
+    let mut listener_1 = event_service.subscribe_to_ledger_updates().unwrap();
+    let mut listener_2 = event_service.subscribe_to_ledger_updates().unwrap();
+
+    // Notify the subscription service of a commit without events and verify the
+    // listeners are notified of the version
+    notify_events(&mut event_service, 10, vec![]);
+    verify_ledger_update_notification_received(vec![&mut listener_1, &mut listener_2], 10);
+
+    // Notify the subscription service of several commits and verify only the
+    // latest version is kept
+    let event = create_test_event(create_random_event_key());
+    for version in 11..20 {
+        notify_events(&mut event_service, version, vec![event.clone()]);
+    }
+    verify_ledger_update_notification_received(vec![&mut listener_1, &mut listener_2], 19);
+    for listener in [&mut listener_1, &mut listener_2] {
+        assert!(listener.select_next_some().now_or_never().is_none());
+    }
+}
+
+#[test]
+fn test_dropped_ledger_update_listener() {
+    // Create subscription service and mock database
+    let mut event_service = create_event_subscription_service();
+
+    // Create a reconfig subscriber and a ledger update subscriber that goes away
+    let mut reconfig_listener = event_service.subscribe_to_reconfigurations().unwrap();
+    let ledger_update_listener = event_service.subscribe_to_ledger_updates().unwrap();
+    drop(ledger_update_listener);
+
+    // Notify the subscription service of a reconfiguration and verify the reconfig
+    // subscriber is still notified
+    let version = 0;
+    let reconfig_event = create_test_event(on_chain_config::new_epoch_event_key());
+    notify_events(&mut event_service, version, vec![reconfig_event]);
+    verify_reconfig_notifications_received(vec![&mut reconfig_listener], version, 1);
+}
+
 #[test]
 fn test_no_events_no_subscribers() {
     // Create subscription service and mock database
@@ -434,6 +478,17 @@ fn verify_no_event_notifications(listeners: Vec<&mut EventNotificationListener>)
     }
 }
 
+// Ensures that the specified listeners have been notified of the expected version
+fn verify_ledger_update_notification_received(
+    listeners: Vec<&mut LedgerUpdateNotificationListener>,
+    expected_version: Version,
+) {
+    for listener in listeners {
+        let notification = listener.select_next_some().now_or_never().unwrap();
+        assert_eq!(notification.version, expected_version);
+    }
+}
+
 // Ensures that no reconfig notifications have been received by the listeners
 fn verify_no_reconfig_notifications(
     listeners: Vec<&mut ReconfigNotificationListener<DbBackedOnChainConfig>>,
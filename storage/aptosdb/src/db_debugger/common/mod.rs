@@ -46,6 +46,17 @@ impl DbDir {
             true,
         )
     }
+
+    pub fn open_ledger_db_for_write(&self) -> Result<LedgerDb> {
+        LedgerDb::new(
+            self.db_dir.as_path(),
+            RocksdbConfigs {
+                split_ledger_db: self.sharding_config.split_ledger_db,
+                ..Default::default()
+            },
+            false,
+        )
+    }
 }
 
 impl AsRef<Path> for DbDir {
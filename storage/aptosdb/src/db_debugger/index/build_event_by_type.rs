@@ -0,0 +1,95 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::DbDir, schema::event::EventSchema,
+    utils::truncation_helper::get_ledger_commit_progress, EventStore,
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{ReadOptions, SchemaBatch};
+use aptos_types::transaction::Version;
+use clap::Parser;
+
+#[derive(Parser)]
+#[clap(about = "Build the event by type index from the events in the db.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// First version to index. Defaults to the oldest event in the db.
+    #[clap(long)]
+    start_version: Option<Version>,
+
+    /// Last version to index. Defaults to the latest committed version.
+    #[clap(long)]
+    end_version: Option<Version>,
+
+    #[clap(long, default_value_t = 10000)]
+    batch_size: usize,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let ledger_db = self.db_dir.open_ledger_db_for_write()?;
+        let event_store = EventStore::new(
+            ledger_db.event_db_arc(),
+            /*enable_event_by_type_index=*/ true,
+        );
+
+        let start_version = match self.start_version {
+            Some(version) => version,
+            None => {
+                let mut iter = ledger_db
+                    .event_db()
+                    .iter::<EventSchema>(ReadOptions::default())?;
+                iter.seek_to_first();
+                match iter.next().transpose()? {
+                    Some(((version, _), _)) => version,
+                    None => {
+                        println!("No events in the db, nothing to index.");
+                        return Ok(());
+                    },
+                }
+            },
+        };
+        let end_version = match self.end_version {
+            Some(version) => version,
+            None => get_ledger_commit_progress(ledger_db.metadata_db())?
+                .expect("Current version of ledger db must exist."),
+        };
+        ensure!(
+            start_version <= end_version,
+            "start_version {} > end_version {}",
+            start_version,
+            end_version
+        );
+        ensure!(self.batch_size > 0, "batch_size must be positive.");
+
+        println!(
+            "Indexing events by type from version {} to {}...",
+            start_version, end_version
+        );
+        let batch = SchemaBatch::new();
+        event_store.put_event_by_type_indexed_since(start_version, &batch)?;
+        ledger_db.event_db().write_schemas(batch)?;
+
+        let mut version = start_version;
+        while version <= end_version {
+            let batch_end_version =
+                std::cmp::min(end_version + 1, version + self.batch_size as Version);
+            let batch = SchemaBatch::new();
+            let events_iter = event_store
+                .get_events_by_version_iter(version, (batch_end_version - version) as usize)?;
+            for (idx, events) in events_iter.enumerate() {
+                event_store.put_events_by_type(version + idx as Version, &events?, &batch)?;
+            }
+            ledger_db.event_db().write_schemas(batch)?;
+
+            version = batch_end_version;
+            println!("Indexed until version {}.", version - 1);
+        }
+        println!("Done!");
+
+        Ok(())
+    }
+}
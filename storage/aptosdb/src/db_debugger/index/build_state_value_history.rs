@@ -0,0 +1,226 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::DbDir, schema::write_set::WriteSetSchema,
+    utils::truncation_helper::get_ledger_commit_progress, TransactionStore,
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{ReadOptions, SchemaBatch};
+use aptos_types::transaction::Version;
+use clap::Parser;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[clap(about = "Build the state value history index from the write sets in the db.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// First version to index. Defaults to the oldest write set in the db.
+    #[clap(long)]
+    start_version: Option<Version>,
+
+    /// Last version to index. Defaults to the latest committed version.
+    #[clap(long)]
+    end_version: Option<Version>,
+
+    #[clap(long, default_value_t = 10000)]
+    batch_size: usize,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let ledger_db = Arc::new(self.db_dir.open_ledger_db_for_write()?);
+        let transaction_store = TransactionStore::new(Arc::clone(&ledger_db));
+
+        let start_version = match self.start_version {
+            Some(version) => version,
+            None => {
+                let mut iter = ledger_db
+                    .write_set_db()
+                    .iter::<WriteSetSchema>(ReadOptions::default())?;
+                iter.seek_to_first();
+                match iter.next().transpose()? {
+                    Some((version, _)) => version,
+                    None => {
+                        println!("No write sets in the db, nothing to index.");
+                        return Ok(());
+                    },
+                }
+            },
+        };
+        let latest_version = get_ledger_commit_progress(ledger_db.metadata_db())?
+            .expect("Current version of ledger db must exist.");
+        let end_version = self.end_version.unwrap_or(latest_version);
+        ensure!(
+            start_version <= end_version,
+            "start_version {} > end_version {}",
+            start_version,
+            end_version
+        );
+        ensure!(self.batch_size > 0, "batch_size must be positive.");
+
+        println!(
+            "Indexing state value history from version {} to {}...",
+            start_version, end_version
+        );
+        let mut version = start_version;
+        while version <= end_version {
+            let batch_end_version =
+                std::cmp::min(end_version + 1, version + self.batch_size as Version);
+            let write_sets = transaction_store.get_write_sets(version, batch_end_version)?;
+            let batch = SchemaBatch::new();
+            for (idx, write_set) in write_sets.iter().enumerate() {
+                transaction_store.put_state_value_history(
+                    version + idx as Version,
+                    write_set,
+                    &batch,
+                )?;
+            }
+            ledger_db.write_set_db().write_schemas(batch)?;
+
+            version = batch_end_version;
+            println!("Indexed until version {}.", version - 1);
+        }
+
+        // The history is served from the first indexed version on, which can only move back to
+        // the start of a range reaching it.
+        let indexed_since = transaction_store
+            .get_state_value_history_indexed_since()?
+            .unwrap_or(latest_version + 1);
+        if start_version < indexed_since {
+            if end_version + 1 >= indexed_since {
+                let batch = SchemaBatch::new();
+                transaction_store.put_state_value_history_indexed_since(start_version, &batch)?;
+                ledger_db.write_set_db().write_schemas(batch)?;
+                println!("History is now served from version {}.", start_version);
+            } else {
+                println!(
+                    "Versions {} to {} are not indexed, history is still served from version {}.",
+                    end_version + 1,
+                    indexed_since - 1,
+                    indexed_since
+                );
+            }
+        }
+        println!("Done!");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        schema::db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+        AptosDB, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    };
+    use aptos_config::config::{RocksdbConfigs, NO_OP_STORAGE_PRUNER_CONFIG};
+    use aptos_temppath::TempPath;
+    use aptos_types::{
+        state_store::{state_key::StateKey, state_value::StateValue},
+        write_set::{TransactionWrite, WriteOp, WriteSetMut},
+    };
+
+    #[test]
+    fn test_build_state_value_history() {
+        let tmp_dir = TempPath::new();
+        let state_key = StateKey::raw(b"resource".to_vec());
+
+        // Versions 0 and 1 are committed before the index is enabled, version 2 after.
+        let db = open_db(&tmp_dir, false);
+        commit_write_sets(&db, &state_key, 0..2);
+        drop(db);
+        let db = open_db(&tmp_dir, true);
+        assert_eq!(
+            db.transaction_store
+                .get_state_value_history_indexed_since()
+                .unwrap(),
+            Some(2)
+        );
+        commit_write_sets(&db, &state_key, 2..3);
+        drop(db);
+
+        // A range not reaching the first indexed version doesn't change it.
+        run(&tmp_dir, &["--start-version", "0", "--end-version", "0"]);
+        let db = open_db(&tmp_dir, true);
+        assert_eq!(
+            db.transaction_store
+                .get_state_value_history_indexed_since()
+                .unwrap(),
+            Some(2)
+        );
+        drop(db);
+
+        run(&tmp_dir, &["--batch-size", "2"]);
+        let db = open_db(&tmp_dir, true);
+        let store = &db.transaction_store;
+        assert_eq!(
+            store.get_state_value_history_indexed_since().unwrap(),
+            Some(0)
+        );
+        assert_eq!(
+            store.get_state_value_history(&state_key, 0, 10, 2).unwrap(),
+            (0..3)
+                .map(|version| (version, Some(state_value(version))))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    fn run(tmp_dir: &TempPath, args: &[&str]) {
+        let db_dir = tmp_dir.path().to_str().unwrap();
+        Cmd::parse_from(
+            ["build-state-value-history", "--db-dir", db_dir]
+                .iter()
+                .chain(args),
+        )
+        .run()
+        .unwrap();
+    }
+
+    fn open_db(tmp_dir: &TempPath, enable_state_value_history_index: bool) -> AptosDB {
+        AptosDB::open(
+            tmp_dir,
+            false,
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            RocksdbConfigs {
+                enable_state_value_history_index,
+                ..Default::default()
+            },
+            false,
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )
+        .unwrap()
+    }
+
+    fn state_value(version: Version) -> StateValue {
+        StateValue::from(version.to_le_bytes().to_vec())
+    }
+
+    /// Writes `state_key` at each version and moves the ledger commit progress to the last one.
+    fn commit_write_sets(db: &AptosDB, state_key: &StateKey, versions: std::ops::Range<Version>) {
+        let batch = SchemaBatch::new();
+        for version in versions.clone() {
+            let write_set = WriteSetMut::new(vec![(
+                state_key.clone(),
+                WriteOp::from_state_value(Some(state_value(version))),
+            )])
+            .freeze()
+            .unwrap();
+            db.transaction_store
+                .put_write_set(version, &write_set, &batch)
+                .unwrap();
+        }
+        db.ledger_db.write_set_db().write_schemas(batch).unwrap();
+        db.ledger_db
+            .metadata_db()
+            .put::<DbMetadataSchema>(
+                &DbMetadataKey::LedgerCommitProgress,
+                &DbMetadataValue::Version(versions.end - 1),
+            )
+            .unwrap();
+    }
+}
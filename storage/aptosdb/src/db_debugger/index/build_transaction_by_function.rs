@@ -0,0 +1,93 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{
+    db_debugger::common::DbDir, schema::transaction::TransactionSchema,
+    utils::truncation_helper::get_ledger_commit_progress, TransactionStore,
+};
+use anyhow::{ensure, Result};
+use aptos_schemadb::{ReadOptions, SchemaBatch};
+use aptos_types::transaction::Version;
+use clap::Parser;
+use std::sync::Arc;
+
+#[derive(Parser)]
+#[clap(about = "Build the transaction by function index from the transactions in the db.")]
+pub struct Cmd {
+    #[clap(flatten)]
+    db_dir: DbDir,
+
+    /// First version to index. Defaults to the oldest transaction in the db.
+    #[clap(long)]
+    start_version: Option<Version>,
+
+    /// Last version to index. Defaults to the latest committed version.
+    #[clap(long)]
+    end_version: Option<Version>,
+
+    #[clap(long, default_value_t = 10000)]
+    batch_size: usize,
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        let ledger_db = Arc::new(self.db_dir.open_ledger_db_for_write()?);
+        let transaction_store = TransactionStore::new(Arc::clone(&ledger_db));
+
+        let start_version = match self.start_version {
+            Some(version) => version,
+            None => {
+                let mut iter = ledger_db
+                    .transaction_db()
+                    .iter::<TransactionSchema>(ReadOptions::default())?;
+                iter.seek_to_first();
+                match iter.next().transpose()? {
+                    Some((version, _)) => version,
+                    None => {
+                        println!("No transactions in the db, nothing to index.");
+                        return Ok(());
+                    },
+                }
+            },
+        };
+        let end_version = match self.end_version {
+            Some(version) => version,
+            None => get_ledger_commit_progress(ledger_db.metadata_db())?
+                .expect("Current version of ledger db must exist."),
+        };
+        ensure!(
+            start_version <= end_version,
+            "start_version {} > end_version {}",
+            start_version,
+            end_version
+        );
+        ensure!(self.batch_size > 0, "batch_size must be positive.");
+
+        println!(
+            "Indexing transactions by function from version {} to {}...",
+            start_version, end_version
+        );
+        let mut version = start_version;
+        while version <= end_version {
+            let batch_end_version =
+                std::cmp::min(end_version + 1, version + self.batch_size as Version);
+            let batch = SchemaBatch::new();
+            let transactions = transaction_store
+                .get_transaction_iter(version, (batch_end_version - version) as usize)?;
+            for (idx, transaction) in transactions.enumerate() {
+                transaction_store.put_transaction_by_function(
+                    version + idx as Version,
+                    &transaction?,
+                    &batch,
+                )?;
+            }
+            ledger_db.transaction_db().write_schemas(batch)?;
+
+            version = batch_end_version;
+            println!("Indexed until version {}.", version - 1);
+        }
+        println!("Done!");
+
+        Ok(())
+    }
+}
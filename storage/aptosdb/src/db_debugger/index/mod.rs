@@ -0,0 +1,29 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+mod build_event_by_type;
+mod build_state_value_history;
+mod build_transaction_by_function;
+
+use anyhow::Result;
+
+#[derive(clap::Subcommand)]
+#[clap(
+    about = "Build the optional ledger indices from existing data. The node must be stopped, and \
+    started with the index enabled to keep it up to date afterwards."
+)]
+pub enum Cmd {
+    BuildEventByType(build_event_by_type::Cmd),
+    BuildStateValueHistory(build_state_value_history::Cmd),
+    BuildTransactionByFunction(build_transaction_by_function::Cmd),
+}
+
+impl Cmd {
+    pub fn run(self) -> Result<()> {
+        match self {
+            Self::BuildEventByType(cmd) => cmd.run(),
+            Self::BuildStateValueHistory(cmd) => cmd.run(),
+            Self::BuildTransactionByFunction(cmd) => cmd.run(),
+        }
+    }
+}
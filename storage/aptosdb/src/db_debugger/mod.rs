@@ -4,6 +4,7 @@
 pub mod checkpoint;
 mod common;
 mod examine;
+pub mod index;
 pub mod ledger;
 pub mod state_tree;
 pub mod truncate;
@@ -33,6 +34,9 @@ pub enum Cmd {
 
     #[clap(subcommand)]
     Examine(examine::Cmd),
+
+    #[clap(subcommand)]
+    Index(index::Cmd),
 }
 
 impl Cmd {
@@ -43,6 +47,7 @@ impl Cmd {
             Cmd::Ledger(cmd) => cmd.run(),
             Cmd::Truncate(cmd) => cmd.run(),
             Cmd::Examine(cmd) => cmd.run(),
+            Cmd::Index(cmd) => cmd.run(),
         }
     }
 }
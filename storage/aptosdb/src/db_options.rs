@@ -17,14 +17,17 @@ pub(super) fn ledger_db_column_families() -> Vec<ColumnFamilyName> {
         EPOCH_BY_VERSION_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
         EVENT_BY_KEY_CF_NAME,
+        EVENT_BY_TYPE_CF_NAME,
         EVENT_BY_VERSION_CF_NAME,
         EVENT_CF_NAME,
         LEDGER_INFO_CF_NAME,
         STALE_STATE_VALUE_INDEX_CF_NAME,
         STATE_VALUE_CF_NAME,
+        STATE_VALUE_HISTORY_CF_NAME,
         TRANSACTION_CF_NAME,
         TRANSACTION_ACCUMULATOR_CF_NAME,
         TRANSACTION_BY_ACCOUNT_CF_NAME,
+        TRANSACTION_BY_FUNCTION_CF_NAME,
         TRANSACTION_BY_HASH_CF_NAME,
         TRANSACTION_INFO_CF_NAME,
         VERSION_DATA_CF_NAME,
@@ -39,6 +42,7 @@ pub(super) fn event_db_column_families() -> Vec<ColumnFamilyName> {
         DB_METADATA_CF_NAME,
         EVENT_ACCUMULATOR_CF_NAME,
         EVENT_BY_KEY_CF_NAME,
+        EVENT_BY_TYPE_CF_NAME,
         EVENT_BY_VERSION_CF_NAME,
         EVENT_CF_NAME,
     ]
@@ -58,6 +62,7 @@ pub(super) fn transaction_db_column_families() -> Vec<ColumnFamilyName> {
         DB_METADATA_CF_NAME,
         TRANSACTION_CF_NAME,
         TRANSACTION_BY_ACCOUNT_CF_NAME,
+        TRANSACTION_BY_FUNCTION_CF_NAME,
         TRANSACTION_BY_HASH_CF_NAME,
     ]
 }
@@ -74,6 +79,7 @@ pub(super) fn write_set_db_column_families() -> Vec<ColumnFamilyName> {
     vec![
         /* empty cf */ DEFAULT_COLUMN_FAMILY_NAME,
         DB_METADATA_CF_NAME,
+        STATE_VALUE_HISTORY_CF_NAME,
         WRITE_SET_CF_NAME,
     ]
 }
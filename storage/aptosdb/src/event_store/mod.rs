@@ -10,8 +10,12 @@ use super::AptosDB;
 use crate::{
     errors::AptosDbError,
     schema::{
-        event::EventSchema, event_accumulator::EventAccumulatorSchema,
-        event_by_key::EventByKeySchema, event_by_version::EventByVersionSchema,
+        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+        event::EventSchema,
+        event_accumulator::EventAccumulatorSchema,
+        event_by_key::EventByKeySchema,
+        event_by_type::EventByTypeSchema,
+        event_by_version::EventByVersionSchema,
     },
     utils::iterators::EventsByVersionIter,
 };
@@ -25,11 +29,12 @@ use aptos_schemadb::{iterator::SchemaIterator, schema::ValueCodec, ReadOptions,
 use aptos_types::{
     account_address::AccountAddress,
     account_config::{new_block_event_key, NewBlockEvent},
-    contract_event::ContractEvent,
+    contract_event::{ContractEvent, EventWithVersion},
     event::EventKey,
     proof::position::Position,
     transaction::Version,
 };
+use move_core_types::language_storage::TypeTag;
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
     convert::{TryFrom, TryInto},
@@ -40,11 +45,58 @@ use std::{
 #[derive(Debug)]
 pub struct EventStore {
     event_db: Arc<DB>,
+    enable_event_by_type_index: bool,
 }
 
 impl EventStore {
-    pub fn new(event_db: Arc<DB>) -> Self {
-        Self { event_db }
+    pub fn new(event_db: Arc<DB>, enable_event_by_type_index: bool) -> Self {
+        Self {
+            event_db,
+            enable_event_by_type_index,
+        }
+    }
+
+    /// Whether the event by type index is maintained when saving events.
+    pub fn event_by_type_index_enabled(&self) -> bool {
+        self.enable_event_by_type_index
+    }
+
+    /// Gets the first version the event by type index was ever built from, `None` if it never
+    /// was. It is kept when the index gets disabled, since the entries stay until pruned.
+    pub fn get_event_by_type_indexed_since(&self) -> Result<Option<Version>> {
+        Ok(self
+            .event_db
+            .get::<DbMetadataSchema>(&DbMetadataKey::EventByTypeIndexedSince)?
+            .map(|v| v.expect_version()))
+    }
+
+    /// Records that the event by type index is built from `version` on, unless it already was
+    /// from an earlier version.
+    pub fn put_event_by_type_indexed_since(
+        &self,
+        version: Version,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        if self
+            .get_event_by_type_indexed_since()?
+            .map_or(false, |indexed_since| indexed_since <= version)
+        {
+            return Ok(());
+        }
+        batch.put::<DbMetadataSchema>(
+            &DbMetadataKey::EventByTypeIndexedSince,
+            &DbMetadataValue::Version(version),
+        )
+    }
+
+    /// Records that the index is built from `next_version` on if it's enabled and wasn't before.
+    pub fn sync_event_by_type_indexed_since(&self, next_version: Version) -> Result<()> {
+        if !self.enable_event_by_type_index {
+            return Ok(());
+        }
+        let batch = SchemaBatch::new();
+        self.put_event_by_type_indexed_since(next_version, &batch)?;
+        self.event_db.write_schemas(batch)
     }
 
     /// Get all of the events given a transaction version.
@@ -293,6 +345,40 @@ impl EventStore {
         }
     }
 
+    /// Gets up to `limit` events of type `type_tag`, starting at the event at `start_index` of
+    /// `start_version` and ending before `end_version`, in (version, index) order. Each event is
+    /// returned with its index in the transaction, so that a next page can start after it.
+    pub fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        start_index: u64,
+        end_version: Version,
+        limit: u64,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        let mut iter = self
+            .event_db
+            .iter::<EventByTypeSchema>(ReadOptions::default())?;
+        iter.seek(&(type_tag.clone(), start_version, start_index))?;
+
+        let mut events = Vec::new();
+        for item in iter {
+            let ((event_type, version, index), ()) = item?;
+            if event_type != *type_tag || version >= end_version || events.len() as u64 >= limit {
+                break;
+            }
+            events.push((
+                index,
+                EventWithVersion::new(
+                    version,
+                    self.get_event_by_version_and_index(version, index)?,
+                ),
+            ));
+        }
+
+        Ok(events)
+    }
+
     pub fn get_block_metadata(&self, version: Version) -> Result<(Version, NewBlockEvent)> {
         let (first_version, event_index, seq_num) = self
             .lookup_event_before_or_at_version(&new_block_event_key(), version)?
@@ -332,6 +418,10 @@ impl EventStore {
                 batch.put::<EventSchema>(&(version, idx as u64), event)
             })?;
 
+        if !skip_index && self.enable_event_by_type_index {
+            self.put_events_by_type(version, events, batch)?;
+        }
+
         if !skip_index {
             // EventAccumulatorSchema updates
             let event_hashes: Vec<HashValue> = events.iter().map(ContractEvent::hash).collect();
@@ -350,6 +440,18 @@ impl EventStore {
         Ok(())
     }
 
+    /// Indexes the contract events yielded by the transaction at `version` by their type.
+    pub fn put_events_by_type(
+        &self,
+        version: Version,
+        events: &[ContractEvent],
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        events.iter().enumerate().try_for_each(|(idx, event)| {
+            batch.put::<EventByTypeSchema>(&(event.type_tag().clone(), version, idx as u64), &())
+        })
+    }
+
     pub(crate) fn put_events_multiple_versions(
         &self,
         first_version: u64,
@@ -473,16 +575,23 @@ impl EventStore {
     }
 
     /// Prune a set of candidate events in the range of version in [begin, end) and all related indices
+    ///
+    /// The event by type index is pruned if it was ever built over the range, even if it isn't
+    /// maintained anymore.
     pub fn prune_events(
         &self,
         start: Version,
         end: Version,
         db_batch: &SchemaBatch,
     ) -> anyhow::Result<()> {
+        let prune_event_by_type_index = self.enable_event_by_type_index
+            || self
+                .get_event_by_type_indexed_since()?
+                .map_or(false, |indexed_since| indexed_since < end);
         let mut current_version = start;
         for events in self.get_events_by_version_iter(start, (end - start) as usize)? {
             for (idx, event) in (events?).into_iter().enumerate() {
-                if let ContractEvent::V1(v1) = event {
+                if let ContractEvent::V1(v1) = &event {
                     db_batch.delete::<EventByVersionSchema>(&(
                         *v1.key(),
                         current_version,
@@ -490,6 +599,13 @@ impl EventStore {
                     ))?;
                     db_batch.delete::<EventByKeySchema>(&(*v1.key(), v1.sequence_number()))?;
                 }
+                if prune_event_by_type_index {
+                    db_batch.delete::<EventByTypeSchema>(&(
+                        event.type_tag().clone(),
+                        current_version,
+                        idx as u64,
+                    ))?;
+                }
                 db_batch.delete::<EventSchema>(&(current_version, idx as u64))?;
             }
             current_version += 1;
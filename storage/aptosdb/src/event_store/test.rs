@@ -40,6 +40,75 @@ fn test_error_on_get_from_empty() {
     assert!(store.get_event_by_version_and_index(100, 0).is_err());
 }
 
+#[test]
+fn test_get_events_by_type() {
+    let tmp_dir = TempPath::new();
+    let db = AptosDB::new_for_test(&tmp_dir);
+    let store = EventStore::new(
+        db.ledger_db.event_db_arc(),
+        /*enable_event_by_type_index=*/ true,
+    );
+    let type_tag = TypeTag::U64;
+    let event = |data: u8| ContractEvent::new_v2(type_tag.clone(), vec![data]);
+    let other_event = ContractEvent::new_v2(TypeTag::Bool, vec![]);
+
+    // The index wasn't enabled when the db was opened, it's built from now on.
+    assert_eq!(store.get_event_by_type_indexed_since().unwrap(), None);
+    store.sync_event_by_type_indexed_since(0).unwrap();
+    store.sync_event_by_type_indexed_since(1).unwrap();
+    assert_eq!(store.get_event_by_type_indexed_since().unwrap(), Some(0));
+
+    let batch = SchemaBatch::new();
+    for (version, events) in [
+        vec![event(0), other_event.clone(), event(1)],
+        vec![other_event.clone()],
+        vec![event(2), event(3)],
+    ]
+    .iter()
+    .enumerate()
+    {
+        store
+            .put_events(
+                version as Version,
+                events,
+                /*skip_index=*/ false,
+                &batch,
+            )
+            .unwrap();
+    }
+    store.event_db.write_schemas(batch).unwrap();
+
+    // Returns (version, index, data) of the events in a page.
+    let get = |start_version, start_index, limit| {
+        store
+            .get_events_by_type(&type_tag, start_version, start_index, Version::MAX, limit)
+            .unwrap()
+            .into_iter()
+            .map(|(index, event)| {
+                (
+                    event.transaction_version,
+                    index,
+                    event.event.event_data()[0],
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    // A page can end in the middle of a version, the next one starts after its last event.
+    assert_eq!(get(0, 0, 3), vec![(0, 0, 0), (0, 2, 1), (2, 0, 2)]);
+    assert_eq!(get(2, 1, 3), vec![(2, 1, 3)]);
+    assert_eq!(get(0, 1, 1), vec![(0, 2, 1)]);
+
+    // The index is pruned even once it isn't maintained anymore, since it was built before.
+    let pruning_store = EventStore::new(
+        db.ledger_db.event_db_arc(),
+        /*enable_event_by_type_index=*/ false,
+    );
+    let batch = SchemaBatch::new();
+    pruning_store.prune_events(0, 1, &batch).unwrap();
+    pruning_store.event_db.write_schemas(batch).unwrap();
+    assert_eq!(get(0, 0, 3), vec![(2, 0, 2), (2, 1, 3)]);
+}
+
 proptest! {
     #![proptest_config(ProptestConfig::with_cases(10))]
 
@@ -864,6 +864,18 @@ impl DbReader for FakeAptosDB {
         self.inner.indexer_enabled()
     }
 
+    fn state_value_history_index_enabled(&self) -> bool {
+        self.inner.state_value_history_index_enabled()
+    }
+
+    fn event_by_type_index_enabled(&self) -> bool {
+        self.inner.event_by_type_index_enabled()
+    }
+
+    fn transaction_by_function_index_enabled(&self) -> bool {
+        self.inner.transaction_by_function_index_enabled()
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         self.inner.get_state_storage_usage(version)
     }
@@ -71,6 +71,9 @@ pub struct LedgerDb {
     transaction_db: Arc<DB>,
     transaction_info_db: Arc<DB>,
     write_set_db: Arc<DB>,
+    enable_state_value_history_index: bool,
+    enable_event_by_type_index: bool,
+    enable_transaction_by_function_index: bool,
 }
 
 impl LedgerDb {
@@ -108,6 +111,10 @@ impl LedgerDb {
                 transaction_db: Arc::clone(&ledger_metadata_db),
                 transaction_info_db: Arc::clone(&ledger_metadata_db),
                 write_set_db: Arc::clone(&ledger_metadata_db),
+                enable_state_value_history_index: rocksdb_configs.enable_state_value_history_index,
+                enable_event_by_type_index: rocksdb_configs.enable_event_by_type_index,
+                enable_transaction_by_function_index: rocksdb_configs
+                    .enable_transaction_by_function_index,
             });
         }
 
@@ -157,6 +164,10 @@ impl LedgerDb {
             transaction_db,
             transaction_info_db,
             write_set_db,
+            enable_state_value_history_index: rocksdb_configs.enable_state_value_history_index,
+            enable_event_by_type_index: rocksdb_configs.enable_event_by_type_index,
+            enable_transaction_by_function_index: rocksdb_configs
+                .enable_transaction_by_function_index,
         })
     }
 
@@ -287,6 +298,22 @@ impl LedgerDb {
         Arc::clone(&self.write_set_db)
     }
 
+    /// Whether the state value history index, stored alongside the write sets, is maintained.
+    pub(crate) fn state_value_history_index_enabled(&self) -> bool {
+        self.enable_state_value_history_index
+    }
+
+    /// Whether the event by type index, stored alongside the events, is maintained.
+    pub(crate) fn event_by_type_index_enabled(&self) -> bool {
+        self.enable_event_by_type_index
+    }
+
+    /// Whether the transaction by function index, stored alongside the transactions, is
+    /// maintained.
+    pub(crate) fn transaction_by_function_index_enabled(&self) -> bool {
+        self.enable_transaction_by_function_index
+    }
+
     fn open_rocksdb(
         path: PathBuf,
         name: &str,
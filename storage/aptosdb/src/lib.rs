@@ -67,6 +67,7 @@ use crate::{
     state_merkle_db::StateMerkleDb,
     state_store::{buffered_state::BufferedState, StateStore},
     transaction_store::TransactionStore,
+    utils::truncation_helper::get_ledger_commit_progress,
 };
 use anyhow::{bail, ensure, Result};
 use aptos_config::config::{
@@ -118,6 +119,10 @@ use aptos_types::{
 };
 use aptos_vm::data_cache::AsMoveResolver;
 use arr_macro::arr;
+use move_core_types::{
+    identifier::IdentStr,
+    language_storage::{ModuleId, TypeTag},
+};
 use move_resource_viewer::MoveValueAnnotator;
 use once_cell::sync::Lazy;
 use rayon::prelude::*;
@@ -383,7 +388,10 @@ impl AptosDB {
         AptosDB {
             ledger_db: Arc::clone(&ledger_db),
             state_kv_db: Arc::clone(&state_kv_db),
-            event_store: Arc::new(EventStore::new(ledger_db.event_db_arc())),
+            event_store: Arc::new(EventStore::new(
+                ledger_db.event_db_arc(),
+                ledger_db.event_by_type_index_enabled(),
+            )),
             ledger_store: Arc::new(LedgerStore::new(Arc::clone(&ledger_db))),
             state_store,
             transaction_store: Arc::new(TransactionStore::new(Arc::clone(&ledger_db))),
@@ -432,6 +440,17 @@ impl AptosDB {
             rocksdb_configs.skip_index_and_usage,
         );
 
+        if !readonly {
+            let next_version = get_ledger_commit_progress(myself.ledger_db.metadata_db())?
+                .map_or(0, |version| version + 1);
+            myself
+                .transaction_store
+                .sync_state_value_history_indexed_since(next_version)?;
+            myself
+                .event_store
+                .sync_event_by_type_indexed_since(next_version)?;
+        }
+
         if !readonly && enable_indexer {
             myself.open_indexer(db_root_path, rocksdb_configs.index_db_config)?;
         }
@@ -1485,6 +1504,34 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_transaction_versions_by_function(
+        &self,
+        module: &ModuleId,
+        function: &IdentStr,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<Version>> {
+        gauged_api("get_transaction_versions_by_function", || {
+            ensure!(
+                self.transaction_store
+                    .transaction_by_function_index_enabled(),
+                "Transaction by function index not enabled."
+            );
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+            let start_version =
+                std::cmp::max(start_version, self.ledger_pruner.get_min_readable_version());
+
+            self.transaction_store.get_transaction_versions_by_function(
+                module,
+                function,
+                start_version,
+                limit,
+                ledger_version,
+            )
+        })
+    }
+
     /// This API is best-effort in that it CANNOT provide absence proof.
     fn get_transaction_by_hash(
         &self,
@@ -1661,6 +1708,39 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_events_by_type(
+        &self,
+        type_tag: &TypeTag,
+        start_version: Version,
+        start_index: u64,
+        end_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(u64, EventWithVersion)>> {
+        gauged_api("get_events_by_type", || {
+            ensure!(
+                self.event_store.event_by_type_index_enabled(),
+                "Event by type index not enabled."
+            );
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+            let min_readable_version = self.ledger_pruner.get_min_readable_version();
+            let (start_version, start_index) = if start_version < min_readable_version {
+                (min_readable_version, 0)
+            } else {
+                (start_version, start_index)
+            };
+            let end_version = std::cmp::min(end_version, ledger_version.saturating_add(1));
+
+            self.event_store.get_events_by_type(
+                type_tag,
+                start_version,
+                start_index,
+                end_version,
+                limit,
+            )
+        })
+    }
+
     fn get_transaction_iterator(
         &self,
         start_version: Version,
@@ -1812,6 +1892,31 @@ impl DbReader for AptosDB {
         })
     }
 
+    fn get_state_value_history(
+        &self,
+        state_key: &StateKey,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(Version, Option<StateValue>)>> {
+        gauged_api("get_state_value_history", || {
+            ensure!(
+                self.ledger_db.state_value_history_index_enabled(),
+                "State value history index not enabled."
+            );
+            error_if_too_many_requested(limit, MAX_REQUEST_LIMIT)?;
+            let start_version =
+                std::cmp::max(start_version, self.ledger_pruner.get_min_readable_version());
+
+            self.transaction_store.get_state_value_history(
+                state_key,
+                start_version,
+                limit,
+                ledger_version,
+            )
+        })
+    }
+
     /// Returns the proof of the given state key and version.
     fn get_state_proof_by_version_ext(
         &self,
@@ -2072,6 +2177,26 @@ impl DbReader for AptosDB {
         self.indexer.is_some()
     }
 
+    fn state_value_history_index_enabled(&self) -> bool {
+        self.ledger_db.state_value_history_index_enabled()
+    }
+
+    fn get_state_value_history_indexed_since(&self) -> Result<Option<Version>> {
+        gauged_api("get_state_value_history_indexed_since", || {
+            self.transaction_store
+                .get_state_value_history_indexed_since()
+        })
+    }
+
+    fn event_by_type_index_enabled(&self) -> bool {
+        self.event_store.event_by_type_index_enabled()
+    }
+
+    fn transaction_by_function_index_enabled(&self) -> bool {
+        self.transaction_store
+            .transaction_by_function_index_enabled()
+    }
+
     fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage> {
         gauged_api("get_state_storage_usage", || {
             if let Some(v) = version {
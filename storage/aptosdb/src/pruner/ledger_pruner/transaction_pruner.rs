@@ -30,6 +30,16 @@ impl DBSubPruner for TransactionPruner {
             .prune_transaction_by_hash(&candidate_transactions, &batch)?;
         self.transaction_store
             .prune_transaction_by_account(&candidate_transactions, &batch)?;
+        if self
+            .transaction_store
+            .transaction_by_function_index_enabled()
+        {
+            self.transaction_store.prune_transaction_by_function(
+                current_progress,
+                &candidate_transactions,
+                &batch,
+            )?;
+        }
         self.transaction_store.prune_transaction_schema(
             current_progress,
             target_version,
@@ -21,6 +21,13 @@ pub struct WriteSetPruner {
 impl DBSubPruner for WriteSetPruner {
     fn prune(&self, current_progress: Version, target_version: Version) -> Result<()> {
         let batch = SchemaBatch::new();
+        if self.transaction_store.state_value_history_index_enabled() {
+            self.transaction_store.prune_state_value_history(
+                current_progress,
+                target_version,
+                &batch,
+            )?;
+        }
         self.transaction_store
             .prune_write_set(current_progress, target_version, &batch)?;
         batch.put::<DbMetadataSchema>(
@@ -66,6 +66,8 @@ pub enum DbMetadataKey {
     EpochEndingStateMerkleShardPrunerProgress(ShardId),
     StateKvShardPrunerProgress(ShardId),
     StateMerkleShardRestoreProgress(ShardId, Version),
+    StateValueHistoryIndexedSince,
+    EventByTypeIndexedSince,
 }
 
 define_schema!(
@@ -0,0 +1,68 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an optional index of the versions at which a
+//! state key was written. With the version one can resort to `WriteSetSchema` for the value that
+//! was written, or whether the key was deleted.
+//!
+//! The state key is identified by its hash, since the encoding of a table item key is not prefix
+//! free and the writes of two keys could otherwise interleave.
+//!
+//! ```text
+//! |<--------key-------->|<-value->|
+//! | state_key_hash | ver |   ()    |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, STATE_VALUE_HISTORY_CF_NAME};
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::transaction::Version;
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use std::mem::size_of;
+
+define_schema!(
+    StateValueHistorySchema,
+    Key,
+    (),
+    STATE_VALUE_HISTORY_CF_NAME
+);
+
+type Key = (HashValue, Version);
+
+impl KeyCodec<StateValueHistorySchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref state_key_hash, version) = *self;
+
+        let mut encoded = state_key_hash.to_vec();
+        encoded.write_u64::<BigEndian>(version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, size_of::<Self>())?;
+
+        let state_key_hash = HashValue::from_slice(&data[..HashValue::LENGTH])?;
+        let version = (&data[HashValue::LENGTH..]).read_u64::<BigEndian>()?;
+
+        Ok((state_key_hash, version))
+    }
+}
+
+impl ValueCodec<StateValueHistorySchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
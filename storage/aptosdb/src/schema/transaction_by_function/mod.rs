@@ -0,0 +1,73 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+//! This module defines physical storage schema for an optional transaction index via which the
+//! versions of the user transactions calling an entry function can be found in order. With the
+//! version one can resort to `TransactionSchema` for the transaction content.
+//!
+//! The module and function names are BCS encoded, which is prefix free, so the transactions of
+//! different functions don't interleave.
+//!
+//! ```text
+//! |<-----------------------key----------------------->|<-value->|
+//! | module_address | module_name | function | txn_ver |   ()    |
+//! ```
+
+use crate::schema::{ensure_slice_len_eq, ensure_slice_len_gt, TRANSACTION_BY_FUNCTION_CF_NAME};
+use anyhow::Result;
+use aptos_schemadb::{
+    define_schema,
+    schema::{KeyCodec, ValueCodec},
+};
+use aptos_types::{account_address::AccountAddress, transaction::Version};
+use byteorder::{BigEndian, ReadBytesExt, WriteBytesExt};
+use move_core_types::identifier::Identifier;
+use std::{convert::TryFrom, mem::size_of};
+
+define_schema!(
+    TransactionByFunctionSchema,
+    Key,
+    (),
+    TRANSACTION_BY_FUNCTION_CF_NAME
+);
+
+type Key = (AccountAddress, Identifier, Identifier, Version);
+
+impl KeyCodec<TransactionByFunctionSchema> for Key {
+    fn encode_key(&self) -> Result<Vec<u8>> {
+        let (ref module_address, ref module_name, ref function_name, version) = *self;
+
+        let mut encoded = module_address.to_vec();
+        encoded.extend(bcs::to_bytes(&(module_name, function_name))?);
+        encoded.write_u64::<BigEndian>(version)?;
+
+        Ok(encoded)
+    }
+
+    fn decode_key(data: &[u8]) -> Result<Self> {
+        const ADDRESS_SIZE: usize = AccountAddress::LENGTH;
+        const VERSION_SIZE: usize = size_of::<Version>();
+        ensure_slice_len_gt(data, ADDRESS_SIZE + VERSION_SIZE)?;
+
+        let version_offset = data.len() - VERSION_SIZE;
+        let module_address = AccountAddress::try_from(&data[..ADDRESS_SIZE])?;
+        let (module_name, function_name) = bcs::from_bytes(&data[ADDRESS_SIZE..version_offset])?;
+        let version = (&data[version_offset..]).read_u64::<BigEndian>()?;
+
+        Ok((module_address, module_name, function_name, version))
+    }
+}
+
+impl ValueCodec<TransactionByFunctionSchema> for () {
+    fn encode_value(&self) -> Result<Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    fn decode_value(data: &[u8]) -> Result<Self> {
+        ensure_slice_len_eq(data, 0)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,23 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+use aptos_schemadb::{schema::fuzzing::assert_encode_decode, test_no_panic_decoding};
+use proptest::prelude::*;
+
+proptest! {
+    #[test]
+    fn test_encode_decode(
+        module_address in any::<AccountAddress>(),
+        module_name in any::<Identifier>(),
+        function_name in any::<Identifier>(),
+        version in any::<Version>(),
+    ) {
+        assert_encode_decode::<TransactionByFunctionSchema>(
+            &(module_address, module_name, function_name, version),
+            &(),
+        );
+    }
+}
+
+test_no_panic_decoding!(TransactionByFunctionSchema);
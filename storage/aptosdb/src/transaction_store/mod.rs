@@ -8,8 +8,13 @@ use crate::{
     errors::AptosDbError,
     ledger_db::LedgerDb,
     schema::{
-        transaction::TransactionSchema, transaction_by_account::TransactionByAccountSchema,
-        transaction_by_hash::TransactionByHashSchema, write_set::WriteSetSchema,
+        db_metadata::{DbMetadataKey, DbMetadataSchema, DbMetadataValue},
+        state_value_history::StateValueHistorySchema,
+        transaction::TransactionSchema,
+        transaction_by_account::TransactionByAccountSchema,
+        transaction_by_function::TransactionByFunctionSchema,
+        transaction_by_hash::TransactionByHashSchema,
+        write_set::WriteSetSchema,
     },
     transaction_accumulator::TransactionAccumulatorSchema,
     transaction_info::TransactionInfoSchema,
@@ -21,9 +26,14 @@ use aptos_schemadb::{ReadOptions, SchemaBatch};
 use aptos_types::{
     account_address::AccountAddress,
     proof::position::Position,
-    transaction::{Transaction, Version},
-    write_set::WriteSet,
+    state_store::{state_key::StateKey, state_value::StateValue},
+    transaction::{
+        EntryFunction, Multisig, MultisigTransactionPayload, Transaction, TransactionPayload,
+        Version,
+    },
+    write_set::{TransactionWrite, WriteSet},
 };
+use move_core_types::{identifier::IdentStr, language_storage::ModuleId};
 use std::sync::Arc;
 
 #[cfg(test)]
@@ -156,6 +166,9 @@ impl TransactionStore {
                     &version,
                 )?;
             }
+            if self.ledger_db.transaction_by_function_index_enabled() {
+                self.put_transaction_by_function(version, transaction, batch)?;
+            }
         }
         batch.put::<TransactionByHashSchema>(&transaction.hash(), &version)?;
         batch.put::<TransactionSchema>(&version, transaction)?;
@@ -163,6 +176,70 @@ impl TransactionStore {
         Ok(())
     }
 
+    /// Whether the transaction by function index is maintained when saving transactions.
+    pub fn transaction_by_function_index_enabled(&self) -> bool {
+        self.ledger_db.transaction_by_function_index_enabled()
+    }
+
+    /// Indexes the transaction at `version` by the entry function it calls, if any.
+    pub fn put_transaction_by_function(
+        &self,
+        version: Version,
+        transaction: &Transaction,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        if let Some(entry_function) = called_entry_function(transaction) {
+            batch.put::<TransactionByFunctionSchema>(
+                &(
+                    *entry_function.module().address(),
+                    entry_function.module().name().to_owned(),
+                    entry_function.function().to_owned(),
+                    version,
+                ),
+                &(),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Gets the versions of the user transactions calling `function` in `module`, at or after
+    /// `start_version`. Returns at most `limit` versions, all `<= ledger_version`.
+    pub fn get_transaction_versions_by_function(
+        &self,
+        module: &ModuleId,
+        function: &IdentStr,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<Version>> {
+        let mut iter = self
+            .ledger_db
+            .transaction_db()
+            .iter::<TransactionByFunctionSchema>(ReadOptions::default())?;
+        iter.seek(&(
+            *module.address(),
+            module.name().to_owned(),
+            function.to_owned(),
+            start_version,
+        ))?;
+
+        let mut versions = Vec::new();
+        for item in iter {
+            let ((module_address, module_name, function_name, version), ()) = item?;
+            if module_address != *module.address()
+                || module_name.as_ident_str() != module.name()
+                || function_name.as_ident_str() != function
+                || version > ledger_version
+                || versions.len() as u64 >= limit
+            {
+                break;
+            }
+            versions.push(version);
+        }
+
+        Ok(versions)
+    }
+
     /// Get executed transaction vm output given `version`
     pub fn get_write_set(&self, version: Version) -> Result<WriteSet> {
         self.ledger_db
@@ -222,9 +299,117 @@ impl TransactionStore {
         write_set: &WriteSet,
         batch: &SchemaBatch,
     ) -> Result<()> {
+        if self.ledger_db.state_value_history_index_enabled() {
+            self.put_state_value_history(version, write_set, batch)?;
+        }
         batch.put::<WriteSetSchema>(&version, write_set)
     }
 
+    /// Whether the state value history index is maintained when saving write sets.
+    pub fn state_value_history_index_enabled(&self) -> bool {
+        self.ledger_db.state_value_history_index_enabled()
+    }
+
+    /// Indexes the state keys written by the write set at `version`.
+    pub fn put_state_value_history(
+        &self,
+        version: Version,
+        write_set: &WriteSet,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        for (state_key, _write_op) in write_set.iter() {
+            batch.put::<StateValueHistorySchema>(&(state_key.hash(), version), &())?;
+        }
+        Ok(())
+    }
+
+    /// Gets the first version covered by the state value history index, `None` if the index
+    /// isn't maintained.
+    pub fn get_state_value_history_indexed_since(&self) -> Result<Option<Version>> {
+        Ok(self
+            .ledger_db
+            .write_set_db()
+            .get::<DbMetadataSchema>(&DbMetadataKey::StateValueHistoryIndexedSince)?
+            .map(|v| v.expect_version()))
+    }
+
+    /// Records that the state value history index covers the versions from `version` on.
+    pub fn put_state_value_history_indexed_since(
+        &self,
+        version: Version,
+        batch: &SchemaBatch,
+    ) -> Result<()> {
+        batch.put::<DbMetadataSchema>(
+            &DbMetadataKey::StateValueHistoryIndexedSince,
+            &DbMetadataValue::Version(version),
+        )
+    }
+
+    /// Brings the first indexed version in line with the config. Enabling the index covers the
+    /// versions from `next_version` on, and disabling it forgets the first indexed version,
+    /// since the versions committed meanwhile won't be indexed.
+    pub fn sync_state_value_history_indexed_since(&self, next_version: Version) -> Result<()> {
+        let batch = SchemaBatch::new();
+        match (
+            self.state_value_history_index_enabled(),
+            self.get_state_value_history_indexed_since()?,
+        ) {
+            (true, None) => self.put_state_value_history_indexed_since(next_version, &batch)?,
+            (false, Some(_)) => {
+                batch.delete::<DbMetadataSchema>(&DbMetadataKey::StateValueHistoryIndexedSince)?
+            },
+            _ => return Ok(()),
+        }
+        self.ledger_db.write_set_db().write_schemas(batch)
+    }
+
+    /// Gets the writes to `state_key` at or after `start_version`, as `(version, value)` pairs
+    /// where the value is `None` if the key was deleted. Returns at most `limit` writes with
+    /// `version <= ledger_version`. Fails if `start_version` is before the first indexed version.
+    pub fn get_state_value_history(
+        &self,
+        state_key: &StateKey,
+        start_version: Version,
+        limit: u64,
+        ledger_version: Version,
+    ) -> Result<Vec<(Version, Option<StateValue>)>> {
+        let indexed_since = self
+            .get_state_value_history_indexed_since()?
+            .ok_or_else(|| format_err!("State value history index not maintained."))?;
+        ensure!(
+            start_version >= indexed_since,
+            "State value history is only indexed since version {}, requested from {}.",
+            indexed_since,
+            start_version,
+        );
+
+        let state_key_hash = state_key.hash();
+        let mut iter = self
+            .ledger_db
+            .write_set_db()
+            .iter::<StateValueHistorySchema>(ReadOptions::default())?;
+        iter.seek(&(state_key_hash, start_version))?;
+
+        let mut history = Vec::new();
+        for item in iter {
+            let ((hash, version), ()) = item?;
+            if hash != state_key_hash || version > ledger_version || history.len() as u64 >= limit {
+                break;
+            }
+            let write_set = self.get_write_set(version)?;
+            let write_op = write_set.get(state_key).ok_or_else(|| {
+                format_err!(
+                    "State key {:?} is indexed at version {} but not in its write set.",
+                    state_key,
+                    version,
+                )
+            })?;
+            history.push((version, write_op.as_state_value()));
+        }
+
+        Ok(history)
+    }
+
     /// Prune the transaction by hash store given a list of transaction
     pub fn prune_transaction_by_hash(
         &self,
@@ -252,6 +437,26 @@ impl TransactionStore {
         Ok(())
     }
 
+    /// Prune the transaction by function store given a list of transaction starting at `begin`
+    pub fn prune_transaction_by_function(
+        &self,
+        begin: Version,
+        transactions: &[Transaction],
+        db_batch: &SchemaBatch,
+    ) -> Result<()> {
+        for (idx, transaction) in transactions.iter().enumerate() {
+            if let Some(entry_function) = called_entry_function(transaction) {
+                db_batch.delete::<TransactionByFunctionSchema>(&(
+                    *entry_function.module().address(),
+                    entry_function.module().name().to_owned(),
+                    entry_function.function().to_owned(),
+                    begin + idx as Version,
+                ))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Prune the transaction schema store between a range of version in [begin, end)
     pub fn prune_transaction_schema(
         &self,
@@ -337,6 +542,23 @@ impl TransactionStore {
         Position::from_level_and_pos(first_ancestor_that_is_a_left_child_level, index_in_level)
     }
 
+    /// Prune the state value history index between a range of version in [begin, end)
+    pub fn prune_state_value_history(
+        &self,
+        begin: Version,
+        end: Version,
+        db_batch: &SchemaBatch,
+    ) -> Result<()> {
+        let write_sets = self.get_write_sets(begin, end)?;
+        for (idx, write_set) in write_sets.iter().enumerate() {
+            let version = begin + idx as Version;
+            for (state_key, _write_op) in write_set.iter() {
+                db_batch.delete::<StateValueHistorySchema>(&(state_key.hash(), version))?;
+            }
+        }
+        Ok(())
+    }
+
     /// Prune the transaction schema store between a range of version in [begin, end)
     pub fn prune_write_set(
         &self,
@@ -350,3 +572,16 @@ impl TransactionStore {
         Ok(())
     }
 }
+
+/// Returns the entry function called by a user transaction, directly or through a multisig
+/// account with the payload given on chain.
+fn called_entry_function(transaction: &Transaction) -> Option<&EntryFunction> {
+    match transaction.try_as_signed_user_txn()?.payload() {
+        TransactionPayload::EntryFunction(entry_function) => Some(entry_function),
+        TransactionPayload::Multisig(Multisig {
+            transaction_payload: Some(MultisigTransactionPayload::EntryFunction(entry_function)),
+            ..
+        }) => Some(entry_function),
+        _ => None,
+    }
+}
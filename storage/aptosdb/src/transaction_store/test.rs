@@ -5,12 +5,15 @@
 #![allow(clippy::redundant_clone)] // Required to work around prop_assert_eq! limitations
 
 use super::*;
-use crate::AptosDB;
+use crate::{AptosDB, BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD};
+use aptos_config::config::{RocksdbConfigs, NO_OP_STORAGE_PRUNER_CONFIG};
 use aptos_proptest_helpers::Index;
 use aptos_temppath::TempPath;
 use aptos_types::{
     proptest_types::{AccountInfoUniverse, SignatureCheckedTransactionGen},
+    state_store::table::TableHandle,
     transaction::Transaction,
+    write_set::{WriteOp, WriteSetMut},
 };
 use proptest::{collection::vec, prelude::*};
 use std::collections::BTreeMap;
@@ -201,6 +204,170 @@ proptest! {
     }
 }
 
+#[test]
+fn test_state_value_history() {
+    let tmp_dir = TempPath::new();
+    let db = open_db_with_state_value_history_index(&tmp_dir, true);
+    let store = &db.transaction_store;
+    // A fresh db is indexed from the genesis on.
+    assert_eq!(
+        store.get_state_value_history_indexed_since().unwrap(),
+        Some(0)
+    );
+
+    let resource_key = StateKey::raw(b"resource".to_vec());
+    let table_item_key = StateKey::table_item(TableHandle(AccountAddress::ONE), b"key".to_vec());
+    put_write_sets(store, 0, &[
+        write_set(&[(&resource_key, Some("r0")), (&table_item_key, Some("t0"))]),
+        write_set(&[(&table_item_key, Some("t1"))]),
+        write_set(&[(&resource_key, None), (&table_item_key, None)]),
+        write_set(&[(&resource_key, Some("r3"))]),
+    ]);
+
+    let resource_history = vec![
+        (0, Some(state_value("r0"))),
+        (2, None),
+        (3, Some(state_value("r3"))),
+    ];
+    assert_eq!(
+        store
+            .get_state_value_history(&resource_key, 0, 10, 3)
+            .unwrap(),
+        resource_history
+    );
+    assert_eq!(
+        store
+            .get_state_value_history(&table_item_key, 0, 10, 3)
+            .unwrap(),
+        vec![
+            (0, Some(state_value("t0"))),
+            (1, Some(state_value("t1"))),
+            (2, None),
+        ]
+    );
+
+    // Pages continue after the last version returned.
+    assert_eq!(
+        store
+            .get_state_value_history(&resource_key, 0, 2, 3)
+            .unwrap(),
+        resource_history[..2]
+    );
+    assert_eq!(
+        store
+            .get_state_value_history(&resource_key, 3, 2, 3)
+            .unwrap(),
+        resource_history[2..]
+    );
+    // Writes after the ledger version aren't returned.
+    assert_eq!(
+        store
+            .get_state_value_history(&resource_key, 0, 10, 2)
+            .unwrap(),
+        resource_history[..2]
+    );
+
+    let batch = SchemaBatch::new();
+    store.prune_state_value_history(0, 2, &batch).unwrap();
+    store.ledger_db.write_set_db().write_schemas(batch).unwrap();
+    assert_eq!(
+        store
+            .get_state_value_history(&resource_key, 0, 10, 3)
+            .unwrap(),
+        resource_history[1..]
+    );
+}
+
+#[test]
+fn test_state_value_history_indexed_since() {
+    let tmp_dir = TempPath::new();
+    let state_key = StateKey::raw(b"resource".to_vec());
+    let db = open_db_with_state_value_history_index(&tmp_dir, false);
+    assert_eq!(
+        db.transaction_store
+            .get_state_value_history_indexed_since()
+            .unwrap(),
+        None
+    );
+    put_write_sets(&db.transaction_store, 0, &[
+        write_set(&[(&state_key, Some("v0"))]),
+        write_set(&[(&state_key, Some("v1"))]),
+    ]);
+    db.ledger_db
+        .metadata_db()
+        .put::<DbMetadataSchema>(
+            &DbMetadataKey::LedgerCommitProgress,
+            &DbMetadataValue::Version(1),
+        )
+        .unwrap();
+    drop(db);
+
+    // Enabling the index only covers the versions committed from then on.
+    let db = open_db_with_state_value_history_index(&tmp_dir, true);
+    let store = &db.transaction_store;
+    assert_eq!(
+        store.get_state_value_history_indexed_since().unwrap(),
+        Some(2)
+    );
+    put_write_sets(store, 2, &[write_set(&[(&state_key, Some("v2"))])]);
+    assert!(store.get_state_value_history(&state_key, 0, 10, 2).is_err());
+    assert_eq!(
+        store.get_state_value_history(&state_key, 2, 10, 2).unwrap(),
+        vec![(2, Some(state_value("v2")))]
+    );
+    drop(db);
+
+    // Disabling it again forgets the first indexed version.
+    let db = open_db_with_state_value_history_index(&tmp_dir, false);
+    let store = &db.transaction_store;
+    assert_eq!(store.get_state_value_history_indexed_since().unwrap(), None);
+    assert!(store.get_state_value_history(&state_key, 2, 10, 2).is_err());
+}
+
+fn open_db_with_state_value_history_index(
+    tmp_dir: &TempPath,
+    enable_state_value_history_index: bool,
+) -> AptosDB {
+    AptosDB::open(
+        tmp_dir,
+        false,
+        NO_OP_STORAGE_PRUNER_CONFIG,
+        RocksdbConfigs {
+            enable_state_value_history_index,
+            ..Default::default()
+        },
+        false,
+        BUFFERED_STATE_TARGET_ITEMS,
+        DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    )
+    .unwrap()
+}
+
+fn state_value(value: &str) -> StateValue {
+    StateValue::from(value.as_bytes().to_vec())
+}
+
+fn write_set(writes: &[(&StateKey, Option<&str>)]) -> WriteSet {
+    WriteSetMut::new(writes.iter().map(|(state_key, value)| {
+        (
+            (*state_key).clone(),
+            WriteOp::from_state_value(value.map(state_value)),
+        )
+    }))
+    .freeze()
+    .unwrap()
+}
+
+fn put_write_sets(store: &TransactionStore, first_version: Version, write_sets: &[WriteSet]) {
+    let batch = SchemaBatch::new();
+    for (idx, write_set) in write_sets.iter().enumerate() {
+        store
+            .put_write_set(first_version + idx as Version, write_set, &batch)
+            .unwrap();
+    }
+    store.ledger_db.write_set_db().write_schemas(batch).unwrap();
+}
+
 fn init_store(
     mut universe: AccountInfoUniverse,
     gens: Vec<(Index, SignatureCheckedTransactionGen)>,
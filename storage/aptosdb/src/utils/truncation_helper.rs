@@ -66,7 +66,13 @@ pub(crate) fn get_state_merkle_commit_progress(
 // TODO(grao): Not all target version is valid. e.g. If we turn on skip_index_and_usage, we can
 // only truncate to a point with usage persisted (end of chunk or block).
 pub(crate) fn truncate_ledger_db(ledger_db: Arc<LedgerDb>, target_version: Version) -> Result<()> {
-    let event_store = EventStore::new(ledger_db.event_db_arc());
+    // The transaction by function index might have been enabled in an earlier run, so it is
+    // truncated regardless of the current config. The event by type index is truncated if it was
+    // ever built, which the event store tracks.
+    let event_store = EventStore::new(
+        ledger_db.event_db_arc(),
+        ledger_db.event_by_type_index_enabled(),
+    );
     let transaction_store = TransactionStore::new(Arc::clone(&ledger_db));
 
     let start_version = target_version + 1;
@@ -263,6 +269,12 @@ fn truncate_ledger_db_single_batch(
         start_version,
         &batch.ledger_metadata_db_batches,
     )?;
+    delete_state_value_history_data(
+        &ledger_db,
+        transaction_store,
+        start_version,
+        &batch.write_set_db_batches,
+    )?;
     delete_per_version_data(&ledger_db, start_version, &batch)?;
 
     delete_event_data(event_store, start_version, &batch.event_db_batches)?;
@@ -299,12 +311,49 @@ fn delete_transaction_index_data(
             "Truncate transaction index data."
         );
         transaction_store.prune_transaction_by_account(&transactions, batch)?;
+        transaction_store.prune_transaction_by_function(start_version, &transactions, batch)?;
         transaction_store.prune_transaction_by_hash(&transactions, batch)?;
     }
 
     Ok(())
 }
 
+fn delete_state_value_history_data(
+    ledger_db: &LedgerDb,
+    transaction_store: &TransactionStore,
+    start_version: Version,
+    batch: &SchemaBatch,
+) -> Result<()> {
+    let indexed_since = match transaction_store.get_state_value_history_indexed_since()? {
+        Some(indexed_since) => indexed_since,
+        None => return Ok(()),
+    };
+    // The truncated versions are indexed again when committed again.
+    if indexed_since > start_version {
+        transaction_store.put_state_value_history_indexed_since(start_version, batch)?;
+    }
+
+    let mut iter = ledger_db
+        .write_set_db()
+        .iter::<WriteSetSchema>(ReadOptions::default())?;
+    iter.seek_to_last();
+    if let Some((latest_version, _)) = iter.next().transpose()? {
+        if latest_version >= start_version {
+            info!(
+                start_version = start_version,
+                latest_version = latest_version,
+                "Truncate state value history data."
+            );
+            transaction_store.prune_state_value_history(
+                start_version,
+                latest_version + 1,
+                batch,
+            )?;
+        }
+    }
+    Ok(())
+}
+
 fn delete_per_epoch_data(
     ledger_db: &DB,
     start_version: Version,
@@ -83,8 +83,9 @@ pub struct TransactionRestoreController {
     inner: TransactionRestoreBatchController,
 }
 
+/// A transaction chunk of a backup, verified against the ledger info in its proof.
 #[allow(dead_code)]
-struct LoadedChunk {
+pub struct LoadedChunk {
     pub manifest: TransactionChunk,
     pub txns: Vec<Transaction>,
     pub txn_infos: Vec<TransactionInfo>,
@@ -95,7 +96,7 @@ struct LoadedChunk {
 }
 
 impl LoadedChunk {
-    async fn load(
+    pub async fn load(
         manifest: TransactionChunk,
         storage: &Arc<dyn BackupStorage>,
         epoch_history: Option<&Arc<EpochHistory>>,
@@ -159,7 +160,7 @@ impl LoadedChunk {
         })
     }
 
-    fn unpack(
+    pub fn unpack(
         self,
     ) -> (
         Vec<Transaction>,
@@ -2,10 +2,11 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use anyhow::Result;
-use aptos_db::db_debugger::{checkpoint, ledger, state_tree, truncate};
+use aptos_db::db_debugger::{checkpoint, index, ledger, state_tree, truncate};
 use clap::Parser;
 
-/// List snapshots, print nodes, make DB checkpoints and validate ledger hash
+/// List snapshots, print nodes, make DB checkpoints, validate ledger hash and build optional
+/// indices
 #[derive(Parser)]
 pub enum Command {
     #[clap(subcommand)]
@@ -14,6 +15,8 @@ pub enum Command {
     #[clap(subcommand)]
     Ledger(ledger::Cmd),
     Truncate(truncate::Cmd),
+    #[clap(subcommand)]
+    Index(index::Cmd),
 }
 
 impl Command {
@@ -23,6 +26,7 @@ impl Command {
             Command::Checkpoint(cmd) => cmd.run(),
             Command::Ledger(cmd) => cmd.run(),
             Command::Truncate(cmd) => cmd.run(),
+            Command::Index(cmd) => cmd.run(),
         }
     }
 }
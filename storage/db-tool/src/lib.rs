@@ -8,6 +8,7 @@ mod backup_maintenance;
 mod debugger;
 mod replay_verify;
 pub mod restore;
+mod simulate_leader_election;
 #[cfg(test)]
 mod tests;
 mod utils;
@@ -27,6 +28,8 @@ pub enum DBTool {
     Debug(debugger::Command),
     #[clap(subcommand)]
     BackupMaintenance(backup_maintenance::Command),
+    #[clap(subcommand)]
+    SimulateLeaderElection(simulate_leader_election::Command),
 }
 
 impl DBTool {
@@ -37,6 +40,7 @@ impl DBTool {
             DBTool::ReplayVerify(cmd) => cmd.run().await,
             DBTool::BackupMaintenance(cmd) => cmd.run().await,
             DBTool::Debug(cmd) => cmd.run(),
+            DBTool::SimulateLeaderElection(cmd) => cmd.run().await,
         }
     }
 }
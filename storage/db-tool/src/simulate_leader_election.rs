@@ -0,0 +1,386 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+use anyhow::{ensure, Result};
+use aptos_backup_cli::{
+    backup_types::{
+        epoch_ending::restore::EpochHistoryRestoreController,
+        transaction::{manifest::TransactionBackup, restore::LoadedChunk},
+    },
+    metadata::cache::{self, MetadataCacheOpt},
+    storage::DBToolStorageOpt,
+    utils::{
+        storage_ext::BackupStorageExt, ConcurrentDownloadsOpt, GlobalRestoreOptions,
+        RestoreRunMode, RocksdbOpt, TrustedWaypointOpt,
+    },
+};
+use aptos_config::config::{
+    BUFFERED_STATE_TARGET_ITEMS, DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+    NO_OP_STORAGE_PRUNER_CONFIG,
+};
+use aptos_consensus::leader_reputation_simulator::{
+    history_window, simulate_proposer_election, BlockHistory, HistoricalBlock,
+};
+use aptos_crypto::hash::{CryptoHash, TransactionAccumulatorHasher};
+use aptos_db::AptosDB;
+use aptos_storage_interface::DbReader;
+use aptos_types::{
+    account_config::{new_block_event_key, NewBlockEvent},
+    ledger_info::LedgerInfo,
+    on_chain_config::OnChainConsensusConfig,
+    proof::accumulator::InMemoryAccumulator,
+    transaction::Version,
+};
+use clap::{Parser, Subcommand};
+use std::{path::PathBuf, sync::Arc};
+
+/// Replay proposer election over the committed block history with alternative configs, and
+/// report the leader distribution each config would give
+#[derive(Subcommand)]
+pub enum Command {
+    #[clap(about = "Read the block history from a db")]
+    FromDb(FromDbOpt),
+    #[clap(about = "Read the block history from backups")]
+    FromBackup(FromBackupOpt),
+}
+
+#[derive(Parser)]
+pub struct SimulationOpt {
+    #[clap(
+        long,
+        num_args = 1..,
+        help = "YAML files of the OnChainConsensusConfigs to simulate. [Defaults to the default \
+        config]"
+    )]
+    config: Vec<PathBuf>,
+    #[clap(
+        long,
+        help = "The first transaction version of the history to replay. [Defaults to 0]"
+    )]
+    start_version: Option<Version>,
+    #[clap(
+        long,
+        help = "The last transaction version of the history to replay. [Defaults to the latest \
+        version available]"
+    )]
+    end_version: Option<Version>,
+}
+
+type NamedConfig = (String, OnChainConsensusConfig);
+
+impl SimulationOpt {
+    fn configs(&self) -> Result<Vec<NamedConfig>> {
+        if self.config.is_empty() {
+            return Ok(vec![(
+                "default".to_string(),
+                OnChainConsensusConfig::default(),
+            )]);
+        }
+        self.config
+            .iter()
+            .map(|path| {
+                let config: OnChainConsensusConfig = serde_yaml::from_slice(&std::fs::read(path)?)?;
+                Ok((path.display().to_string(), config))
+            })
+            .collect()
+    }
+
+    fn run(self, configs: Vec<NamedConfig>, history: BlockHistory) -> Result<()> {
+        let replayed_blocks = history.replayed_blocks();
+        println!(
+            "Replaying {} blocks from version {} to version {}, after {} preceding blocks.",
+            replayed_blocks.len(),
+            replayed_blocks.first().map_or(0, |block| block.version),
+            replayed_blocks.last().map_or(0, |block| block.version),
+            history.num_preceding_blocks,
+        );
+        for (name, config) in configs {
+            let report = simulate_proposer_election(&history, &config)?;
+            println!("Config {}:", name);
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Parser)]
+pub struct FromDbOpt {
+    #[clap(long, value_parser)]
+    db_dir: PathBuf,
+    #[clap(flatten)]
+    rocksdb_opt: RocksdbOpt,
+    #[clap(flatten)]
+    simulation: SimulationOpt,
+}
+
+impl FromDbOpt {
+    fn load_history(&self, configs: &[NamedConfig]) -> Result<BlockHistory> {
+        let db = AptosDB::open(
+            &self.db_dir,
+            true, /* read_only */
+            NO_OP_STORAGE_PRUNER_CONFIG,
+            self.rocksdb_opt.clone().into(),
+            false, /* indexer */
+            BUFFERED_STATE_TARGET_ITEMS,
+            DEFAULT_MAX_NUM_NODES_PER_LRU_CACHE_SHARD,
+        )?;
+        let start_version = self.simulation.start_version.unwrap_or(0);
+        let end_version = match self.simulation.end_version {
+            Some(end_version) => end_version,
+            None => db.get_latest_version()?,
+        };
+        ensure!(
+            start_version <= end_version,
+            "start_version should precede end_version."
+        );
+
+        let mut history = BlockHistory::default();
+        let (mut first_version, _, mut event) = db.get_block_info_by_version(start_version)?;
+        loop {
+            history.blocks.push(HistoricalBlock {
+                root_hash: db.get_accumulator_root_hash(first_version)?,
+                version: first_version,
+                event: event.clone(),
+            });
+            match db.get_block_info_by_height(event.height() + 1) {
+                Ok((next_version, _, next_event)) if next_version <= end_version => {
+                    first_version = next_version;
+                    event = next_event;
+                },
+                // Either past the end version, or past the latest block
+                _ => break,
+            }
+        }
+
+        // Validator sets of the history, and of the epochs before it the reputation looks at
+        let last_epoch = event.epoch();
+        let mut start_epoch = 0;
+        while start_epoch < last_epoch {
+            let proof = db.get_epoch_ending_ledger_infos(start_epoch, last_epoch)?;
+            ensure!(
+                !proof.ledger_info_with_sigs.is_empty(),
+                "No epoch ending ledger info from epoch {}",
+                start_epoch
+            );
+            for ledger_info in &proof.ledger_info_with_sigs {
+                add_validators(&mut history, ledger_info.ledger_info());
+            }
+            start_epoch += proof.ledger_info_with_sigs.len() as u64;
+        }
+
+        let first_height = history.blocks[0].event.height();
+        let num_preceding_blocks = preceding_window(configs, &history) as u64;
+        let preceding_blocks = (first_height.saturating_sub(num_preceding_blocks)..first_height)
+            .map(|height| {
+                let (version, _, event) = db.get_block_info_by_height(height)?;
+                Ok(HistoricalBlock {
+                    root_hash: db.get_accumulator_root_hash(version)?,
+                    version,
+                    event,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        add_preceding_blocks(&mut history, preceding_blocks);
+        Ok(history)
+    }
+
+    fn run(self) -> Result<()> {
+        let configs = self.simulation.configs()?;
+        let history = self.load_history(&configs)?;
+        self.simulation.run(configs, history)
+    }
+}
+
+#[derive(Parser)]
+pub struct FromBackupOpt {
+    #[clap(flatten)]
+    metadata_cache_opt: MetadataCacheOpt,
+    #[clap(flatten)]
+    trusted_waypoints_opt: TrustedWaypointOpt,
+    #[clap(flatten)]
+    storage: DBToolStorageOpt,
+    #[clap(flatten)]
+    concurrent_downloads: ConcurrentDownloadsOpt,
+    #[clap(flatten)]
+    simulation: SimulationOpt,
+}
+
+impl FromBackupOpt {
+    async fn run(self) -> Result<()> {
+        let configs = self.simulation.configs()?;
+        let storage = self.storage.init_storage().await?;
+        let concurrent_downloads = self.concurrent_downloads.get();
+        let metadata_view = cache::sync_and_load(
+            &self.metadata_cache_opt,
+            storage.clone(),
+            concurrent_downloads,
+        )
+        .await?;
+        let start_version = self.simulation.start_version.unwrap_or(0);
+        let end_version = match self.simulation.end_version {
+            Some(end_version) => end_version,
+            None => metadata_view.max_transaction_version()?.unwrap_or(0),
+        };
+        ensure!(
+            start_version <= end_version,
+            "start_version should precede end_version."
+        );
+
+        let epoch_handles = metadata_view
+            .select_epoch_ending_backups(end_version)?
+            .into_iter()
+            .map(|backup| backup.manifest)
+            .collect();
+        let epoch_history = Arc::new(
+            EpochHistoryRestoreController::new(
+                epoch_handles,
+                GlobalRestoreOptions {
+                    target_version: end_version,
+                    trusted_waypoints: Arc::new(self.trusted_waypoints_opt.verify()?),
+                    run_mode: Arc::new(RestoreRunMode::Verify),
+                    concurrent_downloads,
+                    replay_concurrency_level: 0, // won't replay, doesn't matter
+                },
+                storage.clone(),
+            )
+            .run()
+            .await?,
+        );
+        let mut history = BlockHistory::default();
+        for ledger_info in &epoch_history.epoch_endings {
+            add_validators(&mut history, ledger_info);
+        }
+
+        for backup in metadata_view.select_transaction_backups(start_version, end_version)? {
+            let manifest: TransactionBackup = storage.load_json_file(&backup.manifest).await?;
+            manifest.verify()?;
+            for chunk in manifest.chunks {
+                if chunk.last_version < start_version || chunk.first_version > end_version {
+                    continue;
+                }
+                let chunk = LoadedChunk::load(chunk, &storage, Some(&epoch_history)).await?;
+                history
+                    .blocks
+                    .extend(chunk_blocks(&chunk, start_version, end_version)?);
+            }
+        }
+
+        // Blocks before the history the reputation of its first rounds looks at, read backwards
+        // from the start version
+        let num_preceding_blocks = preceding_window(&configs, &history);
+        let mut preceding_blocks = Vec::new();
+        if num_preceding_blocks > 0 && start_version > 0 {
+            'backups: for backup in metadata_view
+                .select_transaction_backups(0, start_version - 1)?
+                .into_iter()
+                .rev()
+            {
+                let manifest: TransactionBackup = storage.load_json_file(&backup.manifest).await?;
+                manifest.verify()?;
+                for chunk in manifest.chunks.into_iter().rev() {
+                    if chunk.first_version >= start_version {
+                        continue;
+                    }
+                    let chunk = LoadedChunk::load(chunk, &storage, Some(&epoch_history)).await?;
+                    let mut blocks = chunk_blocks(&chunk, 0, start_version - 1)?;
+                    blocks.append(&mut preceding_blocks);
+                    preceding_blocks = blocks;
+                    if preceding_blocks.len() >= num_preceding_blocks {
+                        break 'backups;
+                    }
+                }
+            }
+        }
+        let num_skipped = preceding_blocks.len().saturating_sub(num_preceding_blocks);
+        preceding_blocks.drain(..num_skipped);
+        add_preceding_blocks(&mut history, preceding_blocks);
+        self.simulation.run(configs, history)
+    }
+}
+
+/// Collects the blocks started within the versions of the chunk, with the accumulator root hash
+/// after their block metadata transaction, rebuilt from the frozen subtrees of the range proof
+fn chunk_blocks(
+    chunk: &LoadedChunk,
+    start_version: Version,
+    end_version: Version,
+) -> Result<Vec<HistoricalBlock>> {
+    let mut accumulator = InMemoryAccumulator::<TransactionAccumulatorHasher>::new(
+        chunk
+            .range_proof
+            .left_siblings()
+            .iter()
+            .rev()
+            .cloned()
+            .collect(),
+        chunk.manifest.first_version,
+    )?;
+    let mut blocks = Vec::new();
+    for ((version, txn_info), events) in (chunk.manifest.first_version..)
+        .zip(&chunk.txn_infos)
+        .zip(&chunk.event_vecs)
+    {
+        if version > end_version {
+            break;
+        }
+        accumulator = accumulator.append(&[txn_info.hash()]);
+        if version < start_version {
+            continue;
+        }
+        for event in events {
+            if event.event_key() == Some(&new_block_event_key()) {
+                blocks.push(HistoricalBlock {
+                    event: NewBlockEvent::try_from_bytes(event.event_data())?,
+                    version,
+                    root_hash: accumulator.root_hash(),
+                });
+            }
+        }
+    }
+    Ok(blocks)
+}
+
+/// Number of blocks before the history any of the configs reads to elect its first leaders
+fn preceding_window(configs: &[NamedConfig], history: &BlockHistory) -> usize {
+    let num_validators = history
+        .blocks
+        .first()
+        .and_then(|block| history.epoch_to_validators.get(&block.event.epoch()))
+        .map_or(0, |validators| validators.len());
+    configs
+        .iter()
+        .map(|(_, config)| history_window(config, num_validators))
+        .max()
+        .unwrap_or(0)
+}
+
+fn add_preceding_blocks(history: &mut BlockHistory, preceding_blocks: Vec<HistoricalBlock>) {
+    history.num_preceding_blocks = preceding_blocks.len();
+    history.blocks.splice(0..0, preceding_blocks);
+}
+
+/// Records the validator set of the epoch following the epoch ending ledger info
+fn add_validators(history: &mut BlockHistory, ledger_info: &LedgerInfo) {
+    if let Some(epoch_state) = ledger_info.next_epoch_state() {
+        let validators = epoch_state
+            .verifier
+            .get_ordered_account_addresses_iter()
+            .map(|author| {
+                let voting_power = epoch_state.verifier.get_voting_power(&author).unwrap_or(0);
+                (author, voting_power)
+            })
+            .collect();
+        history
+            .epoch_to_validators
+            .insert(epoch_state.epoch, validators);
+    }
+}
+
+impl Command {
+    pub async fn run(self) -> Result<()> {
+        match self {
+            Command::FromDb(opt) => opt.run(),
+            Command::FromBackup(opt) => opt.run().await,
+        }
+    }
+}
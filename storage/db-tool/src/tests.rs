@@ -87,11 +87,15 @@ mod dbtool_tests {
     };
     use aptos_temppath::TempPath;
     use aptos_types::{
-        state_store::{state_key::StateKeyTag::AccessPath, state_key_prefix::StateKeyPrefix},
+        state_store::{
+            state_key::{StateKeyInner, StateKeyTag::AccessPath},
+            state_key_prefix::StateKeyPrefix,
+        },
         transaction::Version,
     };
     use clap::Parser;
     use std::{
+        collections::HashMap,
         default::Default,
         fs,
         ops::Deref,
@@ -550,6 +554,74 @@ mod dbtool_tests {
         rt.shutdown_timeout(Duration::from_secs(1));
     }
 
+    #[test]
+    fn test_backup_debugger_interface() {
+        use aptos_db::utils::iterators::PrefixedStateValueIterator;
+        use aptos_types::{account_config::CORE_CODE_ADDRESS, account_state::AccountState};
+        use aptos_validator_interface::{AptosValidatorInterface, BackupDebuggerInterface};
+
+        let backup_dir = TempPath::new();
+        backup_dir.create_as_dir().unwrap();
+        let new_db_dir = TempPath::new();
+        let old_db_dir = TempPath::new();
+        let (rt, _) = db_restore_test_setup(
+            1,
+            16,
+            PathBuf::from(backup_dir.path()),
+            PathBuf::from(old_db_dir.path()),
+            PathBuf::from(new_db_dir.path()),
+            false,
+        );
+        let (_ledger_db, _tree_db, state_kv_db) =
+            AptosDB::open_dbs(new_db_dir.path(), RocksdbConfigs::default(), true, 0).unwrap();
+        let debugger = rt
+            .block_on(BackupDebuggerInterface::open(
+                PathBuf::from(backup_dir.path()),
+                None,
+                HashMap::new(),
+            ))
+            .unwrap();
+
+        // Versions before and after the second snapshot are served from different snapshots.
+        for ver in 1..=16 {
+            let kvs = PrefixedStateValueIterator::new(
+                &state_kv_db,
+                StateKeyPrefix::new(AccessPath, b"".to_vec()),
+                None,
+                ver,
+                false,
+            )
+            .unwrap()
+            .collect::<Result<HashMap<_, _>, _>>()
+            .unwrap();
+            for (key, value) in &kvs {
+                let backup_value = rt
+                    .block_on(debugger.get_state_value_by_version(key, ver))
+                    .unwrap();
+                assert_eq!(backup_value.as_ref(), Some(value));
+            }
+
+            let framework_kvs = kvs
+                .into_iter()
+                .filter(|(key, _)| match key.inner() {
+                    StateKeyInner::AccessPath(access_path) => {
+                        access_path.address == CORE_CODE_ADDRESS
+                    },
+                    _ => false,
+                })
+                .collect();
+            let expected =
+                AccountState::from_access_paths_and_values(CORE_CODE_ADDRESS, &framework_kvs)
+                    .unwrap();
+            let account_state = rt
+                .block_on(debugger.get_account_state_by_version(CORE_CODE_ADDRESS, ver))
+                .unwrap();
+            assert!(account_state.is_some());
+            assert_eq!(account_state, expected);
+        }
+        rt.shutdown_timeout(Duration::from_secs(1));
+    }
+
     #[test]
     fn test_resume_db_from_kv_replay() {
         let backup_dir = TempPath::new();
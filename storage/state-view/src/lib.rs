@@ -24,6 +24,7 @@ use std::ops::Deref;
 pub mod account_with_state_cache;
 pub mod account_with_state_view;
 pub mod in_memory_state_view;
+pub mod overlay_state_view;
 
 /// `StateView` is a trait that defines a read-only snapshot of the global state. It is passed to
 /// the VM for transaction execution, during which the VM is guaranteed to read anything at the
@@ -67,6 +68,9 @@ pub enum StateViewId {
     TransactionValidation { base_version: Version },
     /// For test, db-bootstrapper, etc. Usually not aimed to pass to VM.
     Miscellaneous,
+    /// A view replacing some of the modules in storage, identified by `code_hash`, e.g. to replay
+    /// or simulate transactions with different code.
+    CodeOverrides { code_hash: HashValue },
 }
 
 impl<R, S, K> TStateView for R
@@ -0,0 +1,80 @@
+// Copyright © Aptos Foundation
+// SPDX-License-Identifier: Apache-2.0
+
+#![forbid(unsafe_code)]
+use crate::{StateViewId, TStateView};
+use anyhow::Result;
+use aptos_crypto::HashValue;
+use aptos_types::{
+    state_store::{
+        state_key::{StateKey, StateKeyInner},
+        state_storage_usage::StateStorageUsage,
+        state_value::StateValue,
+    },
+    write_set::{TransactionWrite, WriteSet},
+};
+use std::collections::HashMap;
+
+// A state view that layers in-memory writes on top of a base state view. A key present in the
+// overlay shadows the base view, with `None` marking the key as deleted.
+//
+// Once a module is overwritten, the view reports a `StateViewId::CodeOverrides` id, so that VMs
+// with the modules of the base view already loaded are not reused for it.
+pub struct OverlayStateView<S> {
+    base: S,
+    overlay: HashMap<StateKey, Option<StateValue>>,
+    code_hash: Option<HashValue>,
+}
+
+impl<S> OverlayStateView<S> {
+    pub fn new(base: S) -> Self {
+        Self {
+            base,
+            overlay: HashMap::new(),
+            code_hash: None,
+        }
+    }
+
+    pub fn base(&self) -> &S {
+        &self.base
+    }
+
+    pub fn overwrite(&mut self, state_key: StateKey, value: Option<StateValue>) {
+        if matches!(state_key.inner(), StateKeyInner::AccessPath(path) if path.is_code()) {
+            let bytes = bcs::to_bytes(&(self.code_hash, &state_key, &value))
+                .expect("Failed to serialize module override.");
+            self.code_hash = Some(HashValue::sha3_256_of(&bytes));
+        }
+        self.overlay.insert(state_key, value);
+    }
+
+    pub fn apply_write_set(&mut self, write_set: &WriteSet) {
+        for (state_key, write_op) in write_set.iter() {
+            self.overwrite(state_key.clone(), write_op.as_state_value());
+        }
+    }
+}
+
+impl<S: TStateView<Key = StateKey>> TStateView for OverlayStateView<S> {
+    type Key = StateKey;
+
+    fn id(&self) -> StateViewId {
+        match self.code_hash {
+            Some(code_hash) => StateViewId::CodeOverrides { code_hash },
+            None => self.base.id(),
+        }
+    }
+
+    fn get_state_value(&self, state_key: &StateKey) -> Result<Option<StateValue>> {
+        match self.overlay.get(state_key) {
+            Some(value) => Ok(value.clone()),
+            None => self.base.get_state_value(state_key),
+        }
+    }
+
+    // Storage usage is only tracked at epoch boundaries, so the base view's usage is still the
+    // one the VM would observe.
+    fn get_usage(&self) -> Result<StateStorageUsage> {
+        self.base.get_usage()
+    }
+}
@@ -35,6 +35,10 @@ use aptos_types::{
     },
     write_set::WriteSet,
 };
+use move_core_types::{
+    identifier::IdentStr,
+    language_storage::{ModuleId, TypeTag},
+};
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use thiserror::Error;
@@ -199,6 +203,20 @@ pub trait DbReader: Send + Sync {
             ledger_version: Version,
         ) -> Result<Vec<EventWithVersion>>;
 
+        /// Returns the events of type `type_tag` starting at the event at `start_index` of
+        /// `start_version` and emitted before `end_version`, with `version <= ledger_version`,
+        /// each with its index in the transaction. Will return no more than `limit` events.
+        /// Events at pruned versions are skipped. Requires the event by type index.
+        fn get_events_by_type(
+            &self,
+            type_tag: &TypeTag,
+            start_version: Version,
+            start_index: u64,
+            end_version: Version,
+            limit: u64,
+            ledger_version: Version,
+        ) -> Result<Vec<(u64, EventWithVersion)>>;
+
         fn get_transaction_iterator(
             &self,
             start_version: Version,
@@ -312,6 +330,19 @@ pub trait DbReader: Send + Sync {
             ledger_version: Version,
         ) -> Result<AccountTransactionsWithProof>;
 
+        /// Returns the versions of the user transactions calling `function` in `module`, at or
+        /// after `start_version`. Will return no more than `limit` versions, with
+        /// `version <= ledger_version`. Transactions at pruned versions are skipped. Requires the
+        /// transaction by function index.
+        fn get_transaction_versions_by_function(
+            &self,
+            module: &ModuleId,
+            function: &IdentStr,
+            start_version: Version,
+            limit: u64,
+            ledger_version: Version,
+        ) -> Result<Vec<Version>>;
+
         /// Returns proof of new state for a given ledger info with signatures relative to version known
         /// to client
         fn get_state_proof_with_ledger_info(
@@ -346,6 +377,18 @@ pub trait DbReader: Send + Sync {
             version: Version,
         ) -> Result<Option<(Version, StateValue)>>;
 
+        /// Returns the writes to `state_key` at or after `start_version`, as `(version, value)`
+        /// pairs where the value is `None` if the key was deleted. Will return no more than
+        /// `limit` writes, with `version <= ledger_version`. Writes at pruned versions are
+        /// skipped. Requires the state value history index.
+        fn get_state_value_history(
+            &self,
+            state_key: &StateKey,
+            start_version: Version,
+            limit: u64,
+            ledger_version: Version,
+        ) -> Result<Vec<(Version, Option<StateValue>)>>;
+
         /// Returns the proof of the given state key and version.
         fn get_state_proof_by_version_ext(
             &self,
@@ -439,6 +482,19 @@ pub trait DbReader: Send + Sync {
         /// Returns whether the internal indexer DB has been enabled or not
         fn indexer_enabled(&self) -> bool;
 
+        /// Returns whether the state value history index is maintained or not
+        fn state_value_history_index_enabled(&self) -> bool;
+
+        /// Returns the first version covered by the state value history index, `None` if the
+        /// index isn't maintained.
+        fn get_state_value_history_indexed_since(&self) -> Result<Option<Version>>;
+
+        /// Returns whether the event by type index is maintained or not
+        fn event_by_type_index_enabled(&self) -> bool;
+
+        /// Returns whether the transaction by function index is maintained or not
+        fn transaction_by_function_index_enabled(&self) -> bool;
+
         /// Returns state storage usage at the end of an epoch.
         fn get_state_storage_usage(&self, version: Option<Version>) -> Result<StateStorageUsage>;
     ); // end delegated
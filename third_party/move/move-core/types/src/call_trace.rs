@@ -1,22 +1,73 @@
-use serde::{Deserialize, Serialize};
+use crate::{
+    account_address::AccountAddress,
+    language_storage::{ModuleId, StructTag, TypeTag},
+    value::MoveValue,
+    vm_status::StatusCode,
+};
+use serde::Serialize;
 
 const CALL_STACK_SIZE_LIMIT: usize = 1024;
 
 /// A call trace
 ///
 /// This is a representation of the debug call trace
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct InternalCallTrace {
     pub pc: u16,
     pub module_id: String,
     pub func_name: String,
-    pub inputs: Vec<String>,
-    pub outputs: Vec<String>,
+    /// Arguments of the call. `None` for values that could not be decoded.
+    pub inputs: Vec<Option<InternalCallTraceValue>>,
+    /// Return values of the call. `None` for values that could not be decoded.
+    pub outputs: Vec<Option<InternalCallTraceValue>>,
     pub type_args: Vec<String>,
     pub sub_traces: Vec<InternalCallTrace>,
+    /// Whether the callee is a native function. Native calls never have sub traces.
+    pub is_native: bool,
+    /// Global storage operations performed by the function's own code, in order of first use.
+    pub resource_accesses: Vec<InternalResourceAccess>,
+    /// Gas consumed by the call including its sub traces, in internal gas units.
+    pub gas_used: u64,
+    /// Set if execution failed while this call was the innermost one.
+    pub abort: Option<InternalCallTraceAbort>,
 }
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+/// An argument or return value of a traced call. References are recorded as the value they
+/// point to.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct InternalCallTraceValue {
+    pub type_tag: TypeTag,
+    pub value: MoveValue,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+pub enum ResourceAccessKind {
+    Exists,
+    Read,
+    Write,
+    MoveFrom,
+    MoveTo,
+}
+
+/// A global storage operation performed by a traced call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct InternalResourceAccess {
+    pub address: AccountAddress,
+    pub struct_tag: StructTag,
+    pub kind: ResourceAccessKind,
+}
+
+/// The error that stopped execution inside a traced call.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct InternalCallTraceAbort {
+    pub status_code: StatusCode,
+    /// The abort code for `ABORTED`, otherwise any sub status of the error.
+    pub sub_status: Option<u64>,
+    pub location: Option<ModuleId>,
+    pub code_offset: Option<u16>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize)]
 pub struct CallTraces(Vec<InternalCallTrace>);
 
 impl CallTraces {
@@ -37,11 +88,29 @@ impl CallTraces {
         self.0.pop()
     }
 
-    pub fn set_outputs(&mut self, outputs: Vec<String>) {
+    pub fn set_outputs(&mut self, outputs: Vec<Option<InternalCallTraceValue>>) {
         let length = self.0.len();
         self.0[length - 1].outputs = outputs
     }
 
+    pub fn set_gas_used(&mut self, gas_used: u64) {
+        let length = self.0.len();
+        self.0[length - 1].gas_used = gas_used
+    }
+
+    pub fn set_abort(&mut self, abort: InternalCallTraceAbort) {
+        let length = self.0.len();
+        self.0[length - 1].abort = Some(abort)
+    }
+
+    pub fn add_resource_access(&mut self, access: InternalResourceAccess) {
+        let length = self.0.len();
+        let resource_accesses = &mut self.0[length - 1].resource_accesses;
+        if !resource_accesses.contains(&access) {
+            resource_accesses.push(access);
+        }
+    }
+
     pub fn push_call_trace(&mut self, call_trace: InternalCallTrace) {
         let length = self.0.len();
         self.0[length - 1].sub_traces.push(call_trace);
@@ -51,7 +120,17 @@ impl CallTraces {
         self.0.len()
     }
 
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the root of the call tree. Frames that are still open, e.g. because execution
+    /// aborted inside them, are folded into their callers first.
     pub fn root(&mut self) -> Option<InternalCallTrace> {
+        while self.0.len() > 1 {
+            let top_call = self.0.pop().unwrap();
+            self.push_call_trace(top_call);
+        }
         self.0.pop()
     }
 }
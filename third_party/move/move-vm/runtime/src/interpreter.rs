@@ -2,15 +2,25 @@
 // Copyright (c) The Move Contributors
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{data_cache::TransactionDataCache, loader::{Function, Loader, Resolver}, native_extensions::NativeContextExtensions, native_functions::NativeContext, trace};
+use crate::{
+    data_cache::TransactionDataCache,
+    loader::{Function, Loader, Resolver},
+    native_extensions::NativeContextExtensions,
+    native_functions::NativeContext,
+    trace,
+};
 use fail::fail_point;
 use move_binary_format::{
     errors::*,
     file_format::{Ability, AbilitySet, Bytecode, FunctionHandleIndex, FunctionInstantiationIndex},
 };
+use move_core_types::call_trace::{
+    CallTraces, InternalCallTrace, InternalCallTraceAbort, InternalCallTraceValue,
+    InternalResourceAccess, ResourceAccessKind,
+};
 use move_core_types::{
     account_address::AccountAddress,
-    gas_algebra::{NumArgs, NumBytes},
+    gas_algebra::{InternalGas, NumArgs, NumBytes},
     language_storage::TypeTag,
     vm_status::{StatusCode, StatusType},
 };
@@ -25,8 +35,6 @@ use move_vm_types::{
     views::TypeView,
 };
 use std::{cmp::min, collections::VecDeque, fmt::Write, sync::Arc};
-use move_core_types::call_trace::{InternalCallTrace, CallTraces};
-use move_core_types::value::MoveTypeLayout;
 
 macro_rules! debug_write {
     ($($toks: tt)*) => {
@@ -64,6 +72,9 @@ pub(crate) struct Interpreter {
     call_stack: CallStack,
     /// Whether to perform a paranoid type safety checks at runtime.
     paranoid_type_checks: bool,
+    /// Global storage operations not yet attributed to a call trace. Only set when calls are
+    /// being traced.
+    resource_accesses: Option<Vec<InternalResourceAccess>>,
 }
 
 struct TypeWithLoader<'a, 'b> {
@@ -80,6 +91,8 @@ impl<'a, 'b> TypeView for TypeWithLoader<'a, 'b> {
 impl Interpreter {
     /// Entrypoint into the interpreter. All external calls need to be routed through this
     /// function.
+    ///
+    /// If `call_traces` is provided, every Move call made during execution is recorded into it.
     pub(crate) fn entrypoint(
         function: Arc<Function>,
         ty_args: Vec<Type>,
@@ -88,34 +101,29 @@ impl Interpreter {
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
         loader: &Loader,
+        call_traces: Option<&mut CallTraces>,
     ) -> VMResult<Vec<Value>> {
-        Interpreter {
-            operand_stack: Stack::new(),
-            call_stack: CallStack::new(),
-            paranoid_type_checks: loader.vm_config().paranoid_type_checks,
-        }
-        .execute_main(
-            loader, data_store, gas_meter, extensions, function, ty_args, args,
-        )
-    }
-
-    pub(crate) fn call_trace(
-        function: Arc<Function>,
-        ty_args: Vec<Type>,
-        args: Vec<Value>,
-        data_store: &mut TransactionDataCache,
-        gas_meter: &mut impl GasMeter,
-        extensions: &mut NativeContextExtensions,
-        loader: &Loader,
-    ) -> VMResult<CallTraces> {
         let interpreter = Interpreter {
             operand_stack: Stack::new(),
             call_stack: CallStack::new(),
             paranoid_type_checks: loader.vm_config().paranoid_type_checks,
+            resource_accesses: call_traces.as_ref().map(|_| vec![]),
         };
-        interpreter.call_trace_internal(
-            loader, data_store, gas_meter, extensions, function, ty_args, args,
-        )
+        match call_traces {
+            Some(call_traces) => interpreter.call_trace_internal(
+                loader,
+                data_store,
+                gas_meter,
+                extensions,
+                function,
+                ty_args,
+                args,
+                call_traces,
+            ),
+            None => interpreter.execute_main(
+                loader, data_store, gas_meter, extensions, function, ty_args, args,
+            ),
+        }
     }
 
     /// Main loop for the execution of a function.
@@ -287,6 +295,7 @@ impl Interpreter {
         }
     }
 
+    /// Same as `execute_main`, but records a call trace for each function invoked.
     fn call_trace_internal(
         mut self,
         loader: &Loader,
@@ -296,10 +305,10 @@ impl Interpreter {
         function: Arc<Function>,
         ty_args: Vec<Type>,
         args: Vec<Value>,
-    ) -> VMResult<CallTraces> {
+        call_traces: &mut CallTraces,
+    ) -> VMResult<Vec<Value>> {
         let mut locals = Locals::new(function.local_count());
         let mut args_1 = vec![];
-        let mut call_traces = CallTraces::new();
         for (i, value) in args.into_iter().enumerate() {
             locals
                 .store_loc(
@@ -313,31 +322,64 @@ impl Interpreter {
             args_1.push(value);
         }
 
-        let mut current_frame = self
+        let current_frame = self
             .make_new_frame(loader, function, ty_args, locals)
             .map_err(|err| self.set_location(err))?;
-        call_traces.push(InternalCallTrace {
-            pc: current_frame.pc,
-            module_id: "".to_string(),
-            func_name: current_frame.function.name().to_string(),
-            inputs: args_1.into_iter().enumerate().map(|(_, i)| i.to_string()).collect(),
-            outputs: vec![],
-            // TODO(pcxu): add type args
-            type_args: current_frame.ty_args().into_iter().map(|ty| {
-                loader.type_to_type_tag(ty).unwrap().to_string()
-            }).collect(),
-            sub_traces: vec![],
-        }).map_err(|_e| {
-            let err = PartialVMError::new(StatusCode::ABORTED);
-            let err = set_err_info!(current_frame, err);
-            self.maybe_core_dump(err, &current_frame)
-        })?;
+        let mut recorder = CallTraceRecorder::new(call_traces);
+        recorder
+            .enter(
+                InternalCallTrace {
+                    pc: current_frame.pc,
+                    module_id: current_frame
+                        .function
+                        .module_id()
+                        .map(|module_id| module_id.to_string())
+                        .unwrap_or_default(),
+                    func_name: current_frame.function.name().to_string(),
+                    inputs: Self::call_trace_values(
+                        loader,
+                        &args_1,
+                        current_frame.function.parameter_types(),
+                        current_frame.ty_args(),
+                    ),
+                    type_args: Self::call_trace_type_args(loader, current_frame.ty_args()),
+                    ..Default::default()
+                },
+                gas_meter,
+            )
+            .map_err(|e| set_err_info!(current_frame, e))?;
+        match self.call_trace_frames(
+            loader,
+            data_store,
+            gas_meter,
+            extensions,
+            current_frame,
+            &mut recorder,
+        ) {
+            // end of execution. `self` should no longer be used afterward
+            Ok(()) => Ok(self.operand_stack.value),
+            Err(err) => {
+                recorder.abort(&err, gas_meter);
+                Err(err)
+            },
+        }
+    }
+
+    /// The main loop of `call_trace_internal`. Returns once the outermost frame has returned.
+    fn call_trace_frames(
+        &mut self,
+        loader: &Loader,
+        data_store: &mut TransactionDataCache,
+        gas_meter: &mut impl GasMeter,
+        extensions: &mut NativeContextExtensions,
+        mut current_frame: Frame,
+        recorder: &mut CallTraceRecorder,
+    ) -> VMResult<()> {
         loop {
             let resolver = current_frame.resolver(loader);
-            let exit_code =
-                current_frame //self
-                    .execute_code(&resolver, &mut self, data_store, gas_meter)
-                    .map_err(|err| self.maybe_core_dump(err, &current_frame))?;
+            let exit_code = current_frame.execute_code(&resolver, self, data_store, gas_meter);
+            recorder.add_resource_accesses(self.take_resource_accesses());
+            let exit_code = exit_code.map_err(|err| self.maybe_core_dump(err, &current_frame))?;
             match exit_code {
                 ExitCode::Return => {
                     let non_ref_vals = current_frame
@@ -351,45 +393,26 @@ impl Interpreter {
                         .charge_drop_frame(non_ref_vals.iter())
                         .map_err(|e| self.set_location(e))?;
 
-                    let mut outputs = vec![];
-                    for val in self.operand_stack.last_n(current_frame.function.return_type_count()).unwrap() {
-                        outputs.push((*val).copy_value().unwrap());
-                    }
-                    call_traces.set_outputs(
-                        outputs.into_iter().zip(current_frame.function.return_types()).map(|(value, ty)| {
-                            let (ty, value) = match ty {
-                                Type::Reference(inner) | Type::MutableReference(inner) => {
-                                    let ref_value: Reference = value.cast().map_err(|_err| {
-                                        PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(
-                                            "non reference value given for a reference typed return value".to_string(),
-                                        )
-                                    })?;
-                                    let inner_value = ref_value.read_ref()?;
-                                    (&**inner, inner_value)
-                                },
-                                _ => (ty, value),
-                            };
-                            let layout = loader.type_to_type_layout(ty).map_err(|_err| {
-                                PartialVMError::new(StatusCode::VERIFICATION_ERROR).with_message(
-                                    "entry point functions cannot have non-serializable return types".to_string(),
-                                )
-                            })?;
-                            Ok(value.as_move_value(&layout).to_string())
-                        }).map(|v: Result<String, PartialVMError>| v.unwrap_or("".to_string())).collect());
+                    recorder.set_outputs(self.call_trace_stack_values(
+                        loader,
+                        current_frame.function.return_type_count(),
+                        current_frame.function.return_types(),
+                        current_frame.ty_args(),
+                    ));
+                    recorder.exit(gas_meter);
 
                     if let Some(frame) = self.call_stack.pop() {
                         // Note: the caller will find the callee's return values at the top of the shared operand stack
                         current_frame = frame;
                         current_frame.pc += 1; // advance past the Call instruction in the caller
-                        let top_call = call_traces.pop().unwrap();
-                        call_traces.push_call_trace(top_call);
                     } else {
-                        // end of execution. `self` should no longer be used afterward
-                        return Ok(call_traces);
+                        return Ok(());
                     }
                 },
                 ExitCode::Call(fh_idx) => {
-                    let func = resolver.function_from_handle(fh_idx);
+                    let func = resolver
+                        .function_from_handle(fh_idx)
+                        .map_err(|e| self.set_location(e))?;
 
                     if self.paranoid_type_checks {
                         self.check_friend_or_private_call(&current_frame.function, &func)?;
@@ -414,56 +437,43 @@ impl Interpreter {
                         )
                         .map_err(|e| set_err_info!(current_frame, e))?;
 
+                    recorder
+                        .enter(
+                            InternalCallTrace {
+                                module_id: module_id.to_string(),
+                                func_name: func.name().to_string(),
+                                inputs: self.call_trace_stack_values(
+                                    loader,
+                                    func.arg_count(),
+                                    func.parameter_types(),
+                                    &[],
+                                ),
+                                is_native: func.is_native(),
+                                ..Default::default()
+                            },
+                            gas_meter,
+                        )
+                        .map_err(|e| set_err_info!(current_frame, e))?;
+
                     if func.is_native() {
                         self.call_native(
                             &resolver,
                             data_store,
                             gas_meter,
                             extensions,
-                            func,
+                            func.clone(),
                             vec![],
                         )?;
+                        recorder.set_outputs(self.call_trace_stack_values(
+                            loader,
+                            func.return_type_count(),
+                            func.return_types(),
+                            &[],
+                        ));
+                        recorder.exit(gas_meter);
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         continue;
                     }
-                    let mut inputs = vec![];
-                    for val in self.operand_stack.last_n(func.arg_count()).unwrap() {
-                        inputs.push((*val).copy_value().unwrap());
-                    }
-                    call_traces.push(InternalCallTrace {
-                        pc: 0,
-                        module_id: module_id.to_string(),
-                        func_name: func.name().to_string(),
-                        inputs: inputs.into_iter().zip(func.parameter_types()).map(|(value, ty)| {
-                            let (ty, value) = match ty {
-                                Type::Reference(inner) | Type::MutableReference(inner) => {
-                                    let ref_value: Reference = value.cast().map_err(|_err| {
-                                        PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(
-                                            "non reference value given for a reference typed return value".to_string(),
-                                        )
-                                    })?;
-                                    let inner_value = ref_value.read_ref()?;
-                                    (&**inner, inner_value)
-                                },
-                                _ => (ty, value),
-                            };
-                            let layout = loader.type_to_type_layout(ty).map_err(|_err| {
-                                PartialVMError::new(StatusCode::VERIFICATION_ERROR).with_message(
-                                    "entry point functions cannot have non-serializable return types".to_string(),
-                                )
-                            })?;
-                            Ok(value.as_move_value(&layout).to_string())
-                        }).map(|v: Result<String, PartialVMError>| v.unwrap_or("".to_string())).collect(),
-                        outputs: vec![],
-                        type_args: current_frame.ty_args().into_iter().map(|ty| {
-                            loader.type_to_type_tag(ty).unwrap().to_string()
-                        }).collect(),
-                        sub_traces: vec![],
-                    }).map_err(|_e| {
-                        let err = PartialVMError::new(StatusCode::ABORTED);
-                        let err = set_err_info!(current_frame, err);
-                        self.maybe_core_dump(err, &current_frame)
-                    })?;
                     let frame = self
                         .make_call_frame(loader, func, vec![])
                         .map_err(|e| self.set_location(e))
@@ -481,7 +491,9 @@ impl Interpreter {
                     let ty_args = resolver
                         .instantiate_generic_function(idx, current_frame.ty_args())
                         .map_err(|e| set_err_info!(current_frame, e))?;
-                    let func = resolver.function_from_instantiation(idx);
+                    let func = resolver
+                        .function_from_instantiation(idx)
+                        .map_err(|e| self.set_location(e))?;
 
                     if self.paranoid_type_checks {
                         self.check_friend_or_private_call(&current_frame.function, &func)?;
@@ -507,51 +519,44 @@ impl Interpreter {
                         )
                         .map_err(|e| set_err_info!(current_frame, e))?;
 
+                    recorder
+                        .enter(
+                            InternalCallTrace {
+                                module_id: module_id.to_string(),
+                                func_name: func.name().to_string(),
+                                inputs: self.call_trace_stack_values(
+                                    loader,
+                                    func.arg_count(),
+                                    func.parameter_types(),
+                                    &ty_args,
+                                ),
+                                type_args: Self::call_trace_type_args(loader, &ty_args),
+                                is_native: func.is_native(),
+                                ..Default::default()
+                            },
+                            gas_meter,
+                        )
+                        .map_err(|e| set_err_info!(current_frame, e))?;
+
                     if func.is_native() {
                         self.call_native(
-                            &resolver, data_store, gas_meter, extensions, func, ty_args,
+                            &resolver,
+                            data_store,
+                            gas_meter,
+                            extensions,
+                            func.clone(),
+                            ty_args.clone(),
                         )?;
+                        recorder.set_outputs(self.call_trace_stack_values(
+                            loader,
+                            func.return_type_count(),
+                            func.return_types(),
+                            &ty_args,
+                        ));
+                        recorder.exit(gas_meter);
                         current_frame.pc += 1; // advance past the Call instruction in the caller
                         continue;
                     }
-                    let mut inputs = vec![];
-                    for val in self.operand_stack.last_n(func.arg_count()).unwrap() {
-                        inputs.push((*val).copy_value().unwrap());
-                    }
-                    call_traces.push(InternalCallTrace {
-                        pc: 0,
-                        module_id: module_id.to_string(),
-                        func_name: func.name().to_string(),
-                        inputs: inputs.into_iter().zip(func.parameter_types()).map(|(value, ty)| {
-                            let (ty, value) = match ty {
-                                Type::Reference(inner) | Type::MutableReference(inner) => {
-                                    let ref_value: Reference = value.cast().map_err(|_err| {
-                                        PartialVMError::new(StatusCode::INTERNAL_TYPE_ERROR).with_message(
-                                            "non reference value given for a reference typed return value".to_string(),
-                                        )
-                                    })?;
-                                    let inner_value = ref_value.read_ref()?;
-                                    (&**inner, inner_value)
-                                },
-                                _ => (ty, value),
-                            };
-                            let layout = loader.type_to_type_layout(ty).map_err(|_err| {
-                                PartialVMError::new(StatusCode::VERIFICATION_ERROR).with_message(
-                                    "entry point functions cannot have non-serializable return types".to_string(),
-                                )
-                            })?;
-                            Ok(value.as_move_value(&layout).to_string())
-                        }).map(|v: Result<String, PartialVMError>| v.unwrap_or("".to_string())).collect(),
-                        outputs: vec![],
-                        type_args: current_frame.ty_args().into_iter().map(|ty| {
-                            loader.type_to_type_tag(ty).unwrap().to_string()
-                        }).collect(),
-                        sub_traces: vec![],
-                    }).map_err(|_e| {
-                        let err = PartialVMError::new(StatusCode::ABORTED);
-                        let err = set_err_info!(current_frame, err);
-                        self.maybe_core_dump(err, &current_frame)
-                    })?;
                     let frame = self
                         .make_call_frame(loader, func, ty_args)
                         .map_err(|e| self.set_location(e))
@@ -567,6 +572,91 @@ impl Interpreter {
         }
     }
 
+    /// Decodes the values of a traced call using the layouts of their declared types.
+    /// References are recorded as the values they point to. Values that cannot be decoded are
+    /// recorded as `None`.
+    fn call_trace_values<'v>(
+        loader: &Loader,
+        values: impl IntoIterator<Item = &'v Value>,
+        tys: &[Type],
+        ty_args: &[Type],
+    ) -> Vec<Option<InternalCallTraceValue>> {
+        values
+            .into_iter()
+            .zip(tys)
+            .map(|(value, ty)| {
+                let value = value.copy_value()?;
+                let (ty, value) = match ty.subst(ty_args)? {
+                    Type::Reference(inner) | Type::MutableReference(inner) => {
+                        let ref_value: Reference = value.cast()?;
+                        (*inner, ref_value.read_ref()?)
+                    },
+                    ty => (ty, value),
+                };
+                let type_tag = loader.type_to_type_tag(&ty)?;
+                let layout = loader.type_to_type_layout(&ty)?;
+                Ok(InternalCallTraceValue {
+                    type_tag,
+                    value: value.as_move_value(&layout),
+                })
+            })
+            .map(|value: PartialVMResult<InternalCallTraceValue>| value.ok())
+            .collect()
+    }
+
+    /// Decodes the top `count` values of the operand stack, e.g. the arguments of a call about
+    /// to be made or the return values of a call that just returned.
+    fn call_trace_stack_values(
+        &self,
+        loader: &Loader,
+        count: usize,
+        tys: &[Type],
+        ty_args: &[Type],
+    ) -> Vec<Option<InternalCallTraceValue>> {
+        match self.operand_stack.last_n(count) {
+            Ok(values) => Self::call_trace_values(loader, values, tys, ty_args),
+            Err(_) => vec![],
+        }
+    }
+
+    fn call_trace_type_args(loader: &Loader, ty_args: &[Type]) -> Vec<String> {
+        ty_args
+            .iter()
+            .map(|ty| {
+                loader
+                    .type_to_type_tag(ty)
+                    .map(|type_tag| type_tag.to_string())
+                    .unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Records a global storage operation when tracing calls.
+    fn record_resource_access(
+        &mut self,
+        loader: &Loader,
+        addr: AccountAddress,
+        ty: &Type,
+        kind: ResourceAccessKind,
+    ) {
+        if let Some(resource_accesses) = self.resource_accesses.as_mut() {
+            if let Ok(TypeTag::Struct(struct_tag)) = loader.type_to_type_tag(ty) {
+                resource_accesses.push(InternalResourceAccess {
+                    address: addr,
+                    struct_tag: *struct_tag,
+                    kind,
+                });
+            }
+        }
+    }
+
+    fn take_resource_accesses(&mut self) -> Vec<InternalResourceAccess> {
+        self.resource_accesses
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
     /// Returns a `Frame` if the call is to a Move function. Calls to native functions are
     /// "inlined" and this returns `None`.
     ///
@@ -876,6 +966,12 @@ impl Interpreter {
         addr: AccountAddress,
         ty: &Type,
     ) -> PartialVMResult<()> {
+        let kind = if is_mut {
+            ResourceAccessKind::Write
+        } else {
+            ResourceAccessKind::Read
+        };
+        self.record_resource_access(loader, addr, ty, kind);
         let res = Self::load_resource(loader, data_store, gas_meter, addr, ty)?.borrow_global();
         gas_meter.charge_borrow_global(
             is_mut,
@@ -899,6 +995,7 @@ impl Interpreter {
         addr: AccountAddress,
         ty: &Type,
     ) -> PartialVMResult<()> {
+        self.record_resource_access(loader, addr, ty, ResourceAccessKind::Exists);
         let gv = Self::load_resource(loader, data_store, gas_meter, addr, ty)?;
         let exists = gv.exists()?;
         gas_meter.charge_exists(is_generic, TypeWithLoader { ty, loader }, exists)?;
@@ -916,6 +1013,7 @@ impl Interpreter {
         addr: AccountAddress,
         ty: &Type,
     ) -> PartialVMResult<()> {
+        self.record_resource_access(loader, addr, ty, ResourceAccessKind::MoveFrom);
         let resource = match Self::load_resource(loader, data_store, gas_meter, addr, ty)?
             .move_from()
         {
@@ -948,6 +1046,7 @@ impl Interpreter {
         ty: &Type,
         resource: Value,
     ) -> PartialVMResult<()> {
+        self.record_resource_access(loader, addr, ty, ResourceAccessKind::MoveTo);
         let gv = Self::load_resource(loader, data_store, gas_meter, addr, ty)?;
         // NOTE(Gas): To maintain backward compatibility, we need to charge gas after attempting
         //            the move_to operation.
@@ -1310,6 +1409,85 @@ impl CallStack {
     }
 }
 
+/// Records the calls made by `call_trace_internal` into `CallTraces`.
+struct CallTraceRecorder<'a> {
+    call_traces: &'a mut CallTraces,
+    /// Gas balance at the time each call opened by this execution was entered, innermost last.
+    gas_balances: Vec<InternalGas>,
+    /// Whether this execution is nested in a call recorded by an earlier one, e.g. the entry
+    /// function that published a module whose initializer is being run now.
+    is_nested: bool,
+}
+
+impl<'a> CallTraceRecorder<'a> {
+    fn new(call_traces: &'a mut CallTraces) -> Self {
+        let is_nested = !call_traces.is_empty();
+        Self {
+            call_traces,
+            gas_balances: vec![],
+            is_nested,
+        }
+    }
+
+    fn enter(
+        &mut self,
+        trace: InternalCallTrace,
+        gas_meter: &impl GasMeter,
+    ) -> PartialVMResult<()> {
+        self.call_traces
+            .push(trace)
+            .map_err(|_| PartialVMError::new(StatusCode::CALL_STACK_OVERFLOW))?;
+        self.gas_balances.push(gas_meter.balance_internal());
+        Ok(())
+    }
+
+    fn set_outputs(&mut self, outputs: Vec<Option<InternalCallTraceValue>>) {
+        self.call_traces.set_outputs(outputs);
+    }
+
+    fn add_resource_accesses(&mut self, resource_accesses: Vec<InternalResourceAccess>) {
+        for access in resource_accesses {
+            self.call_traces.add_resource_access(access);
+        }
+    }
+
+    /// Closes the innermost call and attaches it to its caller. The outermost call of a
+    /// top-level execution stays in `CallTraces` as the root.
+    fn exit(&mut self, gas_meter: &impl GasMeter) {
+        let gas_balance = self
+            .gas_balances
+            .pop()
+            .expect("every traced call is exited once");
+        let gas_used = gas_balance
+            .checked_sub(gas_meter.balance_internal())
+            .unwrap_or_else(|| 0.into());
+        self.call_traces.set_gas_used(gas_used.into());
+        if !self.gas_balances.is_empty() || self.is_nested {
+            let top_call = self.call_traces.pop().unwrap();
+            self.call_traces.push_call_trace(top_call);
+        }
+    }
+
+    /// Attributes `err` to the innermost call and closes all calls opened by this execution.
+    fn abort(&mut self, err: &VMError, gas_meter: &impl GasMeter) {
+        if self.gas_balances.is_empty() {
+            return;
+        }
+        self.call_traces.set_abort(InternalCallTraceAbort {
+            status_code: err.major_status(),
+            sub_status: err.sub_status(),
+            location: match err.location() {
+                Location::Module(module_id) => Some(module_id.clone()),
+                Location::Script | Location::Undefined => None,
+            },
+            code_offset: err.offsets().first().map(|(_, code_offset)| *code_offset),
+        });
+        while !self.gas_balances.is_empty() {
+            self.exit(gas_meter);
+        }
+    }
+}
+
 fn check_depth_of_type(resolver: &Resolver, ty: &Type) -> PartialVMResult<()> {
     // Start at 1 since we always call this right before we add a new node to the value's depth.
     let max_depth = match resolver.loader().vm_config().max_value_nest_depth {
@@ -318,6 +318,7 @@ impl VMRuntime {
         data_store: &mut TransactionDataCache,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        call_traces: Option<&mut CallTraces>,
     ) -> VMResult<SerializedReturnValues> {
         let arg_types = param_types
             .into_iter()
@@ -349,6 +350,7 @@ impl VMRuntime {
             gas_meter,
             extensions,
             &self.loader,
+            call_traces,
         )?;
 
         let serialized_return_values = self
@@ -389,6 +391,7 @@ impl VMRuntime {
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
         bypass_declared_entry_check: bool,
+        call_traces: Option<&mut CallTraces>,
     ) -> VMResult<SerializedReturnValues> {
         // load the function
         let (module, function, instantiation) =
@@ -403,6 +406,7 @@ impl VMRuntime {
             gas_meter,
             extensions,
             bypass_declared_entry_check,
+            call_traces,
         )
     }
 
@@ -415,6 +419,7 @@ impl VMRuntime {
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
         bypass_declared_entry_check: bool,
+        call_traces: Option<&mut CallTraces>,
     ) -> VMResult<SerializedReturnValues> {
         // load the function
         let LoadedFunctionInstantiation {
@@ -462,6 +467,7 @@ impl VMRuntime {
             data_store,
             gas_meter,
             extensions,
+            call_traces,
         )
     }
 
@@ -474,6 +480,7 @@ impl VMRuntime {
         data_store: &mut TransactionDataCache,
         gas_meter: &mut impl GasMeter,
         extensions: &mut NativeContextExtensions,
+        call_traces: Option<&mut CallTraces>,
     ) -> VMResult<SerializedReturnValues> {
         // load the script, perform verification
         let (
@@ -496,76 +503,7 @@ impl VMRuntime {
             data_store,
             gas_meter,
             extensions,
-        )
-    }
-
-    pub(crate) fn call_trace(
-        &self,
-        module: &ModuleId,
-        function_name: &IdentStr,
-        ty_args: Vec<TypeTag>,
-        serialized_args: Vec<impl Borrow<[u8]>>,
-        data_store: &mut TransactionDataCache,
-        gas_meter: &mut impl GasMeter,
-        extensions: &mut NativeContextExtensions,
-        bypass_declared_entry_check: bool,
-    ) -> VMResult<CallTraces> {
-        // load the function
-        let (module, function, function_instantiation) =
-            self.loader
-                .load_function(module, function_name, &ty_args, data_store)?;
-
-        // load the function
-        let LoadedFunctionInstantiation {
-            type_arguments,
-            parameters,
-            return_,
-        } = function_instantiation;
-
-        use move_binary_format::{binary_views::BinaryIndexedView, file_format::SignatureIndex};
-        fn check_is_entry(
-            _resolver: &BinaryIndexedView,
-            is_entry: bool,
-            _parameters_idx: SignatureIndex,
-            _return_idx: Option<SignatureIndex>,
-        ) -> PartialVMResult<()> {
-            if is_entry {
-                Ok(())
-            } else {
-                Err(PartialVMError::new(
-                    StatusCode::EXECUTE_ENTRY_FUNCTION_CALLED_ON_NON_ENTRY_FUNCTION,
-                ))
-            }
-        }
-        let additional_signature_checks = if bypass_declared_entry_check {
-            move_bytecode_verifier::no_additional_script_signature_checks
-        } else {
-            check_is_entry
-        };
-
-        script_signature::verify_module_function_signature_by_name(
-            module.module(),
-            IdentStr::new(function.as_ref().name()).expect(""),
-            additional_signature_checks,
-        )?;
-
-        let arg_types = parameters
-            .into_iter()
-            .map(|ty| ty.subst(&type_arguments))
-            .collect::<PartialVMResult<Vec<_>>>()
-            .map_err(|err| err.finish(Location::Undefined))?;
-        let (mut dummy_locals, deserialized_args) = self
-            .deserialize_args(arg_types, serialized_args)
-            .map_err(|e| e.finish(Location::Undefined))?;
-
-        Interpreter::call_trace(
-            function,
-            type_arguments,
-            deserialized_args,
-            data_store,
-            gas_meter,
-            extensions,
-            &self.loader,
+            call_traces,
         )
     }
 
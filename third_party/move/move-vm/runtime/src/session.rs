@@ -12,6 +12,7 @@ use move_binary_format::{
     errors::*,
     file_format::{AbilitySet, LocalIndex},
 };
+use move_core_types::call_trace::CallTraces;
 use move_core_types::{
     account_address::AccountAddress,
     effects::{ChangeSet, Changes},
@@ -26,7 +27,6 @@ use move_vm_types::{
     values::{GlobalValue, Value},
 };
 use std::{borrow::Borrow, sync::Arc};
-use move_core_types::call_trace::{CallTraces};
 
 pub struct Session<'r, 'l> {
     pub(crate) move_vm: &'l MoveVM,
@@ -89,6 +89,7 @@ impl<'r, 'l> Session<'r, 'l> {
             gas_meter,
             &mut self.native_extensions,
             bypass_declared_entry_check,
+            None,
         )
     }
 
@@ -111,19 +112,73 @@ impl<'r, 'l> Session<'r, 'l> {
             gas_meter,
             &mut self.native_extensions,
             bypass_declared_entry_check,
+            None,
         )
     }
 
+    /// Similar to execute_entry_function, but records the Move calls made during execution into
+    /// `call_traces`. Frames recorded before an abort are kept in `call_traces`.
+    pub fn execute_entry_function_with_call_trace(
+        &mut self,
+        module: &ModuleId,
+        function_name: &IdentStr,
+        ty_args: Vec<TypeTag>,
+        args: Vec<impl Borrow<[u8]>>,
+        gas_meter: &mut impl GasMeter,
+        call_traces: &mut CallTraces,
+    ) -> VMResult<SerializedReturnValues> {
+        let bypass_declared_entry_check = false;
+        self.move_vm.runtime.execute_function(
+            module,
+            function_name,
+            ty_args,
+            args,
+            &mut self.data_cache,
+            gas_meter,
+            &mut self.native_extensions,
+            bypass_declared_entry_check,
+            Some(call_traces),
+        )
+    }
+
+    /// Similar to execute_function_bypass_visibility, but records the Move calls made during
+    /// execution into `call_traces`.
+    pub fn execute_function_bypass_visibility_with_call_trace(
+        &mut self,
+        module: &ModuleId,
+        function_name: &IdentStr,
+        ty_args: Vec<TypeTag>,
+        args: Vec<impl Borrow<[u8]>>,
+        gas_meter: &mut impl GasMeter,
+        call_traces: &mut CallTraces,
+    ) -> VMResult<SerializedReturnValues> {
+        let bypass_declared_entry_check = true;
+        self.move_vm.runtime.execute_function(
+            module,
+            function_name,
+            ty_args,
+            args,
+            &mut self.data_cache,
+            gas_meter,
+            &mut self.native_extensions,
+            bypass_declared_entry_check,
+            Some(call_traces),
+        )
+    }
+
+    /// Similar to execute_function_bypass_visibility, but returns the call traces of the
+    /// execution instead of the return values.
     pub fn call_trace(
         &mut self,
         module: &ModuleId,
         function_name: &IdentStr,
         ty_args: Vec<TypeTag>,
         args: Vec<impl Borrow<[u8]>>,
-        gas_meter: &mut impl GasMeter
+        gas_meter: &mut impl GasMeter,
     ) -> VMResult<CallTraces> {
         let bypass_declared_entry_check = true;
-        self.move_vm.runtime.call_trace(
+        let mut call_traces = CallTraces::new();
+        self.move_vm.runtime.execute_function(
             module,
             function_name,
             ty_args,
@@ -132,7 +187,9 @@ impl<'r, 'l> Session<'r, 'l> {
             gas_meter,
             &mut self.native_extensions,
             bypass_declared_entry_check,
-        )
+            Some(&mut call_traces),
+        )?;
+        Ok(call_traces)
     }
 
     pub fn execute_instantiated_function(
@@ -150,6 +207,7 @@ impl<'r, 'l> Session<'r, 'l> {
             gas_meter,
             &mut self.native_extensions,
             true,
+            None,
         )
     }
 
@@ -183,6 +241,28 @@ impl<'r, 'l> Session<'r, 'l> {
             &mut self.data_cache,
             gas_meter,
             &mut self.native_extensions,
+            None,
+        )
+    }
+
+    /// Similar to execute_script, but records the Move calls made during execution into
+    /// `call_traces`. Frames recorded before an abort are kept in `call_traces`.
+    pub fn execute_script_with_call_trace(
+        &mut self,
+        script: impl Borrow<[u8]>,
+        ty_args: Vec<TypeTag>,
+        args: Vec<impl Borrow<[u8]>>,
+        gas_meter: &mut impl GasMeter,
+        call_traces: &mut CallTraces,
+    ) -> VMResult<SerializedReturnValues> {
+        self.move_vm.runtime.execute_script(
+            script,
+            ty_args,
+            args,
+            &mut self.data_cache,
+            gas_meter,
+            &mut self.native_extensions,
+            Some(call_traces),
         )
     }
 
@@ -109,6 +109,10 @@ pub enum TransactionShufflerType {
     NoShuffling,
     DeprecatedSenderAwareV1(u32),
     SenderAwareV2(u32),
+    FeeAndConflictAwareV1 {
+        sender_conflict_window_size: u32,
+        module_conflict_window_size: u32,
+    },
 }
 
 #[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
@@ -182,6 +186,24 @@ mod test {
             TransactionShufflerType::SenderAwareV2(32)
         ));
         assert!(result.block_gas_limit().is_none());
+
+        // V3 test with fee and conflict aware shuffling
+        let shuffler_type = TransactionShufflerType::FeeAndConflictAwareV1 {
+            sender_conflict_window_size: 32,
+            module_conflict_window_size: 4,
+        };
+        let config = OnChainExecutionConfig::V3(ExecutionConfigV3 {
+            transaction_shuffler_type: shuffler_type.clone(),
+            block_gas_limit: None,
+            transaction_deduper_type: TransactionDeduperType::TxnHashAndAuthenticatorV1,
+        });
+
+        let s = serde_yaml::to_string(&config).unwrap();
+        let result = serde_yaml::from_str::<OnChainExecutionConfig>(&s).unwrap();
+        assert_eq!(result.transaction_shuffler_type(), shuffler_type);
+        let result =
+            bcs::from_bytes::<OnChainExecutionConfig>(&bcs::to_bytes(&config).unwrap()).unwrap();
+        assert_eq!(result.transaction_shuffler_type(), shuffler_type);
     }
 
     #[test]
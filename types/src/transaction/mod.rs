@@ -818,12 +818,6 @@ impl TransactionWithProof {
             .try_as_signed_user_txn()
             .context("not user transaction")?;
 
-        ensure!(
-            self.version == version,
-            "Version ({}) is not expected ({}).",
-            self.version,
-            version,
-        );
         ensure!(
             signed_transaction.sender() == sender,
             "Sender ({}) not expected ({}).",
@@ -837,6 +831,22 @@ impl TransactionWithProof {
             sequence_number,
         );
 
+        self.verify(ledger_info, version)
+    }
+
+    /// Verifies the transaction with the proof, both carried by `self`.
+    ///
+    /// A few things are ensured if no error is raised:
+    ///   1. This transaction exists in the ledger represented by `ledger_info`, at `version`.
+    ///   2. The events carried by `self`, if any, are the events emitted by this transaction.
+    pub fn verify(&self, ledger_info: &LedgerInfo, version: Version) -> Result<()> {
+        ensure!(
+            self.version == version,
+            "Version ({}) is not expected ({}).",
+            self.version,
+            version,
+        );
+
         let txn_hash = self.transaction.hash();
         ensure!(
             txn_hash == self.proof.transaction_info().transaction_hash(),